@@ -0,0 +1,485 @@
+use std::collections::BTreeSet;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::PoisonError;
+use std::time::{Duration, SystemTime};
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+
+use crate::config::Config;
+use crate::versioned::{self, VersionError, Versioned};
+
+use super::model::{AnomalyEvent, AnomalyIndexEntry};
+use super::paths::{DbPaths, paths_from_config};
+use super::read::{is_compressed, read_jsonl_bytes};
+use super::write::day_write_lock;
+
+const REPORT_FILE_NAME: &str = "integrity.json";
+/// Files written more recently than this may still be mid-append and are
+/// never truncated or rebuilt.
+const WRITE_GRACE_PERIOD: Duration = Duration::from_secs(60);
+
+/// Findings of one integrity pass over `events/` and `index/`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IntegrityReport {
+    pub checked_at_utc: String,
+    pub repaired: bool,
+    pub event_files: usize,
+    pub index_files: usize,
+    pub event_lines: usize,
+    pub index_lines: usize,
+    pub corrupt_event_lines: usize,
+    pub corrupt_index_lines: usize,
+    /// Index entries whose timestamp has no event on the same day.
+    pub index_without_event: usize,
+    /// Events that are missing from the same day's index.
+    pub events_without_index: usize,
+    pub partial_trailing_lines: usize,
+    pub rebuilt_index_days: usize,
+}
+
+impl IntegrityReport {
+    pub fn is_clean(&self) -> bool {
+        self.corrupt_event_lines == 0
+            && self.corrupt_index_lines == 0
+            && self.index_without_event == 0
+            && self.events_without_index == 0
+            && self.partial_trailing_lines == 0
+    }
+
+    pub fn summary_line(&self) -> String {
+        format!(
+            "{} (files: {} events / {} index, lines: {} / {}, corrupt: {} / {}, index-only: {}, unindexed: {}, partial tails: {}, rebuilt days: {}{})",
+            if self.is_clean() {
+                "clean"
+            } else {
+                "issues found"
+            },
+            self.event_files,
+            self.index_files,
+            self.event_lines,
+            self.index_lines,
+            self.corrupt_event_lines,
+            self.corrupt_index_lines,
+            self.index_without_event,
+            self.events_without_index,
+            self.partial_trailing_lines,
+            self.rebuilt_index_days,
+            if self.repaired { ", repaired" } else { "" }
+        )
+    }
+}
+
+/// Parsed contents of one JSONL file.
+struct ScannedFile<T> {
+    records: Vec<T>,
+    corrupt_lines: usize,
+    /// Lines written by a newer build; their day is never repaired.
+    newer_lines: usize,
+    /// Bytes after the last newline, if any.
+    partial_tail: Option<Vec<u8>>,
+}
+
+#[derive(Default)]
+struct DayFiles {
+    events: Vec<PathBuf>,
+    index: Vec<PathBuf>,
+}
+
+/// Checks every day file for corrupt lines, partial trailing lines and
+/// index/event drift. With `repair`, partial tails are fixed and drifted days
+/// get their index rebuilt from `events/`. The report is persisted under
+/// `meta/` for `/status`.
+pub fn check_integrity(config: &Config, repair: bool) -> IntegrityReport {
+    let paths = paths_from_config(config);
+    let report = check_paths(&paths, repair);
+    if let Err(error) = save_report(&paths, &report) {
+        log::warn!("anomaly_db_integrity_report_write_failed error={error}");
+    }
+    if !report.is_clean() {
+        log::warn!("anomaly_db_integrity {}", report.summary_line());
+    }
+    report
+}
+
+/// Most recent persisted report, if a check has run.
+pub fn last_integrity_report(config: &Config) -> Option<IntegrityReport> {
+    let path = paths_from_config(config).meta_dir.join(REPORT_FILE_NAME);
+    let raw = fs::read(path).ok()?;
+    serde_json::from_slice(&raw).ok()
+}
+
+fn check_paths(paths: &DbPaths, repair: bool) -> IntegrityReport {
+    let mut report = IntegrityReport {
+        checked_at_utc: Utc::now().to_rfc3339(),
+        repaired: repair,
+        ..IntegrityReport::default()
+    };
+
+    let days = day_files(&paths.events_dir, "events-")
+        .into_iter()
+        .chain(day_files(&paths.index_dir, "index-"))
+        .map(|(day, _)| day)
+        .collect::<BTreeSet<_>>();
+
+    for day in days {
+        // A repair reads and rewrites the day under its write lock, and lists
+        // the files again once the lock is held in case a write rotated them.
+        let day_lock = day_write_lock(&day);
+        let _day_guard = repair.then(|| day_lock.lock().unwrap_or_else(PoisonError::into_inner));
+        let files = files_for_day(paths, &day);
+        report.event_files += files.events.len();
+        report.index_files += files.index.len();
+        let scanned_events = files
+            .events
+            .iter()
+            .map(|path| (path, scan_file::<AnomalyEvent>(path)))
+            .collect::<Vec<_>>();
+        let scanned_index = files
+            .index
+            .iter()
+            .map(|path| (path, scan_file::<AnomalyIndexEntry>(path)))
+            .collect::<Vec<_>>();
+        let newer_lines = scanned_events
+            .iter()
+            .map(|(_, scanned)| scanned.newer_lines)
+            .chain(scanned_index.iter().map(|(_, scanned)| scanned.newer_lines))
+            .sum::<usize>();
+        if newer_lines > 0 {
+            log::warn!(
+                "anomaly_db_integrity_skip_day day={day} reason=newer_format lines={newer_lines}"
+            );
+        }
+        let settled = newer_lines == 0
+            && files
+                .events
+                .iter()
+                .chain(&files.index)
+                .all(|path| !recently_modified(path));
+
+        let mut events = Vec::new();
+        let mut corrupt_events = 0;
+        for (path, scanned) in scanned_events {
+            corrupt_events += scanned.corrupt_lines;
+            report.partial_trailing_lines +=
+                usize::from(handle_partial_tail(path, &scanned, repair && settled));
+            events.extend(scanned.records);
+        }
+
+        let mut index = Vec::new();
+        let mut corrupt_index = 0;
+        for (path, scanned) in scanned_index {
+            corrupt_index += scanned.corrupt_lines;
+            report.partial_trailing_lines +=
+                usize::from(handle_partial_tail(path, &scanned, repair && settled));
+            index.extend(scanned.records);
+        }
+
+        let event_times = events
+            .iter()
+            .map(|event| event.timestamp.as_str())
+            .collect::<BTreeSet<_>>();
+        let index_times = index
+            .iter()
+            .map(|entry| entry.timestamp.as_str())
+            .collect::<BTreeSet<_>>();
+        let index_without_event = index_times.difference(&event_times).count();
+        let events_without_index = event_times.difference(&index_times).count();
+
+        report.event_lines += events.len();
+        report.index_lines += index.len();
+        report.corrupt_event_lines += corrupt_events;
+        report.corrupt_index_lines += corrupt_index;
+        report.index_without_event += index_without_event;
+        report.events_without_index += events_without_index;
+
+        let drifted = index_without_event > 0 || events_without_index > 0 || corrupt_index > 0;
+        if repair && settled && drifted && !files.events.is_empty() {
+            match rebuild_day_index(&paths.index_dir, &day, &files.index, events) {
+                Ok(()) => report.rebuilt_index_days += 1,
+                Err(error) => {
+                    log::warn!("anomaly_db_index_rebuild_failed day={day} error={error}");
+                }
+            }
+        }
+    }
+
+    report
+}
+
+fn files_for_day(paths: &DbPaths, day: &str) -> DayFiles {
+    let of_day = |dir: &Path, prefix: &str| {
+        day_files(dir, prefix)
+            .into_iter()
+            .filter_map(|(file_day, path)| (file_day == day).then_some(path))
+            .collect()
+    };
+    DayFiles {
+        events: of_day(&paths.events_dir, "events-"),
+        index: of_day(&paths.index_dir, "index-"),
+    }
+}
+
+/// `(YYYY-MM-DD, path)` for day files (including rotated ones) in `dir`.
+pub(super) fn day_files(dir: &Path, prefix: &str) -> Vec<(String, PathBuf)> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let mut files = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter_map(|path| {
+            let name = path.file_name()?.to_str()?;
+            if !name.contains(".jsonl") || name.ends_with(".tmp") {
+                return None;
+            }
+            let day = name.strip_prefix(prefix)?.get(0..10)?.to_string();
+            Some((day, path))
+        })
+        .collect::<Vec<_>>();
+    files.sort();
+    files
+}
+
+fn scan_file<T: Versioned>(path: &Path) -> ScannedFile<T> {
+    let bytes = read_jsonl_bytes(path).unwrap_or_default();
+    let (complete, partial_tail) = match bytes.iter().rposition(|byte| *byte == b'\n') {
+        Some(last_newline) if last_newline + 1 == bytes.len() => (&bytes[..], None),
+        Some(last_newline) => (
+            &bytes[..=last_newline],
+            Some(bytes[last_newline + 1..].to_vec()),
+        ),
+        None if bytes.is_empty() => (&bytes[..], None),
+        None => (&bytes[..0], Some(bytes.clone())),
+    };
+
+    let mut records = Vec::new();
+    let mut corrupt_lines = 0;
+    let mut newer_lines = 0;
+    for line in complete.split(|byte| *byte == b'\n') {
+        if line.iter().all(u8::is_ascii_whitespace) {
+            continue;
+        }
+        match versioned::decode::<T>(line) {
+            Ok(record) => records.push(record),
+            Err(VersionError::Future { .. }) => newer_lines += 1,
+            Err(_) => corrupt_lines += 1,
+        }
+    }
+
+    // A complete record that only lacks its newline still counts.
+    if let Some(tail) = &partial_tail {
+        match versioned::decode::<T>(tail) {
+            Ok(record) => records.push(record),
+            Err(VersionError::Future { .. }) => newer_lines += 1,
+            Err(_) => {}
+        }
+    }
+
+    ScannedFile {
+        records,
+        corrupt_lines,
+        newer_lines,
+        partial_tail,
+    }
+}
+
+/// Returns whether `path` ends in a partial line. With `repair`, a parsable
+/// tail gets its newline and an unparsable one is cut off. Compressed rotated
+/// files are only reported.
+fn handle_partial_tail<T: Versioned>(path: &Path, scanned: &ScannedFile<T>, repair: bool) -> bool {
+    let Some(tail) = &scanned.partial_tail else {
+        return false;
+    };
+    if !repair || is_compressed(path) {
+        return true;
+    }
+
+    let result = if versioned::decode::<T>(tail).is_ok() {
+        OpenOptions::new()
+            .append(true)
+            .open(path)
+            .and_then(|mut file| file.write_all(b"\n"))
+    } else {
+        fs::metadata(path).and_then(|meta| {
+            let keep = meta.len().saturating_sub(tail.len() as u64);
+            OpenOptions::new()
+                .write(true)
+                .open(path)
+                .and_then(|file| file.set_len(keep))
+        })
+    };
+
+    if let Err(error) = result {
+        log::warn!(
+            "anomaly_db_partial_line_repair_failed path={} error={error}",
+            path.display()
+        );
+    } else {
+        log::info!(
+            "anomaly_db_partial_line_repaired path={} bytes={}",
+            path.display(),
+            tail.len()
+        );
+    }
+    true
+}
+
+fn rebuild_day_index(
+    index_dir: &Path,
+    day: &str,
+    existing: &[PathBuf],
+    mut events: Vec<AnomalyEvent>,
+) -> Result<(), std::io::Error> {
+    events.sort_by(|left, right| left.timestamp.cmp(&right.timestamp));
+    events.dedup_by(|left, right| left.timestamp == right.timestamp);
+
+    let mut payload = Vec::new();
+    for event in events {
+        let entry = AnomalyIndexEntry::from(event);
+        payload.extend(versioned::encode(&entry).map_err(std::io::Error::other)?);
+        payload.push(b'\n');
+    }
+
+    fs::create_dir_all(index_dir)?;
+    let target = index_dir.join(format!("index-{day}.jsonl"));
+    let mut tmp = tempfile::Builder::new()
+        .prefix(&format!("index-{day}.jsonl."))
+        .suffix(".tmp")
+        .tempfile_in(index_dir)?;
+    tmp.write_all(&payload)?;
+    tmp.persist(&target).map_err(|error| error.error)?;
+    for path in existing.iter().filter(|path| **path != target) {
+        fs::remove_file(path)?;
+    }
+
+    log::info!("anomaly_db_index_rebuilt day={day}");
+    Ok(())
+}
+
+fn recently_modified(path: &Path) -> bool {
+    fs::metadata(path)
+        .and_then(|meta| meta.modified())
+        .ok()
+        .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+        .is_some_and(|age| age < WRITE_GRACE_PERIOD)
+}
+
+fn save_report(paths: &DbPaths, report: &IntegrityReport) -> Result<(), std::io::Error> {
+    fs::create_dir_all(&paths.meta_dir)?;
+    let path = paths.meta_dir.join(REPORT_FILE_NAME);
+    let tmp = paths.meta_dir.join(format!("{REPORT_FILE_NAME}.tmp"));
+    fs::write(
+        &tmp,
+        serde_json::to_vec_pretty(report).map_err(std::io::Error::other)?,
+    )?;
+    fs::rename(tmp, path)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::time::{Duration, SystemTime};
+
+    use super::check_paths;
+    use crate::anomaly_db::paths::DbPaths;
+
+    fn event_line(timestamp: &str) -> String {
+        format!(
+            r#"{{"timestamp":"{timestamp}","cpu":95.0,"ram":10.0,"disk":10.0,"cpu_threshold":90.0,"ram_threshold":90.0,"disk_threshold":90.0,"cpu_over":true,"ram_over":false,"disk_over":false}}"#
+        )
+    }
+
+    fn settle(path: &std::path::Path) {
+        let old = SystemTime::now() - Duration::from_secs(3600);
+        fs::File::options()
+            .write(true)
+            .open(path)
+            .and_then(|file| file.set_modified(old))
+            .expect("set mtime");
+    }
+
+    fn test_paths(root: &std::path::Path) -> DbPaths {
+        let paths = DbPaths {
+            events_dir: root.join("events"),
+            index_dir: root.join("index"),
+            meta_dir: root.join("meta"),
+            store_dir: root.join("store"),
+        };
+        fs::create_dir_all(&paths.events_dir).unwrap();
+        fs::create_dir_all(&paths.index_dir).unwrap();
+        paths
+    }
+
+    #[test]
+    fn detects_and_repairs_drift_corruption_and_partial_tails() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let paths = test_paths(temp.path());
+        let events = paths.events_dir.join("events-2026-10-17.jsonl");
+        let index = paths.index_dir.join("index-2026-10-17.jsonl");
+        fs::write(
+            &events,
+            format!(
+                "{}\nnot json\n{}\n{{\"timest",
+                event_line("2026-10-17T10:00:00+00:00"),
+                event_line("2026-10-17T11:00:00+00:00")
+            ),
+        )
+        .unwrap();
+        fs::write(
+            &index,
+            format!(
+                "{}\n{}\n",
+                event_line("2026-10-17T10:00:00+00:00"),
+                event_line("2026-10-17T12:00:00+00:00")
+            ),
+        )
+        .unwrap();
+        settle(&events);
+        settle(&index);
+
+        let report = check_paths(&paths, false);
+        assert_eq!(report.event_lines, 2);
+        assert_eq!(report.corrupt_event_lines, 1);
+        assert_eq!(report.partial_trailing_lines, 1);
+        assert_eq!(report.index_without_event, 1);
+        assert_eq!(report.events_without_index, 1);
+        assert!(!report.is_clean());
+
+        let foreign_tmp = paths.index_dir.join("index-2026-10-17.jsonl.tmp");
+        fs::write(&foreign_tmp, "in progress").unwrap();
+        let repaired = check_paths(&paths, true);
+        assert_eq!(repaired.rebuilt_index_days, 1);
+        assert_eq!(fs::read_to_string(&foreign_tmp).unwrap(), "in progress");
+        assert!(fs::read_to_string(&events).unwrap().ends_with('\n'));
+        settle(&events);
+        settle(&index);
+
+        let after = check_paths(&paths, false);
+        assert_eq!(after.partial_trailing_lines, 0);
+        assert_eq!(after.index_without_event, 0);
+        assert_eq!(after.events_without_index, 0);
+        assert_eq!(after.index_lines, 2);
+    }
+
+    #[test]
+    fn recently_written_files_are_left_alone() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let paths = test_paths(temp.path());
+        let events = paths.events_dir.join("events-2026-10-18.jsonl");
+        fs::write(
+            &events,
+            format!("{}\n{{\"cpu", event_line("2026-10-18T10:00:00+00:00")),
+        )
+        .unwrap();
+
+        let report = check_paths(&paths, true);
+        assert_eq!(report.partial_trailing_lines, 1);
+        assert_eq!(report.events_without_index, 1);
+        assert_eq!(report.rebuilt_index_days, 0);
+        assert!(fs::read_to_string(&events).unwrap().ends_with("{\"cpu"));
+    }
+}
@@ -1,11 +1,12 @@
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use chrono::{Duration as ChronoDuration, NaiveDate, Utc};
 
 use crate::config::Config;
 
+use super::integrity::{check_integrity, day_files};
 use super::paths::{DbPaths, ensure_db_dirs, paths_from_config};
 
 pub fn run_maintenance(config: &Config) {
@@ -20,6 +21,8 @@ pub fn run_maintenance(config: &Config) {
     }
 
     prune_old_daily_files(&paths, config.anomaly_db.retention_days);
+    prune_to_total_bytes(&paths, config.anomaly_db.max_total_bytes);
+    check_integrity(config, true);
 }
 
 fn prune_old_daily_files(paths: &DbPaths, retention_days: u16) {
@@ -39,6 +42,40 @@ fn prune_old_daily_files(paths: &DbPaths, retention_days: u16) {
     }
 }
 
+/// Removes whole days (events and index), oldest first, until the day files
+/// fit in `max_total_bytes`. The newest day is always kept.
+fn prune_to_total_bytes(paths: &DbPaths, max_total_bytes: u64) {
+    let mut days = BTreeMap::<String, Vec<(PathBuf, u64)>>::new();
+    for (day, path) in day_files(&paths.events_dir, "events-")
+        .into_iter()
+        .chain(day_files(&paths.index_dir, "index-"))
+    {
+        let len = fs::metadata(&path).map_or(0, |meta| meta.len());
+        days.entry(day).or_default().push((path, len));
+    }
+
+    let mut total = days.values().flatten().map(|(_, len)| len).sum::<u64>();
+    let removable = days.len().saturating_sub(1);
+    for (day, files) in days.into_iter().take(removable) {
+        if total <= max_total_bytes {
+            break;
+        }
+        for (path, len) in files {
+            match fs::remove_file(&path) {
+                Ok(()) => total = total.saturating_sub(len),
+                Err(error) => log::warn!(
+                    "anomaly db: failed to remove file {} over size limit: {}",
+                    path.display(),
+                    error
+                ),
+            }
+        }
+        log::info!(
+            "anomaly db maintenance: removed day {day} over size limit, total_bytes={total}"
+        );
+    }
+}
+
 fn prune_directory_by_date_prefix(
     dir: &Path,
     prefix: &str,
@@ -123,3 +160,52 @@ fn remove_matching_date_files(dir: &Path, prefix: &str, day: &str) {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::prune_to_total_bytes;
+    use crate::anomaly_db::paths::DbPaths;
+
+    #[test]
+    fn size_limit_drops_oldest_days_but_keeps_the_newest() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let paths = DbPaths {
+            events_dir: temp.path().join("events"),
+            index_dir: temp.path().join("index"),
+            meta_dir: temp.path().join("meta"),
+            store_dir: temp.path().join("store"),
+        };
+        fs::create_dir_all(&paths.events_dir).unwrap();
+        fs::create_dir_all(&paths.index_dir).unwrap();
+        for day in ["2026-10-15", "2026-10-16", "2026-10-17"] {
+            fs::write(
+                paths.events_dir.join(format!("events-{day}.jsonl")),
+                [b'x'; 100],
+            )
+            .unwrap();
+            fs::write(
+                paths.events_dir.join(format!("events-{day}.jsonl.1.gz")),
+                [b'x'; 50],
+            )
+            .unwrap();
+            fs::write(
+                paths.index_dir.join(format!("index-{day}.jsonl")),
+                [b'x'; 50],
+            )
+            .unwrap();
+        }
+
+        prune_to_total_bytes(&paths, 450);
+        let remaining = |dir: &std::path::Path| fs::read_dir(dir).unwrap().count();
+        assert_eq!(remaining(&paths.events_dir), 4);
+        assert!(!paths.index_dir.join("index-2026-10-15.jsonl").exists());
+        assert!(paths.index_dir.join("index-2026-10-16.jsonl").exists());
+
+        prune_to_total_bytes(&paths, 1);
+        assert_eq!(remaining(&paths.events_dir), 2);
+        assert_eq!(remaining(&paths.index_dir), 1);
+        assert!(paths.index_dir.join("index-2026-10-17.jsonl").exists());
+    }
+}
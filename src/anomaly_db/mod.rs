@@ -1,29 +1,100 @@
+mod integrity;
 mod maintenance;
 mod model;
 mod paths;
+mod query;
 mod read;
+mod sled_store;
+mod stats;
 mod write;
 
-pub use maintenance::run_maintenance;
+pub use integrity::{check_integrity, last_integrity_report};
 pub use model::AnomalyEvent;
+pub(crate) use paths::paths_from_config;
+pub(crate) use query::{AnomalyQuery, MetricCondition, MetricField, Operator};
+pub use sled_store::SledAnomalyStorage;
+pub use stats::anomaly_db_stats;
+
+use std::sync::Arc;
 
 /// Storage abstraction for anomaly database operations.  `record_if_needed` is
 /// responsible for threshold logic and durable persistence; `recent` fetches
-/// the latest events up to a limit.
+/// the latest events up to a limit and `query` applies time/metric filters.
 use async_trait::async_trait;
 
+use crate::config::AnomalyDbBackend;
+use crate::versioned::{StoreOpenError, VersionError, check_format_file};
+
+const FORMAT_MARKER_FILE: &str = "format.json";
+/// Record format of the JSONL files; bump with the record versions.
+const JSONL_FORMAT_VERSION: u32 = 1;
+
+/// How many recent events the default `query` scans before filtering.
+const QUERY_SCAN_LIMIT: usize = 500;
+
 #[async_trait]
 pub trait AnomalyStorage: Send + Sync {
     async fn record_if_needed(&self, config: &crate::config::Config, cpu: f32, ram: f32, disk: f32);
     async fn recent(&self, config: &crate::config::Config, limit: usize) -> Vec<AnomalyEvent>;
+
+    /// Newest events matching `query`. Backends without indexes filter the
+    /// most recent `QUERY_SCAN_LIMIT` events.
+    async fn query(
+        &self,
+        config: &crate::config::Config,
+        query: &AnomalyQuery,
+    ) -> Vec<AnomalyEvent> {
+        let mut events = self.recent(config, QUERY_SCAN_LIMIT).await;
+        events.retain(|event| query.matches(event));
+        events.truncate(query.limit);
+        events
+    }
+
+    /// Retention and housekeeping, run hourly.
+    async fn run_maintenance(&self, _config: &crate::config::Config) {}
+
+    /// Persists buffered writes, e.g. before the data directory is archived.
+    /// JSONL appends are written through, so only sled has work to do.
+    async fn flush(&self) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+/// Storage selected by `anomaly_db.backend`. Falls back to JSONL files when
+/// the sled store cannot be opened; data stamped by a newer build is an error.
+pub fn anomaly_storage_from_config(
+    config: &crate::config::Config,
+) -> Result<Arc<dyn AnomalyStorage>, VersionError> {
+    if !config.anomaly_db.enabled {
+        return Ok(Arc::new(FileAnomalyStorage::new()));
+    }
+
+    let marker = paths_from_config(config).meta_dir.join(FORMAT_MARKER_FILE);
+    check_format_file(&marker, "anomaly_db", JSONL_FORMAT_VERSION)?;
+
+    match config.anomaly_db.backend {
+        AnomalyDbBackend::Jsonl => Ok(Arc::new(FileAnomalyStorage::new())),
+        AnomalyDbBackend::Sled => match SledAnomalyStorage::open_from_config(config) {
+            Ok(store) => Ok(Arc::new(store)),
+            Err(StoreOpenError::Version(error)) => Err(error),
+            Err(StoreOpenError::Sled(error)) => {
+                log::warn!("anomaly_db_sled_disabled reason=open_failed error={error}");
+                Ok(Arc::new(FileAnomalyStorage::new()))
+            }
+        },
+    }
 }
 
 /// Concrete implementation that uses the normal filesystem-based storage.
-pub struct FileAnomalyStorage;
+pub struct FileAnomalyStorage {
+    writer: std::sync::Mutex<Option<write::EventWriter>>,
+}
 
 impl FileAnomalyStorage {
     pub fn new() -> Self {
-        Self
+        Self {
+            writer: std::sync::Mutex::new(None),
+        }
     }
 }
 
@@ -36,12 +107,16 @@ impl AnomalyStorage for FileAnomalyStorage {
         ram: f32,
         disk: f32,
     ) {
-        write::record_anomaly_if_needed(config, cpu, ram, disk);
+        write::record_anomaly_if_needed(config, &self.writer, cpu, ram, disk);
     }
 
     async fn recent(&self, config: &crate::config::Config, limit: usize) -> Vec<AnomalyEvent> {
         read::recent_anomalies(config, limit)
     }
+
+    async fn run_maintenance(&self, config: &crate::config::Config) {
+        maintenance::run_maintenance(config);
+    }
 }
 
 #[cfg(test)]
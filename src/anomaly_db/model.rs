@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use crate::versioned::Versioned;
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct AnomalyEvent {
     pub timestamp: String,
@@ -14,6 +16,11 @@ pub struct AnomalyEvent {
     pub disk_over: bool,
 }
 
+impl Versioned for AnomalyEvent {
+    const KIND: &'static str = "anomaly_event";
+    const VERSION: u32 = 1;
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub(crate) struct AnomalyIndexEntry {
     pub(crate) timestamp: String,
@@ -27,3 +34,53 @@ pub(crate) struct AnomalyIndexEntry {
     pub(crate) ram_over: bool,
     pub(crate) disk_over: bool,
 }
+
+impl Versioned for AnomalyIndexEntry {
+    const KIND: &'static str = "anomaly_index_entry";
+    const VERSION: u32 = 1;
+}
+
+impl From<AnomalyEvent> for AnomalyIndexEntry {
+    fn from(event: AnomalyEvent) -> Self {
+        Self {
+            timestamp: event.timestamp,
+            cpu: event.cpu,
+            ram: event.ram,
+            disk: event.disk,
+            cpu_threshold: event.cpu_threshold,
+            ram_threshold: event.ram_threshold,
+            disk_threshold: event.disk_threshold,
+            cpu_over: event.cpu_over,
+            ram_over: event.ram_over,
+            disk_over: event.disk_over,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AnomalyEvent, AnomalyIndexEntry};
+    use crate::versioned::{VersionError, decode, encode};
+
+    const LEGACY_LINE: &str = r#"{"timestamp":"2026-10-01T12:00:00+00:00","cpu":95.0,"ram":40.0,"disk":10.0,"cpu_threshold":90.0,"ram_threshold":90.0,"disk_threshold":90.0,"cpu_over":true,"ram_over":false,"disk_over":false}"#;
+
+    #[test]
+    fn pre_envelope_lines_still_decode_and_round_trip() {
+        let event = decode::<AnomalyEvent>(LEGACY_LINE.as_bytes()).expect("legacy event");
+        let entry = decode::<AnomalyIndexEntry>(LEGACY_LINE.as_bytes()).expect("legacy entry");
+        assert_eq!(event.cpu, 95.0);
+        assert_eq!(entry.timestamp, event.timestamp);
+
+        let encoded = encode(&event).expect("encode");
+        assert!(encoded.starts_with(br#"{"v":1,"data":"#));
+        let decoded = decode::<AnomalyEvent>(&encoded).expect("round trip");
+        assert_eq!(decoded.timestamp, event.timestamp);
+        assert!(decoded.cpu_over);
+
+        let newer = format!(r#"{{"v":2,"data":{LEGACY_LINE}}}"#);
+        assert!(matches!(
+            decode::<AnomalyIndexEntry>(newer.as_bytes()),
+            Err(VersionError::Future { found: 2, .. })
+        ));
+    }
+}
@@ -8,6 +8,8 @@ pub(crate) struct DbPaths {
     pub(crate) events_dir: PathBuf,
     pub(crate) index_dir: PathBuf,
     pub(crate) meta_dir: PathBuf,
+    /// sled database used by the `sled` backend.
+    pub(crate) store_dir: PathBuf,
 }
 
 pub(crate) fn paths_from_config(config: &Config) -> DbPaths {
@@ -16,6 +18,7 @@ pub(crate) fn paths_from_config(config: &Config) -> DbPaths {
         events_dir: root.join("events"),
         index_dir: root.join("index"),
         meta_dir: root.join("meta"),
+        store_dir: root.join("store"),
     }
 }
 
@@ -0,0 +1,73 @@
+use chrono::{DateTime, Utc};
+
+use super::model::AnomalyEvent;
+
+#[derive(Clone, Copy)]
+pub(crate) enum MetricField {
+    Cpu,
+    Ram,
+    Disk,
+}
+
+impl MetricField {
+    pub(crate) const ALL: [Self; 3] = [Self::Cpu, Self::Ram, Self::Disk];
+
+    pub(crate) fn value(self, event: &AnomalyEvent) -> f32 {
+        match self {
+            Self::Cpu => event.cpu,
+            Self::Ram => event.ram,
+            Self::Disk => event.disk,
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+pub(crate) enum Operator {
+    Gt,
+    Gte,
+    Lt,
+    Lte,
+}
+
+#[derive(Clone, Copy)]
+pub(crate) struct MetricCondition {
+    pub(crate) field: MetricField,
+    pub(crate) op: Operator,
+    pub(crate) threshold: f32,
+}
+
+impl MetricCondition {
+    pub(crate) fn matches(&self, event: &AnomalyEvent) -> bool {
+        let value = self.field.value(event);
+        match self.op {
+            Operator::Gt => value > self.threshold,
+            Operator::Gte => value >= self.threshold,
+            Operator::Lt => value < self.threshold,
+            Operator::Lte => value <= self.threshold,
+        }
+    }
+}
+
+/// Filtered lookup of anomaly events, newest first.
+#[derive(Clone)]
+pub(crate) struct AnomalyQuery {
+    pub(crate) since: Option<DateTime<Utc>>,
+    pub(crate) conditions: Vec<MetricCondition>,
+    pub(crate) limit: usize,
+}
+
+impl AnomalyQuery {
+    pub(crate) fn matches(&self, event: &AnomalyEvent) -> bool {
+        if let Some(since) = self.since {
+            let in_window = DateTime::parse_from_rfc3339(&event.timestamp)
+                .is_ok_and(|timestamp| timestamp.with_timezone(&Utc) >= since);
+            if !in_window {
+                return false;
+            }
+        }
+
+        self.conditions
+            .iter()
+            .all(|condition| condition.matches(event))
+    }
+}
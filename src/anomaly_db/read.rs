@@ -1,8 +1,9 @@
 use std::fs::{self, File};
-use std::io::{Read, Seek, SeekFrom};
+use std::io::{self, Read, Seek, SeekFrom};
 use std::path::{Path, PathBuf};
 
 use crate::config::Config;
+use crate::versioned;
 
 use super::model::{AnomalyEvent, AnomalyIndexEntry};
 use super::paths::paths_from_config;
@@ -31,7 +32,7 @@ pub fn recent_anomalies(config: &Config, limit: usize) -> Vec<AnomalyEvent> {
         };
 
         for line in lines.into_iter().rev() {
-            let Ok(index_entry) = serde_json::from_str::<AnomalyIndexEntry>(&line) else {
+            let Ok(index_entry) = versioned::decode::<AnomalyIndexEntry>(line.as_bytes()) else {
                 continue;
             };
 
@@ -57,6 +58,26 @@ pub fn recent_anomalies(config: &Config, limit: usize) -> Vec<AnomalyEvent> {
     out
 }
 
+/// Whether `path` is a rotated file compressed by the event writer.
+pub(super) fn is_compressed(path: &Path) -> bool {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| matches!(extension, "gz" | "xz"))
+}
+
+/// Reads a JSONL file, transparently decompressing rotated `.gz` and `.xz`
+/// files.
+pub(super) fn read_jsonl_bytes(path: &Path) -> io::Result<Vec<u8>> {
+    let file = File::open(path)?;
+    let mut bytes = Vec::new();
+    match path.extension().and_then(|extension| extension.to_str()) {
+        Some("gz") => flate2::read::GzDecoder::new(file).read_to_end(&mut bytes)?,
+        Some("xz") => xz2::read::XzDecoder::new(file).read_to_end(&mut bytes)?,
+        _ => io::BufReader::new(file).read_to_end(&mut bytes)?,
+    };
+    Ok(bytes)
+}
+
 fn newest_index_files(index_dir: &Path) -> Vec<PathBuf> {
     let Ok(entries) = fs::read_dir(index_dir) else {
         return Vec::new();
@@ -119,3 +140,39 @@ fn read_tail_lines(path: &Path, max_lines: usize) -> Result<Vec<String>, std::io
 
     Ok(lines)
 }
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::io::Write;
+
+    use super::{is_compressed, read_jsonl_bytes};
+
+    #[test]
+    fn reads_plain_and_compressed_files_alike() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let content = b"{\"v\":1}\n{\"v\":2}\n";
+
+        let plain = temp.path().join("events-2026-10-17.jsonl");
+        fs::write(&plain, content).expect("write plain");
+
+        let gzip = temp.path().join("events-2026-10-17.jsonl.1.gz");
+        let mut encoder = flate2::write::GzEncoder::new(
+            fs::File::create(&gzip).expect("create gz"),
+            flate2::Compression::default(),
+        );
+        encoder.write_all(content).expect("write gz");
+        encoder.finish().expect("finish gz");
+
+        let xz = temp.path().join("events-2026-10-17.jsonl.2.xz");
+        let mut encoder = xz2::write::XzEncoder::new(fs::File::create(&xz).expect("create xz"), 6);
+        encoder.write_all(content).expect("write xz");
+        encoder.finish().expect("finish xz");
+
+        for path in [&plain, &gzip, &xz] {
+            assert_eq!(read_jsonl_bytes(path).expect("readable"), content);
+        }
+        assert!(!is_compressed(&plain));
+        assert!(is_compressed(&gzip) && is_compressed(&xz));
+    }
+}
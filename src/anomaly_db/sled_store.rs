@@ -0,0 +1,456 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+
+use crate::config::Config;
+use crate::versioned::{self, StoreOpenError, check_format_tree};
+
+use super::AnomalyStorage;
+use super::model::AnomalyEvent;
+use super::paths::paths_from_config;
+use super::query::{AnomalyQuery, MetricField, Operator};
+use super::read::read_jsonl_bytes;
+use super::write::anomaly_event_if_needed;
+
+const JSONL_IMPORTED_KEY: &[u8] = b"jsonl_imported";
+const FORMAT_VERSION: u32 = 1;
+
+/// Anomaly events in sled, keyed by `timestamp_millis ++ seq` (big endian) so
+/// iteration is time ordered. Each metric has a secondary tree keyed by
+/// `ordered(value) ++ event key`, which turns `cpu>90` into a range scan.
+#[derive(Clone)]
+pub struct SledAnomalyStorage {
+    db: sled::Db,
+    events: sled::Tree,
+    by_metric: [sled::Tree; 3],
+    meta: sled::Tree,
+    sequence: Arc<AtomicU32>,
+}
+
+impl SledAnomalyStorage {
+    pub fn open(path: &Path) -> Result<Self, sled::Error> {
+        let db = sled::open(path)?;
+        Ok(Self {
+            events: db.open_tree("events")?,
+            by_metric: [
+                db.open_tree("by_cpu")?,
+                db.open_tree("by_ram")?,
+                db.open_tree("by_disk")?,
+            ],
+            meta: db.open_tree("meta")?,
+            sequence: Arc::new(AtomicU32::new(0)),
+            db,
+        })
+    }
+
+    /// Opens `<anomaly_db.dir>/store` and runs the one-shot JSONL import.
+    pub fn open_from_config(config: &Config) -> Result<Self, StoreOpenError> {
+        let paths = paths_from_config(config);
+        let store = Self::open(&paths.store_dir)?;
+        check_format_tree(&store.meta, "anomaly_db store", FORMAT_VERSION)?;
+        store.import_jsonl_once(&paths.events_dir)?;
+        Ok(store)
+    }
+
+    fn insert(&self, event: &AnomalyEvent) -> Result<(), sled::Error> {
+        let Some(timestamp) = parse_timestamp(&event.timestamp) else {
+            return Ok(());
+        };
+        let key = event_key(timestamp, self.sequence.fetch_add(1, Ordering::Relaxed));
+        let value = versioned::encode(event).map_err(std::io::Error::other)?;
+
+        self.events.insert(key, value)?;
+        for field in MetricField::ALL {
+            self.metric_tree(field)
+                .insert(metric_key(field.value(event), &key), &[])?;
+        }
+        Ok(())
+    }
+
+    fn remove(&self, key: &[u8], event: &AnomalyEvent) -> Result<(), sled::Error> {
+        self.events.remove(key)?;
+        for field in MetricField::ALL {
+            self.metric_tree(field)
+                .remove(metric_key(field.value(event), key))?;
+        }
+        Ok(())
+    }
+
+    fn metric_tree(&self, field: MetricField) -> &sled::Tree {
+        match field {
+            MetricField::Cpu => &self.by_metric[0],
+            MetricField::Ram => &self.by_metric[1],
+            MetricField::Disk => &self.by_metric[2],
+        }
+    }
+
+    /// Imports every line of the JSONL `events/` files the first time the
+    /// store is opened; a marker in `meta` keeps later opens from re-importing.
+    fn import_jsonl_once(&self, events_dir: &Path) -> Result<(), sled::Error> {
+        if self.meta.contains_key(JSONL_IMPORTED_KEY)? {
+            return Ok(());
+        }
+
+        let mut imported = 0usize;
+        let mut skipped = 0usize;
+        let mut files = fs::read_dir(events_dir)
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .map(|entry| entry.path())
+                    .filter(|path| {
+                        path.file_name()
+                            .and_then(|name| name.to_str())
+                            .is_some_and(|name| {
+                                name.starts_with("events-")
+                                    && name.contains(".jsonl")
+                                    && !name.ends_with(".tmp")
+                            })
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        files.sort();
+
+        for path in files {
+            let Ok(content) = read_jsonl_bytes(&path) else {
+                skipped += 1;
+                continue;
+            };
+            let content = String::from_utf8_lossy(&content);
+            for line in content.lines().filter(|line| !line.trim().is_empty()) {
+                match versioned::decode::<AnomalyEvent>(line.as_bytes()) {
+                    Ok(event) => {
+                        self.insert(&event)?;
+                        imported += 1;
+                    }
+                    Err(_) => skipped += 1,
+                }
+            }
+        }
+
+        self.meta
+            .insert(JSONL_IMPORTED_KEY, Utc::now().to_rfc3339().as_bytes())?;
+        self.db.flush()?;
+        log::info!("anomaly_db_jsonl_imported events={imported} skipped={skipped}");
+        Ok(())
+    }
+
+    fn query_sync(&self, query: &AnomalyQuery) -> Result<Vec<AnomalyEvent>, sled::Error> {
+        let since_key = query.since.map(|since| event_key(since, 0));
+
+        // Prefer a metric index when the query has a metric bound; otherwise
+        // walk the time-ordered tree backwards from now.
+        let Some(condition) = query.conditions.first() else {
+            let range = match since_key {
+                Some(start) => self.events.range(start..),
+                None => self.events.iter(),
+            };
+            let mut out = Vec::new();
+            for entry in range.rev() {
+                let (_, value) = entry?;
+                if let Some(event) = decode_event(&value)
+                    && query.matches(&event)
+                {
+                    out.push(event);
+                    if out.len() >= query.limit {
+                        break;
+                    }
+                }
+            }
+            return Ok(out);
+        };
+
+        let tree = self.metric_tree(condition.field);
+        let bound = ordered_f32(condition.threshold);
+        let candidates = match condition.op {
+            Operator::Gt | Operator::Gte => tree.range(bound.to_vec()..),
+            Operator::Lt | Operator::Lte => tree.range(..=[bound.as_slice(), &[0xff; 12]].concat()),
+        };
+
+        // Keep only the newest `limit` matches while walking the index, so
+        // memory stays bounded however many rows fall inside the value range.
+        let mut newest = BTreeMap::new();
+        for entry in candidates {
+            let (index_key, _) = entry?;
+            let event_key = &index_key[4..];
+            if since_key.is_some_and(|start| event_key < start.as_slice()) {
+                continue;
+            }
+            if newest.len() >= query.limit
+                && newest
+                    .first_key_value()
+                    .is_some_and(|(oldest, _): (&Vec<u8>, _)| event_key <= oldest.as_slice())
+            {
+                continue;
+            }
+            if let Some(value) = self.events.get(event_key)?
+                && let Some(event) = decode_event(&value)
+                && query.matches(&event)
+            {
+                newest.insert(event_key.to_vec(), event);
+                if newest.len() > query.limit {
+                    newest.pop_first();
+                }
+            }
+        }
+        Ok(newest.into_values().rev().collect())
+    }
+
+    /// Drops events older than `retention_days` along with their index keys.
+    fn prune(&self, retention_days: u16) -> Result<usize, sled::Error> {
+        let cutoff = Utc::now() - ChronoDuration::days(i64::from(retention_days));
+        let mut removed = 0usize;
+        for entry in self.events.range(..event_key(cutoff, 0)) {
+            let (key, value) = entry?;
+            match decode_event(&value) {
+                Some(event) => self.remove(&key, &event)?,
+                None => {
+                    self.events.remove(&key)?;
+                }
+            }
+            removed += 1;
+        }
+        Ok(removed)
+    }
+}
+
+#[async_trait]
+impl AnomalyStorage for SledAnomalyStorage {
+    async fn record_if_needed(&self, config: &Config, cpu: f32, ram: f32, disk: f32) {
+        let Some(event) = anomaly_event_if_needed(config, Utc::now(), cpu, ram, disk) else {
+            return;
+        };
+        if let Err(error) = self.insert(&event) {
+            log::warn!("anomaly_db_sled_write_failed error={error}");
+            return;
+        }
+        if let Err(error) = self.db.flush_async().await {
+            log::warn!("anomaly_db_sled_flush_failed error={error}");
+        }
+    }
+
+    async fn recent(&self, config: &Config, limit: usize) -> Vec<AnomalyEvent> {
+        self.query(
+            config,
+            &AnomalyQuery {
+                since: None,
+                conditions: Vec::new(),
+                limit,
+            },
+        )
+        .await
+    }
+
+    async fn query(&self, config: &Config, query: &AnomalyQuery) -> Vec<AnomalyEvent> {
+        if !config.anomaly_db.enabled || query.limit == 0 {
+            return Vec::new();
+        }
+        self.query_sync(query).unwrap_or_else(|error| {
+            log::warn!("anomaly_db_sled_read_failed error={error}");
+            Vec::new()
+        })
+    }
+
+    async fn run_maintenance(&self, config: &Config) {
+        if !config.anomaly_db.enabled {
+            return;
+        }
+        // JSONL files are only an import source here; they are not pruned
+        // or integrity-checked once the sled store owns the events.
+        match self.prune(config.anomaly_db.retention_days) {
+            Ok(0) => {}
+            Ok(removed) => log::info!("anomaly_db_sled_pruned events={removed}"),
+            Err(error) => log::warn!("anomaly_db_sled_prune_failed error={error}"),
+        }
+    }
+
+    async fn flush(&self) -> Result<(), String> {
+        self.db
+            .flush_async()
+            .await
+            .map(|_| ())
+            .map_err(|error| error.to_string())
+    }
+}
+
+fn parse_timestamp(raw: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(raw)
+        .ok()
+        .map(|timestamp| timestamp.with_timezone(&Utc))
+}
+
+fn decode_event(value: &[u8]) -> Option<AnomalyEvent> {
+    versioned::decode(value).ok()
+}
+
+fn event_key(timestamp: DateTime<Utc>, seq: u32) -> [u8; 12] {
+    let mut key = [0u8; 12];
+    key[..8].copy_from_slice(&timestamp.timestamp_millis().to_be_bytes());
+    key[8..].copy_from_slice(&seq.to_be_bytes());
+    key
+}
+
+fn metric_key(value: f32, event_key: &[u8]) -> Vec<u8> {
+    let mut key = Vec::with_capacity(4 + event_key.len());
+    key.extend_from_slice(&ordered_f32(value));
+    key.extend_from_slice(event_key);
+    key
+}
+
+/// Big-endian bytes of `value` whose lexicographic order matches numeric order.
+fn ordered_f32(value: f32) -> [u8; 4] {
+    let bits = value.to_bits();
+    let ordered = if bits & 0x8000_0000 == 0 {
+        bits | 0x8000_0000
+    } else {
+        !bits
+    };
+    ordered.to_be_bytes()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use chrono::{Duration as ChronoDuration, Utc};
+
+    use super::{SledAnomalyStorage, ordered_f32};
+    use crate::anomaly_db::AnomalyStorage;
+    use crate::anomaly_db::model::AnomalyEvent;
+    use crate::anomaly_db::query::{AnomalyQuery, MetricCondition, MetricField, Operator};
+
+    fn event(age: ChronoDuration, cpu: f32) -> AnomalyEvent {
+        AnomalyEvent {
+            timestamp: (Utc::now() - age).to_rfc3339(),
+            cpu,
+            ram: 10.0,
+            disk: 10.0,
+            cpu_threshold: 85.0,
+            ram_threshold: 90.0,
+            disk_threshold: 90.0,
+            cpu_over: cpu > 85.0,
+            ram_over: false,
+            disk_over: false,
+        }
+    }
+
+    #[test]
+    fn ordered_f32_preserves_numeric_order() {
+        let values = [-5.0f32, -0.5, 0.0, 0.5, 42.0, 99.9, 100.0];
+        for pair in values.windows(2) {
+            assert!(ordered_f32(pair[0]) < ordered_f32(pair[1]));
+        }
+    }
+
+    #[tokio::test]
+    async fn imports_jsonl_once_and_answers_filtered_queries() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let mut config = crate::test_utils::base_test_config();
+        config.anomaly_db.dir = temp.path().display().to_string();
+        config.anomaly_db.retention_days = 30;
+
+        let events_dir = temp.path().join("events");
+        fs::create_dir_all(&events_dir).unwrap();
+        let lines = [
+            event(ChronoDuration::days(20), 95.0),
+            event(ChronoDuration::days(2), 88.0),
+            event(ChronoDuration::hours(1), 97.0),
+        ]
+        .iter()
+        .map(|event| serde_json::to_string(event).unwrap())
+        .collect::<Vec<_>>()
+        .join("\n");
+        fs::write(
+            events_dir.join("events-2026-10-01.jsonl"),
+            lines + "\nbroken\n",
+        )
+        .unwrap();
+
+        let store = SledAnomalyStorage::open_from_config(&config).expect("open store");
+        assert_eq!(store.recent(&config, 10).await.len(), 3);
+        store
+            .import_jsonl_once(&events_dir)
+            .expect("second import is a no-op");
+        assert_eq!(store.recent(&config, 10).await.len(), 3);
+
+        let query = AnomalyQuery {
+            since: Some(Utc::now() - ChronoDuration::days(30)),
+            conditions: vec![MetricCondition {
+                field: MetricField::Cpu,
+                op: Operator::Gt,
+                threshold: 90.0,
+            }],
+            limit: 100,
+        };
+        let hits = store.query(&config, &query).await;
+        assert_eq!(
+            hits.iter().map(|event| event.cpu).collect::<Vec<_>>(),
+            [97.0, 95.0]
+        );
+        let newest = AnomalyQuery {
+            limit: 1,
+            ..query.clone()
+        };
+        let newest = store.query(&config, &newest).await;
+        assert_eq!(
+            newest.iter().map(|event| event.cpu).collect::<Vec<_>>(),
+            [97.0]
+        );
+
+        let low = AnomalyQuery {
+            conditions: vec![MetricCondition {
+                field: MetricField::Cpu,
+                op: Operator::Lte,
+                threshold: 88.0,
+            }],
+            ..query
+        };
+        assert_eq!(store.query(&config, &low).await.len(), 1);
+
+        config.anomaly_db.retention_days = 7;
+        store.run_maintenance(&config).await;
+        assert_eq!(store.recent(&config, 10).await.len(), 2);
+        assert!(store.by_metric.iter().all(|tree| tree.len() == 2));
+        assert!(
+            events_dir.join("events-2026-10-01.jsonl").exists(),
+            "sled maintenance must leave the imported JSONL files alone"
+        );
+    }
+
+    #[tokio::test]
+    async fn flush_makes_inserts_visible_to_a_copy_of_the_store() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let source = temp.path().join("store");
+        let store = SledAnomalyStorage::open(&source).expect("open store");
+        store
+            .insert(&event(ChronoDuration::minutes(5), 91.0))
+            .expect("insert event");
+        store.flush().await.expect("flush store");
+
+        let copy = temp.path().join("copy");
+        copy_dir(&source, &copy);
+        let reopened = SledAnomalyStorage::open(&copy).expect("open copy");
+        let config = crate::test_utils::base_test_config();
+        let events = reopened.recent(&config, 10).await;
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].cpu, 91.0);
+    }
+
+    fn copy_dir(from: &std::path::Path, to: &std::path::Path) {
+        fs::create_dir_all(to).unwrap();
+        for entry in fs::read_dir(from).unwrap().flatten() {
+            let target = to.join(entry.file_name());
+            if entry.file_type().unwrap().is_dir() {
+                copy_dir(&entry.path(), &target);
+            } else {
+                fs::copy(entry.path(), target).unwrap();
+            }
+        }
+    }
+}
@@ -0,0 +1,96 @@
+use std::fs;
+
+use chrono::NaiveDate;
+
+use crate::config::Config;
+use crate::fs_usage::dir_size_bytes;
+
+use super::paths::paths_from_config;
+
+/// On-disk footprint of the anomaly DB, for `/storage`.
+#[derive(Debug, Clone, Default)]
+pub struct AnomalyDbStats {
+    pub event_files: usize,
+    /// Event files rotated out by size (`events-<day>.jsonl.<n>`).
+    pub rotated_files: usize,
+    pub index_files: usize,
+    /// Bytes in `events/`, `index/` and `meta/`.
+    pub file_bytes: u64,
+    /// Bytes of the sled store, when the `sled` backend has created one.
+    pub store_bytes: u64,
+    pub oldest_day: Option<NaiveDate>,
+    pub newest_day: Option<NaiveDate>,
+}
+
+pub fn anomaly_db_stats(config: &Config) -> AnomalyDbStats {
+    let paths = paths_from_config(config);
+    let mut stats = AnomalyDbStats {
+        store_bytes: dir_size_bytes(&paths.store_dir),
+        ..AnomalyDbStats::default()
+    };
+
+    for (dir, prefix) in [(&paths.events_dir, "events-"), (&paths.index_dir, "index-")] {
+        let Ok(entries) = fs::read_dir(dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+                continue;
+            };
+            let Some((stem, rotation)) = name
+                .strip_prefix(prefix)
+                .and_then(|tail| tail.split_once(".jsonl"))
+            else {
+                continue;
+            };
+
+            stats.file_bytes += entry.metadata().map_or(0, |meta| meta.len());
+            if prefix == "events-" {
+                stats.event_files += 1;
+                stats.rotated_files += usize::from(!rotation.is_empty());
+            } else {
+                stats.index_files += 1;
+            }
+
+            if let Ok(day) = NaiveDate::parse_from_str(stem, "%Y-%m-%d") {
+                stats.oldest_day = Some(stats.oldest_day.map_or(day, |oldest| oldest.min(day)));
+                stats.newest_day = Some(stats.newest_day.map_or(day, |newest| newest.max(day)));
+            }
+        }
+    }
+    stats.file_bytes += dir_size_bytes(&paths.meta_dir);
+
+    stats
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::anomaly_db_stats;
+
+    #[test]
+    fn counts_files_rotations_and_day_range() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let mut config = crate::test_utils::base_test_config();
+        config.anomaly_db.dir = temp.path().display().to_string();
+        let events = temp.path().join("events");
+        let index = temp.path().join("index");
+        fs::create_dir_all(&events).unwrap();
+        fs::create_dir_all(&index).unwrap();
+        fs::write(events.join("events-2026-10-01.jsonl"), "12345").unwrap();
+        fs::write(events.join("events-2026-10-01.jsonl.1"), "123").unwrap();
+        fs::write(events.join("events-2026-10-03.jsonl"), "1").unwrap();
+        fs::write(index.join("index-2026-10-03.jsonl"), "12").unwrap();
+        fs::write(index.join("notes.txt"), "ignored").unwrap();
+
+        let stats = anomaly_db_stats(&config);
+        assert_eq!(stats.event_files, 3);
+        assert_eq!(stats.rotated_files, 1);
+        assert_eq!(stats.index_files, 1);
+        assert_eq!(stats.file_bytes, 11);
+        assert_eq!(stats.oldest_day.unwrap().to_string(), "2026-10-01");
+        assert_eq!(stats.newest_day.unwrap().to_string(), "2026-10-03");
+    }
+}
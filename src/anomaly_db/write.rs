@@ -1,32 +1,39 @@
+use std::collections::HashMap;
 use std::fs::OpenOptions;
 use std::io::Write;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock, PoisonError};
 
-use chrono::{Datelike, Utc};
+use crate::config::{AnomalyCompression, Config};
+use crate::versioned::{self, Versioned};
+use chrono::{DateTime, Datelike, Utc};
 use file_rotate::{ContentLimit, FileRotate, compression::Compression, suffix::AppendCount};
-use serde::Serialize;
-
-use crate::config::Config;
 
 use super::model::{AnomalyEvent, AnomalyIndexEntry};
 use super::paths::{ensure_db_dirs, paths_from_config};
 
-pub fn record_anomaly_if_needed(config: &Config, cpu: f32, ram: f32, disk: f32) {
+/// Builds the event to persist for one sample, or `None` when the anomaly DB is
+/// disabled or no metric is over its threshold.
+pub(super) fn anomaly_event_if_needed(
+    config: &Config,
+    now: DateTime<Utc>,
+    cpu: f32,
+    ram: f32,
+    disk: f32,
+) -> Option<AnomalyEvent> {
     if !config.anomaly_db.enabled {
-        return;
+        return None;
     }
 
     let cpu_over = cpu > config.alerts.cpu;
     let ram_over = ram > config.alerts.ram;
     let disk_over = disk > config.alerts.disk;
     if !(cpu_over || ram_over || disk_over) {
-        return;
+        return None;
     }
 
-    let now = Utc::now();
-    let timestamp = now.to_rfc3339();
-    let event = AnomalyEvent {
-        timestamp: timestamp.clone(),
+    Some(AnomalyEvent {
+        timestamp: now.to_rfc3339(),
         cpu,
         ram,
         disk,
@@ -36,6 +43,79 @@ pub fn record_anomaly_if_needed(config: &Config, cpu: f32, ram: f32, disk: f32)
         cpu_over,
         ram_over,
         disk_over,
+    })
+}
+
+static DAY_WRITE_LOCKS: OnceLock<Mutex<HashMap<String, Arc<Mutex<()>>>>> = OnceLock::new();
+
+/// Lock for one day's (`YYYY-MM-DD`) event and index files. Appends hold it,
+/// and so does an integrity repair of that day, so a rebuilt index never drops
+/// an event written while the day was being scanned.
+pub(super) fn day_write_lock(day: &str) -> Arc<Mutex<()>> {
+    let mut locks = DAY_WRITE_LOCKS
+        .get_or_init(Mutex::default)
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner);
+    locks.retain(|_, lock| Arc::strong_count(lock) > 1);
+    Arc::clone(locks.entry(day.to_string()).or_default())
+}
+
+/// Upper bound on size-rotated files kept per day (`events-<day>.jsonl.N`).
+/// Overall volume is bounded by `max_total_bytes` during maintenance.
+const MAX_ROTATED_FILES_PER_DAY: usize = 64;
+
+/// Rotating writer for the current day's event file, kept open between events
+/// and rebuilt when the day or the rotation settings change.
+pub(super) struct EventWriter {
+    path: PathBuf,
+    max_file_size_bytes: u64,
+    compression: AnomalyCompression,
+    file: FileRotate<AppendCount>,
+}
+
+impl EventWriter {
+    fn open(path: &Path, max_file_size_bytes: u64, compression: AnomalyCompression) -> Self {
+        let max_bytes = usize::try_from(max_file_size_bytes).unwrap_or(usize::MAX);
+        let rotated_compression = match compression {
+            AnomalyCompression::None => Compression::None,
+            AnomalyCompression::Gzip => Compression::OnRotate(0),
+        };
+        Self {
+            path: path.to_path_buf(),
+            max_file_size_bytes,
+            compression,
+            file: FileRotate::new(
+                path,
+                AppendCount::new(MAX_ROTATED_FILES_PER_DAY),
+                ContentLimit::BytesSurpassed(max_bytes),
+                rotated_compression,
+                None,
+            ),
+        }
+    }
+
+    fn matches(
+        &self,
+        path: &Path,
+        max_file_size_bytes: u64,
+        compression: AnomalyCompression,
+    ) -> bool {
+        self.path == path
+            && self.max_file_size_bytes == max_file_size_bytes
+            && self.compression == compression
+    }
+}
+
+pub(super) fn record_anomaly_if_needed(
+    config: &Config,
+    writer: &Mutex<Option<EventWriter>>,
+    cpu: f32,
+    ram: f32,
+    disk: f32,
+) {
+    let now = Utc::now();
+    let Some(event) = anomaly_event_if_needed(config, now, cpu, ram, disk) else {
+        return;
     };
 
     let paths = paths_from_config(config);
@@ -44,89 +124,74 @@ pub fn record_anomaly_if_needed(config: &Config, cpu: f32, ram: f32, disk: f32)
         return;
     }
 
-    let events_file_name = format!(
-        "events-{:04}-{:02}-{:02}.jsonl",
-        now.year(),
-        now.month(),
-        now.day()
-    );
-    let events_path = paths.events_dir.join(&events_file_name);
-
-    if let Err(error) = append_event_with_rotation(
-        &events_path,
-        &event,
-        config.anomaly_db.max_file_size_bytes,
-        config.anomaly_db.retention_days,
-    ) {
+    let day = format!("{:04}-{:02}-{:02}", now.year(), now.month(), now.day());
+    let events_path = paths.events_dir.join(format!("events-{day}.jsonl"));
+
+    let day_lock = day_write_lock(&day);
+    let _day_guard = day_lock.lock().unwrap_or_else(PoisonError::into_inner);
+    let mut writer = writer.lock().unwrap_or_else(PoisonError::into_inner);
+    let cached = writer.take().filter(|cached| {
+        cached.matches(
+            &events_path,
+            config.anomaly_db.max_file_size_bytes,
+            config.anomaly_db.compression,
+        )
+    });
+    let writer = writer.insert(cached.unwrap_or_else(|| {
+        EventWriter::open(
+            &events_path,
+            config.anomaly_db.max_file_size_bytes,
+            config.anomaly_db.compression,
+        )
+    }));
+    if let Err(error) = append_event(writer, &event) {
         log::warn!("anomaly db: failed to write event line: {error}");
         return;
     }
 
-    let index_file_name = format!(
-        "index-{:04}-{:02}-{:02}.jsonl",
-        now.year(),
-        now.month(),
-        now.day()
-    );
-
-    let index_entry = AnomalyIndexEntry {
-        timestamp,
-        cpu,
-        ram,
-        disk,
-        cpu_threshold: config.alerts.cpu,
-        ram_threshold: config.alerts.ram,
-        disk_threshold: config.alerts.disk,
-        cpu_over,
-        ram_over,
-        disk_over,
-    };
-    let index_path = paths.index_dir.join(index_file_name);
+    let index_entry = AnomalyIndexEntry::from(event);
+    let index_path = paths.index_dir.join(format!("index-{day}.jsonl"));
     if let Err(error) = append_json_line(&index_path, &index_entry) {
         log::warn!("anomaly db: failed to write index line: {error}");
     }
 }
 
-fn append_json_line<T: Serialize>(path: &Path, value: &T) -> Result<(), std::io::Error> {
+fn append_json_line<T: Versioned>(path: &Path, value: &T) -> Result<(), std::io::Error> {
+    let mut line = versioned::encode(value).map_err(std::io::Error::other)?;
+    line.push(b'\n');
     let mut file = OpenOptions::new().create(true).append(true).open(path)?;
-    serde_json::to_writer(&mut file, value).map_err(std::io::Error::other)?;
-    file.write_all(b"\n")?;
+    file.write_all(&line)?;
     Ok(())
 }
 
-fn append_event_with_rotation(
-    path: &Path,
-    event: &AnomalyEvent,
-    max_file_size_bytes: u64,
-    retention_days: u16,
-) -> Result<(), std::io::Error> {
-    let max_bytes = usize::try_from(max_file_size_bytes).unwrap_or(usize::MAX);
-    let mut writer = FileRotate::new(
-        path,
-        AppendCount::new(retention_days as usize),
-        ContentLimit::BytesSurpassed(max_bytes),
-        Compression::None,
-        None,
-    );
-
-    serde_json::to_writer(&mut writer, event).map_err(std::io::Error::other)?;
-    writer.write_all(b"\n")?;
-    Ok(())
+fn append_event(writer: &mut EventWriter, event: &AnomalyEvent) -> Result<(), std::io::Error> {
+    let mut line = versioned::encode(event).map_err(std::io::Error::other)?;
+    line.push(b'\n');
+    writer.file.write_all(&line)?;
+    writer.file.flush()
 }
 
 #[cfg(test)]
 mod tests {
+    use std::sync::Mutex;
     use std::{fs, path::PathBuf};
 
-    use serde::Serialize;
+    use serde::{Deserialize, Serialize};
 
-    use super::append_json_line;
+    use super::{append_json_line, record_anomaly_if_needed};
+    use crate::anomaly_db::read::{is_compressed, read_jsonl_bytes};
+    use crate::versioned::Versioned;
 
-    #[derive(Serialize)]
+    #[derive(Serialize, Deserialize)]
     struct TestLine {
         value: u32,
     }
 
+    impl Versioned for TestLine {
+        const KIND: &'static str = "test_line";
+        const VERSION: u32 = 1;
+    }
+
     fn temp_file(name: &str) -> PathBuf {
         let dir = tempfile::tempdir().expect("temp dir");
         dir.keep().join(name)
@@ -147,6 +212,36 @@ mod tests {
 
         let _ = fs::remove_file(path);
     }
+
+    #[test]
+    fn cached_writer_rotates_into_gzip_files_that_read_back() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let mut config = crate::test_utils::base_test_config();
+        config.anomaly_db.dir = temp.path().display().to_string();
+        config.anomaly_db.max_file_size_bytes = 400;
+        let writer = Mutex::new(None);
+
+        for _ in 0..6 {
+            record_anomaly_if_needed(&config, &writer, 99.0, 10.0, 10.0);
+        }
+
+        let events_dir = temp.path().join("events");
+        let rotated = fs::read_dir(&events_dir)
+            .expect("events dir")
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| is_compressed(path))
+            .collect::<Vec<_>>();
+        assert!(!rotated.is_empty());
+
+        let lines = fs::read_dir(&events_dir)
+            .expect("events dir")
+            .flatten()
+            .map(|entry| read_jsonl_bytes(&entry.path()).expect("readable"))
+            .map(|bytes| bytes.iter().filter(|byte| **byte == b'\n').count())
+            .sum::<usize>();
+        assert_eq!(lines, 6);
+    }
 }
 
 // additional tests for the new async storage trait
@@ -164,6 +259,9 @@ mod storage_tests {
             dir: "logs".to_string(),
             max_file_size_bytes: 0,
             retention_days: 0,
+            backend: crate::config::AnomalyDbBackend::Jsonl,
+            compression: crate::config::AnomalyCompression::Gzip,
+            max_total_bytes: 0,
         };
 
         let store = crate::anomaly_db::InMemoryAnomalyStorage::new();
@@ -6,6 +6,7 @@ use tokio::net::lookup_host;
 use crate::app_context::AppContext;
 use crate::capabilities::Capabilities;
 use crate::config::load_config;
+use crate::i18n;
 use crate::jobs::start_background_jobs;
 
 pub struct AppRuntime {
@@ -22,18 +23,22 @@ pub async fn build_runtime(
     config
         .validate()
         .map_err(|error| format!("Configuration validation failed: {error}"))?;
+    i18n::set_language(config.language);
 
     let capabilities = Capabilities::detect();
     log_capability_warnings(&capabilities);
     log_dns_probe().await;
 
     let bot = Bot::new(&config.bot_token);
-    let app_context = Arc::new(AppContext::new(
-        config.clone(),
-        command_concurrency,
-        config_path,
-        capabilities,
-    ));
+    let app_context = Arc::new(
+        AppContext::new(
+            config.clone(),
+            command_concurrency,
+            config_path,
+            capabilities,
+        )
+        .map_err(|error| format!("Storage check failed: {error}"))?,
+    );
 
     start_background_jobs(bot.clone(), (*app_context).clone());
 
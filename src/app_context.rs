@@ -1,16 +1,19 @@
+use std::path::Path;
 use std::sync::Arc;
 
 use tokio::sync::{Notify, RwLock};
 
 use crate::{
     architecture::{
-        adapters::{FileAnomalyStorage, ReportingStoreAdapter},
-        ports::{AnomalyStoragePort, ReportingStoragePort},
+        adapters::{ReportingStoreAdapter, anomaly_storage_from_config},
+        ports::{AnomalyStoragePort, IncidentStoragePort, ReportingStoragePort},
     },
     bot_runtime::BotRuntime,
     capabilities::Capabilities,
     config::{Config, Graph, RuntimeConfig},
+    monitor::{AlertState, load_deferred_alerts},
     monitor_context::MonitorContext,
+    versioned::VersionError,
 };
 
 #[derive(Clone)]
@@ -24,6 +27,7 @@ pub struct AppContext {
     pub bot_runtime: BotRuntime,
     pub capabilities: Arc<Capabilities>,
     pub reporting_store: Arc<dyn ReportingStoragePort>,
+    pub incident_store: Arc<dyn IncidentStoragePort>,
     pub anomaly_storage: Arc<dyn AnomalyStoragePort>,
 }
 
@@ -33,25 +37,34 @@ impl AppContext {
         command_concurrency: usize,
         config_path: impl Into<String>,
         capabilities: Capabilities,
-    ) -> Self {
+    ) -> Result<Self, VersionError> {
         let monitor_interval = config.monitor_interval;
         let graph_runtime = config.graph.clone();
         let runtime_config = RuntimeConfig::from_config(&config);
-        let reporting_store = ReportingStoreAdapter::new_arc_from_config(&config);
-        let anomaly_storage: Arc<dyn AnomalyStoragePort> = Arc::new(FileAnomalyStorage::new());
+        let stores = ReportingStoreAdapter::new_arcs_from_config(&config)?;
+        let anomaly_storage: Arc<dyn AnomalyStoragePort> = anomaly_storage_from_config(&config)?;
+        let alert_state = AlertState {
+            deferred_alerts: if config.quiet_hours.enabled {
+                load_deferred_alerts(Path::new(&config.quiet_hours.state_path))
+            } else {
+                Vec::new()
+            },
+            ..AlertState::default()
+        };
 
-        Self {
+        Ok(Self {
             config,
             runtime_config: Arc::new(RwLock::new(runtime_config)),
             config_path: Arc::new(config_path.into()),
             graph_runtime: Arc::new(RwLock::new(graph_runtime)),
             runtime_update_notify: Arc::new(Notify::new()),
-            monitor: MonitorContext::new(monitor_interval),
+            monitor: MonitorContext::new(monitor_interval, alert_state),
             bot_runtime: BotRuntime::new(command_concurrency),
             capabilities: Arc::new(capabilities),
-            reporting_store,
+            reporting_store: stores.reporting,
+            incident_store: stores.incidents,
             anomaly_storage,
-        }
+        })
     }
 
     pub async fn update_graph_runtime(&self, graph: Graph) {
@@ -81,8 +94,9 @@ mod tests {
 
     use super::AppContext;
 
-    fn test_config() -> crate::config::Config {
+    fn test_config(data_dir: &std::path::Path) -> crate::config::Config {
         let mut config = base_test_config();
+        config.anomaly_db.dir = data_dir.join("anomaly_db").display().to_string();
         config.alerts.cpu = 85.0;
         config.alerts.ram = 90.0;
         config.alerts.disk = 90.0;
@@ -97,7 +111,14 @@ mod tests {
 
     #[tokio::test]
     async fn runtime_update_triggers_notify() {
-        let app = AppContext::new(test_config(), 2, "config.toml", Capabilities::detect());
+        let temp = tempfile::tempdir().expect("temp dir");
+        let app = AppContext::new(
+            test_config(temp.path()),
+            2,
+            "config.toml",
+            Capabilities::detect(),
+        )
+        .expect("app context");
         let notify = app.runtime_update_notify.clone();
 
         let wait = tokio::spawn(async move { notify.notified().await });
@@ -119,7 +140,14 @@ mod tests {
 
     #[tokio::test]
     async fn runtime_config_concurrent_reads_and_updates_remain_consistent() {
-        let app = AppContext::new(test_config(), 2, "config.toml", Capabilities::detect());
+        let temp = tempfile::tempdir().expect("temp dir");
+        let app = AppContext::new(
+            test_config(temp.path()),
+            2,
+            "config.toml",
+            Capabilities::detect(),
+        )
+        .expect("app context");
 
         let writer_app = app.clone();
         let writer = tokio::spawn(async move {
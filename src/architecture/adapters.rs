@@ -1,8 +1,11 @@
 #![allow(unused_imports)]
 
-pub use crate::anomaly_db::FileAnomalyStorage;
+pub use crate::anomaly_db::{FileAnomalyStorage, SledAnomalyStorage, anomaly_storage_from_config};
 pub use crate::monitor::{TeloxideNotifier, new_metrics_provider};
-pub use crate::reporting_store::{NullReportingStorage, ReportingStore as ReportingStoreAdapter};
+pub use crate::reporting_store::{
+    InMemoryIncidentStorage, IncidentStore as IncidentStoreAdapter, NullReportingStorage,
+    ReportingStore as ReportingStoreAdapter,
+};
 
 #[cfg(test)]
 pub use crate::monitor::{SentItem, SpyNotifier};
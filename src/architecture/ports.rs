@@ -1,7 +1,8 @@
 #![allow(unused_imports)]
 
 pub use crate::contracts::{
-    AnomalyStorage as AnomalyStoragePort, MetricsProvider as MetricsProviderPort,
-    Notifier as NotifierPort, ReportingStorage as ReportingStoragePort,
+    AnomalyStorage as AnomalyStoragePort, IncidentStorage as IncidentStoragePort,
+    MetricsProvider as MetricsProviderPort, Notifier as NotifierPort,
+    ReportingStorage as ReportingStoragePort,
 };
-pub use crate::monitor::MuteActionError;
+pub use crate::monitor::{MediaPhoto, MuteActionError};
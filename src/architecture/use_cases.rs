@@ -1,8 +1,10 @@
 #![allow(unused_imports)]
 
 pub use crate::monitor::{
-    CheckAlertsContext, DailySummaryReport, alert_snapshot as alert_snapshot_use_case,
-    check_alerts as check_alerts_use_case, mute_alerts_for as mute_alerts_use_case,
+    CheckAlertsContext, DailySummaryReport, DeferredAlert,
+    acknowledge_incident as acknowledge_incident_use_case,
+    alert_snapshot as alert_snapshot_use_case, check_alerts as check_alerts_use_case,
+    mute_alerts_for as mute_alerts_use_case,
     take_daily_summary_report as take_daily_summary_report_use_case,
-    unmute_alerts as unmute_alerts_use_case,
+    take_deferred_alerts as take_deferred_alerts_use_case, unmute_alerts as unmute_alerts_use_case,
 };
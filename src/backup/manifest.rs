@@ -0,0 +1,148 @@
+use std::io::Read;
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use super::BackupError;
+
+pub(super) const MANIFEST_FILE_NAME: &str = "manifest.json";
+/// Highest archive layout this build can restore.
+pub(super) const MANIFEST_FORMAT_VERSION: u32 = 1;
+
+/// Describes every data file in a backup archive so a restore can verify it
+/// before touching live data.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(super) struct BackupManifest {
+    pub format_version: u32,
+    pub bot_version: String,
+    pub created_at_utc: String,
+    /// Archive roots (e.g. `reporting_store`) that a restore replaces.
+    pub components: Vec<String>,
+    pub files: Vec<ManifestFile>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(super) struct ManifestFile {
+    /// Path inside the archive, relative to `data/`.
+    pub path: String,
+    pub size_bytes: u64,
+    pub sha256: String,
+}
+
+#[cfg(test)]
+impl ManifestFile {
+    pub(super) fn from_bytes(path: String, bytes: &[u8]) -> Self {
+        Self {
+            path,
+            size_bytes: bytes.len() as u64,
+            sha256: sha256_hex(bytes),
+        }
+    }
+}
+
+/// Passes a file's bytes through while hashing them, so archiving and
+/// verification stream files instead of loading them whole.
+pub(super) struct HashingReader<R> {
+    inner: R,
+    hasher: Sha256,
+    bytes: u64,
+}
+
+impl<R: Read> HashingReader<R> {
+    pub(super) fn new(inner: R) -> Self {
+        Self {
+            inner,
+            hasher: Sha256::new(),
+            bytes: 0,
+        }
+    }
+
+    /// Bytes read so far.
+    pub(super) fn bytes_read(&self) -> u64 {
+        self.bytes
+    }
+
+    pub(super) fn into_manifest_file(self, path: String) -> ManifestFile {
+        ManifestFile {
+            path,
+            size_bytes: self.bytes,
+            sha256: format!("{:x}", self.hasher.finalize()),
+        }
+    }
+}
+
+impl<R: Read> Read for HashingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        self.hasher.update(&buf[..read]);
+        self.bytes += read as u64;
+        Ok(read)
+    }
+}
+
+impl BackupManifest {
+    pub(super) fn parse(raw: &[u8]) -> Result<Self, BackupError> {
+        let manifest: Self = serde_json::from_slice(raw)
+            .map_err(|error| BackupError::Manifest(format!("unreadable manifest: {error}")))?;
+        if manifest.format_version == 0 || manifest.format_version > MANIFEST_FORMAT_VERSION {
+            return Err(BackupError::Manifest(format!(
+                "unsupported format_version {} (this build reads up to {MANIFEST_FORMAT_VERSION})",
+                manifest.format_version
+            )));
+        }
+        if manifest.components.is_empty() {
+            return Err(BackupError::Manifest("no components listed".to_string()));
+        }
+        for file in &manifest.files {
+            if !manifest
+                .components
+                .iter()
+                .any(|component| is_within(&file.path, component))
+            {
+                return Err(BackupError::Manifest(format!(
+                    "file {} is outside the listed components",
+                    file.path
+                )));
+            }
+        }
+        Ok(manifest)
+    }
+}
+
+#[cfg(test)]
+fn sha256_hex(bytes: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(bytes))
+}
+
+pub(super) fn is_within(path: &str, component: &str) -> bool {
+    path == component
+        || path
+            .strip_prefix(component)
+            .is_some_and(|rest| rest.starts_with('/'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BackupManifest, ManifestFile, is_within};
+
+    fn manifest_json(format_version: u32, file_path: &str) -> Vec<u8> {
+        serde_json::to_vec(&BackupManifest {
+            format_version,
+            bot_version: "1.0.0".to_string(),
+            created_at_utc: "2026-10-18T12:00:00+00:00".to_string(),
+            components: vec!["reporting_store".to_string()],
+            files: vec![ManifestFile::from_bytes(file_path.to_string(), b"data")],
+        })
+        .expect("serialize manifest")
+    }
+
+    #[test]
+    fn rejects_future_versions_and_stray_files() {
+        assert!(BackupManifest::parse(&manifest_json(1, "reporting_store/db")).is_ok());
+        assert!(BackupManifest::parse(&manifest_json(2, "reporting_store/db")).is_err());
+        assert!(BackupManifest::parse(&manifest_json(1, "reporting_store_old/db")).is_err());
+        assert!(BackupManifest::parse(b"not json").is_err());
+        assert!(is_within("anomaly_db/events/a.jsonl", "anomaly_db/events"));
+        assert!(!is_within("anomaly_db/eventsx", "anomaly_db/events"));
+    }
+}
@@ -0,0 +1,326 @@
+mod manifest;
+mod restore;
+
+use std::fs;
+use std::io::{ErrorKind, Read, Write};
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use thiserror::Error;
+
+use crate::anomaly_db::paths_from_config;
+use crate::config::Config;
+
+use manifest::{
+    BackupManifest, HashingReader, MANIFEST_FILE_NAME, MANIFEST_FORMAT_VERSION, ManifestFile,
+};
+
+pub use restore::restore_backup;
+
+const ARCHIVE_PREFIX: &str = "kars_bot-backup-";
+const ARCHIVE_SUFFIX: &str = ".tar.xz";
+const DATA_ROOT: &str = "data";
+const XZ_LEVEL: u32 = 6;
+/// Archive names tried within one second before giving up.
+const MAX_NAME_ATTEMPTS: u32 = 100;
+
+#[derive(Debug, Error)]
+pub enum BackupError {
+    #[error("backup io error at {path}: {source}")]
+    Io {
+        path: String,
+        source: std::io::Error,
+    },
+    #[error("invalid backup manifest: {0}")]
+    Manifest(String),
+    #[error("backup verification failed: {0}")]
+    Verification(String),
+    #[error("nothing to back up: no data directories exist yet")]
+    Empty,
+}
+
+impl BackupError {
+    fn io(path: &Path, source: std::io::Error) -> Self {
+        Self::Io {
+            path: path.display().to_string(),
+            source,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BackupSummary {
+    pub archive_path: PathBuf,
+    pub archive_bytes: u64,
+    pub file_count: usize,
+    pub components: Vec<String>,
+}
+
+/// A piece of durable state and where it lives inside the archive.
+struct BackupSource {
+    archive_root: String,
+    path: PathBuf,
+}
+
+fn backup_sources(config: &Config) -> Vec<BackupSource> {
+    let db_paths = paths_from_config(config);
+    let file_root = |component: &str, path: &str| BackupSource {
+        archive_root: format!(
+            "{component}/{}",
+            Path::new(path)
+                .file_name()
+                .map_or_else(|| "state.json".into(), |name| name.to_string_lossy())
+        ),
+        path: PathBuf::from(path),
+    };
+
+    vec![
+        BackupSource {
+            archive_root: "reporting_store".to_string(),
+            path: PathBuf::from(&config.reporting_store.path),
+        },
+        BackupSource {
+            archive_root: "anomaly_db/events".to_string(),
+            path: db_paths.events_dir,
+        },
+        BackupSource {
+            archive_root: "anomaly_db/index".to_string(),
+            path: db_paths.index_dir,
+        },
+        BackupSource {
+            archive_root: "anomaly_db/meta".to_string(),
+            path: db_paths.meta_dir,
+        },
+        BackupSource {
+            archive_root: "anomaly_db/store".to_string(),
+            path: db_paths.store_dir,
+        },
+        file_root("release_notifier", &config.release_notifier.state_path),
+        file_root("scheduler", &config.scheduler.state_path),
+    ]
+}
+
+/// Writes a tar.xz of all durable state into `output_dir` (defaults to
+/// `backup.dir`) and prunes archives beyond `backup.keep_last`.
+///
+/// Callers holding the reporting store or the sled anomaly store open should
+/// flush both first. Files are copied one by one while the bot keeps
+/// writing, so the newest writes may be missing or torn; stop the bot for an
+/// exact copy.
+pub fn create_backup(
+    config: &Config,
+    output_dir: Option<&Path>,
+) -> Result<BackupSummary, BackupError> {
+    let output_dir =
+        output_dir.map_or_else(|| PathBuf::from(&config.backup.dir), Path::to_path_buf);
+    fs::create_dir_all(&output_dir).map_err(|error| BackupError::io(&output_dir, error))?;
+
+    let now = Utc::now();
+    let (archive_path, tmp_path, file) = reserve_archive(&output_dir, now)?;
+    let mut builder = tar::Builder::new(xz2::write::XzEncoder::new(file, XZ_LEVEL));
+
+    let mut manifest = BackupManifest {
+        format_version: MANIFEST_FORMAT_VERSION,
+        bot_version: env!("CARGO_PKG_VERSION").to_string(),
+        created_at_utc: now.to_rfc3339(),
+        components: Vec::new(),
+        files: Vec::new(),
+    };
+
+    for source in backup_sources(config) {
+        if !source.path.exists() {
+            continue;
+        }
+        for (relative, path) in collect_files(&source.path)? {
+            let archive_path = if relative.is_empty() {
+                source.archive_root.clone()
+            } else {
+                format!("{}/{relative}", source.archive_root)
+            };
+            let file = append_file(
+                &mut builder,
+                &format!("{DATA_ROOT}/{archive_path}"),
+                archive_path,
+                &path,
+            )
+            .map_err(|error| BackupError::io(&path, error))?;
+            manifest.files.push(file);
+        }
+        manifest.components.push(source.archive_root);
+    }
+
+    if manifest.components.is_empty() {
+        drop(builder);
+        let _ = fs::remove_file(&tmp_path);
+        return Err(BackupError::Empty);
+    }
+
+    let manifest_bytes = serde_json::to_vec_pretty(&manifest)
+        .map_err(|error| BackupError::Manifest(error.to_string()))?;
+    append_bytes(&mut builder, MANIFEST_FILE_NAME, &manifest_bytes)
+        .map_err(|error| BackupError::io(&tmp_path, error))?;
+    builder
+        .into_inner()
+        .and_then(xz2::write::XzEncoder::finish)
+        .and_then(|file| file.sync_all())
+        .map_err(|error| BackupError::io(&tmp_path, error))?;
+    fs::rename(&tmp_path, &archive_path).map_err(|error| BackupError::io(&archive_path, error))?;
+
+    prune_old_archives(&output_dir, config.backup.keep_last);
+
+    let archive_bytes = fs::metadata(&archive_path)
+        .map_err(|error| BackupError::io(&archive_path, error))?
+        .len();
+    log::info!(
+        "backup_created path={} bytes={archive_bytes} files={}",
+        archive_path.display(),
+        manifest.files.len()
+    );
+
+    Ok(BackupSummary {
+        archive_path,
+        archive_bytes,
+        file_count: manifest.files.len(),
+        components: manifest.components,
+    })
+}
+
+/// Picks an unused archive name for `now` and creates its temporary file with
+/// `create_new`, so backups started within the same second (e.g. the CLI next
+/// to `/backup`) never share a file. Retries append `_NN`, which sorts after
+/// the plain name when pruning.
+fn reserve_archive(
+    output_dir: &Path,
+    now: DateTime<Utc>,
+) -> Result<(PathBuf, PathBuf, fs::File), BackupError> {
+    let stamp = now.format("%Y%m%dT%H%M%SZ");
+    for attempt in 0..MAX_NAME_ATTEMPTS {
+        let unique = if attempt == 0 {
+            String::new()
+        } else {
+            format!("_{attempt:02}")
+        };
+        let archive_path =
+            output_dir.join(format!("{ARCHIVE_PREFIX}{stamp}{unique}{ARCHIVE_SUFFIX}"));
+        if archive_path.exists() {
+            continue;
+        }
+        let tmp_path = archive_path.with_extension("xz.tmp");
+        match fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&tmp_path)
+        {
+            Ok(file) => return Ok((archive_path, tmp_path, file)),
+            Err(error) if error.kind() == ErrorKind::AlreadyExists => {}
+            Err(error) => return Err(BackupError::io(&tmp_path, error)),
+        }
+    }
+    Err(BackupError::io(
+        output_dir,
+        std::io::Error::new(ErrorKind::AlreadyExists, "no free backup archive name"),
+    ))
+}
+
+fn append_bytes<W: Write>(
+    builder: &mut tar::Builder<W>,
+    path: &str,
+    bytes: &[u8],
+) -> std::io::Result<()> {
+    builder.append_data(&mut entry_header(bytes.len() as u64), path, bytes)
+}
+
+/// Streams the file at `source` into the archive under `path` and returns
+/// its manifest entry, listed as `manifest_path`.
+fn append_file<W: Write>(
+    builder: &mut tar::Builder<W>,
+    path: &str,
+    manifest_path: String,
+    source: &Path,
+) -> std::io::Result<ManifestFile> {
+    let file = fs::File::open(source)?;
+    let size = file.metadata()?.len();
+    // The header announces `size`; a file that grows meanwhile is cut there.
+    let mut reader = HashingReader::new(file.take(size));
+    builder.append_data(&mut entry_header(size), path, &mut reader)?;
+    if reader.bytes_read() != size {
+        return Err(std::io::Error::new(
+            ErrorKind::UnexpectedEof,
+            "file shrank while it was archived",
+        ));
+    }
+    Ok(reader.into_manifest_file(manifest_path))
+}
+
+fn entry_header(size: u64) -> tar::Header {
+    let mut header = tar::Header::new_gnu();
+    header.set_size(size);
+    header.set_mode(0o640);
+    header.set_mtime(Utc::now().timestamp().max(0) as u64);
+    header.set_cksum();
+    header
+}
+
+/// Regular files under `root` as (`/`-separated relative path, absolute path),
+/// sorted for reproducible archives. A file root yields a single empty path.
+fn collect_files(root: &Path) -> Result<Vec<(String, PathBuf)>, BackupError> {
+    if root.is_file() {
+        return Ok(vec![(String::new(), root.to_path_buf())]);
+    }
+
+    let mut files = Vec::new();
+    let mut pending = vec![root.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let entries = fs::read_dir(&dir).map_err(|error| BackupError::io(&dir, error))?;
+        for entry in entries {
+            let entry = entry.map_err(|error| BackupError::io(&dir, error))?;
+            let path = entry.path();
+            let file_type = entry
+                .file_type()
+                .map_err(|error| BackupError::io(&path, error))?;
+            if file_type.is_dir() {
+                pending.push(path);
+            } else if file_type.is_file() {
+                let relative = path
+                    .strip_prefix(root)
+                    .unwrap_or(&path)
+                    .components()
+                    .map(|part| part.as_os_str().to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join("/");
+                files.push((relative, path));
+            }
+        }
+    }
+
+    files.sort();
+    Ok(files)
+}
+
+fn prune_old_archives(dir: &Path, keep_last: usize) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    let mut archives = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| {
+                    name.starts_with(ARCHIVE_PREFIX) && name.ends_with(ARCHIVE_SUFFIX)
+                })
+        })
+        .collect::<Vec<_>>();
+    archives.sort();
+
+    let excess = archives.len().saturating_sub(keep_last);
+    for path in archives.into_iter().take(excess) {
+        if let Err(error) = fs::remove_file(&path) {
+            log::warn!("backup_prune_failed path={} error={error}", path.display());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,260 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chrono::Utc;
+
+use crate::config::Config;
+
+use super::manifest::{BackupManifest, HashingReader, MANIFEST_FILE_NAME};
+use super::{BackupError, DATA_ROOT, backup_sources, collect_files};
+
+#[derive(Debug, Clone)]
+pub struct RestoreSummary {
+    pub components: Vec<String>,
+    pub file_count: usize,
+    pub created_at_utc: String,
+    /// Suffix appended to the data that was replaced, kept next to it.
+    pub previous_data_suffix: String,
+}
+
+/// Restores a `create_backup` archive. The archive is extracted to a staging
+/// directory and checked against its manifest before any live data is moved;
+/// replaced data is kept alongside with a `.pre-restore-*` suffix. If a
+/// component cannot be moved into place, the components already swapped are
+/// put back so the live data is left as it was.
+///
+/// The bot must not be running: the reporting store holds an exclusive lock.
+pub fn restore_backup(config: &Config, archive: &Path) -> Result<RestoreSummary, BackupError> {
+    let staging_parent = PathBuf::from(&config.backup.dir);
+    fs::create_dir_all(&staging_parent).map_err(|error| BackupError::io(&staging_parent, error))?;
+    let staging = tempfile::Builder::new()
+        .prefix(".restore-")
+        .tempdir_in(&staging_parent)
+        .map_err(|error| BackupError::io(&staging_parent, error))?;
+
+    extract_archive(archive, staging.path())?;
+    let manifest_path = staging.path().join(MANIFEST_FILE_NAME);
+    let manifest = BackupManifest::parse(
+        &fs::read(&manifest_path).map_err(|error| BackupError::io(&manifest_path, error))?,
+    )?;
+    let data_root = staging.path().join(DATA_ROOT);
+    verify_files(&manifest, &data_root)?;
+
+    let targets = backup_sources(config)
+        .into_iter()
+        .map(|source| (source.archive_root, source.path))
+        .collect::<BTreeMap<_, _>>();
+    let mut plan = Vec::with_capacity(manifest.components.len());
+    for component in &manifest.components {
+        let target = targets
+            .get(component)
+            .ok_or_else(|| BackupError::Manifest(format!("unknown component {component}")))?;
+        plan.push((data_root.join(component), target.clone()));
+    }
+
+    let suffix = format!("pre-restore-{}", Utc::now().format("%Y%m%dT%H%M%SZ"));
+    let mut swapped = Vec::with_capacity(plan.len());
+    for (staged, target) in plan {
+        match replace_path(&staged, &target, &suffix) {
+            Ok(swap) => swapped.push(swap),
+            Err(error) => {
+                roll_back(swapped);
+                return Err(error);
+            }
+        }
+    }
+
+    log::info!(
+        "backup_restored archive={} components={} files={}",
+        archive.display(),
+        manifest.components.join(","),
+        manifest.files.len()
+    );
+
+    Ok(RestoreSummary {
+        components: manifest.components,
+        file_count: manifest.files.len(),
+        created_at_utc: manifest.created_at_utc,
+        previous_data_suffix: suffix,
+    })
+}
+
+fn extract_archive(archive: &Path, staging: &Path) -> Result<(), BackupError> {
+    let file = fs::File::open(archive).map_err(|error| BackupError::io(archive, error))?;
+    let mut tar = tar::Archive::new(xz2::read::XzDecoder::new(file));
+    let entries = tar
+        .entries()
+        .map_err(|error| BackupError::io(archive, error))?;
+
+    for entry in entries {
+        let mut entry = entry.map_err(|error| BackupError::io(archive, error))?;
+        let path = entry
+            .path()
+            .map_err(|error| BackupError::io(archive, error))?
+            .to_string_lossy()
+            .into_owned();
+        let allowed = path == MANIFEST_FILE_NAME || path.starts_with(&format!("{DATA_ROOT}/"));
+        if !allowed || !entry.header().entry_type().is_file() {
+            return Err(BackupError::Verification(format!(
+                "unexpected archive entry {path}"
+            )));
+        }
+        let unpacked = entry
+            .unpack_in(staging)
+            .map_err(|error| BackupError::io(archive, error))?;
+        if !unpacked {
+            return Err(BackupError::Verification(format!(
+                "archive entry {path} escapes the staging directory"
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+fn verify_files(manifest: &BackupManifest, data_root: &Path) -> Result<(), BackupError> {
+    let extracted = if data_root.exists() {
+        collect_files(data_root)?
+    } else {
+        Vec::new()
+    };
+    let extracted_paths = extracted
+        .iter()
+        .map(|(relative, _)| relative.as_str())
+        .collect::<BTreeSet<_>>();
+    let listed_paths = manifest
+        .files
+        .iter()
+        .map(|file| file.path.as_str())
+        .collect::<BTreeSet<_>>();
+    if extracted_paths != listed_paths {
+        let unlisted = extracted_paths.difference(&listed_paths).count();
+        let missing = listed_paths.difference(&extracted_paths).count();
+        return Err(BackupError::Verification(format!(
+            "archive contents differ from manifest (missing={missing} unlisted={unlisted})"
+        )));
+    }
+
+    for file in &manifest.files {
+        let path = data_root.join(&file.path);
+        let mut reader = HashingReader::new(
+            fs::File::open(&path).map_err(|error| BackupError::io(&path, error))?,
+        );
+        std::io::copy(&mut reader, &mut std::io::sink())
+            .map_err(|error| BackupError::io(&path, error))?;
+        let actual = reader.into_manifest_file(file.path.clone());
+        if actual.size_bytes != file.size_bytes || actual.sha256 != file.sha256 {
+            return Err(BackupError::Verification(format!(
+                "checksum mismatch for {}",
+                file.path
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+/// A component moved into place, with where its previous data went.
+struct Swap {
+    target: PathBuf,
+    previous: Option<PathBuf>,
+}
+
+/// Moves `staged` into `target`. The new data is first placed next to the
+/// target, so the final step is a rename within one directory; on failure
+/// the live target is left untouched.
+fn replace_path(staged: &Path, target: &Path, suffix: &str) -> Result<Swap, BackupError> {
+    if let Some(parent) = target.parent()
+        && !parent.as_os_str().is_empty()
+    {
+        fs::create_dir_all(parent).map_err(|error| BackupError::io(parent, error))?;
+    }
+
+    let incoming = with_suffix(target, "restore-incoming");
+    remove_path(&incoming);
+    let placed = if !staged.exists() {
+        // Component was empty when backed up; recreate it as an empty directory.
+        fs::create_dir_all(&incoming).map_err(|error| BackupError::io(&incoming, error))
+    } else if fs::rename(staged, &incoming).is_ok() {
+        Ok(())
+    } else {
+        // Staging may live on another filesystem; fall back to copying.
+        copy_recursive(staged, &incoming)
+    };
+    if let Err(error) = placed {
+        remove_path(&incoming);
+        return Err(error);
+    }
+
+    let previous = if target.exists() {
+        let previous = with_suffix(target, suffix);
+        if let Err(error) = fs::rename(target, &previous) {
+            remove_path(&incoming);
+            return Err(BackupError::io(target, error));
+        }
+        Some(previous)
+    } else {
+        None
+    };
+
+    if let Err(error) = fs::rename(&incoming, target) {
+        remove_path(&incoming);
+        if let Some(previous) = &previous {
+            let _ = fs::rename(previous, target);
+        }
+        return Err(BackupError::io(target, error));
+    }
+
+    Ok(Swap {
+        target: target.to_path_buf(),
+        previous,
+    })
+}
+
+/// Undoes completed swaps, newest first, after a later component failed.
+fn roll_back(swapped: Vec<Swap>) {
+    for swap in swapped.into_iter().rev() {
+        remove_path(&swap.target);
+        if let Some(previous) = swap.previous
+            && let Err(error) = fs::rename(&previous, &swap.target)
+        {
+            log::error!(
+                "backup_restore_rollback_failed target={} previous={} error={error}",
+                swap.target.display(),
+                previous.display()
+            );
+        }
+    }
+}
+
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(format!(".{suffix}"));
+    PathBuf::from(name)
+}
+
+fn remove_path(path: &Path) {
+    let _ = if path.is_dir() {
+        fs::remove_dir_all(path)
+    } else {
+        fs::remove_file(path)
+    };
+}
+
+fn copy_recursive(from: &Path, to: &Path) -> Result<(), BackupError> {
+    if from.is_file() {
+        return fs::copy(from, to)
+            .map(|_| ())
+            .map_err(|error| BackupError::io(to, error));
+    }
+
+    for (relative, path) in collect_files(from)? {
+        let dest = to.join(relative);
+        if let Some(parent) = dest.parent() {
+            fs::create_dir_all(parent).map_err(|error| BackupError::io(parent, error))?;
+        }
+        fs::copy(&path, &dest).map_err(|error| BackupError::io(&dest, error))?;
+    }
+    Ok(())
+}
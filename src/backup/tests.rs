@@ -0,0 +1,160 @@
+use std::fs;
+use std::path::Path;
+
+use super::manifest::{BackupManifest, MANIFEST_FILE_NAME, ManifestFile};
+use super::{BackupError, append_bytes, create_backup, restore_backup};
+use crate::config::Config;
+
+fn config_in(root: &Path) -> Config {
+    let mut config = crate::test_utils::base_test_config();
+    let path = |name: &str| root.join(name).display().to_string();
+    config.reporting_store.path = path("reporting_store");
+    config.anomaly_db.dir = path("anomaly_db");
+    config.release_notifier.state_path = path("release_state.json");
+    config.scheduler.state_path = path("scheduler/state.json");
+    config.backup.dir = path("backups");
+    config.backup.keep_last = 2;
+    config
+}
+
+fn write(path: &Path, contents: &str) {
+    fs::create_dir_all(path.parent().expect("parent")).expect("create parent");
+    fs::write(path, contents).expect("write file");
+}
+
+#[test]
+fn backup_round_trips_through_restore() {
+    let temp = tempfile::tempdir().expect("temp dir");
+    let config = config_in(temp.path());
+    write(&temp.path().join("reporting_store/db"), "sled-bytes");
+    write(
+        &temp
+            .path()
+            .join("anomaly_db/events/events-2026-10-18.jsonl"),
+        "{\"cpu\":95}\n",
+    );
+    write(&temp.path().join("release_state.json"), "{\"v\":\"1.8.2\"}");
+
+    let summary = create_backup(&config, None).expect("backup succeeds");
+    assert_eq!(summary.file_count, 3);
+    assert!(summary.components.contains(&"reporting_store".to_string()));
+    assert!(
+        !summary
+            .components
+            .iter()
+            .any(|component| component.starts_with("scheduler"))
+    );
+
+    write(&temp.path().join("reporting_store/db"), "corrupted");
+    write(&temp.path().join("reporting_store/extra"), "stray");
+
+    let restored = restore_backup(&config, &summary.archive_path).expect("restore succeeds");
+    assert_eq!(restored.file_count, 3);
+    assert_eq!(
+        fs::read_to_string(temp.path().join("reporting_store/db")).unwrap(),
+        "sled-bytes"
+    );
+    assert!(!temp.path().join("reporting_store/extra").exists());
+    let previous = temp.path().join(format!(
+        "reporting_store.{}/db",
+        restored.previous_data_suffix
+    ));
+    assert_eq!(fs::read_to_string(previous).unwrap(), "corrupted");
+}
+
+#[test]
+fn restore_rejects_checksum_mismatch_without_touching_data() {
+    let temp = tempfile::tempdir().expect("temp dir");
+    let config = config_in(temp.path());
+    write(&temp.path().join("reporting_store/db"), "live");
+
+    let manifest = BackupManifest {
+        format_version: 1,
+        bot_version: "test".to_string(),
+        created_at_utc: "2026-10-18T12:00:00+00:00".to_string(),
+        components: vec!["reporting_store".to_string()],
+        files: vec![ManifestFile::from_bytes(
+            "reporting_store/db".to_string(),
+            b"original",
+        )],
+    };
+    let archive = temp.path().join("tampered.tar.xz");
+    let file = fs::File::create(&archive).expect("create archive");
+    let mut builder = tar::Builder::new(xz2::write::XzEncoder::new(file, 1));
+    append_bytes(&mut builder, "data/reporting_store/db", b"tampered").unwrap();
+    append_bytes(
+        &mut builder,
+        MANIFEST_FILE_NAME,
+        &serde_json::to_vec(&manifest).unwrap(),
+    )
+    .unwrap();
+    builder.into_inner().unwrap().finish().unwrap();
+
+    let error = restore_backup(&config, &archive).expect_err("restore must fail");
+    assert!(matches!(error, BackupError::Verification(_)));
+    assert_eq!(
+        fs::read_to_string(temp.path().join("reporting_store/db")).unwrap(),
+        "live"
+    );
+}
+
+#[test]
+fn failed_restore_puts_swapped_components_back() {
+    let temp = tempfile::tempdir().expect("temp dir");
+    let config = config_in(temp.path());
+    write(&temp.path().join("reporting_store/db"), "sled-bytes");
+    write(&temp.path().join("scheduler/state.json"), "{}");
+    let summary = create_backup(&config, None).expect("backup succeeds");
+
+    write(&temp.path().join("reporting_store/db"), "live");
+    // The scheduler component cannot be placed once its parent is a file.
+    fs::remove_dir_all(temp.path().join("scheduler")).unwrap();
+    fs::write(temp.path().join("scheduler"), "not a directory").unwrap();
+
+    restore_backup(&config, &summary.archive_path).expect_err("restore must fail");
+    assert_eq!(
+        fs::read_to_string(temp.path().join("reporting_store/db")).unwrap(),
+        "live"
+    );
+    let leftovers = fs::read_dir(temp.path())
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+        .filter(|name| name.starts_with("reporting_store."))
+        .collect::<Vec<_>>();
+    assert!(leftovers.is_empty(), "{leftovers:?}");
+}
+
+#[test]
+fn keeps_only_the_newest_archives() {
+    let temp = tempfile::tempdir().expect("temp dir");
+    let config = config_in(temp.path());
+    let backups = temp.path().join("backups");
+    for stamp in ["20260101T000000Z", "20260102T000000Z", "20260103T000000Z"] {
+        write(
+            &backups.join(format!("kars_bot-backup-{stamp}.tar.xz")),
+            "old",
+        );
+    }
+    write(&temp.path().join("reporting_store/db"), "data");
+
+    create_backup(&config, None).expect("backup succeeds");
+    let mut remaining = fs::read_dir(&backups)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+        .collect::<Vec<_>>();
+    remaining.sort();
+    assert_eq!(remaining.len(), 2);
+    assert_eq!(remaining[0], "kars_bot-backup-20260103T000000Z.tar.xz");
+}
+
+#[test]
+fn backups_within_one_second_get_distinct_archives() {
+    let temp = tempfile::tempdir().expect("temp dir");
+    let config = config_in(temp.path());
+    write(&temp.path().join("reporting_store/db"), "data");
+
+    let first = create_backup(&config, None).expect("first backup");
+    let second = create_backup(&config, None).expect("second backup");
+    assert_ne!(first.archive_path, second.archive_path);
+    assert!(first.archive_path.exists() && second.archive_path.exists());
+}
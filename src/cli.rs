@@ -0,0 +1,82 @@
+use std::path::Path;
+
+use crate::backup::{create_backup, restore_backup};
+use crate::config::load_config;
+
+const CLI_USAGE: &str = "Usage:
+  kars_bot                        run the bot
+  kars_bot backup [<output_dir>]  write a tar.xz backup of bot data
+  kars_bot restore <archive>      restore bot data (stop the service first)";
+
+/// Runs a maintenance subcommand when one is given. Returns the process exit
+/// code, or `None` when the bot should start normally.
+pub fn run_subcommand(args: &[String], config_path: &str) -> Option<i32> {
+    let subcommand = args.get(1)?;
+    let code = match (subcommand.as_str(), args.get(2), args.len()) {
+        ("backup", output_dir, 2 | 3) => run_backup(config_path, output_dir.map(Path::new)),
+        ("restore", Some(archive), 3) => run_restore(config_path, Path::new(archive)),
+        ("help" | "--help" | "-h", None, 2) => {
+            println!("{CLI_USAGE}");
+            0
+        }
+        _ => {
+            eprintln!("{CLI_USAGE}");
+            2
+        }
+    };
+    Some(code)
+}
+
+fn run_backup(config_path: &str, output_dir: Option<&Path>) -> i32 {
+    let config = match load_config(config_path) {
+        Ok(config) => config,
+        Err(error) => {
+            eprintln!("Configuration error: {error}");
+            return 1;
+        }
+    };
+
+    match create_backup(&config, output_dir) {
+        Ok(summary) => {
+            println!(
+                "Backup written to {} ({} bytes, {} files, components: {})",
+                summary.archive_path.display(),
+                summary.archive_bytes,
+                summary.file_count,
+                summary.components.join(", ")
+            );
+            0
+        }
+        Err(error) => {
+            eprintln!("Backup failed: {error}");
+            1
+        }
+    }
+}
+
+fn run_restore(config_path: &str, archive: &Path) -> i32 {
+    let config = match load_config(config_path) {
+        Ok(config) => config,
+        Err(error) => {
+            eprintln!("Configuration error: {error}");
+            return 1;
+        }
+    };
+
+    match restore_backup(&config, archive) {
+        Ok(summary) => {
+            println!(
+                "Restored {} files from backup created at {} (components: {}). Previous data kept with suffix .{}",
+                summary.file_count,
+                summary.created_at_utc,
+                summary.components.join(", "),
+                summary.previous_data_suffix
+            );
+            0
+        }
+        Err(error) => {
+            eprintln!("Restore failed: {error}");
+            1
+        }
+    }
+}
@@ -34,20 +34,39 @@ pub enum MyCommands {
         description = "Smart recent query. Examples: /recent, /recent 5, /recent 6h, /recent cpu>85"
     )]
     Recent(String),
-    #[command(description = "Render metric graph. Usage: /graph cpu|ram|disk [30m|1h|6h|24h]")]
+    #[command(
+        description = "Render metric graph. Usage: /graph cpu|ram|disk[,ram,...] [30m|1h|6h|24h|2026-10-01..2026-10-03] [vs 7d-ago] [dark] [1600x600] [svg]"
+    )]
     Graph(String),
     #[command(
-        description = "Export metric snapshot. Usage: /export cpu|ram|disk [30m|1h|6h|24h] [csv|json]"
+        description = "Render a weekday x hour heatmap. Usage: /heatmap cpu|ram|disk [7d|4w] [avg|p95] [dark] [svg]"
+    )]
+    Heatmap(String),
+    #[command(description = "Show sparklines of all metrics. Usage: /spark [30m|2h]")]
+    Spark(String),
+    #[command(
+        description = "Export metric snapshot. Usage: /export cpu|ram|disk[,ram,...]|all [30m|1h|6h|24h|from=.. to=..] [csv|json|ndjson|openmetrics|influx]"
     )]
     Export(String),
     #[command(description = "Show alert thresholds and current alert states.")]
     Alerts,
+    #[command(description = "List alert incidents. Usage: /incidents [7d]")]
+    Incidents(String),
+    #[command(description = "Show an incident timeline and graph. Usage: /incident <id> [ack]")]
+    Incident(String),
 
     #[command(description = "Mute alerts for a duration, e.g. /mute 30m")]
     Mute(String),
     #[command(description = "Unmute alerts immediately.")]
     Unmute,
 
+    #[command(description = "Create a backup archive of bot data and send it.")]
+    Backup,
+    #[command(description = "Show disk usage of bot data stores.")]
+    Storage,
+    #[command(description = "Check anomaly DB integrity. Usage: /dbcheck [repair]")]
+    Dbcheck(String),
+
     #[command(description = "Release check and controlled restart. Usage: /update [check|apply]")]
     Update(String),
 }
@@ -5,6 +5,9 @@ use crate::architecture::{
     ports::MuteActionError,
     use_cases::{alert_snapshot_use_case, mute_alerts_use_case, unmute_alerts_use_case},
 };
+use crate::config::QuietHoursMode;
+use crate::i18n::{t, t_with};
+use crate::time_format::format_local_time;
 
 use super::super::helpers::{as_html_card, escape_html_text, parse_mute_duration};
 use super::menu::{main_menu_keyboard, upsert_message_with_menu};
@@ -17,35 +20,74 @@ pub(crate) async fn handle_alerts(
     let runtime_config = app_context.runtime_config.read().await.clone();
     let snapshot = alert_snapshot_use_case(&app_context.monitor.alert_state).await;
     let now = chrono::Utc::now();
+    let tz = app_context.config.tz();
     let mute_line = match snapshot.muted_until {
         Some(until) if now <= until => {
             let remaining = until.signed_duration_since(now).num_seconds().max(0);
-            format!(
-                "muted ({}s remaining until {})",
-                remaining,
-                until.to_rfc3339()
+            t_with(
+                "alerts.muted_remaining",
+                &[
+                    ("remaining", remaining.to_string()),
+                    ("until", format_local_time(until, tz)),
+                ],
             )
         }
-        _ => "not muted".to_string(),
+        _ => t("alerts.not_muted").to_string(),
     };
-    let summary_line = snapshot
-        .last_daily_summary_at
-        .map_or_else(|| "not generated yet".to_string(), |time| time.to_rfc3339());
-    let body = format!(
-        "Thresholds:\n- CPU: {:.1}%\n- RAM: {:.1}%\n- Disk: {:.1}%\n\nControl:\n- Cooldown: {}s\n- Hysteresis: {:.1}%\n- Mute: {}\n- Last daily summary (UTC): {}\n\nCurrent State:\n- CPU alerting: {}\n- RAM alerting: {}\n- Disk alerting: {}",
-        runtime_config.alerts.cpu,
-        runtime_config.alerts.ram,
-        runtime_config.alerts.disk,
-        runtime_config.alerts.cooldown_secs,
-        runtime_config.alerts.hysteresis,
-        mute_line,
-        summary_line,
-        if snapshot.cpu_alerting { "yes" } else { "no" },
-        if snapshot.ram_alerting { "yes" } else { "no" },
-        if snapshot.disk_alerting { "yes" } else { "no" }
+    let quiet_hours = &app_context.config.quiet_hours;
+    let quiet_hours_line = if quiet_hours.enabled {
+        t_with(
+            "alerts.quiet_hours",
+            &[
+                ("start", quiet_hours.start.clone()),
+                ("end", quiet_hours.end.clone()),
+                (
+                    "timezone",
+                    quiet_hours
+                        .timezone
+                        .clone()
+                        .unwrap_or_else(|| app_context.config.timezone.clone()),
+                ),
+                (
+                    "mode",
+                    match quiet_hours.mode {
+                        QuietHoursMode::Digest => "digest",
+                        QuietHoursMode::Silent => "silent",
+                    }
+                    .to_string(),
+                ),
+                ("critical", format!("{:.1}", quiet_hours.critical_threshold)),
+                ("held", snapshot.deferred_alert_count.to_string()),
+            ],
+        )
+    } else {
+        t("alerts.disabled").to_string()
+    };
+    let summary_line = snapshot.last_daily_summary_at.map_or_else(
+        || t("alerts.not_generated").to_string(),
+        |time| format_local_time(time, tz),
+    );
+    let body = t_with(
+        "alerts.body",
+        &[
+            ("cpu", format!("{:.1}", runtime_config.alerts.cpu)),
+            ("ram", format!("{:.1}", runtime_config.alerts.ram)),
+            ("disk", format!("{:.1}", runtime_config.alerts.disk)),
+            ("cooldown", runtime_config.alerts.cooldown_secs.to_string()),
+            (
+                "hysteresis",
+                format!("{:.1}", runtime_config.alerts.hysteresis),
+            ),
+            ("mute", mute_line),
+            ("quiet_hours", quiet_hours_line),
+            ("last_summary", summary_line),
+            ("cpu_alerting", yes_no(snapshot.cpu_alerting)),
+            ("ram_alerting", yes_no(snapshot.ram_alerting)),
+            ("disk_alerting", yes_no(snapshot.disk_alerting)),
+        ],
     );
 
-    let alert_html = as_html_card("Alert Configuration", &escape_html_text(&body));
+    let alert_html = as_html_card(t("alerts.title"), &escape_html_text(&body));
 
     bot.send_message(msg.chat.id, alert_html)
         .reply_markup(main_menu_keyboard(&app_context.capabilities))
@@ -62,10 +104,7 @@ pub(crate) async fn handle_mute(
     duration_str: &str,
 ) -> ResponseResult<()> {
     let Some(duration) = parse_mute_duration(duration_str) else {
-        let message = as_html_card(
-            "Mute failed",
-            "Invalid duration. Use format like: <b>30s</b>, <b>15m</b>, <b>2h</b>, <b>1d</b>.",
-        );
+        let message = as_html_card(t("alerts.mute_failed.title"), t("alerts.mute_failed.body"));
         upsert_message_with_menu(bot, msg, message, "monitor", &app_context.capabilities).await?;
         return Ok(());
     };
@@ -74,9 +113,10 @@ pub(crate) async fn handle_mute(
         Ok(until) => until,
         Err(MuteActionError::Cooldown { retry_after_secs }) => {
             let message = as_html_card(
-                "Mute cooldown",
-                &format!(
-                    "Please wait <b>{retry_after_secs}s</b> before changing mute state again."
+                t("alerts.mute_cooldown.title"),
+                &t_with(
+                    "alerts.cooldown.body",
+                    &[("secs", retry_after_secs.to_string())],
                 ),
             );
             upsert_message_with_menu(bot, msg, message, "monitor", &app_context.capabilities)
@@ -85,10 +125,13 @@ pub(crate) async fn handle_mute(
         }
     };
     let message = as_html_card(
-        "Alerts muted ✅",
-        &format!(
-            "Alerts are muted until <b>{}</b>.\n\nYou can continue from the Monitor menu below.",
-            escape_html_text(&muted_until.to_rfc3339())
+        t("alerts.muted.title"),
+        &t_with(
+            "alerts.muted.body",
+            &[(
+                "until",
+                escape_html_text(&format_local_time(muted_until, app_context.config.tz())),
+            )],
         ),
     );
     upsert_message_with_menu(bot, msg, message, "monitor", &app_context.capabilities).await?;
@@ -105,17 +148,21 @@ pub(crate) async fn handle_unmute(
         unmute_alerts_use_case(&app_context.monitor.alert_state).await
     {
         let message = as_html_card(
-            "Unmute cooldown",
-            &format!("Please wait <b>{retry_after_secs}s</b> before changing mute state again."),
+            t("alerts.unmute_cooldown.title"),
+            &t_with(
+                "alerts.cooldown.body",
+                &[("secs", retry_after_secs.to_string())],
+            ),
         );
         upsert_message_with_menu(bot, msg, message, "monitor", &app_context.capabilities).await?;
         return Ok(());
     }
-    let message = as_html_card(
-        "Alerts unmuted ✅",
-        "Alerts are active again.\n\nYou can continue from the Monitor menu below.",
-    );
+    let message = as_html_card(t("alerts.unmuted.title"), t("alerts.unmuted.body"));
     upsert_message_with_menu(bot, msg, message, "monitor", &app_context.capabilities).await?;
 
     Ok(())
 }
+
+fn yes_no(value: bool) -> String {
+    t(if value { "common.yes" } else { "common.no" }).to_string()
+}
@@ -0,0 +1,88 @@
+use std::time::Duration;
+
+use teloxide::{prelude::*, types::InputFile, types::ParseMode};
+
+use crate::app_context::AppContext;
+use crate::backup::create_backup;
+use crate::i18n::{t, t_with};
+
+use super::super::command_def::MyCommands;
+use super::super::helpers::{
+    TELEGRAM_DOCUMENT_LIMIT_BYTES, acquire_command_slot, as_html_block, timeout_for,
+};
+use super::menu::main_menu_keyboard;
+
+pub(crate) async fn handle_backup(
+    bot: &Bot,
+    msg: &Message,
+    app_context: &AppContext,
+) -> ResponseResult<()> {
+    let Some(_permit) =
+        acquire_command_slot(&app_context.bot_runtime.command_slots, msg, bot).await?
+    else {
+        return Ok(());
+    };
+
+    if let Err(error) = app_context.reporting_store.flush() {
+        log::warn!("backup_reporting_store_flush_failed error={error}");
+    }
+    if let Err(error) = app_context.incident_store.flush() {
+        log::warn!("backup_incident_store_flush_failed error={error}");
+    }
+    if let Err(error) = app_context.anomaly_storage.flush().await {
+        log::warn!("backup_anomaly_store_flush_failed error={error}");
+    }
+
+    let runtime_config = app_context.runtime_config.read().await.clone();
+    let timeout = timeout_for(&MyCommands::Backup, runtime_config.command_timeout_secs);
+    let config = app_context.config.clone();
+    let task = tokio::task::spawn_blocking(move || create_backup(&config, None));
+    let result = match tokio::time::timeout(Duration::from_secs(timeout), task).await {
+        Ok(Ok(result)) => result.map_err(|error| error.to_string()),
+        Ok(Err(error)) => Err(error.to_string()),
+        Err(_) => Err(format!("timed out after {timeout}s")),
+    };
+
+    let summary = match result {
+        Ok(summary) => summary,
+        Err(error) => {
+            log::warn!("backup_command_failed error={error}");
+            bot.send_message(
+                msg.chat.id,
+                as_html_block(
+                    t("backup.title"),
+                    &t_with("backup.failed", &[("error", error)]),
+                ),
+            )
+            .reply_markup(main_menu_keyboard(&app_context.capabilities))
+            .parse_mode(ParseMode::Html)
+            .await?;
+            return Ok(());
+        }
+    };
+
+    let params = [
+        ("files", summary.file_count.to_string()),
+        ("bytes", summary.archive_bytes.to_string()),
+        ("components", summary.components.join(", ")),
+        ("path", summary.archive_path.display().to_string()),
+    ];
+
+    if summary.archive_bytes > TELEGRAM_DOCUMENT_LIMIT_BYTES as u64 {
+        bot.send_message(
+            msg.chat.id,
+            as_html_block(t("backup.title"), &t_with("backup.too_large", &params)),
+        )
+        .reply_markup(main_menu_keyboard(&app_context.capabilities))
+        .parse_mode(ParseMode::Html)
+        .await?;
+        return Ok(());
+    }
+
+    bot.send_document(msg.chat.id, InputFile::file(summary.archive_path.clone()))
+        .caption(t_with("backup.caption", &params))
+        .reply_markup(main_menu_keyboard(&app_context.capabilities))
+        .await?;
+
+    Ok(())
+}
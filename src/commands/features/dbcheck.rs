@@ -0,0 +1,113 @@
+use std::time::Duration;
+
+use teloxide::{prelude::*, types::ParseMode};
+
+use crate::anomaly_db::check_integrity;
+use crate::app_context::AppContext;
+use crate::config::AnomalyDbBackend;
+use crate::i18n::{t, t_with};
+
+use super::super::command_def::MyCommands;
+use super::super::helpers::{acquire_command_slot, as_html_block, timeout_for};
+use super::menu::main_menu_keyboard;
+
+pub(crate) async fn handle_dbcheck(
+    bot: &Bot,
+    msg: &Message,
+    app_context: &AppContext,
+    args: &str,
+) -> ResponseResult<()> {
+    let repair = match args.trim().to_lowercase().as_str() {
+        "" => false,
+        "repair" => true,
+        _ => {
+            return send_dbcheck_reply(bot, msg, app_context, t("dbcheck.usage").to_string()).await;
+        }
+    };
+
+    if !app_context.config.anomaly_db.enabled {
+        return send_dbcheck_reply(bot, msg, app_context, t("dbcheck.disabled").to_string()).await;
+    }
+    if app_context.config.anomaly_db.backend == AnomalyDbBackend::Sled {
+        return send_dbcheck_reply(bot, msg, app_context, t("dbcheck.sled").to_string()).await;
+    }
+
+    let Some(_permit) =
+        acquire_command_slot(&app_context.bot_runtime.command_slots, msg, bot).await?
+    else {
+        return Ok(());
+    };
+
+    let runtime_config = app_context.runtime_config.read().await.clone();
+    let timeout = timeout_for(
+        &MyCommands::Dbcheck(args.to_string()),
+        runtime_config.command_timeout_secs,
+    );
+    let config = app_context.config.clone();
+    let task = tokio::task::spawn_blocking(move || check_integrity(&config, repair));
+    let report = match tokio::time::timeout(Duration::from_secs(timeout), task).await {
+        Ok(Ok(report)) => report,
+        Ok(Err(error)) => {
+            let error = error.to_string();
+            log::warn!("dbcheck_command_failed error={error}");
+            return send_dbcheck_reply(
+                bot,
+                msg,
+                app_context,
+                t_with("dbcheck.failed", &[("error", error)]),
+            )
+            .await;
+        }
+        Err(_) => {
+            return send_dbcheck_reply(
+                bot,
+                msg,
+                app_context,
+                t_with(
+                    "dbcheck.failed",
+                    &[("error", format!("timed out after {timeout}s"))],
+                ),
+            )
+            .await;
+        }
+    };
+
+    let status_key = if report.is_clean() {
+        "dbcheck.status.clean"
+    } else if repair {
+        "dbcheck.status.repaired"
+    } else {
+        "dbcheck.status.issues"
+    };
+    let body = t_with(
+        "dbcheck.result",
+        &[
+            ("status", t(status_key).to_string()),
+            ("event_files", report.event_files.to_string()),
+            ("index_files", report.index_files.to_string()),
+            ("event_lines", report.event_lines.to_string()),
+            ("index_lines", report.index_lines.to_string()),
+            ("corrupt_events", report.corrupt_event_lines.to_string()),
+            ("corrupt_index", report.corrupt_index_lines.to_string()),
+            ("index_only", report.index_without_event.to_string()),
+            ("unindexed", report.events_without_index.to_string()),
+            ("partial", report.partial_trailing_lines.to_string()),
+            ("rebuilt", report.rebuilt_index_days.to_string()),
+        ],
+    );
+
+    send_dbcheck_reply(bot, msg, app_context, body).await
+}
+
+async fn send_dbcheck_reply(
+    bot: &Bot,
+    msg: &Message,
+    app_context: &AppContext,
+    body: String,
+) -> ResponseResult<()> {
+    bot.send_message(msg.chat.id, as_html_block(t("dbcheck.title"), &body))
+        .reply_markup(main_menu_keyboard(&app_context.capabilities))
+        .parse_mode(ParseMode::Html)
+        .await?;
+    Ok(())
+}
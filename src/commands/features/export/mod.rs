@@ -1,17 +1,22 @@
 use teloxide::{prelude::*, types::InputFile, types::ParseMode};
 
 use crate::app_context::AppContext;
+use crate::i18n::{t, t_with};
+use crate::monitor::MetricSample;
+use crate::reporting_store::rollup_bucket_for;
+use crate::templates::hostname;
 
-use super::super::helpers::{acquire_command_slot, as_html_block};
+use super::super::helpers::{
+    TELEGRAM_DOCUMENT_LIMIT_BYTES, acquire_command_slot, as_html_block, format_window_suffix,
+};
 use super::menu::main_menu_keyboard;
-use parser::{format_window_suffix, parse_export_request};
-use payload::build_export_payload;
+use super::time_range::TimeRange;
+use parser::{metrics_label, parse_export_request};
+use payload::{build_export_payload, compress_if_over};
 
 mod parser;
 mod payload;
 
-const EXPORT_USAGE_TEXT: &str = "Usage: /export cpu|ram|disk [<Nm|Nh>] [csv|json]";
-
 pub(crate) async fn handle_export(
     bot: &Bot,
     msg: &Message,
@@ -28,7 +33,7 @@ pub(crate) async fn handle_export(
     if !graph_runtime.enabled {
         bot.send_message(
             msg.chat.id,
-            as_html_block("Export Disabled", "Export feature is disabled in config."),
+            as_html_block(t("export.disabled.title"), t("export.disabled.body")),
         )
         .reply_markup(main_menu_keyboard(&app_context.capabilities))
         .parse_mode(ParseMode::Html)
@@ -40,10 +45,11 @@ pub(crate) async fn handle_export(
         query,
         graph_runtime.default_window_minutes as i64,
         graph_runtime.max_window_hours as i64,
+        app_context.config.tz(),
     ) else {
         bot.send_message(
             msg.chat.id,
-            as_html_block("Export Usage", EXPORT_USAGE_TEXT),
+            as_html_block(t("export.usage.title"), t("export.usage")),
         )
         .reply_markup(main_menu_keyboard(&app_context.capabilities))
         .parse_mode(ParseMode::Html)
@@ -51,15 +57,22 @@ pub(crate) async fn handle_export(
         return Ok(());
     };
 
-    let samples = {
-        let history = app_context.monitor.metric_history.lock().await;
-        history.latest_window(request.window_minutes)
+    let (samples, rollup_bucket) = match request.range {
+        Some(range) => range_samples(app_context, range),
+        None => {
+            let history = app_context.monitor.metric_history.lock().await;
+            (history.latest_window(request.window_minutes), None)
+        }
     };
+    let window_label = request.range.map_or_else(
+        || format_window_suffix(request.window_minutes),
+        |range| range.label(),
+    );
 
     if samples.is_empty() {
         bot.send_message(
             msg.chat.id,
-            as_html_block("Export", "not enough samples yet"),
+            as_html_block(t("export.title"), t("error.not_enough_samples")),
         )
         .reply_markup(main_menu_keyboard(&app_context.capabilities))
         .parse_mode(ParseMode::Html)
@@ -69,18 +82,32 @@ pub(crate) async fn handle_export(
 
     let file_name = format!(
         "{}-{}.{},",
-        request.metric.as_str(),
-        format_window_suffix(request.window_minutes),
+        metrics_label(&request.metrics, "-"),
+        request.range.map_or_else(
+            || format_window_suffix(request.window_minutes),
+            |range| range.file_suffix()
+        ),
         request.format.extension()
     );
     let file_name = file_name.trim_end_matches(',').to_string();
 
-    let body = match build_export_payload(&samples, request.metric, request.format) {
+    let body = build_export_payload(&samples, &request.metrics, request.format, hostname())
+        .and_then(|body| compress_if_over(body, TELEGRAM_DOCUMENT_LIMIT_BYTES))
+        .and_then(|(body, compressed)| {
+            if body.len() > TELEGRAM_DOCUMENT_LIMIT_BYTES {
+                return Err(t("export.too_large").to_string());
+            }
+            Ok((body, compressed))
+        });
+    let (body, compressed) = match body {
         Ok(body) => body,
         Err(error) => {
             bot.send_message(
                 msg.chat.id,
-                as_html_block("Export", &format!("Could not build export: {error}")),
+                as_html_block(
+                    t("export.title"),
+                    &t_with("export.failed", &[("error", error.to_string())]),
+                ),
             )
             .reply_markup(main_menu_keyboard(&app_context.capabilities))
             .parse_mode(ParseMode::Html)
@@ -89,15 +116,57 @@ pub(crate) async fn handle_export(
         }
     };
 
+    let mut caption = t_with(
+        "export.caption",
+        &[
+            ("count", samples.len().to_string()),
+            ("metric", metrics_label(&request.metrics, ",")),
+            ("window", window_label),
+        ],
+    );
+    if let Some(bucket) = rollup_bucket {
+        caption.push('\n');
+        caption.push_str(&t_with(
+            "export.rollup_averages",
+            &[("bucket", bucket.to_string())],
+        ));
+    }
+    let file_name = if compressed {
+        caption.push('\n');
+        caption.push_str(t("export.compressed"));
+        log::info!(
+            "export_compressed metrics={} bytes={}",
+            metrics_label(&request.metrics, ","),
+            body.len()
+        );
+        format!("{file_name}.gz")
+    } else {
+        file_name
+    };
+
     bot.send_document(msg.chat.id, InputFile::memory(body).file_name(file_name))
-        .caption(format!(
-            "Exported {} samples for {} ({})",
-            samples.len(),
-            request.metric.as_str(),
-            format_window_suffix(request.window_minutes)
-        ))
+        .caption(caption)
         .reply_markup(main_menu_keyboard(&app_context.capabilities))
         .await?;
 
     Ok(())
 }
+
+/// Raw samples when the raw tier still reaches back to `range.from`, otherwise
+/// averages of the finest rollup tier that does, with its bucket label.
+fn range_samples(
+    app_context: &AppContext,
+    range: TimeRange,
+) -> (Vec<MetricSample>, Option<String>) {
+    let store = &app_context.reporting_store;
+    let Some(bucket) = rollup_bucket_for(range.from, &app_context.config.reporting_store) else {
+        return (store.range(range.from, range.to), None);
+    };
+
+    let samples = store
+        .rollup_range(range.from, range.to, bucket)
+        .into_iter()
+        .map(|bucket| bucket.avg)
+        .collect();
+    (samples, Some(format_window_suffix(bucket.num_minutes())))
+}
@@ -1,4 +1,8 @@
-#[derive(Clone, Copy)]
+use chrono_tz::Tz;
+
+use super::super::time_range::{TimeRange, parse_time_point};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(super) enum ExportMetric {
     Cpu,
     Ram,
@@ -6,6 +10,8 @@ pub(super) enum ExportMetric {
 }
 
 impl ExportMetric {
+    const ALL: [Self; 3] = [Self::Cpu, Self::Ram, Self::Disk];
+
     fn parse(input: &str) -> Option<Self> {
         match input.trim().to_lowercase().as_str() {
             "cpu" => Some(Self::Cpu),
@@ -32,10 +38,45 @@ impl ExportMetric {
     }
 }
 
-#[derive(Clone, Copy)]
+/// `all`, one metric or a comma-separated list without repeats.
+fn parse_metrics(input: &str) -> Option<Vec<ExportMetric>> {
+    if input.eq_ignore_ascii_case("all") {
+        return Some(ExportMetric::ALL.to_vec());
+    }
+    let mut metrics = Vec::new();
+    for part in input.split(',') {
+        let metric = ExportMetric::parse(part)?;
+        if metrics.contains(&metric) {
+            return None;
+        }
+        metrics.push(metric);
+    }
+    Some(metrics)
+}
+
+/// `all` when every metric is exported, otherwise the names joined by `sep`.
+pub(super) fn metrics_label(metrics: &[ExportMetric], sep: &str) -> String {
+    if metrics.len() == ExportMetric::ALL.len() {
+        return "all".to_string();
+    }
+    metrics
+        .iter()
+        .map(|metric| metric.as_str())
+        .collect::<Vec<_>>()
+        .join(sep)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(super) enum ExportFormat {
     Csv,
     Json,
+    /// One JSON row per line.
+    Ndjson,
+    /// OpenMetrics text exposition with sample timestamps, readable by
+    /// `promtool tsdb create-blocks-from openmetrics`.
+    OpenMetrics,
+    /// InfluxDB line protocol, one line per sample with a field per metric.
+    Influx,
 }
 
 impl ExportFormat {
@@ -43,6 +84,9 @@ impl ExportFormat {
         match input.trim().to_lowercase().as_str() {
             "csv" => Some(Self::Csv),
             "json" => Some(Self::Json),
+            "ndjson" => Some(Self::Ndjson),
+            "openmetrics" | "prom" => Some(Self::OpenMetrics),
+            "influx" => Some(Self::Influx),
             _ => None,
         }
     }
@@ -51,13 +95,18 @@ impl ExportFormat {
         match self {
             Self::Csv => "csv",
             Self::Json => "json",
+            Self::Ndjson => "ndjson",
+            Self::OpenMetrics => "prom",
+            Self::Influx => "lp",
         }
     }
 }
 
 pub(super) struct ExportRequest {
-    pub(super) metric: ExportMetric,
+    pub(super) metrics: Vec<ExportMetric>,
     pub(super) window_minutes: i64,
+    /// Absolute period from `from=`/`to=`; replaces `window_minutes` when set.
+    pub(super) range: Option<TimeRange>,
     pub(super) format: ExportFormat,
 }
 
@@ -65,16 +114,23 @@ pub(super) fn parse_export_request(
     query: &str,
     default_window_minutes: i64,
     max_window_hours: i64,
+    tz: Tz,
 ) -> Option<ExportRequest> {
     let mut args = query.split_whitespace();
     let metric_arg = args.next()?;
-    let second = args.next();
-    let third = args.next();
-    if args.next().is_some() {
+    let metrics = parse_metrics(metric_arg)?;
+
+    let rest = args.collect::<Vec<_>>();
+    if rest.iter().any(|token| token.contains('=')) {
+        return parse_range_request(metrics, &rest, tz);
+    }
+
+    let second = rest.first().copied();
+    let third = rest.get(1).copied();
+    if rest.len() > 2 {
         return None;
     }
 
-    let metric = ExportMetric::parse(metric_arg)?;
     let max_window_minutes = max_window_hours.checked_mul(60)?;
 
     let mut window_minutes = default_window_minutes;
@@ -101,27 +157,44 @@ pub(super) fn parse_export_request(
     }
 
     Some(ExportRequest {
-        metric,
+        metrics,
         window_minutes,
+        range: None,
         format,
     })
 }
 
-pub(super) fn format_window_suffix(window_minutes: i64) -> String {
-    if window_minutes % 60 == 0 {
-        format!("{}h", window_minutes / 60)
-    } else {
-        format!("{window_minutes}m")
+/// `from=<date|datetime> to=<date|datetime> [format]`; `to` defaults to now.
+fn parse_range_request(
+    metrics: Vec<ExportMetric>,
+    tokens: &[&str],
+    tz: Tz,
+) -> Option<ExportRequest> {
+    let mut from = None;
+    let mut to = None;
+    let mut format = ExportFormat::Csv;
+
+    for token in tokens {
+        match token.split_once('=') {
+            Some(("from", value)) if from.is_none() => from = parse_time_point(value, tz, false),
+            Some(("to", value)) if to.is_none() => to = parse_time_point(value, tz, true),
+            Some(_) => return None,
+            None => format = ExportFormat::parse(token)?,
+        }
     }
+
+    let range = TimeRange::new(from?, to.unwrap_or_else(chrono::Utc::now), tz)?;
+    Some(ExportRequest {
+        metrics,
+        window_minutes: range.minutes(),
+        range: Some(range),
+        format,
+    })
 }
 
 fn parse_window_minutes(input: &str) -> Option<i64> {
     let value = input.trim().to_lowercase();
-    if value.len() < 2 {
-        return None;
-    }
-
-    let (number_part, unit_part) = value.split_at(value.len() - 1);
+    let (number_part, unit_part) = value.split_at_checked(value.len().checked_sub(1)?)?;
     let number = number_part.parse::<i64>().ok()?;
     if number <= 0 {
         return None;
@@ -136,24 +209,60 @@ fn parse_window_minutes(input: &str) -> Option<i64> {
 
 #[cfg(test)]
 mod tests {
-    use super::parse_export_request;
+    use chrono_tz::Tz;
+
+    use super::{ExportFormat, ExportMetric, parse_export_request};
 
     #[test]
     fn parses_default_window_and_format() {
-        let request = parse_export_request("cpu", 60, 24).expect("should parse");
+        let request = parse_export_request("cpu", 60, 24, Tz::UTC).expect("should parse");
         assert_eq!(request.window_minutes, 60);
     }
 
+    #[test]
+    fn rejects_multi_byte_window_suffixes() {
+        assert!(parse_export_request("cpu 1ş", 60, 24, Tz::UTC).is_none());
+    }
+
     #[test]
     fn parses_window_and_format() {
-        let request = parse_export_request("ram 6h json", 60, 24).expect("should parse");
+        let request = parse_export_request("ram 6h json", 60, 24, Tz::UTC).expect("should parse");
         assert_eq!(request.window_minutes, 360);
     }
 
+    #[test]
+    fn parses_metric_lists_and_new_formats() {
+        let request =
+            parse_export_request("all 24h openmetrics", 60, 24, Tz::UTC).expect("should parse");
+        assert_eq!(request.metrics, ExportMetric::ALL);
+        assert_eq!(request.format, ExportFormat::OpenMetrics);
+
+        let request = parse_export_request("disk,cpu influx", 60, 24, Tz::UTC).expect("list");
+        assert_eq!(request.metrics, [ExportMetric::Disk, ExportMetric::Cpu]);
+        assert_eq!(request.format, ExportFormat::Influx);
+
+        assert!(parse_export_request("cpu,cpu ndjson", 60, 24, Tz::UTC).is_none());
+        assert!(parse_export_request("cpu,gpu", 60, 24, Tz::UTC).is_none());
+    }
+
     #[test]
     fn rejects_invalid_queries() {
-        assert!(parse_export_request("", 60, 24).is_none());
-        assert!(parse_export_request("cpu 25h", 60, 24).is_none());
-        assert!(parse_export_request("cpu foo bar", 60, 24).is_none());
+        assert!(parse_export_request("", 60, 24, Tz::UTC).is_none());
+        assert!(parse_export_request("cpu 25h", 60, 24, Tz::UTC).is_none());
+        assert!(parse_export_request("cpu foo bar", 60, 24, Tz::UTC).is_none());
+        assert!(
+            parse_export_request("cpu from=2026-10-03 to=2026-10-01", 60, 24, Tz::UTC).is_none()
+        );
+        assert!(parse_export_request("cpu to=2026-10-03", 60, 24, Tz::UTC).is_none());
+    }
+
+    #[test]
+    fn parses_absolute_range() {
+        let request =
+            parse_export_request("disk from=2026-10-01 to=2026-10-02 json", 60, 24, Tz::UTC)
+                .expect("should parse");
+        let range = request.range.expect("range is set");
+        assert_eq!(range.minutes(), 2 * 24 * 60);
+        assert_eq!(range.label(), "2026-10-01..2026-10-02");
     }
 }
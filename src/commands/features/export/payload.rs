@@ -1,9 +1,16 @@
 use std::fmt::Write;
+use std::io::Write as _;
 
+use flate2::{Compression, write::GzEncoder};
 use serde::Serialize;
 
+use crate::monitor::MetricSample;
+
 use super::parser::{ExportFormat, ExportMetric};
 
+/// InfluxDB measurement and OpenMetrics name prefix.
+const METRIC_NAMESPACE: &str = "kars";
+
 #[derive(Serialize)]
 struct JsonExportRow {
     timestamp_utc: String,
@@ -12,42 +19,191 @@ struct JsonExportRow {
 }
 
 pub(super) fn build_export_payload(
-    samples: &[crate::monitor::MetricSample],
-    metric: ExportMetric,
+    samples: &[MetricSample],
+    metrics: &[ExportMetric],
     format: ExportFormat,
+    host: &str,
 ) -> Result<Vec<u8>, String> {
     match format {
-        ExportFormat::Csv => Ok(build_csv(samples, metric).into_bytes()),
-        ExportFormat::Json => build_json(samples, metric),
+        ExportFormat::Csv => Ok(build_csv(samples, metrics).into_bytes()),
+        ExportFormat::Json => {
+            serde_json::to_vec_pretty(&json_rows(samples, metrics)).map_err(|e| e.to_string())
+        }
+        ExportFormat::Ndjson => build_ndjson(samples, metrics),
+        ExportFormat::OpenMetrics => Ok(build_openmetrics(samples, metrics, host).into_bytes()),
+        ExportFormat::Influx => Ok(build_influx(samples, metrics, host).into_bytes()),
+    }
+}
+
+/// Gzips `body` when it is over `limit` bytes; `true` when compressed.
+pub(super) fn compress_if_over(body: Vec<u8>, limit: usize) -> Result<(Vec<u8>, bool), String> {
+    if body.len() <= limit {
+        return Ok((body, false));
     }
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(&body).map_err(|e| e.to_string())?;
+    Ok((encoder.finish().map_err(|e| e.to_string())?, true))
 }
 
-fn build_csv(samples: &[crate::monitor::MetricSample], metric: ExportMetric) -> String {
+fn build_csv(samples: &[MetricSample], metrics: &[ExportMetric]) -> String {
     let mut out = String::from("timestamp_utc,metric,value_percent\n");
     for sample in samples {
+        for metric in metrics {
+            let _ = writeln!(
+                out,
+                "{},{},{:.2}",
+                sample.timestamp.to_rfc3339(),
+                metric.as_str(),
+                metric.value(sample)
+            );
+        }
+    }
+    out
+}
+
+fn json_rows(samples: &[MetricSample], metrics: &[ExportMetric]) -> Vec<JsonExportRow> {
+    samples
+        .iter()
+        .flat_map(|sample| {
+            metrics.iter().map(|metric| JsonExportRow {
+                timestamp_utc: sample.timestamp.to_rfc3339(),
+                metric: metric.as_str().to_string(),
+                value_percent: metric.value(sample),
+            })
+        })
+        .collect()
+}
+
+fn build_ndjson(samples: &[MetricSample], metrics: &[ExportMetric]) -> Result<Vec<u8>, String> {
+    let mut out = Vec::new();
+    for row in json_rows(samples, metrics) {
+        serde_json::to_writer(&mut out, &row).map_err(|e| e.to_string())?;
+        out.push(b'\n');
+    }
+    Ok(out)
+}
+
+/// One gauge family per metric; OpenMetrics timestamps are in seconds.
+fn build_openmetrics(samples: &[MetricSample], metrics: &[ExportMetric], host: &str) -> String {
+    let host = host
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n");
+    let mut out = String::new();
+    for metric in metrics {
+        let name = format!("{METRIC_NAMESPACE}_{}_usage_percent", metric.as_str());
+        let _ = writeln!(out, "# TYPE {name} gauge");
+        let _ = writeln!(out, "# UNIT {name} percent");
         let _ = writeln!(
             out,
-            "{},{},{:.2}",
-            sample.timestamp.to_rfc3339(),
-            metric.as_str(),
-            metric.value(sample)
+            "# HELP {name} {} usage in percent.",
+            metric.as_str().to_uppercase()
         );
+        for sample in samples {
+            let _ = writeln!(
+                out,
+                "{name}{{host=\"{host}\"}} {:.2} {}.{:03}",
+                metric.value(sample),
+                sample.timestamp.timestamp(),
+                sample.timestamp.timestamp_subsec_millis()
+            );
+        }
     }
+    out.push_str("# EOF\n");
     out
 }
 
-fn build_json(
-    samples: &[crate::monitor::MetricSample],
-    metric: ExportMetric,
-) -> Result<Vec<u8>, String> {
-    let rows: Vec<JsonExportRow> = samples
-        .iter()
-        .map(|sample| JsonExportRow {
-            timestamp_utc: sample.timestamp.to_rfc3339(),
-            metric: metric.as_str().to_string(),
-            value_percent: metric.value(sample),
-        })
-        .collect();
+/// Line protocol with nanosecond timestamps, e.g.
+/// `kars,host=web-1 cpu=12.50,ram=40.00 1792317600000000000`.
+fn build_influx(samples: &[MetricSample], metrics: &[ExportMetric], host: &str) -> String {
+    let host = host
+        .replace('\\', "\\\\")
+        .replace(',', "\\,")
+        .replace('=', "\\=")
+        .replace(' ', "\\ ");
+    let mut out = String::new();
+    for sample in samples {
+        let fields = metrics
+            .iter()
+            .map(|metric| format!("{}={:.2}", metric.as_str(), metric.value(sample)))
+            .collect::<Vec<_>>()
+            .join(",");
+        let Some(nanos) = sample.timestamp.timestamp_nanos_opt() else {
+            continue;
+        };
+        let _ = writeln!(out, "{METRIC_NAMESPACE},host={host} {fields} {nanos}");
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use chrono::{TimeZone, Utc};
+
+    use super::super::parser::{ExportFormat, ExportMetric};
+    use super::{build_export_payload, compress_if_over};
+    use crate::monitor::MetricSample;
+
+    fn samples() -> Vec<MetricSample> {
+        let start = Utc.with_ymd_and_hms(2026, 10, 18, 10, 0, 0).unwrap();
+        [(12.5, 40.0), (20.0, 41.25)]
+            .into_iter()
+            .enumerate()
+            .map(|(index, (cpu, ram))| MetricSample {
+                timestamp: start + chrono::Duration::seconds(30 * index as i64),
+                cpu,
+                ram,
+                disk: 70.0,
+            })
+            .collect()
+    }
+
+    fn render(format: ExportFormat) -> String {
+        let metrics = [ExportMetric::Cpu, ExportMetric::Ram];
+        let body = build_export_payload(&samples(), &metrics, format, "web 1").expect("payload");
+        String::from_utf8(body).expect("utf-8")
+    }
 
-    serde_json::to_vec_pretty(&rows).map_err(|error| error.to_string())
+    #[test]
+    fn renders_multi_metric_ndjson_openmetrics_and_influx() {
+        let ndjson = render(ExportFormat::Ndjson);
+        assert_eq!(ndjson.lines().count(), 4);
+        assert_eq!(
+            ndjson.lines().next(),
+            Some(
+                r#"{"timestamp_utc":"2026-10-18T10:00:00+00:00","metric":"cpu","value_percent":12.5}"#
+            )
+        );
+
+        let openmetrics = render(ExportFormat::OpenMetrics);
+        assert!(openmetrics.starts_with("# TYPE kars_cpu_usage_percent gauge\n"));
+        assert!(
+            openmetrics.contains("kars_ram_usage_percent{host=\"web 1\"} 41.25 1792317630.000\n")
+        );
+        assert!(openmetrics.ends_with("# EOF\n"));
+
+        assert_eq!(
+            render(ExportFormat::Influx),
+            "kars,host=web\\ 1 cpu=12.50,ram=40.00 1792317600000000000\n\
+             kars,host=web\\ 1 cpu=20.00,ram=41.25 1792317630000000000\n"
+        );
+    }
+
+    #[test]
+    fn gzips_only_payloads_over_the_limit() {
+        let body = b"timestamp_utc,metric,value_percent\n".repeat(100);
+        let (plain, compressed) = compress_if_over(body.clone(), body.len()).expect("plain");
+        assert!(!compressed);
+        assert_eq!(plain, body);
+
+        let (gzipped, compressed) = compress_if_over(body.clone(), 64).expect("gzip");
+        assert!(compressed && gzipped.len() < body.len());
+        let mut restored = Vec::new();
+        flate2::read::GzDecoder::new(gzipped.as_slice())
+            .read_to_end(&mut restored)
+            .expect("gunzip");
+        assert_eq!(restored, body);
+    }
 }
@@ -0,0 +1,298 @@
+//! Events drawn over graphs so a spike can be matched with what happened
+//! around it: incidents, anomalies, mutes, quiet hours and deploys.
+
+use std::path::Path;
+
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+
+use crate::anomaly_db::{AnomalyEvent, AnomalyQuery};
+use crate::app_context::AppContext;
+use crate::incidents::{Incident, IncidentEventKind};
+use crate::jobs::deploy_history;
+use crate::monitor::QuietHoursPolicy;
+use crate::time_bands::time_bands;
+
+use super::types::GraphMetric;
+
+/// Anomaly events read per graph; older ones in the window are not marked.
+const ANOMALY_READ_LIMIT: usize = 5_000;
+/// Markers drawn per chart. Longer windows keep one event per equal time band
+/// so the marks stay spread over the whole window.
+const MAX_ANOMALY_MARKERS: usize = 200;
+/// Incidents are listed by start time, so look back this far for ones that
+/// were already open when the window begins.
+const INCIDENT_LOOKBACK_DAYS: i64 = 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum AnnotationKind {
+    /// Alert firing until resolution.
+    Incident(GraphMetric),
+    /// Anomaly DB event with this metric over its threshold.
+    Anomaly(GraphMetric),
+    Mute,
+    QuietHours,
+    Deploy,
+}
+
+#[derive(Debug, Clone)]
+pub(super) struct GraphAnnotation {
+    pub(super) kind: AnnotationKind,
+    pub(super) start: DateTime<Utc>,
+    /// End of a shaded region; `None` draws a vertical marker at `start`.
+    pub(super) end: Option<DateTime<Utc>>,
+    pub(super) label: Option<String>,
+}
+
+impl GraphAnnotation {
+    fn marker(kind: AnnotationKind, at: DateTime<Utc>) -> Self {
+        Self {
+            kind,
+            start: at,
+            end: None,
+            label: None,
+        }
+    }
+
+    fn region(kind: AnnotationKind, start: DateTime<Utc>, end: DateTime<Utc>) -> Self {
+        Self {
+            kind,
+            start,
+            end: Some(end),
+            label: None,
+        }
+    }
+
+    /// Metric-specific annotations only belong on charts of that metric.
+    pub(super) fn applies_to(&self, metrics: &[GraphMetric]) -> bool {
+        match self.kind {
+            AnnotationKind::Incident(metric) | AnnotationKind::Anomaly(metric) => {
+                metrics.contains(&metric)
+            }
+            AnnotationKind::Mute | AnnotationKind::QuietHours | AnnotationKind::Deploy => true,
+        }
+    }
+}
+
+/// Annotations for `metrics` overlapping `from..to`.
+pub(super) async fn collect_annotations(
+    app_context: &AppContext,
+    metrics: &[GraphMetric],
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+) -> Vec<GraphAnnotation> {
+    let now = Utc::now();
+    let mut annotations = Vec::new();
+
+    if let Some(policy) =
+        QuietHoursPolicy::from_config(&app_context.config.quiet_hours, app_context.config.tz())
+    {
+        annotations.extend(
+            policy
+                .windows_between(from, to)
+                .into_iter()
+                .map(|(start, end)| {
+                    GraphAnnotation::region(AnnotationKind::QuietHours, start, end)
+                }),
+        );
+    }
+
+    let incidents = app_context
+        .incident_store
+        .incidents_since(from - ChronoDuration::days(INCIDENT_LOOKBACK_DAYS));
+    annotations.extend(incident_annotations(&incidents, metrics, from, to, now));
+
+    let anomalies = app_context
+        .anomaly_storage
+        .query(
+            &app_context.config,
+            &AnomalyQuery {
+                since: Some(from),
+                conditions: Vec::new(),
+                limit: ANOMALY_READ_LIMIT,
+            },
+        )
+        .await;
+    annotations.extend(anomaly_annotations(&anomalies, metrics, to));
+
+    annotations.extend(
+        deploy_history(Path::new(&app_context.config.release_notifier.state_path))
+            .into_iter()
+            .filter_map(|deploy| {
+                let at = deploy.time().filter(|at| (from..=to).contains(at))?;
+                Some(GraphAnnotation {
+                    label: Some(format!("v{}", deploy.version)),
+                    ..GraphAnnotation::marker(AnnotationKind::Deploy, at)
+                })
+            }),
+    );
+
+    annotations
+}
+
+/// Shaded spans for incidents of the graphed metrics (open ones run to
+/// `now`), plus a mute span from the first mute on an incident's persisted
+/// timeline to its end.
+fn incident_annotations(
+    incidents: &[Incident],
+    metrics: &[GraphMetric],
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+    now: DateTime<Utc>,
+) -> Vec<GraphAnnotation> {
+    let mut annotations = Vec::new();
+    for incident in incidents {
+        let metric = GraphMetric::from(incident.metric);
+        let Some(started) = incident.started() else {
+            continue;
+        };
+        let ended = incident.ended().unwrap_or(now);
+        if !metrics.contains(&metric) || started > to || ended < from {
+            continue;
+        }
+
+        annotations.push(GraphAnnotation::region(
+            AnnotationKind::Incident(metric),
+            started,
+            ended,
+        ));
+        let muted = incident
+            .timeline
+            .iter()
+            .filter(|event| event.kind == IncidentEventKind::Muted)
+            .find_map(|event| DateTime::parse_from_rfc3339(&event.at).ok())
+            .map(|at| at.with_timezone(&Utc));
+        if let Some(muted) = muted
+            && muted <= to
+        {
+            annotations.push(GraphAnnotation::region(AnnotationKind::Mute, muted, ended));
+        }
+    }
+    annotations
+}
+
+fn anomaly_annotations(
+    events: &[AnomalyEvent],
+    metrics: &[GraphMetric],
+    to: DateTime<Utc>,
+) -> Vec<GraphAnnotation> {
+    let mut timed = events
+        .iter()
+        .filter_map(|event| {
+            let at = DateTime::parse_from_rfc3339(&event.timestamp).ok()?;
+            Some((event, at.with_timezone(&Utc)))
+        })
+        .filter(|(event, at)| *at <= to && metrics.iter().any(|metric| is_over(*metric, event)))
+        .collect::<Vec<_>>();
+    timed.sort_by_key(|(_, at)| *at);
+
+    time_bands(&timed, MAX_ANOMALY_MARKERS, |(_, at)| *at)
+        .into_iter()
+        .map(|band| timed[band.start])
+        .flat_map(|(event, at)| {
+            metrics
+                .iter()
+                .filter(|metric| is_over(**metric, event))
+                .map(move |metric| GraphAnnotation::marker(AnnotationKind::Anomaly(*metric), at))
+        })
+        .collect()
+}
+
+fn is_over(metric: GraphMetric, event: &AnomalyEvent) -> bool {
+    match metric {
+        GraphMetric::Cpu => event.cpu_over,
+        GraphMetric::Ram => event.ram_over,
+        GraphMetric::Disk => event.disk_over,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, TimeZone, Utc};
+
+    use super::{AnnotationKind, MAX_ANOMALY_MARKERS, anomaly_annotations, incident_annotations};
+    use crate::anomaly_db::AnomalyEvent;
+    use crate::commands::features::graph::types::GraphMetric;
+    use crate::incidents::{Incident, IncidentMetric};
+
+    #[test]
+    fn incidents_become_regions_with_mute_markers_for_graphed_metrics() {
+        let from = Utc.with_ymd_and_hms(2026, 10, 18, 8, 0, 0).unwrap();
+        let to = from + Duration::hours(6);
+        let now = to + Duration::hours(1);
+
+        let mut cpu = Incident::open(
+            1,
+            IncidentMetric::Cpu,
+            85.0,
+            91.0,
+            from - Duration::hours(1),
+        );
+        cpu.record_mute(from + Duration::minutes(30));
+        cpu.resolve(Some(60.0), from + Duration::hours(2));
+        let ram = Incident::open(2, IncidentMetric::Ram, 90.0, 95.0, from);
+        let mut stale = Incident::open(
+            3,
+            IncidentMetric::Cpu,
+            85.0,
+            90.0,
+            from - Duration::hours(5),
+        );
+        stale.resolve(None, from - Duration::hours(4));
+        let open = Incident::open(
+            4,
+            IncidentMetric::Cpu,
+            85.0,
+            99.0,
+            from + Duration::hours(5),
+        );
+
+        let annotations =
+            incident_annotations(&[cpu, ram, stale, open], &[GraphMetric::Cpu], from, to, now);
+        let summary = annotations
+            .iter()
+            .map(|annotation| (annotation.kind, annotation.end.is_some()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            summary,
+            [
+                (AnnotationKind::Incident(GraphMetric::Cpu), true),
+                (AnnotationKind::Mute, true),
+                (AnnotationKind::Incident(GraphMetric::Cpu), true),
+            ]
+        );
+        assert_eq!(annotations[1].start, from + Duration::minutes(30));
+        assert_eq!(annotations[1].end, annotations[0].end);
+        assert_eq!(annotations[2].end, Some(now));
+        assert!(!annotations[0].applies_to(&[GraphMetric::Ram]));
+        assert!(annotations[1].applies_to(&[GraphMetric::Ram]));
+    }
+
+    #[test]
+    fn anomaly_markers_are_thinned_evenly_across_the_window() {
+        let to = Utc.with_ymd_and_hms(2026, 10, 18, 12, 0, 0).unwrap();
+        let events = (0..2_000)
+            .map(|minutes| {
+                let cpu_over = minutes % 2 == 0;
+                AnomalyEvent {
+                    timestamp: (to - Duration::minutes(5 * minutes)).to_rfc3339(),
+                    cpu: if cpu_over { 95.0 } else { 50.0 },
+                    ram: 95.0,
+                    disk: 10.0,
+                    cpu_threshold: 90.0,
+                    ram_threshold: 90.0,
+                    disk_threshold: 90.0,
+                    cpu_over,
+                    ram_over: true,
+                    disk_over: false,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let markers = anomaly_annotations(&events, &[GraphMetric::Cpu], to);
+        assert!(markers.len() <= MAX_ANOMALY_MARKERS);
+        assert!(markers.len() > MAX_ANOMALY_MARKERS / 2);
+        let oldest = to - Duration::minutes(5 * 1_998);
+        assert_eq!(markers.first().map(|marker| marker.start), Some(oldest));
+        assert!(markers.last().unwrap().start > to - Duration::hours(2));
+    }
+}
@@ -0,0 +1,110 @@
+use chrono::Duration as ChronoDuration;
+
+use crate::app_context::AppContext;
+use crate::i18n::t_with;
+use crate::reporting_store::MetricBucket;
+
+use super::super::time_range::TimeRange;
+use super::handler::range_buckets;
+use super::render::GraphSeries;
+use super::stats::{GraphBand, downsample_bands, summarize_buckets};
+use super::types::{GraphMetric, GraphWindow};
+
+/// The same window `offset` earlier, shifted onto the current time axis.
+pub(super) struct ComparisonPeriod {
+    pub(super) series: GraphSeries,
+    pub(super) caption: String,
+}
+
+/// Reads the earlier period from the reporting store. `None` when it holds
+/// too few samples to draw.
+pub(super) fn build_comparison(
+    app_context: &AppContext,
+    metric: GraphMetric,
+    window: GraphWindow,
+    offset: ChronoDuration,
+    current: &[MetricBucket],
+    points_limit: usize,
+) -> Option<ComparisonPeriod> {
+    let (from, to) = window.bounds(chrono::Utc::now());
+    let range = TimeRange::new(from - offset, to - offset, app_context.config.tz())?;
+    let previous = range_buckets(app_context, range, points_limit);
+    if previous.len() < 2 {
+        return None;
+    }
+
+    let label = t_with("graph.compare.ago", &[("period", offset_label(offset))]);
+    let caption = comparison_caption(
+        &label,
+        summarize_buckets(current, metric)?,
+        summarize_buckets(&previous, metric)?,
+    );
+    let bands = downsample_bands(&previous, metric, points_limit)
+        .into_iter()
+        .map(|band| GraphBand {
+            timestamp: band.timestamp + offset,
+            ..band
+        })
+        .collect();
+
+    Some(ComparisonPeriod {
+        series: GraphSeries {
+            metric,
+            bands,
+            threshold: 0.0,
+            comparison: Some(format!("{} {label}", metric.title())),
+        },
+        caption,
+    })
+}
+
+/// `7d`, `12h` or `90m`: the largest unit that divides the offset.
+pub(super) fn offset_label(offset: ChronoDuration) -> String {
+    let minutes = offset.num_minutes();
+    if minutes % (24 * 60) == 0 {
+        format!("{}d", minutes / (24 * 60))
+    } else if minutes % 60 == 0 {
+        format!("{}h", minutes / 60)
+    } else {
+        format!("{minutes}m")
+    }
+}
+
+fn comparison_caption(label: &str, current: GraphBand, previous: GraphBand) -> String {
+    t_with(
+        "graph.compare.caption",
+        &[
+            ("period", label.to_string()),
+            ("avg", format!("{:.1}", current.avg)),
+            ("avg_delta", format!("{:+.1}", current.avg - previous.avg)),
+            ("max", format!("{:.1}", current.max)),
+            ("max_delta", format!("{:+.1}", current.max - previous.max)),
+        ],
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+
+    use super::{GraphBand, comparison_caption, offset_label};
+
+    #[test]
+    fn labels_offsets_and_signs_deltas() {
+        assert_eq!(offset_label(Duration::days(7)), "7d");
+        assert_eq!(offset_label(Duration::hours(36)), "36h");
+        assert_eq!(offset_label(Duration::minutes(90)), "90m");
+
+        let band = |avg, max| GraphBand {
+            timestamp: Utc::now(),
+            min: 0.0,
+            max,
+            avg,
+            p95: max,
+        };
+        assert_eq!(
+            comparison_caption("7d ago", band(35.2, 92.0), band(31.1, 95.0)),
+            "vs 7d ago: avg 35.2% (+4.1) | max 92.0% (-3.0)"
+        );
+    }
+}
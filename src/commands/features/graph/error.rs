@@ -1,5 +1,7 @@
 use thiserror::Error;
 
+use crate::i18n::t;
+
 #[derive(Debug, Error)]
 pub(super) enum GraphRenderError {
     #[error("not enough points to render")]
@@ -38,15 +40,11 @@ impl GraphRenderError {
     }
 
     pub(super) fn user_message(&self) -> &'static str {
-        match self {
-            Self::FontUnavailable(_) => {
-                "Graph engine embedded font failed to initialize. Please check build assets and restart."
-            }
-            Self::RenderSlotTimeout(_) => "Graph renderer is busy. Please retry shortly.",
-            Self::RenderTimeout(_) => {
-                "Graph render took too long and was cancelled. Please try a shorter window."
-            }
-            _ => "Could not render graph right now. Please try again.",
-        }
+        t(match self {
+            Self::FontUnavailable(_) => "graph.error.font",
+            Self::RenderSlotTimeout(_) => "graph.error.busy",
+            Self::RenderTimeout(_) => "graph.error.timeout",
+            _ => "graph.error.generic",
+        })
     }
 }
@@ -6,9 +6,12 @@ use tokio::{
     time::timeout,
 };
 
-use super::{
-    error::GraphRenderError, render::render_graph_png, stats::GraphPoint, types::GraphMetric,
-};
+use super::error::GraphRenderError;
+
+/// How long a chart waits for a free render slot.
+pub(super) const RENDER_SLOT_WAIT_TIMEOUT_SECS: u64 = 3;
+/// How long one chart may render before it is abandoned.
+pub(super) const RENDER_EXECUTION_TIMEOUT_SECS: u64 = 8;
 
 pub(super) async fn acquire_render_slot(
     slots: Arc<Semaphore>,
@@ -26,19 +29,21 @@ pub(super) async fn acquire_render_slot(
     }
 }
 
-pub(super) async fn run_render_task(
-    points: Vec<GraphPoint>,
-    metric: GraphMetric,
-    threshold: f32,
+/// Runs `render` on the blocking pool while holding `render_slot`, turning
+/// panics and overruns into render errors.
+pub(super) async fn run_render_task<F>(
+    render: F,
     render_slot: OwnedSemaphorePermit,
     render_timeout_secs: u64,
-) -> Result<Vec<u8>, GraphRenderError> {
+) -> Result<Vec<u8>, GraphRenderError>
+where
+    F: FnOnce() -> Result<Vec<u8>, GraphRenderError> + Send + 'static,
+{
     let render_handle = tokio::task::spawn_blocking(move || {
         let _render_slot = render_slot;
-        std::panic::catch_unwind(AssertUnwindSafe(|| {
-            render_graph_png(points, metric, threshold)
-        }))
-        .map_err(|panic_payload| GraphRenderError::Panic(describe_panic_payload(panic_payload)))?
+        std::panic::catch_unwind(AssertUnwindSafe(render)).map_err(|panic_payload| {
+            GraphRenderError::Panic(describe_panic_payload(panic_payload))
+        })?
     });
 
     match timeout(Duration::from_secs(render_timeout_secs), render_handle).await {
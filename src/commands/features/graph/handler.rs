@@ -3,19 +3,25 @@ use std::time::Instant;
 use teloxide::{prelude::*, types::InputFile, types::ParseMode};
 
 use crate::app_context::AppContext;
+use crate::i18n::{t, t_with};
+use crate::reporting_store::MetricBucket;
 
 use super::super::super::helpers::{acquire_command_slot, as_html_block};
 use super::super::menu::main_menu_keyboard;
+use super::super::time_range::TimeRange;
+use super::annotations::collect_annotations;
+use super::compare::{build_comparison, offset_label};
 use super::cooldown::graph_cooldown_remaining_secs;
-use super::executor::{acquire_render_slot, run_render_task};
+use super::executor::{
+    RENDER_EXECUTION_TIMEOUT_SECS, RENDER_SLOT_WAIT_TIMEOUT_SECS, acquire_render_slot,
+    run_render_task,
+};
 use super::parser::parse_graph_request;
-use super::render::GRAPH_WIDTH_PX;
-use super::stats::{assess_anomaly_labels, compute_metric_summary, downsample_points};
-use super::types::GraphRequest;
+use super::render::{GraphLayout, GraphOptions, GraphSeries, render_graph};
+use super::stats::{assess_anomaly_labels, downsample_bands, sample_buckets, summarize_buckets};
+use super::types::{GraphFormat, GraphMetric, GraphRequest, GraphWindow};
 
-const GRAPH_USAGE_TEXT: &str = "Usage: /graph cpu|ram|disk [<Nm|Nh>]";
-const RENDER_SLOT_WAIT_TIMEOUT_SECS: u64 = 3;
-const RENDER_EXECUTION_TIMEOUT_SECS: u64 = 8;
+const RANGE_ROLLUP_STEP_SECS: u64 = 300;
 
 pub(crate) async fn handle_graph(
     bot: &Bot,
@@ -35,10 +41,7 @@ pub(crate) async fn handle_graph(
     if !graph_runtime.enabled {
         bot.send_message(
             msg.chat.id,
-            as_html_block(
-                "Graph Disabled",
-                "Graph feature is disabled (runtime config). Enable graph in config if needed.",
-            ),
+            as_html_block(t("graph.disabled.title"), t("graph.disabled.body")),
         )
         .reply_markup(main_menu_keyboard(&app_context.capabilities))
         .parse_mode(ParseMode::Html)
@@ -49,36 +52,26 @@ pub(crate) async fn handle_graph(
         query,
         graph_runtime.default_window_minutes as i64,
         graph_runtime.max_window_hours as i64,
+        app_context.config.tz(),
     ) else {
-        bot.send_message(msg.chat.id, as_html_block("Graph Usage", GRAPH_USAGE_TEXT))
-            .reply_markup(main_menu_keyboard(&app_context.capabilities))
-            .parse_mode(ParseMode::Html)
-            .await?;
-        return Ok(());
-    };
-    if let Some(remaining_secs) = graph_cooldown_remaining_secs(app_context).await {
         bot.send_message(
             msg.chat.id,
-            as_html_block(
-                "Graph Cooldown",
-                &format!("Please wait {remaining_secs}s before using /graph again."),
-            ),
+            as_html_block(t("graph.usage.title"), t("graph.usage")),
         )
         .reply_markup(main_menu_keyboard(&app_context.capabilities))
         .parse_mode(ParseMode::Html)
         .await?;
         return Ok(());
-    }
-    let samples = {
-        let history = app_context.monitor.metric_history.lock().await;
-        history.latest_window(request.window.minutes())
     };
-    if samples.len() < 2 {
+    if let Some(remaining_secs) = graph_cooldown_remaining_secs(app_context).await {
         bot.send_message(
             msg.chat.id,
             as_html_block(
-                &format!("{} Graph", request.metric.title()),
-                "not enough samples yet",
+                t("graph.cooldown.title"),
+                &t_with(
+                    "graph.cooldown.body",
+                    &[("secs", remaining_secs.to_string())],
+                ),
             ),
         )
         .reply_markup(main_menu_keyboard(&app_context.capabilities))
@@ -86,31 +79,123 @@ pub(crate) async fn handle_graph(
         .await?;
         return Ok(());
     }
-    let summary = if let Some(summary) = compute_metric_summary(request.metric, &samples) {
-        summary
-    } else {
+    let (width, height) = request
+        .size
+        .unwrap_or((graph_runtime.width, graph_runtime.height));
+    let options = GraphOptions {
+        theme: request.theme.unwrap_or(graph_runtime.theme),
+        width,
+        height,
+        format: request.format,
+        window_end: match request.window {
+            GraphWindow::Recent { .. } => None,
+            GraphWindow::Range(range) => Some(range.local_end()),
+        },
+    };
+    let max_points = usize::from(graph_runtime.max_points).max(2);
+    let width_limit = usize::try_from(options.width).unwrap_or(max_points);
+    let points_limit = max_points.min(width_limit);
+    let buckets = match request.window {
+        GraphWindow::Recent { minutes } => {
+            let history = app_context.monitor.metric_history.lock().await;
+            sample_buckets(&history.latest_window(minutes))
+        }
+        GraphWindow::Range(range) => range_buckets(app_context, range, points_limit),
+    };
+    let samples = buckets.iter().map(|bucket| bucket.avg).collect::<Vec<_>>();
+    let title = graph_title(&request.metrics);
+    if samples.len() < 2 {
         bot.send_message(
             msg.chat.id,
-            as_html_block(
-                &format!("{} Graph", request.metric.title()),
-                "not enough samples yet",
-            ),
+            as_html_block(&title, t("error.not_enough_samples")),
         )
         .reply_markup(main_menu_keyboard(&app_context.capabilities))
         .parse_mode(ParseMode::Html)
         .await?;
         return Ok(());
-    };
-    let threshold = request.metric.threshold(&runtime_config.alerts);
-    let anomaly_labels = assess_anomaly_labels(request.metric, &samples, threshold)
-        .map(|assessment| assessment.labels().join(" | "))
-        .unwrap_or_default();
-    let max_points = usize::from(graph_runtime.max_points).max(2);
-    let width_limit = usize::try_from(GRAPH_WIDTH_PX).unwrap_or(max_points);
-    let points_limit = max_points.min(width_limit);
-    let points = downsample_points(&samples, request.metric, points_limit);
-    let point_count = points.len();
-    let GraphRequest { metric, window } = request;
+    }
+    let mut caption_parts = Vec::with_capacity(request.metrics.len());
+    let mut series = Vec::with_capacity(request.metrics.len());
+    for &metric in &request.metrics {
+        let Some(summary) = summarize_buckets(&buckets, metric) else {
+            bot.send_message(
+                msg.chat.id,
+                as_html_block(&title, t("error.not_enough_samples")),
+            )
+            .reply_markup(main_menu_keyboard(&app_context.capabilities))
+            .parse_mode(ParseMode::Html)
+            .await?;
+            return Ok(());
+        };
+        let threshold = metric.threshold(&runtime_config.alerts);
+        let values = [
+            ("min", format!("{:.1}", summary.min)),
+            ("max", format!("{:.1}", summary.max)),
+            ("avg", format!("{:.1}", summary.avg)),
+        ];
+        if request.metrics.len() == 1 {
+            caption_parts.push(t_with("graph.caption.summary", &values));
+            caption_parts.extend(
+                assess_anomaly_labels(metric, &samples, threshold)
+                    .into_iter()
+                    .flat_map(|assessment| assessment.labels())
+                    .map(str::to_string),
+            );
+        } else {
+            let [min, max, avg] = values;
+            caption_parts.push(t_with(
+                "graph.caption.metric_summary",
+                &[("metric", metric.title().to_string()), min, max, avg],
+            ));
+        }
+        series.push(GraphSeries {
+            metric,
+            bands: downsample_bands(&buckets, metric, points_limit),
+            threshold,
+            comparison: None,
+        });
+    }
+    if let (Some(offset), [metric]) = (request.compare, request.metrics.as_slice()) {
+        let Some(comparison) = build_comparison(
+            app_context,
+            *metric,
+            request.window,
+            offset,
+            &buckets,
+            points_limit,
+        ) else {
+            bot.send_message(
+                msg.chat.id,
+                as_html_block(
+                    &title,
+                    &t_with("graph.compare.no_data", &[("period", offset_label(offset))]),
+                ),
+            )
+            .reply_markup(main_menu_keyboard(&app_context.capabilities))
+            .parse_mode(ParseMode::Html)
+            .await?;
+            return Ok(());
+        };
+        caption_parts = vec![comparison.caption];
+        series.push(comparison.series);
+    }
+    let point_count = series
+        .iter()
+        .map(|series| series.bands.len())
+        .sum::<usize>();
+    let GraphRequest {
+        metrics,
+        window,
+        compare,
+        ..
+    } = request;
+    let metric_names = metrics
+        .iter()
+        .map(|metric| metric.title())
+        .collect::<Vec<_>>()
+        .join("+");
+    let (from, to) = window.bounds(chrono::Utc::now());
+    let annotations = collect_annotations(app_context, &metrics, from, to).await;
     let render_slot = match acquire_render_slot(
         app_context.bot_runtime.graph_render_slots.clone(),
         RENDER_SLOT_WAIT_TIMEOUT_SECS,
@@ -121,7 +206,7 @@ pub(crate) async fn handle_graph(
         Err(error) => {
             log::warn!(
                 "graph_render_slot_unavailable metric={} window_minutes={} code={} error={}",
-                metric.title(),
+                metric_names,
                 window.minutes(),
                 error.code(),
                 error
@@ -129,7 +214,7 @@ pub(crate) async fn handle_graph(
             bot.send_message(
                 msg.chat.id,
                 as_html_block(
-                    "Graph Render",
+                    t("graph.render.title"),
                     &format!("{} (code: {})", error.user_message(), error.code()),
                 ),
             )
@@ -140,42 +225,37 @@ pub(crate) async fn handle_graph(
         }
     };
     let render_result = run_render_task(
-        points,
-        metric,
-        threshold,
+        move || render_graph(series, GraphLayout::Overlay, annotations, options),
         render_slot,
         RENDER_EXECUTION_TIMEOUT_SECS,
     )
     .await;
 
     match render_result {
-        Ok(png_bytes) => {
-            bot.send_photo(
-                msg.chat.id,
-                InputFile::memory(png_bytes).file_name(format!(
-                    "{}-{}.png",
-                    metric.file_name(),
-                    window.suffix()
-                )),
-            )
-            .caption(format!(
-                "{} ({}) | min: {:.1}% | max: {:.1}% | avg: {:.1}%{}",
-                metric.caption(),
+        Ok(bytes) => {
+            let file = InputFile::memory(bytes).file_name(format!(
+                "{}-{}{}.{}",
+                metrics
+                    .iter()
+                    .map(|metric| metric.file_name())
+                    .collect::<Vec<_>>()
+                    .join("-"),
                 window.suffix(),
-                summary.min,
-                summary.max,
-                summary.avg,
-                if anomaly_labels.is_empty() {
-                    String::new()
-                } else {
-                    format!(" | {anomaly_labels}")
-                }
-            ))
-            .reply_markup(main_menu_keyboard(&app_context.capabilities))
-            .await?;
+                compare
+                    .map(|offset| format!("-vs-{}", offset_label(offset)))
+                    .unwrap_or_default(),
+                options.format.extension()
+            ));
+            let caption = format!(
+                "{} ({}) | {}",
+                graph_caption(&metrics),
+                window.label(),
+                caption_parts.join(" | ")
+            );
+            send_rendered_graph(bot, msg, app_context, file, caption, options.format).await?;
             log::info!(
                 "graph_command_completed metric={} window_minutes={} source_samples={} rendered_points={} elapsed_ms={}",
-                metric.title(),
+                metric_names,
                 window.minutes(),
                 samples.len(),
                 point_count,
@@ -185,7 +265,7 @@ pub(crate) async fn handle_graph(
         Err(error) => {
             log::error!(
                 "graph_render_failed metric={} window_minutes={} code={} error={}",
-                metric.title(),
+                metric_names,
                 window.minutes(),
                 error.code(),
                 error
@@ -193,7 +273,7 @@ pub(crate) async fn handle_graph(
             bot.send_message(
                 msg.chat.id,
                 as_html_block(
-                    &format!("{} Graph", metric.title()),
+                    &title,
                     &format!("{} (code: {})", error.user_message(), error.code()),
                 ),
             )
@@ -204,3 +284,72 @@ pub(crate) async fn handle_graph(
     }
     Ok(())
 }
+
+/// PNGs go out as photos, SVGs as documents so Telegram keeps them vector.
+pub(super) async fn send_rendered_graph(
+    bot: &Bot,
+    msg: &Message,
+    app_context: &AppContext,
+    file: InputFile,
+    caption: String,
+    format: GraphFormat,
+) -> ResponseResult<()> {
+    match format {
+        GraphFormat::Png => {
+            bot.send_photo(msg.chat.id, file)
+                .caption(caption)
+                .reply_markup(main_menu_keyboard(&app_context.capabilities))
+                .await?;
+        }
+        GraphFormat::Svg => {
+            bot.send_document(msg.chat.id, file)
+                .caption(caption)
+                .reply_markup(main_menu_keyboard(&app_context.capabilities))
+                .await?;
+        }
+    }
+    Ok(())
+}
+
+/// Raw samples for short periods; rollup buckets with their min/max once a
+/// drawn band would span at least one 5-minute rollup.
+pub(super) fn range_buckets(
+    app_context: &AppContext,
+    range: TimeRange,
+    points_limit: usize,
+) -> Vec<MetricBucket> {
+    let span_secs = u64::try_from(range.minutes() * 60).unwrap_or_default();
+    let bucket_secs = span_secs.div_ceil(points_limit.max(1) as u64);
+    let store = &app_context.reporting_store;
+    if bucket_secs < RANGE_ROLLUP_STEP_SECS {
+        return sample_buckets(&store.range(range.from, range.to));
+    }
+
+    let bucket_secs = bucket_secs.div_ceil(RANGE_ROLLUP_STEP_SECS) * RANGE_ROLLUP_STEP_SECS;
+    store.rollup_range(
+        range.from,
+        range.to,
+        chrono::Duration::seconds(i64::try_from(bucket_secs).unwrap_or(i64::from(u32::MAX))),
+    )
+}
+
+fn graph_title(metrics: &[GraphMetric]) -> String {
+    let names = metrics
+        .iter()
+        .map(|metric| metric.title())
+        .collect::<Vec<_>>()
+        .join(", ");
+    t_with("graph.title", &[("metric", names)])
+}
+
+/// The single metric's caption, or the metric names when several are overlaid.
+fn graph_caption(metrics: &[GraphMetric]) -> String {
+    match metrics {
+        [metric] => metric.caption().to_string(),
+        _ => metrics
+            .iter()
+            .map(|metric| metric.title())
+            .collect::<Vec<_>>()
+            .join(" / "),
+    }
+}
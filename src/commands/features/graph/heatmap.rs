@@ -0,0 +1,409 @@
+//! `/heatmap`: one metric by weekday and hour of day, built from hourly
+//! rollups, for capacity planning and spotting recurring (e.g. cron) load.
+
+use chrono::{Datelike, Duration as ChronoDuration, Timelike, Utc};
+use chrono_tz::Tz;
+use teloxide::{prelude::*, types::InputFile, types::ParseMode};
+
+use crate::app_context::AppContext;
+use crate::config::GraphTheme;
+use crate::i18n::{t, t_with};
+use crate::reporting_store::MetricBucket;
+
+use super::super::super::helpers::{acquire_command_slot, as_html_block};
+use super::super::menu::main_menu_keyboard;
+use super::executor::{
+    RENDER_EXECUTION_TIMEOUT_SECS, RENDER_SLOT_WAIT_TIMEOUT_SECS, acquire_render_slot,
+    run_render_task,
+};
+use super::handler::send_rendered_graph;
+use super::parser::{parse_format, parse_theme};
+use super::render::{GraphOptions, render_heatmap};
+use super::stats::summarize_buckets;
+use super::types::{GraphFormat, GraphMetric};
+
+const DEFAULT_HEATMAP_DAYS: i64 = 28;
+const WEEKDAYS: [&str; HeatmapGrid::DAYS] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+
+/// Value shown per cell.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum HeatmapStat {
+    /// Sample-weighted mean of the hourly averages.
+    Avg,
+    /// Upper percentile of the hourly averages, to surface occasional peaks.
+    P95,
+}
+
+impl HeatmapStat {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Avg => "avg",
+            Self::P95 => "p95",
+        }
+    }
+}
+
+struct HeatmapRequest {
+    metric: GraphMetric,
+    days: i64,
+    stat: HeatmapStat,
+    theme: Option<GraphTheme>,
+    format: GraphFormat,
+}
+
+/// Cell values by weekday from Monday, then local hour; `None` where no
+/// hourly rollup fell.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct HeatmapGrid {
+    cells: [[Option<f32>; HeatmapGrid::HOURS]; HeatmapGrid::DAYS],
+}
+
+impl HeatmapGrid {
+    pub(super) const DAYS: usize = 7;
+    pub(super) const HOURS: usize = 24;
+
+    /// Groups hourly buckets by their local weekday and hour in `tz`.
+    pub(super) fn from_buckets(
+        buckets: &[MetricBucket],
+        metric: GraphMetric,
+        stat: HeatmapStat,
+        tz: Tz,
+    ) -> Self {
+        let mut groups: [[Vec<MetricBucket>; Self::HOURS]; Self::DAYS] = Default::default();
+        for bucket in buckets {
+            let local = bucket.start.with_timezone(&tz);
+            groups[local.weekday().num_days_from_monday() as usize][local.hour() as usize]
+                .push(*bucket);
+        }
+
+        let cells = groups.map(|hours| {
+            hours.map(|buckets| {
+                summarize_buckets(&buckets, metric).map(|band| match stat {
+                    HeatmapStat::Avg => band.avg,
+                    HeatmapStat::P95 => band.p95,
+                })
+            })
+        });
+        Self { cells }
+    }
+
+    pub(super) fn is_empty(&self) -> bool {
+        self.cells().next().is_none()
+    }
+
+    /// `(weekday from Monday, hour, value)` of every filled cell.
+    pub(super) fn cells(&self) -> impl Iterator<Item = (usize, usize, f32)> + '_ {
+        self.cells.iter().enumerate().flat_map(|(day, hours)| {
+            hours
+                .iter()
+                .enumerate()
+                .filter_map(move |(hour, value)| Some((day, hour, (*value)?)))
+        })
+    }
+
+    pub(super) fn weekday(day: u32) -> &'static str {
+        WEEKDAYS[day as usize % Self::DAYS]
+    }
+
+    fn cell_label((day, hour, value): (usize, usize, f32)) -> String {
+        format!("{} {hour:02}:00 {value:.1}%", WEEKDAYS[day])
+    }
+}
+
+pub(crate) async fn handle_heatmap(
+    bot: &Bot,
+    msg: &Message,
+    app_context: &AppContext,
+    query: &str,
+) -> ResponseResult<()> {
+    let graph_runtime = app_context.graph_runtime.read().await.clone();
+    let Some(_permit) =
+        acquire_command_slot(&app_context.bot_runtime.command_slots, msg, bot).await?
+    else {
+        return Ok(());
+    };
+
+    if !graph_runtime.enabled {
+        bot.send_message(
+            msg.chat.id,
+            as_html_block(t("graph.disabled.title"), t("graph.disabled.body")),
+        )
+        .reply_markup(main_menu_keyboard(&app_context.capabilities))
+        .parse_mode(ParseMode::Html)
+        .await?;
+        return Ok(());
+    }
+    let max_days = i64::from(app_context.config.reporting_store.rollup_1h_retention_days);
+    let Some(request) = parse_heatmap_request(query, max_days) else {
+        bot.send_message(
+            msg.chat.id,
+            as_html_block(t("heatmap.usage.title"), t("heatmap.usage")),
+        )
+        .reply_markup(main_menu_keyboard(&app_context.capabilities))
+        .parse_mode(ParseMode::Html)
+        .await?;
+        return Ok(());
+    };
+
+    let HeatmapRequest {
+        metric,
+        days,
+        stat,
+        theme,
+        format,
+    } = request;
+    let tz = app_context.config.tz();
+    let to = Utc::now();
+    let buckets = app_context.reporting_store.rollup_range(
+        to - ChronoDuration::days(days),
+        to,
+        ChronoDuration::hours(1),
+    );
+    let grid = HeatmapGrid::from_buckets(&buckets, metric, stat, tz);
+    let title = t_with("heatmap.title", &[("metric", metric.title().to_string())]);
+    let (Some(busiest), Some(quietest)) = (
+        grid.cells()
+            .max_by(|left, right| left.2.total_cmp(&right.2)),
+        grid.cells()
+            .min_by(|left, right| left.2.total_cmp(&right.2)),
+    ) else {
+        bot.send_message(
+            msg.chat.id,
+            as_html_block(&title, t("error.not_enough_samples")),
+        )
+        .reply_markup(main_menu_keyboard(&app_context.capabilities))
+        .parse_mode(ParseMode::Html)
+        .await?;
+        return Ok(());
+    };
+
+    let window = window_label(days);
+    let caption = t_with(
+        "heatmap.caption",
+        &[
+            ("metric", metric.title().to_string()),
+            ("window", window.clone()),
+            ("stat", stat.label().to_string()),
+            ("timezone", tz.to_string()),
+            ("busiest", HeatmapGrid::cell_label(busiest)),
+            ("quietest", HeatmapGrid::cell_label(quietest)),
+        ],
+    );
+    let options = GraphOptions {
+        theme: theme.unwrap_or(graph_runtime.theme),
+        format,
+        ..GraphOptions::from_config(&graph_runtime)
+    };
+
+    let render_slot = match acquire_render_slot(
+        app_context.bot_runtime.graph_render_slots.clone(),
+        RENDER_SLOT_WAIT_TIMEOUT_SECS,
+    )
+    .await
+    {
+        Ok(permit) => permit,
+        Err(error) => {
+            log::warn!(
+                "heatmap_render_slot_unavailable metric={} days={} code={} error={}",
+                metric.title(),
+                days,
+                error.code(),
+                error
+            );
+            bot.send_message(
+                msg.chat.id,
+                as_html_block(
+                    t("graph.render.title"),
+                    &format!("{} (code: {})", error.user_message(), error.code()),
+                ),
+            )
+            .reply_markup(main_menu_keyboard(&app_context.capabilities))
+            .parse_mode(ParseMode::Html)
+            .await?;
+            return Ok(());
+        }
+    };
+    let render_result = run_render_task(
+        move || render_heatmap(grid, metric, options),
+        render_slot,
+        RENDER_EXECUTION_TIMEOUT_SECS,
+    )
+    .await;
+
+    match render_result {
+        Ok(bytes) => {
+            let file = InputFile::memory(bytes).file_name(format!(
+                "{}-heatmap-{window}-{}.{}",
+                metric.file_name(),
+                stat.label(),
+                format.extension()
+            ));
+            send_rendered_graph(bot, msg, app_context, file, caption, format).await?;
+            log::info!(
+                "heatmap_command_completed metric={} days={} stat={} source_buckets={}",
+                metric.title(),
+                days,
+                stat.label(),
+                buckets.len()
+            );
+        }
+        Err(error) => {
+            log::error!(
+                "heatmap_render_failed metric={} days={} code={} error={}",
+                metric.title(),
+                days,
+                error.code(),
+                error
+            );
+            bot.send_message(
+                msg.chat.id,
+                as_html_block(
+                    &title,
+                    &format!("{} (code: {})", error.user_message(), error.code()),
+                ),
+            )
+            .reply_markup(main_menu_keyboard(&app_context.capabilities))
+            .parse_mode(ParseMode::Html)
+            .await?;
+        }
+    }
+    Ok(())
+}
+
+/// `<metric> [Nd|Nw] [avg|p95] [light|dark] [png|svg]` in any order after the
+/// metric; the window must fit in `max_days` of hourly rollups.
+fn parse_heatmap_request(query: &str, max_days: i64) -> Option<HeatmapRequest> {
+    let mut args = query.split_whitespace();
+    let metric = GraphMetric::parse(args.next()?)?;
+
+    let mut days = None;
+    let mut stat = None;
+    let mut theme = None;
+    let mut format = None;
+    for arg in args {
+        let replaced = if let Some(value) = parse_stat(arg) {
+            stat.replace(value).is_some()
+        } else if let Some(value) = parse_theme(arg) {
+            theme.replace(value).is_some()
+        } else if let Some(value) = parse_format(arg) {
+            format.replace(value).is_some()
+        } else {
+            days.replace(parse_window_days(arg)?).is_some()
+        };
+        if replaced {
+            return None;
+        }
+    }
+
+    let days = days.unwrap_or(DEFAULT_HEATMAP_DAYS.min(max_days));
+    if days <= 0 || days > max_days {
+        return None;
+    }
+    Some(HeatmapRequest {
+        metric,
+        days,
+        stat: stat.unwrap_or(HeatmapStat::Avg),
+        theme,
+        format: format.unwrap_or(GraphFormat::Png),
+    })
+}
+
+fn parse_stat(input: &str) -> Option<HeatmapStat> {
+    match input.to_lowercase().as_str() {
+        "avg" => Some(HeatmapStat::Avg),
+        "p95" => Some(HeatmapStat::P95),
+        _ => None,
+    }
+}
+
+/// `Nd` or `Nw`, in days.
+fn parse_window_days(input: &str) -> Option<i64> {
+    let value = input.trim().to_lowercase();
+    let (number, unit) = value.split_at_checked(value.len().checked_sub(1)?)?;
+    let number = number.parse::<i64>().ok().filter(|number| *number > 0)?;
+    match unit {
+        "d" => Some(number),
+        "w" => number.checked_mul(7),
+        _ => None,
+    }
+}
+
+fn window_label(days: i64) -> String {
+    if days % 7 == 0 {
+        format!("{}w", days / 7)
+    } else {
+        format!("{days}d")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, TimeZone, Utc};
+    use chrono_tz::Tz;
+
+    use super::{HeatmapGrid, HeatmapStat, parse_heatmap_request};
+    use crate::commands::features::graph::stats::sample_buckets;
+    use crate::commands::features::graph::types::{GraphFormat, GraphMetric};
+    use crate::monitor::MetricSample;
+
+    #[test]
+    fn groups_hourly_buckets_by_local_weekday_and_hour() {
+        // Monday 2026-10-12 06:00 UTC is 09:00 in Istanbul.
+        let monday = Utc.with_ymd_and_hms(2026, 10, 12, 6, 0, 0).unwrap();
+        let buckets = sample_buckets(
+            &[
+                (monday, 40.0),
+                (monday + Duration::weeks(1), 60.0),
+                (monday + Duration::hours(1), 10.0),
+                (monday + Duration::days(6), 90.0),
+            ]
+            .map(|(timestamp, cpu)| MetricSample {
+                timestamp,
+                cpu,
+                ram: 0.0,
+                disk: 0.0,
+            }),
+        );
+
+        let grid = HeatmapGrid::from_buckets(
+            &buckets,
+            GraphMetric::Cpu,
+            HeatmapStat::Avg,
+            Tz::Europe__Istanbul,
+        );
+        assert_eq!(
+            grid.cells().collect::<Vec<_>>(),
+            [(0, 9, 50.0), (0, 10, 10.0), (6, 9, 90.0)]
+        );
+        let p95 = HeatmapGrid::from_buckets(
+            &buckets,
+            GraphMetric::Cpu,
+            HeatmapStat::P95,
+            Tz::Europe__Istanbul,
+        );
+        assert_eq!(p95.cells().next(), Some((0, 9, 60.0)));
+        assert!(
+            HeatmapGrid::from_buckets(&[], GraphMetric::Cpu, HeatmapStat::Avg, Tz::UTC).is_empty()
+        );
+    }
+
+    #[test]
+    fn parses_window_stat_and_appearance_within_rollup_retention() {
+        let request = parse_heatmap_request("cpu", 90).expect("request parses");
+        assert_eq!((request.days, request.stat), (28, HeatmapStat::Avg));
+
+        let request = parse_heatmap_request("ram p95 10d svg", 90).expect("request parses");
+        assert_eq!((request.days, request.stat), (10, HeatmapStat::P95));
+        assert_eq!(request.format, GraphFormat::Svg);
+        assert_eq!(
+            parse_heatmap_request("disk 2w", 90).map(|r| r.days),
+            Some(14)
+        );
+        assert_eq!(parse_heatmap_request("cpu", 7).map(|r| r.days), Some(7));
+
+        assert!(parse_heatmap_request("cpu 20w", 90).is_none());
+        assert!(parse_heatmap_request("cpu 4w 2w", 90).is_none());
+        assert!(parse_heatmap_request("cpu 4x", 90).is_none());
+        assert!(parse_heatmap_request("gpu 4w", 90).is_none());
+    }
+}
@@ -0,0 +1,94 @@
+use chrono::{Duration as ChronoDuration, Utc};
+
+use crate::app_context::AppContext;
+use crate::incidents::Incident;
+
+use super::super::time_range::TimeRange;
+use super::GeneratedGraphReport;
+use super::annotations::collect_annotations;
+use super::executor::{
+    RENDER_EXECUTION_TIMEOUT_SECS, RENDER_SLOT_WAIT_TIMEOUT_SECS, acquire_render_slot,
+    run_render_task,
+};
+use super::handler::range_buckets;
+use super::render::{GraphLayout, GraphOptions, GraphSeries, render_graph};
+use super::stats::downsample_bands;
+use super::types::GraphMetric;
+
+/// Context shown on each side of the incident, at least this much.
+const MIN_PADDING_MINUTES: i64 = 5;
+
+/// Chart of the incident's metric from shortly before it opened until shortly
+/// after it resolved (or now), with the threshold it crossed.
+pub(crate) async fn build_incident_graph(
+    app_context: &AppContext,
+    incident: &Incident,
+) -> Result<GeneratedGraphReport, String> {
+    let graph_runtime = app_context.graph_runtime.read().await.clone();
+    if !graph_runtime.enabled {
+        return Err("graph feature is disabled in config".to_string());
+    }
+
+    let now = Utc::now();
+    let started = incident
+        .started()
+        .ok_or_else(|| "incident has no valid start time".to_string())?;
+    let ended = incident.ended().unwrap_or(now).min(now);
+    let padding = ((ended - started) / 4).max(ChronoDuration::minutes(MIN_PADDING_MINUTES));
+    let range = TimeRange::new(
+        started - padding,
+        (ended + padding).min(now),
+        app_context.config.tz(),
+    )
+    .ok_or_else(|| "incident window is out of range".to_string())?;
+
+    let options = GraphOptions::from_config(&graph_runtime);
+    let max_points = usize::from(graph_runtime.max_points).max(2);
+    let width_limit = usize::try_from(options.width).unwrap_or(max_points);
+    let points_limit = max_points.min(width_limit);
+    let buckets = range_buckets(app_context, range, points_limit);
+    if buckets.len() < 2 {
+        return Err("not enough samples yet".to_string());
+    }
+
+    let metric = GraphMetric::from(incident.metric);
+    let bands = downsample_bands(&buckets, metric, points_limit);
+    let annotations = collect_annotations(app_context, &[metric], range.from, range.to).await;
+    let render_slot = acquire_render_slot(
+        app_context.bot_runtime.graph_render_slots.clone(),
+        RENDER_SLOT_WAIT_TIMEOUT_SECS,
+    )
+    .await
+    .map_err(|error| {
+        format!(
+            "incident render slot failed code={} error={}",
+            error.code(),
+            error
+        )
+    })?;
+    let series = vec![GraphSeries {
+        metric,
+        bands,
+        threshold: incident.threshold,
+        comparison: None,
+    }];
+    let png_bytes = run_render_task(
+        move || render_graph(series, GraphLayout::Overlay, annotations, options),
+        render_slot,
+        RENDER_EXECUTION_TIMEOUT_SECS,
+    )
+    .await
+    .map_err(|error| {
+        format!(
+            "incident render failed code={} error={}",
+            error.code(),
+            error
+        )
+    })?;
+
+    Ok(GeneratedGraphReport {
+        png_bytes,
+        file_name: format!("incident-{}-{}.png", incident.id, metric.file_name()),
+        caption: format!("{} ({})", metric.caption(), range.label()),
+    })
+}
@@ -1,18 +1,36 @@
+mod annotations;
+mod compare;
 mod cooldown;
 mod error;
 mod executor;
 mod handler;
+mod heatmap;
+mod incident;
+mod monthly;
 mod parser;
 mod render;
+mod sparkline;
 mod stats;
 mod types;
 mod weekly;
 
 pub(crate) use handler::handle_graph;
-pub(crate) use weekly::build_weekly_cpu_report;
+pub(crate) use heatmap::handle_heatmap;
+pub(crate) use incident::build_incident_graph;
+pub(crate) use monthly::build_monthly_capacity_report;
+pub(crate) use sparkline::{handle_spark, spark_lines};
+pub(crate) use weekly::build_weekly_report;
 
 pub(crate) struct GeneratedGraphReport {
     pub png_bytes: Vec<u8>,
     pub file_name: String,
     pub caption: String,
 }
+
+/// Monthly capacity chart plus the full text report sent as a document.
+pub(crate) struct GeneratedCapacityReport {
+    pub chart: GeneratedGraphReport,
+    pub document: Vec<u8>,
+    pub document_name: String,
+    pub document_caption: String,
+}
@@ -0,0 +1,401 @@
+//! Monthly capacity report over the previous calendar month: CPU, RAM and
+//! disk panels from the daily rollups, plus a text document with percentiles,
+//! alert and incident counts, disk growth and the busiest hours.
+
+use chrono::{
+    DateTime, Datelike, Duration as ChronoDuration, NaiveDate, NaiveTime, TimeZone, Timelike, Utc,
+};
+use chrono_tz::Tz;
+
+use crate::app_context::AppContext;
+use crate::i18n::t_with;
+use crate::incidents::{Incident, IncidentMetric, IncidentStats};
+use crate::reporting_store::MetricBucket;
+
+use super::super::incidents::format_mttr;
+use super::annotations::collect_annotations;
+use super::executor::{
+    RENDER_EXECUTION_TIMEOUT_SECS, RENDER_SLOT_WAIT_TIMEOUT_SECS, acquire_render_slot,
+    run_render_task,
+};
+use super::render::{GraphLayout, GraphOptions, GraphSeries, render_graph};
+use super::stats::{bucket_percentile, downsample_bands, summarize_buckets};
+use super::types::GraphMetric;
+use super::{GeneratedCapacityReport, GeneratedGraphReport};
+
+/// Hours of the day listed as busiest.
+const BUSIEST_HOURS: usize = 3;
+/// Disk usage is projected this many days past the last day of the month.
+const PROJECTION_DAYS: f32 = 30.0;
+
+/// Least-squares trend of daily disk averages.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct DiskGrowth {
+    per_day: f32,
+    latest: f32,
+}
+
+impl DiskGrowth {
+    fn from_days(days: &[MetricBucket]) -> Option<Self> {
+        let (first, latest) = (days.first()?, days.last()?);
+        let points = days
+            .iter()
+            .map(|day| {
+                let x = (day.start - first.start).num_days() as f64;
+                (x, f64::from(day.avg.disk))
+            })
+            .collect::<Vec<_>>();
+        let count = points.len() as f64;
+        let mean_x = points.iter().map(|(x, _)| x).sum::<f64>() / count;
+        let mean_y = points.iter().map(|(_, y)| y).sum::<f64>() / count;
+        let spread = points
+            .iter()
+            .map(|(x, _)| (x - mean_x).powi(2))
+            .sum::<f64>();
+        if spread == 0.0 {
+            return None;
+        }
+        let covariance = points
+            .iter()
+            .map(|(x, y)| (x - mean_x) * (y - mean_y))
+            .sum::<f64>();
+
+        Some(Self {
+            per_day: (covariance / spread) as f32,
+            latest: latest.avg.disk,
+        })
+    }
+
+    fn projected(self) -> f32 {
+        (self.latest + self.per_day * PROJECTION_DAYS).clamp(0.0, 100.0)
+    }
+
+    /// Days until the trend reaches 100%, when disk usage is growing.
+    fn days_until_full(self) -> Option<f32> {
+        (self.per_day > 0.0).then(|| ((100.0 - self.latest) / self.per_day).max(0.0))
+    }
+
+    fn describe(self) -> String {
+        let rate = format!("{:+.2}", self.per_day);
+        match self.days_until_full() {
+            Some(days) => t_with(
+                "summary.monthly.growth",
+                &[
+                    ("rate", rate),
+                    ("projected", format!("{:.1}", self.projected())),
+                    ("full_days", format!("{days:.0}")),
+                ],
+            ),
+            None => t_with("summary.monthly.growth.flat", &[("rate", rate)]),
+        }
+    }
+}
+
+pub(crate) async fn build_monthly_capacity_report(
+    app_context: &AppContext,
+) -> Result<GeneratedCapacityReport, String> {
+    let graph_runtime = app_context.graph_runtime.read().await.clone();
+    let runtime_config = app_context.runtime_config.read().await.clone();
+    if !graph_runtime.enabled {
+        return Err("graph feature is disabled in config".to_string());
+    }
+
+    let tz = app_context.config.tz();
+    let (first_day, next_month) = previous_month(Utc::now().with_timezone(&tz).date_naive());
+    let month = first_day.format("%Y-%m").to_string();
+    let store = &app_context.reporting_store;
+    let days = store.daily_range(first_day, next_month);
+    if days.len() < 2 {
+        return Err(format!("not enough daily rollups for {month}"));
+    }
+
+    // The month is local; daily rollups are keyed by UTC day, so only the
+    // hourly data, incidents and annotations follow the local bounds.
+    let from = local_midnight(first_day, tz);
+    let to = local_midnight(next_month, tz);
+    let hourly = store.rollup_range(from, to, ChronoDuration::hours(1));
+    // Percentiles read hourly averages; daily ones flatten every peak.
+    let percentile_source = if hourly.is_empty() { &days } else { &hourly };
+    let incidents = app_context
+        .incident_store
+        .incidents_since(from)
+        .into_iter()
+        .filter(|incident| incident.started().is_some_and(|started| started < to))
+        .collect::<Vec<_>>();
+    let growth = DiskGrowth::from_days(&days);
+
+    let options = GraphOptions::from_config(&graph_runtime);
+    let series = GraphMetric::ALL
+        .iter()
+        .map(|&metric| GraphSeries {
+            metric,
+            bands: downsample_bands(&days, metric, days.len()),
+            threshold: metric.threshold(&runtime_config.alerts),
+            comparison: None,
+        })
+        .collect();
+    let annotations = collect_annotations(app_context, &GraphMetric::ALL, from, to).await;
+    let render_slot = acquire_render_slot(
+        app_context.bot_runtime.graph_render_slots.clone(),
+        RENDER_SLOT_WAIT_TIMEOUT_SECS,
+    )
+    .await
+    .map_err(|error| {
+        format!(
+            "monthly render slot failed code={} error={}",
+            error.code(),
+            error
+        )
+    })?;
+    let png_bytes = run_render_task(
+        move || render_graph(series, GraphLayout::Stacked, annotations, options),
+        render_slot,
+        RENDER_EXECUTION_TIMEOUT_SECS,
+    )
+    .await
+    .map_err(|error| {
+        format!(
+            "monthly render failed code={} error={}",
+            error.code(),
+            error
+        )
+    })?;
+
+    let incident_stats = IncidentStats::from_incidents(&incidents);
+    let alerts_sent = incidents
+        .iter()
+        .map(|incident| u64::from(incident.alerts_sent))
+        .sum::<u64>();
+    let growth_text = growth.map_or_else(
+        || {
+            t_with(
+                "summary.monthly.growth.flat",
+                &[("rate", "n/a".to_string())],
+            )
+        },
+        DiskGrowth::describe,
+    );
+    let percentile =
+        |metric, quantile| bucket_percentile(percentile_source, metric, quantile).unwrap_or(0.0);
+    let peak = |metric: GraphMetric| {
+        days.iter()
+            .map(|day| metric.value(&day.max))
+            .fold(0.0, f32::max)
+    };
+
+    let caption = t_with(
+        "summary.monthly.caption",
+        &[
+            ("month", month.clone()),
+            (
+                "cpu_p95",
+                format!("{:.1}", percentile(GraphMetric::Cpu, 0.95)),
+            ),
+            (
+                "ram_p95",
+                format!("{:.1}", percentile(GraphMetric::Ram, 0.95)),
+            ),
+            ("disk_max", format!("{:.1}", peak(GraphMetric::Disk))),
+            ("alerts", alerts_sent.to_string()),
+            ("incidents", incident_stats.count.to_string()),
+            ("mttr", format_mttr(incident_stats.mttr)),
+            ("growth", growth_text.clone()),
+        ],
+    );
+    let metric_lines = GraphMetric::ALL
+        .iter()
+        .map(|&metric| {
+            format!(
+                "{:<4} p50 {:>5.1}% | p95 {:>5.1}% | max {:>5.1}%",
+                metric.title(),
+                percentile(metric, 0.5),
+                percentile(metric, 0.95),
+                peak(metric)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    let daily_lines = days
+        .iter()
+        .map(|day| {
+            format!(
+                "{} | CPU {:>5.1}/{:>5.1} | RAM {:>5.1}/{:>5.1} | Disk {:>5.1}/{:>5.1}",
+                day.start.format("%Y-%m-%d"),
+                day.avg.cpu,
+                day.max.cpu,
+                day.avg.ram,
+                day.max.ram,
+                day.avg.disk,
+                day.max.disk
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    let document = t_with(
+        "summary.monthly.document",
+        &[
+            ("month", month.clone()),
+            ("timezone", tz.to_string()),
+            ("from", format_local_minute(from, tz)),
+            ("to", format_local_minute(to, tz)),
+            ("days", days.len().to_string()),
+            ("metrics", metric_lines),
+            ("alerts", alerts_sent.to_string()),
+            ("incidents", incident_stats.count.to_string()),
+            ("by_metric", incidents_by_metric(&incidents)),
+            ("mttr", format_mttr(incident_stats.mttr)),
+            ("growth", growth_text),
+            ("busiest", format_busiest_hours(&hourly, tz)),
+            ("daily", daily_lines),
+        ],
+    );
+
+    Ok(GeneratedCapacityReport {
+        chart: GeneratedGraphReport {
+            png_bytes,
+            file_name: format!("capacity-{month}.png"),
+            caption,
+        },
+        document: document.into_bytes(),
+        document_name: format!("capacity-{month}.txt"),
+        document_caption: t_with("summary.monthly.document.caption", &[("month", month)]),
+    })
+}
+
+/// First day of the month before `today`'s, and first day of `today`'s month.
+fn previous_month(today: NaiveDate) -> (NaiveDate, NaiveDate) {
+    let this_month = today.with_day(1).unwrap_or(today);
+    let previous = this_month
+        .pred_opt()
+        .and_then(|last_day| last_day.with_day(1))
+        .unwrap_or(this_month);
+    (previous, this_month)
+}
+
+/// Start of `day` in `tz`, as UTC; the earlier instant when DST repeats it.
+fn local_midnight(day: NaiveDate, tz: Tz) -> DateTime<Utc> {
+    let midnight = day.and_time(NaiveTime::MIN);
+    tz.from_local_datetime(&midnight)
+        .earliest()
+        .map_or_else(|| midnight.and_utc(), |time| time.with_timezone(&Utc))
+}
+
+fn format_local_minute(time: DateTime<Utc>, tz: Tz) -> String {
+    time.with_timezone(&tz).format("%Y-%m-%d %H:%M").to_string()
+}
+
+fn incidents_by_metric(incidents: &[Incident]) -> String {
+    IncidentMetric::ALL
+        .iter()
+        .map(|metric| {
+            let count = incidents
+                .iter()
+                .filter(|incident| incident.metric == *metric)
+                .count();
+            format!("{} {count}", metric.label())
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Local hours of the day with the highest average CPU, busiest first.
+fn busiest_hours(hourly: &[MetricBucket], tz: Tz) -> Vec<(u32, f32)> {
+    let mut by_hour: [Vec<MetricBucket>; 24] = Default::default();
+    for bucket in hourly {
+        by_hour[bucket.start.with_timezone(&tz).hour() as usize].push(*bucket);
+    }
+    let mut hours = by_hour
+        .iter()
+        .enumerate()
+        .filter_map(|(hour, buckets)| {
+            let band = summarize_buckets(buckets, GraphMetric::Cpu)?;
+            Some((hour as u32, band.avg))
+        })
+        .collect::<Vec<_>>();
+    hours.sort_by(|left, right| right.1.total_cmp(&left.1));
+    hours.truncate(BUSIEST_HOURS);
+    hours
+}
+
+fn format_busiest_hours(hourly: &[MetricBucket], tz: Tz) -> String {
+    let hours = busiest_hours(hourly, tz);
+    if hours.is_empty() {
+        return "n/a".to_string();
+    }
+    hours
+        .iter()
+        .map(|(hour, cpu)| format!("{hour:02}:00 {cpu:.1}%"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, NaiveDate, TimeZone, Utc};
+    use chrono_tz::Tz;
+
+    use super::{DiskGrowth, busiest_hours, local_midnight, previous_month};
+    use crate::commands::features::graph::stats::sample_buckets;
+    use crate::monitor::MetricSample;
+
+    fn sample(timestamp: chrono::DateTime<Utc>, cpu: f32, disk: f32) -> MetricSample {
+        MetricSample {
+            timestamp,
+            cpu,
+            ram: 50.0,
+            disk,
+        }
+    }
+
+    #[test]
+    fn previous_month_spans_the_whole_calendar_month() {
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        assert_eq!(
+            previous_month(date(2026, 10, 1)),
+            (date(2026, 9, 1), date(2026, 10, 1))
+        );
+        assert_eq!(
+            previous_month(date(2027, 1, 18)),
+            (date(2026, 12, 1), date(2027, 1, 1))
+        );
+        assert_eq!(
+            local_midnight(date(2026, 9, 1), Tz::Europe__Istanbul),
+            Utc.with_ymd_and_hms(2026, 8, 31, 21, 0, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn disk_trend_projects_growth_and_busiest_hours_rank_cpu() {
+        let start = Utc.with_ymd_and_hms(2026, 9, 1, 0, 0, 0).unwrap();
+        let days = sample_buckets(
+            &(0..30)
+                .map(|day| sample(start + Duration::days(day), 10.0, 40.0 + day as f32 * 0.5))
+                .collect::<Vec<_>>(),
+        );
+        let growth = DiskGrowth::from_days(&days).expect("trend");
+        assert!((growth.per_day - 0.5).abs() < 1e-4);
+        assert_eq!(growth.latest, 54.5);
+        assert!((growth.projected() - 69.5).abs() < 1e-3);
+        assert_eq!(growth.days_until_full().map(f32::round), Some(91.0));
+        assert_eq!(DiskGrowth::from_days(&days[..1]), None);
+
+        // 09:00 UTC is 12:00 in Istanbul.
+        let hourly = sample_buckets(
+            &[(9, 80.0), (10, 30.0), (9, 60.0), (2, 5.0), (14, 50.0)]
+                .into_iter()
+                .enumerate()
+                .map(|(day, (hour, cpu))| {
+                    sample(
+                        start + Duration::days(day as i64) + Duration::hours(hour),
+                        cpu,
+                        0.0,
+                    )
+                })
+                .collect::<Vec<_>>(),
+        );
+        assert_eq!(
+            busiest_hours(&hourly, Tz::Europe__Istanbul),
+            [(12, 70.0), (17, 50.0), (13, 30.0)]
+        );
+    }
+}
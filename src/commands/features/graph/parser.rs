@@ -1,43 +1,146 @@
-use super::types::{GraphMetric, GraphRequest, GraphWindow};
+use chrono::Duration as ChronoDuration;
+use chrono_tz::Tz;
+
+use crate::config::{Graph, GraphTheme};
+
+use super::super::super::helpers::parse_mute_duration;
+use super::super::time_range::TimeRange;
+use super::types::{GraphFormat, GraphMetric, GraphRequest, GraphWindow};
+
+/// Longest shift accepted for a comparison period.
+const MAX_COMPARE_DAYS: i64 = 365;
 
 pub(super) fn parse_graph_request(
     query: &str,
     default_window_minutes: i64,
     max_window_hours: i64,
+    tz: Tz,
 ) -> Option<GraphRequest> {
     let mut args = query.split_whitespace();
-    let metric_arg = args.next();
-    let window_arg = args.next();
-    let has_extra = args.next().is_some();
+    let metrics = parse_metrics(args.next()?)?;
 
-    let (Some(metric_text), false) = (metric_arg, has_extra) else {
+    let mut window_arg = None;
+    let mut compare = None;
+    let mut theme = None;
+    let mut size = None;
+    let mut format = None;
+    while let Some(arg) = args.next() {
+        let offset = if arg.eq_ignore_ascii_case("vs") {
+            args.next()?
+        } else if let Some(value) = arg.strip_prefix("compare=") {
+            value
+        } else if let Some(value) = parse_theme(arg) {
+            if theme.replace(value).is_some() {
+                return None;
+            }
+            continue;
+        } else if let Some(value) = parse_format(arg) {
+            if format.replace(value).is_some() {
+                return None;
+            }
+            continue;
+        } else if arg.contains('x') && !arg.contains("..") {
+            if size.replace(parse_size(arg)?).is_some() {
+                return None;
+            }
+            continue;
+        } else if window_arg.is_none() {
+            window_arg = Some(arg);
+            continue;
+        } else {
+            return None;
+        };
+        if compare.is_some() {
+            return None;
+        }
+        compare = Some(parse_compare_offset(offset)?);
+    }
+    if compare.is_some() && metrics.len() > 1 {
         return None;
+    }
+
+    let window = match window_arg {
+        Some(window_text) if window_text.contains("..") => {
+            GraphWindow::Range(TimeRange::parse(window_text, tz)?)
+        }
+        _ => {
+            let max_window_minutes = max_window_hours.checked_mul(60)?;
+            let requested_minutes = match window_arg {
+                Some(window_text) => parse_window_minutes(window_text)?,
+                None => default_window_minutes,
+            };
+            if requested_minutes <= 0 || requested_minutes > max_window_minutes {
+                return None;
+            }
+            GraphWindow::from_minutes(requested_minutes)?
+        }
     };
 
-    let metric = GraphMetric::parse(metric_text)?;
-    let max_window_minutes = max_window_hours.checked_mul(60)?;
+    Some(GraphRequest {
+        metrics,
+        window,
+        compare,
+        theme,
+        size,
+        format: format.unwrap_or(GraphFormat::Png),
+    })
+}
 
-    let requested_minutes = match window_arg {
-        Some(window_text) => parse_window_minutes(window_text)?,
-        None => default_window_minutes,
+/// `cpu` or a comma-separated list such as `cpu,ram,disk`; repeats are dropped.
+fn parse_metrics(input: &str) -> Option<Vec<GraphMetric>> {
+    let mut metrics = Vec::new();
+    for part in input.split(',') {
+        let metric = GraphMetric::parse(part)?;
+        if !metrics.contains(&metric) {
+            metrics.push(metric);
+        }
+    }
+    Some(metrics)
+}
+
+/// `yesterday`, `lastweek`, or a shift such as `7d-ago` / `24h`.
+fn parse_compare_offset(input: &str) -> Option<ChronoDuration> {
+    let value = input.trim().to_lowercase();
+    let offset = match value.as_str() {
+        "yesterday" => ChronoDuration::days(1),
+        "lastweek" | "last-week" => ChronoDuration::days(7),
+        shift => parse_mute_duration(shift.strip_suffix("-ago").unwrap_or(shift))?,
     };
+    (offset >= ChronoDuration::minutes(1) && offset <= ChronoDuration::days(MAX_COMPARE_DAYS))
+        .then_some(offset)
+}
 
-    if requested_minutes <= 0 || requested_minutes > max_window_minutes {
-        return None;
+pub(super) fn parse_theme(input: &str) -> Option<GraphTheme> {
+    match input.to_lowercase().as_str() {
+        "light" => Some(GraphTheme::Light),
+        "dark" => Some(GraphTheme::Dark),
+        _ => None,
     }
+}
 
-    let window = GraphWindow::from_minutes(requested_minutes)?;
+pub(super) fn parse_format(input: &str) -> Option<GraphFormat> {
+    match input.to_lowercase().as_str() {
+        "png" => Some(GraphFormat::Png),
+        "svg" => Some(GraphFormat::Svg),
+        _ => None,
+    }
+}
 
-    Some(GraphRequest { metric, window })
+/// `WIDTHxHEIGHT` in pixels, within the `[graph]` size limits.
+fn parse_size(input: &str) -> Option<(u32, u32)> {
+    let (width, height) = input
+        .to_lowercase()
+        .split_once('x')
+        .and_then(|(width, height)| {
+            Some((width.parse::<u32>().ok()?, height.parse::<u32>().ok()?))
+        })?;
+    (Graph::WIDTH_RANGE.contains(&width) && Graph::HEIGHT_RANGE.contains(&height))
+        .then_some((width, height))
 }
 
-fn parse_window_minutes(input: &str) -> Option<i64> {
+pub(super) fn parse_window_minutes(input: &str) -> Option<i64> {
     let value = input.trim().to_lowercase();
-    if value.len() < 2 {
-        return None;
-    }
-
-    let (number_part, unit_part) = value.split_at(value.len() - 1);
+    let (number_part, unit_part) = value.split_at_checked(value.len().checked_sub(1)?)?;
     let number = number_part.parse::<i64>().ok()?;
     if number <= 0 {
         return None;
@@ -52,25 +155,95 @@ fn parse_window_minutes(input: &str) -> Option<i64> {
 
 #[cfg(test)]
 mod tests {
+    use chrono::Duration;
+    use chrono_tz::Tz;
+
+    use super::super::types::{GraphFormat, GraphMetric, GraphWindow};
     use super::parse_graph_request;
+    use crate::config::GraphTheme;
 
     #[test]
     fn parses_metric_with_default_window() {
-        let request = parse_graph_request("cpu", 60, 24).expect("request should parse");
+        let request = parse_graph_request("cpu", 60, 24, Tz::UTC).expect("request should parse");
         assert_eq!(request.window.minutes(), 60);
     }
 
     #[test]
     fn parses_metric_with_explicit_window() {
-        let request = parse_graph_request("ram 6h", 60, 24).expect("request should parse");
+        let request = parse_graph_request("ram 6h", 60, 24, Tz::UTC).expect("request should parse");
         assert_eq!(request.window.minutes(), 360);
     }
 
     #[test]
     fn rejects_parse_errors_and_out_of_bounds_windows() {
-        assert!(parse_graph_request("", 60, 24).is_none());
-        assert!(parse_graph_request("cpu 99x", 60, 24).is_none());
-        assert!(parse_graph_request("cpu 25h", 60, 24).is_none());
-        assert!(parse_graph_request("cpu 1h extra", 60, 24).is_none());
+        assert!(parse_graph_request("", 60, 24, Tz::UTC).is_none());
+        assert!(parse_graph_request("cpu 99x", 60, 24, Tz::UTC).is_none());
+        assert!(parse_graph_request("cpu 25h", 60, 24, Tz::UTC).is_none());
+        assert!(parse_graph_request("cpu 1h extra", 60, 24, Tz::UTC).is_none());
+        assert!(parse_graph_request("cpu 2026-10-03..2026-10-01", 60, 24, Tz::UTC).is_none());
+        assert!(parse_graph_request("cpu,gpu 1h", 60, 24, Tz::UTC).is_none());
+        assert!(parse_graph_request("cpu,,ram", 60, 24, Tz::UTC).is_none());
+        assert!(parse_graph_request("cpu,ram 6h vs 1d-ago", 60, 24, Tz::UTC).is_none());
+        assert!(parse_graph_request("cpu 6h vs", 60, 24, Tz::UTC).is_none());
+        assert!(parse_graph_request("cpu 6h vs 1d compare=1d", 60, 24, Tz::UTC).is_none());
+        assert!(parse_graph_request("cpu 6h compare=400d", 60, 24, Tz::UTC).is_none());
+    }
+
+    #[test]
+    fn rejects_multi_byte_window_suffixes() {
+        assert!(parse_graph_request("cpu 1ş", 60, 24, Tz::UTC).is_none());
+        assert_eq!(super::parse_window_minutes("5€"), None);
+    }
+
+    #[test]
+    fn parses_comparison_offsets() {
+        let offset = |query: &str| {
+            parse_graph_request(query, 60, 24, Tz::UTC)
+                .expect("request parses")
+                .compare
+        };
+        assert_eq!(offset("cpu 24h vs 7d-ago"), Some(Duration::days(7)));
+        assert_eq!(offset("cpu compare=yesterday"), Some(Duration::days(1)));
+        assert_eq!(offset("ram compare=lastweek 6h"), Some(Duration::days(7)));
+        assert_eq!(offset("disk 6h vs 12h"), Some(Duration::hours(12)));
+        assert_eq!(offset("cpu 6h"), None);
+    }
+
+    #[test]
+    fn parses_comma_separated_metrics_without_repeats() {
+        let request =
+            parse_graph_request("cpu,RAM,disk,cpu 6h", 60, 24, Tz::UTC).expect("request parses");
+        assert_eq!(
+            request.metrics,
+            [GraphMetric::Cpu, GraphMetric::Ram, GraphMetric::Disk]
+        );
+        assert_eq!(request.window.minutes(), 360);
+    }
+
+    #[test]
+    fn parses_theme_size_and_format_in_any_order() {
+        let request = parse_graph_request("cpu dark 6h 1600x900 svg", 60, 24, Tz::UTC)
+            .expect("request parses");
+        assert_eq!(request.theme, Some(GraphTheme::Dark));
+        assert_eq!(request.size, Some((1600, 900)));
+        assert_eq!(request.format, GraphFormat::Svg);
+        assert_eq!(request.window.minutes(), 360);
+
+        let defaults = parse_graph_request("ram", 60, 24, Tz::UTC).expect("request parses");
+        assert_eq!((defaults.theme, defaults.size), (None, None));
+        assert_eq!(defaults.format, GraphFormat::Png);
+
+        assert!(parse_graph_request("cpu dark light", 60, 24, Tz::UTC).is_none());
+        assert!(parse_graph_request("cpu 10x10", 60, 24, Tz::UTC).is_none());
+        assert!(parse_graph_request("cpu 1600x", 60, 24, Tz::UTC).is_none());
+    }
+
+    #[test]
+    fn parses_absolute_range_beyond_relative_limit() {
+        let request = parse_graph_request("cpu 2026-10-01..2026-10-03", 60, 24, Tz::UTC)
+            .expect("range parses");
+        assert!(matches!(request.window, GraphWindow::Range(_)));
+        assert_eq!(request.window.minutes(), 3 * 24 * 60);
+        assert_eq!(request.window.label(), "2026-10-01..2026-10-03");
     }
 }
@@ -1,14 +1,30 @@
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
 use image::{DynamicImage, ImageFormat, RgbImage};
+use plotters::coord::Shift;
+use plotters::coord::types::RangedCoordf32;
+use plotters::element::DashedPathElement;
 use plotters::prelude::*;
+use plotters::style::text_anchor::{HPos, Pos, VPos};
 use plotters::style::{FontStyle, register_font};
 use std::io::Cursor;
 use std::sync::OnceLock;
 
-use super::{error::GraphRenderError, stats::GraphPoint, types::GraphMetric};
+use crate::config::{Graph, GraphTheme};
+use crate::i18n::t;
+
+use super::{
+    annotations::{AnnotationKind, GraphAnnotation},
+    error::GraphRenderError,
+    heatmap::HeatmapGrid,
+    stats::GraphBand,
+    types::{GraphFormat, GraphMetric},
+};
 
-pub(super) const GRAPH_WIDTH_PX: u32 = 1200;
-const GRAPH_HEIGHT_PX: u32 = 480;
 const EMBEDDED_FONT_FAMILY: &str = "kars-embedded";
+/// SVG viewers cannot see the embedded font, so text falls back to Roboto by
+/// name.
+const SVG_FONT_FAMILY: &str = "Roboto, sans-serif";
 const EMBEDDED_FONT_BYTES: &[u8] = include_bytes!("../../../../assets/Roboto-Regular.ttf");
 
 static FONT_REGISTRATION_RESULT: OnceLock<Result<(), String>> = OnceLock::new();
@@ -21,92 +37,635 @@ impl GraphStyle {
     const Y_LABEL_AREA_SIZE: u32 = 48;
     const Y_MIN: f32 = 0.0;
     const Y_MAX: f32 = 100.0;
-    const BACKGROUND: RGBColor = WHITE;
-    const THRESHOLD_LINE: RGBColor = BLACK;
     const THRESHOLD_ALPHA: f64 = 0.5;
+    const LEGEND_FONT_SIZE: u32 = 14;
+    const LEGEND_LINE_PX: i32 = 16;
+    const LEGEND_BACKGROUND_ALPHA: f64 = 0.8;
+    const REGION_ALPHA: f64 = 0.12;
+    const BAND_ALPHA: f64 = 0.18;
+    const P95_ALPHA: f64 = 0.45;
+    const COMPARISON_ALPHA: f64 = 0.7;
+    /// Dash and gap length of an earlier period's line.
+    const COMPARISON_DASH: (u32, u32) = (6, 4);
+    const RELATIVE_AXIS_LABELS: usize = 7;
+    const MARKER_ALPHA: f64 = 0.7;
+    /// Anomaly ticks rise this far from the bottom of the chart.
+    const ANOMALY_TICK_HEIGHT: f32 = 3.0;
+    const ANNOTATION_FONT_SIZE: u32 = 12;
+    const ANNOTATION_LABEL_OFFSET: (i32, i32) = (3, 2);
+}
+
+/// Colours of one [`GraphTheme`]. The foreground draws lone thresholds,
+/// legend text and borders, and axis labels.
+struct Palette {
+    background: RGBColor,
+    foreground: RGBColor,
+    metrics: [RGBColor; 3],
+    mute: RGBColor,
+    quiet_hours: RGBColor,
+    deploy: RGBColor,
+}
+
+impl Palette {
+    const LIGHT: Self = Self {
+        background: WHITE,
+        foreground: BLACK,
+        metrics: [RED, BLUE, GREEN],
+        mute: RGBColor(255, 140, 0),
+        quiet_hours: RGBColor(128, 128, 128),
+        deploy: RGBColor(128, 0, 160),
+    };
+    const DARK: Self = Self {
+        background: RGBColor(24, 26, 31),
+        foreground: RGBColor(220, 220, 220),
+        metrics: [
+            RGBColor(255, 99, 99),
+            RGBColor(100, 160, 255),
+            RGBColor(90, 210, 120),
+        ],
+        mute: RGBColor(255, 170, 60),
+        quiet_hours: RGBColor(150, 150, 150),
+        deploy: RGBColor(190, 120, 255),
+    };
+
+    fn for_theme(theme: GraphTheme) -> &'static Self {
+        match theme {
+            GraphTheme::Light => &Self::LIGHT,
+            GraphTheme::Dark => &Self::DARK,
+        }
+    }
 
-    fn metric_line(metric: GraphMetric) -> RGBColor {
+    fn metric_line(&self, metric: GraphMetric) -> RGBColor {
         match metric {
-            GraphMetric::Cpu => RED,
-            GraphMetric::Ram => BLUE,
-            GraphMetric::Disk => GREEN,
+            GraphMetric::Cpu => self.metrics[0],
+            GraphMetric::Ram => self.metrics[1],
+            GraphMetric::Disk => self.metrics[2],
+        }
+    }
+
+    fn annotation(&self, kind: AnnotationKind) -> RGBColor {
+        match kind {
+            AnnotationKind::Incident(metric) | AnnotationKind::Anomaly(metric) => {
+                self.metric_line(metric)
+            }
+            AnnotationKind::Mute => self.mute,
+            AnnotationKind::QuietHours => self.quiet_hours,
+            AnnotationKind::Deploy => self.deploy,
+        }
+    }
+}
+
+/// Theme, canvas size and output format of one render.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct GraphOptions {
+    pub(super) theme: GraphTheme,
+    pub(super) width: u32,
+    /// Height of an overlay chart; stacked panels get half of it each.
+    pub(super) height: u32,
+    pub(super) format: GraphFormat,
+    /// End of an absolute window in the display timezone. The relative axis
+    /// drawn under comparisons labels its right end with this date instead
+    /// of "now".
+    pub(super) window_end: Option<DateTime<Tz>>,
+}
+
+impl GraphOptions {
+    pub(super) fn from_config(graph: &Graph) -> Self {
+        Self {
+            theme: graph.theme,
+            width: graph.width,
+            height: graph.height,
+            format: GraphFormat::Png,
+            window_end: None,
         }
     }
 }
 
-pub(super) fn render_graph_png(
-    points: Vec<GraphPoint>,
+/// One metric's average line, drawn inside its min/max band with a p95
+/// line, and its alert threshold.
+pub(super) struct GraphSeries {
+    pub(super) metric: GraphMetric,
+    pub(super) bands: Vec<GraphBand>,
+    pub(super) threshold: f32,
+    /// Legend label of an earlier period already shifted onto the current
+    /// window. Drawn dashed, without band or threshold, and switches the time
+    /// axis to labels relative to the window end.
+    pub(super) comparison: Option<String>,
+}
+
+/// How several series share the canvas.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum GraphLayout {
+    /// All series on one chart, with a legend when there is more than one.
+    Overlay,
+    /// One panel per series, stacked over the same time axis.
+    Stacked,
+}
+
+/// Renders `series` as PNG or SVG bytes, per `options.format`.
+pub(super) fn render_graph(
+    series: Vec<GraphSeries>,
+    layout: GraphLayout,
+    annotations: Vec<GraphAnnotation>,
+    options: GraphOptions,
+) -> Result<Vec<u8>, GraphRenderError> {
+    if series.is_empty() || series.iter().any(|series| series.bands.len() < 2) {
+        return Err(GraphRenderError::NotEnoughPoints);
+    }
+
+    let height = match layout {
+        GraphLayout::Stacked if series.len() > 1 => options.height / 2 * series.len() as u32,
+        _ => options.height,
+    };
+    encode(
+        &SeriesChart {
+            series: &series,
+            layout,
+            annotations: &annotations,
+            window_end: options.window_end,
+        },
+        (options.width, height),
+        options,
+    )
+}
+
+/// Renders a weekday by hour-of-day grid of `metric` at the configured size.
+pub(super) fn render_heatmap(
+    grid: HeatmapGrid,
     metric: GraphMetric,
-    threshold: f32,
+    options: GraphOptions,
 ) -> Result<Vec<u8>, GraphRenderError> {
-    if points.len() < 2 {
+    if grid.is_empty() {
         return Err(GraphRenderError::NotEnoughPoints);
     }
 
+    encode(
+        &HeatmapChart {
+            grid: &grid,
+            metric,
+        },
+        (options.width, options.height),
+        options,
+    )
+}
+
+/// A full canvas drawn the same way on the bitmap and SVG backends.
+trait Drawing {
+    fn draw<DB: DrawingBackend>(
+        &self,
+        area: &DrawingArea<DB, Shift>,
+        palette: &Palette,
+    ) -> Result<(), GraphRenderError>;
+}
+
+fn encode(
+    drawing: &impl Drawing,
+    (width, height): (u32, u32),
+    options: GraphOptions,
+) -> Result<Vec<u8>, GraphRenderError> {
     ensure_embedded_font_registered()?;
+    let palette = Palette::for_theme(options.theme);
 
-    let width = GRAPH_WIDTH_PX;
-    let height = GRAPH_HEIGHT_PX;
-    let mut rgb_buffer = vec![255u8; width as usize * height as usize * 3];
+    match options.format {
+        GraphFormat::Png => {
+            let mut rgb_buffer = vec![0u8; width as usize * height as usize * 3];
+            draw_canvas(
+                BitMapBackend::with_buffer(&mut rgb_buffer, (width, height)).into_drawing_area(),
+                drawing,
+                palette,
+            )?;
 
-    {
-        let drawing_area =
-            BitMapBackend::with_buffer(&mut rgb_buffer, (width, height)).into_drawing_area();
-        drawing_area
-            .fill(&GraphStyle::BACKGROUND)
-            .map_err(|error| classify_plotters_error("background_fill", format!("{error:?}")))?;
-
-        let mut x_start = points
-            .first()
-            .map(|point| point.timestamp)
-            .ok_or_else(|| GraphRenderError::Backend("missing start timestamp".to_string()))?;
-        let mut x_end = points
-            .last()
-            .map(|point| point.timestamp)
-            .ok_or_else(|| GraphRenderError::Backend("missing end timestamp".to_string()))?;
-
-        if x_start == x_end {
-            x_start -= chrono::Duration::seconds(1);
-            x_end += chrono::Duration::seconds(1);
+            let rgb_image = RgbImage::from_raw(width, height, rgb_buffer).ok_or_else(|| {
+                GraphRenderError::Backend("image buffer conversion failed".to_string())
+            })?;
+            let mut output = Cursor::new(Vec::new());
+            DynamicImage::ImageRgb8(rgb_image)
+                .write_to(&mut output, ImageFormat::Png)
+                .map_err(|error| GraphRenderError::PngEncoding(error.to_string()))?;
+            Ok(output.into_inner())
+        }
+        GraphFormat::Svg => {
+            let mut svg = String::new();
+            draw_canvas(
+                SVGBackend::with_string(&mut svg, (width, height)).into_drawing_area(),
+                drawing,
+                palette,
+            )?;
+            Ok(svg
+                .replace(
+                    &format!("font-family=\"{EMBEDDED_FONT_FAMILY}\""),
+                    &format!("font-family=\"{SVG_FONT_FAMILY}\""),
+                )
+                .into_bytes())
+        }
+    }
+}
+
+fn draw_canvas<DB: DrawingBackend>(
+    drawing_area: DrawingArea<DB, Shift>,
+    drawing: &impl Drawing,
+    palette: &Palette,
+) -> Result<(), GraphRenderError> {
+    drawing_area
+        .fill(&palette.background)
+        .map_err(|error| classify_plotters_error("background_fill", format!("{error:?}")))?;
+    drawing.draw(&drawing_area, palette)?;
+    drawing_area
+        .present()
+        .map_err(|error| classify_plotters_error("present", format!("{error:?}")))
+}
+
+struct SeriesChart<'a> {
+    series: &'a [GraphSeries],
+    layout: GraphLayout,
+    annotations: &'a [GraphAnnotation],
+    window_end: Option<DateTime<Tz>>,
+}
+
+impl Drawing for SeriesChart<'_> {
+    fn draw<DB: DrawingBackend>(
+        &self,
+        area: &DrawingArea<DB, Shift>,
+        palette: &Palette,
+    ) -> Result<(), GraphRenderError> {
+        let x_range = shared_time_range(self.series)?;
+        match self.layout {
+            GraphLayout::Overlay => draw_panel(
+                area,
+                self.series,
+                self.annotations,
+                x_range,
+                self.series.len() > 1,
+                self.window_end,
+                palette,
+            ),
+            GraphLayout::Stacked => {
+                let panels = area.split_evenly((self.series.len(), 1));
+                for (panel, series) in panels.iter().zip(self.series) {
+                    draw_panel(
+                        panel,
+                        std::slice::from_ref(series),
+                        self.annotations,
+                        x_range,
+                        true,
+                        self.window_end,
+                        palette,
+                    )?;
+                }
+                Ok(())
+            }
         }
+    }
+}
 
-        let mut chart = ChartBuilder::on(&drawing_area)
+struct HeatmapChart<'a> {
+    grid: &'a HeatmapGrid,
+    metric: GraphMetric,
+}
+
+impl Drawing for HeatmapChart<'_> {
+    /// Monday is the top row; cells shade from the background to the metric
+    /// colour at 100% and are labelled with their value.
+    fn draw<DB: DrawingBackend>(
+        &self,
+        area: &DrawingArea<DB, Shift>,
+        palette: &Palette,
+    ) -> Result<(), GraphRenderError> {
+        let last_row = HeatmapGrid::DAYS as u32 - 1;
+        let mut chart = ChartBuilder::on(area)
             .margin(GraphStyle::MARGIN)
             .x_label_area_size(GraphStyle::X_LABEL_AREA_SIZE)
             .y_label_area_size(GraphStyle::Y_LABEL_AREA_SIZE)
-            .build_cartesian_2d(x_start..x_end, GraphStyle::Y_MIN..GraphStyle::Y_MAX)
+            .build_cartesian_2d(
+                (0u32..HeatmapGrid::HOURS as u32 - 1).into_segmented(),
+                (0u32..last_row).into_segmented(),
+            )
             .map_err(|error| classify_plotters_error("chart_build", format!("{error:?}")))?;
 
+        let label_style = TextStyle::from((EMBEDDED_FONT_FAMILY, GraphStyle::LEGEND_FONT_SIZE))
+            .color(&palette.foreground);
         chart
-            .draw_series(std::iter::once(PathElement::new(
-                points
-                    .iter()
-                    .map(|point| (point.timestamp, point.value))
-                    .collect::<Vec<_>>(),
-                GraphStyle::metric_line(metric),
+            .configure_mesh()
+            .disable_mesh()
+            .x_labels(HeatmapGrid::HOURS)
+            .y_labels(HeatmapGrid::DAYS)
+            .x_label_formatter(&|value| match value {
+                SegmentValue::CenterOf(hour) => format!("{hour:02}"),
+                _ => String::new(),
+            })
+            .y_label_formatter(&|value| match value {
+                SegmentValue::CenterOf(row) => {
+                    HeatmapGrid::weekday(last_row.saturating_sub(*row)).to_string()
+                }
+                _ => String::new(),
+            })
+            .label_style(label_style.clone())
+            .axis_style(palette.foreground)
+            .draw()
+            .map_err(|error| classify_plotters_error("axis_draw", format!("{error:?}")))?;
+
+        let color = palette.metric_line(self.metric);
+        let cells = self.grid.cells().collect::<Vec<_>>();
+        chart
+            .draw_series(cells.iter().map(|&(day, hour, value)| {
+                let (hour, row) = (hour as u32, last_row - day as u32);
+                Rectangle::new(
+                    [
+                        (SegmentValue::Exact(hour), SegmentValue::Exact(row)),
+                        (SegmentValue::Exact(hour + 1), SegmentValue::Exact(row + 1)),
+                    ],
+                    heat_color(palette.background, color, value).filled(),
+                )
+            }))
+            .map_err(|error| classify_plotters_error("cell_draw", format!("{error:?}")))?;
+
+        let value_style = TextStyle::from((EMBEDDED_FONT_FAMILY, GraphStyle::ANNOTATION_FONT_SIZE))
+            .color(&palette.foreground)
+            .pos(Pos::new(HPos::Center, VPos::Center));
+        chart
+            .draw_series(cells.iter().map(|&(day, hour, value)| {
+                let (hour, row) = (hour as u32, last_row - day as u32);
+                EmptyElement::at((SegmentValue::CenterOf(hour), SegmentValue::CenterOf(row)))
+                    + Text::new(format!("{value:.0}"), (0, 0), value_style.clone())
+            }))
+            .map_err(|error| classify_plotters_error("cell_label_draw", format!("{error:?}")))?;
+        Ok(())
+    }
+}
+
+/// Linear blend from `background` at 0% to `color` at 100%.
+fn heat_color(background: RGBColor, color: RGBColor, value: f32) -> RGBColor {
+    let share = f64::from(value.clamp(GraphStyle::Y_MIN, GraphStyle::Y_MAX) / GraphStyle::Y_MAX);
+    let blend = |from: u8, to: u8| {
+        (f64::from(from) + (f64::from(to) - f64::from(from)) * share).round() as u8
+    };
+    RGBColor(
+        blend(background.0, color.0),
+        blend(background.1, color.1),
+        blend(background.2, color.2),
+    )
+}
+
+/// Earliest start and latest end over all series, widened when they meet.
+fn shared_time_range(
+    series: &[GraphSeries],
+) -> Result<(DateTime<Utc>, DateTime<Utc>), GraphRenderError> {
+    let mut x_start = series
+        .iter()
+        .filter_map(|series| series.bands.first())
+        .map(|band| band.timestamp)
+        .min()
+        .ok_or_else(|| GraphRenderError::Backend("missing start timestamp".to_string()))?;
+    let mut x_end = series
+        .iter()
+        .filter_map(|series| series.bands.last())
+        .map(|band| band.timestamp)
+        .max()
+        .ok_or_else(|| GraphRenderError::Backend("missing end timestamp".to_string()))?;
+
+    if x_start == x_end {
+        x_start -= chrono::Duration::seconds(1);
+        x_end += chrono::Duration::seconds(1);
+    }
+    Ok((x_start, x_end))
+}
+
+/// Draws `series` and their thresholds on one chart over the annotations
+/// that apply to them. A lone series keeps the neutral threshold colour; with
+/// several, each threshold takes its series' colour so the lines can be told
+/// apart.
+fn draw_panel<DB: DrawingBackend>(
+    area: &DrawingArea<DB, Shift>,
+    series: &[GraphSeries],
+    annotations: &[GraphAnnotation],
+    (x_start, x_end): (DateTime<Utc>, DateTime<Utc>),
+    legend: bool,
+    window_end: Option<DateTime<Tz>>,
+    palette: &Palette,
+) -> Result<(), GraphRenderError> {
+    let mut chart = ChartBuilder::on(area)
+        .margin(GraphStyle::MARGIN)
+        .x_label_area_size(GraphStyle::X_LABEL_AREA_SIZE)
+        .y_label_area_size(GraphStyle::Y_LABEL_AREA_SIZE)
+        .build_cartesian_2d(x_start..x_end, GraphStyle::Y_MIN..GraphStyle::Y_MAX)
+        .map_err(|error| classify_plotters_error("chart_build", format!("{error:?}")))?;
+
+    let metrics = series
+        .iter()
+        .map(|series| series.metric)
+        .collect::<Vec<_>>();
+    for annotation in annotations
+        .iter()
+        .filter(|annotation| annotation.applies_to(&metrics))
+    {
+        draw_annotation(&mut chart, annotation, (x_start, x_end), palette)?;
+    }
+
+    if series.iter().any(|series| series.comparison.is_some()) {
+        chart
+            .configure_mesh()
+            .disable_mesh()
+            .disable_y_axis()
+            .x_labels(GraphStyle::RELATIVE_AXIS_LABELS)
+            .x_label_formatter(&|time| relative_label(*time - x_end, window_end))
+            .x_label_style(
+                TextStyle::from((EMBEDDED_FONT_FAMILY, GraphStyle::ANNOTATION_FONT_SIZE))
+                    .color(&palette.foreground),
+            )
+            .axis_style(palette.foreground)
+            .draw()
+            .map_err(|error| classify_plotters_error("axis_draw", format!("{error:?}")))?;
+    }
+
+    for series in series.iter().filter(|series| series.comparison.is_none()) {
+        draw_band(&mut chart, series, palette)?;
+    }
+
+    for series in series {
+        let color = palette.metric_line(series.metric);
+        let line = series
+            .bands
+            .iter()
+            .map(|band| (band.timestamp, band.avg))
+            .collect::<Vec<_>>();
+        let series_error = |error: String| classify_plotters_error("series_draw", error);
+        let Some(label) = &series.comparison else {
+            let drawn = chart
+                .draw_series(std::iter::once(PathElement::new(line, color)))
+                .map_err(|error| series_error(format!("{error:?}")))?;
+            if legend {
+                drawn
+                    .label(format!(
+                        "{} (>{:.0}%)",
+                        series.metric.title(),
+                        series.threshold
+                    ))
+                    .legend(move |(x, y)| {
+                        PathElement::new(vec![(x, y), (x + GraphStyle::LEGEND_LINE_PX, y)], color)
+                    });
+            }
+            continue;
+        };
+
+        let faded = color.mix(GraphStyle::COMPARISON_ALPHA);
+        let (dash, gap) = GraphStyle::COMPARISON_DASH;
+        let drawn = chart
+            .draw_series(std::iter::once(DashedPathElement::new(
+                line, dash, gap, faded,
             )))
-            .map_err(|error| classify_plotters_error("series_draw", format!("{error:?}")))?;
+            .map_err(|error| series_error(format!("{error:?}")))?;
+        if legend {
+            drawn.label(label.clone()).legend(move |(x, y)| {
+                DashedPathElement::new(
+                    vec![(x, y), (x + GraphStyle::LEGEND_LINE_PX, y)],
+                    dash,
+                    gap,
+                    faded,
+                )
+            });
+        }
+    }
 
+    for series in series.iter().filter(|series| series.comparison.is_none()) {
+        let threshold_color = if legend {
+            palette.metric_line(series.metric)
+        } else {
+            palette.foreground
+        };
         chart
             .draw_series(std::iter::once(PathElement::new(
-                vec![(x_start, threshold), (x_end, threshold)],
-                GraphStyle::THRESHOLD_LINE.mix(GraphStyle::THRESHOLD_ALPHA),
+                vec![(x_start, series.threshold), (x_end, series.threshold)],
+                threshold_color.mix(GraphStyle::THRESHOLD_ALPHA),
             )))
             .map_err(|error| classify_plotters_error("threshold_draw", format!("{error:?}")))?;
+    }
+
+    if legend {
+        chart
+            .configure_series_labels()
+            .position(SeriesLabelPosition::UpperLeft)
+            .background_style(palette.background.mix(GraphStyle::LEGEND_BACKGROUND_ALPHA))
+            .border_style(palette.foreground)
+            .label_font(
+                TextStyle::from((EMBEDDED_FONT_FAMILY, GraphStyle::LEGEND_FONT_SIZE))
+                    .color(&palette.foreground),
+            )
+            .draw()
+            .map_err(|error| classify_plotters_error("legend_draw", format!("{error:?}")))?;
+    }
+    Ok(())
+}
+
+/// How long before the window end, in the largest whole unit. The end itself
+/// reads "now", or the date of an absolute window's end.
+fn relative_label(offset: chrono::Duration, window_end: Option<DateTime<Tz>>) -> String {
+    let minutes = (-offset.num_minutes()).max(0);
+    match minutes {
+        0 => window_end.map_or_else(
+            || t("graph.axis.now").to_string(),
+            |end| end.format("%Y-%m-%d %H:%M").to_string(),
+        ),
+        minutes if minutes % (24 * 60) == 0 => format!("-{}d", minutes / (24 * 60)),
+        minutes if minutes >= 120 => format!("-{}h", minutes / 60),
+        minutes => format!("-{minutes}m"),
+    }
+}
+
+type TimeChart<'a, DB> =
+    ChartContext<'a, DB, Cartesian2d<RangedDateTime<DateTime<Utc>>, RangedCoordf32>>;
+
+/// Shaded min/max band and p95 line; skipped where buckets held one value.
+fn draw_band<DB: DrawingBackend>(
+    chart: &mut TimeChart<'_, DB>,
+    series: &GraphSeries,
+    palette: &Palette,
+) -> Result<(), GraphRenderError> {
+    let color = palette.metric_line(series.metric);
+    if series.bands.iter().any(|band| band.max > band.min) {
+        let outline = series
+            .bands
+            .iter()
+            .map(|band| (band.timestamp, band.max))
+            .chain(
+                series
+                    .bands
+                    .iter()
+                    .rev()
+                    .map(|band| (band.timestamp, band.min)),
+            )
+            .collect::<Vec<_>>();
+        chart
+            .draw_series(std::iter::once(Polygon::new(
+                outline,
+                color.mix(GraphStyle::BAND_ALPHA).filled(),
+            )))
+            .map_err(|error| classify_plotters_error("band_draw", format!("{error:?}")))?;
+    }
+    if series.bands.iter().any(|band| band.p95 > band.avg) {
+        chart
+            .draw_series(std::iter::once(PathElement::new(
+                series
+                    .bands
+                    .iter()
+                    .map(|band| (band.timestamp, band.p95))
+                    .collect::<Vec<_>>(),
+                color.mix(GraphStyle::P95_ALPHA),
+            )))
+            .map_err(|error| classify_plotters_error("p95_draw", format!("{error:?}")))?;
+    }
+    Ok(())
+}
+
+/// Regions are clipped to the chart; markers outside it are skipped.
+fn draw_annotation<DB: DrawingBackend>(
+    chart: &mut TimeChart<'_, DB>,
+    annotation: &GraphAnnotation,
+    (x_start, x_end): (DateTime<Utc>, DateTime<Utc>),
+    palette: &Palette,
+) -> Result<(), GraphRenderError> {
+    let color = palette.annotation(annotation.kind);
+    let draw_error = |error: String| classify_plotters_error("annotation_draw", error);
 
-        drawing_area
-            .present()
-            .map_err(|error| classify_plotters_error("present", format!("{error:?}")))?;
+    if let Some(end) = annotation.end {
+        let (start, end) = (annotation.start.max(x_start), end.min(x_end));
+        if start >= end {
+            return Ok(());
+        }
+        return chart
+            .draw_series(std::iter::once(Rectangle::new(
+                [(start, GraphStyle::Y_MIN), (end, GraphStyle::Y_MAX)],
+                color.mix(GraphStyle::REGION_ALPHA).filled(),
+            )))
+            .map(|_| ())
+            .map_err(|error| draw_error(format!("{error:?}")));
     }
 
-    let rgb_image = RgbImage::from_raw(width, height, rgb_buffer)
-        .ok_or_else(|| GraphRenderError::Backend("image buffer conversion failed".to_string()))?;
-    let mut output = Cursor::new(Vec::new());
-    DynamicImage::ImageRgb8(rgb_image)
-        .write_to(&mut output, ImageFormat::Png)
-        .map_err(|error| GraphRenderError::PngEncoding(error.to_string()))?;
+    let at = annotation.start;
+    if at < x_start || at > x_end {
+        return Ok(());
+    }
+    let top = match annotation.kind {
+        AnnotationKind::Anomaly(_) => GraphStyle::Y_MIN + GraphStyle::ANOMALY_TICK_HEIGHT,
+        _ => GraphStyle::Y_MAX,
+    };
+    chart
+        .draw_series(std::iter::once(PathElement::new(
+            vec![(at, GraphStyle::Y_MIN), (at, top)],
+            color.mix(GraphStyle::MARKER_ALPHA),
+        )))
+        .map_err(|error| draw_error(format!("{error:?}")))?;
 
-    Ok(output.into_inner())
+    if let Some(label) = &annotation.label {
+        let style =
+            TextStyle::from((EMBEDDED_FONT_FAMILY, GraphStyle::ANNOTATION_FONT_SIZE)).color(&color);
+        chart
+            .draw_series(std::iter::once(
+                EmptyElement::at((at, GraphStyle::Y_MAX))
+                    + Text::new(label.clone(), GraphStyle::ANNOTATION_LABEL_OFFSET, style),
+            ))
+            .map_err(|error| draw_error(format!("{error:?}")))?;
+    }
+    Ok(())
 }
 
 fn ensure_embedded_font_registered() -> Result<(), GraphRenderError> {
@@ -143,20 +702,155 @@ fn classify_plotters_error(stage: &str, detail: String) -> GraphRenderError {
 
 #[cfg(test)]
 mod tests {
-    use chrono::Utc;
+    use chrono::{Duration, TimeZone, Utc};
+
+    use chrono_tz::Tz;
 
-    use super::{GraphPoint, ensure_embedded_font_registered, render_graph_png};
-    use crate::commands::features::graph::types::GraphMetric;
+    use super::{
+        AnnotationKind, GraphAnnotation, GraphBand, GraphLayout, GraphOptions, GraphSeries,
+        ensure_embedded_font_registered, relative_label, render_graph, render_heatmap,
+    };
+    use crate::commands::features::graph::heatmap::{HeatmapGrid, HeatmapStat};
+    use crate::commands::features::graph::stats::sample_buckets;
+    use crate::commands::features::graph::types::{GraphFormat, GraphMetric};
+    use crate::config::{Graph, GraphTheme};
+    use crate::monitor::MetricSample;
+
+    fn png() -> GraphOptions {
+        GraphOptions::from_config(&Graph::default())
+    }
+
+    fn series(metric: GraphMetric, len: i64) -> GraphSeries {
+        let start = Utc::now();
+        GraphSeries {
+            metric,
+            bands: (0..len)
+                .map(|index| {
+                    let avg = (index * 10 % 100) as f32;
+                    GraphBand {
+                        timestamp: start + Duration::minutes(index),
+                        min: (avg - 5.0).max(0.0),
+                        max: (avg + 10.0).min(100.0),
+                        avg,
+                        p95: (avg + 6.0).min(100.0),
+                    }
+                })
+                .collect(),
+            threshold: 80.0,
+            comparison: None,
+        }
+    }
 
     #[test]
     fn rejects_not_enough_points() {
-        let points = vec![GraphPoint {
-            timestamp: Utc::now(),
-            value: 42.0,
-        }];
-
-        let result = render_graph_png(points, GraphMetric::Cpu, 80.0);
+        let result = render_graph(
+            vec![series(GraphMetric::Cpu, 1)],
+            GraphLayout::Overlay,
+            Vec::new(),
+            png(),
+        );
         assert!(result.is_err());
+        let result = render_graph(
+            vec![series(GraphMetric::Cpu, 5), series(GraphMetric::Ram, 1)],
+            GraphLayout::Overlay,
+            Vec::new(),
+            png(),
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn renders_overlay_and_stacked_layouts() {
+        let all = || {
+            [GraphMetric::Cpu, GraphMetric::Ram, GraphMetric::Disk]
+                .into_iter()
+                .map(|metric| series(metric, 20))
+                .collect::<Vec<_>>()
+        };
+        let start = all()[0].bands[0].timestamp;
+        let annotations = vec![
+            GraphAnnotation {
+                kind: AnnotationKind::Incident(GraphMetric::Cpu),
+                start: start - Duration::minutes(5),
+                end: Some(start + Duration::minutes(4)),
+                label: None,
+            },
+            GraphAnnotation {
+                kind: AnnotationKind::Deploy,
+                start: start + Duration::minutes(10),
+                end: None,
+                label: Some("v1.8.2".to_string()),
+            },
+        ];
+        let overlay =
+            render_graph(all(), GraphLayout::Overlay, annotations, png()).expect("overlay renders");
+        let stacked =
+            render_graph(all(), GraphLayout::Stacked, Vec::new(), png()).expect("stacked renders");
+
+        let height = |png: &[u8]| image::load_from_memory(png).expect("valid png").height();
+        assert_eq!(height(&overlay), 480);
+        assert_eq!(height(&stacked), 3 * 240);
+    }
+
+    #[test]
+    fn renders_dark_svg_at_requested_size() {
+        let options = GraphOptions {
+            theme: GraphTheme::Dark,
+            width: 800,
+            height: 300,
+            format: GraphFormat::Svg,
+            window_end: None,
+        };
+        let svg = render_graph(
+            vec![series(GraphMetric::Cpu, 20), series(GraphMetric::Ram, 20)],
+            GraphLayout::Overlay,
+            Vec::new(),
+            options,
+        )
+        .expect("svg renders");
+        let svg = String::from_utf8(svg).expect("svg is utf-8");
+
+        assert!(svg.contains(r#"width="800" height="300""#));
+        assert!(svg.contains(r#"font-family="Roboto, sans-serif""#));
+        assert!(!svg.contains("kars-embedded"));
+        assert!(svg.contains("#181A1F"));
+    }
+
+    #[test]
+    fn renders_heatmap_png_and_svg_at_requested_size() {
+        let start = Utc::now();
+        let samples = (0..7 * 24)
+            .map(|hour| MetricSample {
+                timestamp: start + Duration::hours(hour),
+                cpu: (hour % 24) as f32 * 4.0,
+                ram: 50.0,
+                disk: 50.0,
+            })
+            .collect::<Vec<_>>();
+        let grid = HeatmapGrid::from_buckets(
+            &sample_buckets(&samples),
+            GraphMetric::Cpu,
+            HeatmapStat::Avg,
+            Tz::UTC,
+        );
+        assert_eq!(grid.cells().count(), 7 * 24);
+
+        let options = |format| GraphOptions {
+            theme: GraphTheme::Light,
+            width: 900,
+            height: 360,
+            format,
+            window_end: None,
+        };
+        let png = render_heatmap(grid.clone(), GraphMetric::Cpu, options(GraphFormat::Png))
+            .expect("png renders");
+        let image = image::load_from_memory(&png).expect("valid png");
+        assert_eq!((image.width(), image.height()), (900, 360));
+
+        let svg =
+            render_heatmap(grid, GraphMetric::Cpu, options(GraphFormat::Svg)).expect("svg renders");
+        let svg = String::from_utf8(svg).expect("svg is utf-8");
+        assert!(svg.contains(r#"width="900" height="360""#));
     }
 
     #[test]
@@ -164,4 +858,17 @@ mod tests {
         let result = ensure_embedded_font_registered();
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn relative_axis_ends_at_now_or_the_absolute_window_end() {
+        assert_eq!(relative_label(Duration::hours(-3), None), "-3h");
+        assert_eq!(relative_label(Duration::zero(), None), "now");
+        let end = Tz::Europe__Istanbul
+            .with_ymd_and_hms(2026, 10, 4, 0, 0, 0)
+            .unwrap();
+        assert_eq!(
+            relative_label(Duration::zero(), Some(end)),
+            "2026-10-04 00:00"
+        );
+    }
 }
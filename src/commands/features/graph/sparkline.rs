@@ -0,0 +1,73 @@
+//! `/spark`: sparklines of every metric over a recent window.
+
+use teloxide::{prelude::*, types::ParseMode};
+
+use crate::app_context::AppContext;
+use crate::i18n::{t, t_with};
+use crate::incidents::IncidentMetric;
+use crate::monitor::{MetricSample, SPARKLINE_WIDTH, metric_sparkline};
+
+use super::super::super::helpers::{as_html_block, format_window_suffix};
+use super::super::menu::main_menu_keyboard;
+use super::parser::parse_window_minutes;
+use super::types::GraphMetric;
+
+const DEFAULT_SPARK_MINUTES: i64 = 60;
+
+pub(crate) async fn handle_spark(
+    bot: &Bot,
+    msg: &Message,
+    app_context: &AppContext,
+    query: &str,
+) -> ResponseResult<()> {
+    let max_minutes = app_context.graph_runtime.read().await.max_window_hours as i64 * 60;
+    let minutes = match query.trim() {
+        "" => Some(DEFAULT_SPARK_MINUTES.min(max_minutes)),
+        window => parse_window_minutes(window).filter(|minutes| *minutes <= max_minutes),
+    };
+    let Some(minutes) = minutes else {
+        bot.send_message(
+            msg.chat.id,
+            as_html_block(t("spark.usage.title"), t("spark.usage")),
+        )
+        .reply_markup(main_menu_keyboard(&app_context.capabilities))
+        .parse_mode(ParseMode::Html)
+        .await?;
+        return Ok(());
+    };
+
+    let samples = app_context
+        .monitor
+        .metric_history
+        .lock()
+        .await
+        .latest_window(minutes);
+    let lines = spark_lines(&samples);
+    let title = t_with("spark.title", &[("window", format_window_suffix(minutes))]);
+    let body = if lines.is_empty() {
+        t("error.not_enough_samples").to_string()
+    } else {
+        lines.join("\n")
+    };
+
+    bot.send_message(msg.chat.id, as_html_block(&title, &body))
+        .reply_markup(main_menu_keyboard(&app_context.capabilities))
+        .parse_mode(ParseMode::Html)
+        .await?;
+    Ok(())
+}
+
+/// One `CPU  ▁▂▅█  42.0%` line per metric, with the latest value.
+pub(crate) fn spark_lines(samples: &[MetricSample]) -> Vec<String> {
+    let Some(latest) = samples.last() else {
+        return Vec::new();
+    };
+    IncidentMetric::ALL
+        .iter()
+        .filter_map(|&metric| {
+            let line = metric_sparkline(samples, metric, SPARKLINE_WIDTH)?;
+            let value = GraphMetric::from(metric).value(latest);
+            Some(format!("{:<4} {line} {value:.1}%", metric.label()))
+        })
+        .collect()
+}
@@ -1,101 +1,113 @@
 use chrono::{DateTime, Utc};
 
 use crate::monitor::MetricSample;
+use crate::reporting_store::MetricBucket;
+use crate::time_bands::time_bands;
 
 use super::super::types::GraphMetric;
 
-#[derive(Clone, Copy)]
-pub(crate) struct GraphPoint {
-    pub(crate) timestamp: DateTime<Utc>,
-    pub(crate) value: f32,
-}
+const BAND_PERCENTILE: f64 = 0.95;
 
-struct BucketAggregate {
-    min: GraphPoint,
-    max: GraphPoint,
+/// One drawn position: the average line plus the min/max band and p95 of the
+/// samples (or finer rollups) it covers.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct GraphBand {
+    pub(crate) timestamp: DateTime<Utc>,
+    pub(crate) min: f32,
+    pub(crate) max: f32,
+    pub(crate) avg: f32,
+    pub(crate) p95: f32,
 }
 
-pub(crate) fn downsample_points(
-    samples: &[MetricSample],
-    metric: GraphMetric,
-    width_px: usize,
-) -> Vec<GraphPoint> {
-    if samples.len() <= 2 || samples.len() <= width_px {
-        return samples
+impl GraphBand {
+    /// Aggregates consecutive buckets. Averages are weighted by sample count
+    /// and p95 is taken over the bucket averages, so it is exact for raw
+    /// samples and an upper percentile of rollup averages otherwise.
+    fn from_buckets(buckets: &[MetricBucket], metric: GraphMetric) -> Option<Self> {
+        let first = buckets.first()?;
+        let weighted = weighted_averages(buckets, metric);
+        let total_weight = weighted.iter().map(|(_, weight)| weight).sum::<u64>();
+        let avg = weighted
             .iter()
-            .map(|sample| GraphPoint {
-                timestamp: sample.timestamp,
-                value: metric.value(sample),
-            })
-            .collect();
-    }
-
-    let start_ts = samples
-        .first()
-        .map(|sample| sample.timestamp.timestamp_millis());
-    let end_ts = samples
-        .last()
-        .map(|sample| sample.timestamp.timestamp_millis());
-    let (Some(start_ts), Some(end_ts)) = (start_ts, end_ts) else {
-        return Vec::new();
-    };
+            .map(|(value, weight)| f64::from(*value) * *weight as f64)
+            .sum::<f64>()
+            / total_weight as f64;
+        let p95 = bucket_percentile(buckets, metric, BAND_PERCENTILE).unwrap_or(avg as f32);
 
-    if end_ts <= start_ts {
-        return samples
-            .iter()
-            .map(|sample| GraphPoint {
-                timestamp: sample.timestamp,
-                value: metric.value(sample),
-            })
-            .collect();
+        Some(Self {
+            timestamp: first.start,
+            min: buckets
+                .iter()
+                .map(|bucket| metric.value(&bucket.min))
+                .fold(f32::INFINITY, f32::min),
+            max: buckets
+                .iter()
+                .map(|bucket| metric.value(&bucket.max))
+                .fold(f32::NEG_INFINITY, f32::max),
+            avg: avg as f32,
+            p95,
+        })
     }
+}
 
-    let bucket_count = width_px.max(1);
-    let mut buckets: Vec<Option<BucketAggregate>> =
-        std::iter::repeat_with(|| None).take(bucket_count).collect();
+fn weighted_averages(buckets: &[MetricBucket], metric: GraphMetric) -> Vec<(f32, u64)> {
+    buckets
+        .iter()
+        .map(|bucket| (metric.value(&bucket.avg), bucket.sample_count.max(1)))
+        .collect()
+}
 
-    for sample in samples {
-        let position =
-            (sample.timestamp.timestamp_millis() - start_ts) as f64 / (end_ts - start_ts) as f64;
-        let bucket_index = (position * (bucket_count - 1) as f64).floor() as usize;
-        let point = GraphPoint {
-            timestamp: sample.timestamp,
-            value: metric.value(sample),
-        };
+/// Sample-weighted `quantile` (0 to 1) of the bucket averages.
+pub(crate) fn bucket_percentile(
+    buckets: &[MetricBucket],
+    metric: GraphMetric,
+    quantile: f64,
+) -> Option<f32> {
+    let mut weighted = weighted_averages(buckets, metric);
+    weighted.sort_by(|left, right| left.0.total_cmp(&right.0));
+    let total_weight = weighted.iter().map(|(_, weight)| weight).sum::<u64>();
+    let target = ((total_weight as f64 * quantile).ceil() as u64).max(1);
+    let mut seen = 0;
+    weighted
+        .iter()
+        .find(|(_, weight)| {
+            seen += weight;
+            seen >= target
+        })
+        .map(|(value, _)| *value)
+}
 
-        let bucket = &mut buckets[bucket_index];
-        match bucket {
-            Some(existing) => {
-                if point.value < existing.min.value {
-                    existing.min = point;
-                }
-                if point.value > existing.max.value {
-                    existing.max = point;
-                }
-            }
-            None => {
-                *bucket = Some(BucketAggregate {
-                    min: point,
-                    max: point,
-                });
-            }
-        }
-    }
+/// One band over all of `buckets`, for period-level avg/max.
+pub(crate) fn summarize_buckets(
+    buckets: &[MetricBucket],
+    metric: GraphMetric,
+) -> Option<GraphBand> {
+    GraphBand::from_buckets(buckets, metric)
+}
 
-    let mut reduced = Vec::with_capacity(bucket_count * 2);
-    for bucket in buckets.into_iter().flatten() {
-        if bucket.min.timestamp <= bucket.max.timestamp {
-            reduced.push(bucket.min);
-            if bucket.max.timestamp != bucket.min.timestamp {
-                reduced.push(bucket.max);
-            }
-        } else {
-            reduced.push(bucket.max);
-            if bucket.max.timestamp != bucket.min.timestamp {
-                reduced.push(bucket.min);
-            }
-        }
-    }
+/// Wraps raw samples as one-sample buckets for [`downsample_bands`].
+pub(crate) fn sample_buckets(samples: &[MetricSample]) -> Vec<MetricBucket> {
+    samples
+        .iter()
+        .map(|sample| MetricBucket {
+            start: sample.timestamp,
+            sample_count: 1,
+            avg: *sample,
+            min: *sample,
+            max: *sample,
+        })
+        .collect()
+}
 
-    reduced
+/// Groups time-ordered buckets into at most `width_px` bands of equal time
+/// span. Short inputs keep one band per bucket.
+pub(crate) fn downsample_bands(
+    buckets: &[MetricBucket],
+    metric: GraphMetric,
+    width_px: usize,
+) -> Vec<GraphBand> {
+    time_bands(buckets, width_px, |bucket| bucket.start)
+        .into_iter()
+        .filter_map(|band| GraphBand::from_buckets(&buckets[band], metric))
+        .collect()
 }
@@ -3,7 +3,9 @@ mod downsample;
 mod summary;
 
 pub(super) use anomaly::assess_anomaly_labels;
-pub(super) use downsample::{GraphPoint, downsample_points};
+pub(super) use downsample::{
+    GraphBand, bucket_percentile, downsample_bands, sample_buckets, summarize_buckets,
+};
 pub(super) use summary::compute_metric_summary;
 
 #[cfg(test)]
@@ -13,7 +15,7 @@ mod tests {
 
     use crate::monitor::MetricSample;
 
-    use super::{assess_anomaly_labels, compute_metric_summary, downsample_points};
+    use super::{assess_anomaly_labels, compute_metric_summary, downsample_bands, sample_buckets};
     use crate::commands::features::graph::types::GraphMetric;
 
     #[test]
@@ -53,9 +55,41 @@ mod tests {
             });
         }
 
-        let reduced = downsample_points(&samples, GraphMetric::Cpu, 10);
+        let reduced = downsample_bands(&sample_buckets(&samples), GraphMetric::Cpu, 10);
         assert!(!reduced.is_empty());
-        assert!(reduced.len() <= 20);
+        assert!(reduced.len() <= 10);
+    }
+
+    #[test]
+    fn bands_keep_short_spikes_that_averages_hide() {
+        let start = Utc::now();
+        let samples = (0..600)
+            .map(|idx| MetricSample {
+                timestamp: start + Duration::seconds(idx),
+                cpu: if (300..310).contains(&idx) {
+                    98.0
+                } else {
+                    20.0
+                },
+                ram: 10.0,
+                disk: 10.0,
+            })
+            .collect::<Vec<_>>();
+
+        let bands = downsample_bands(&sample_buckets(&samples), GraphMetric::Cpu, 3);
+        assert_eq!(bands.len(), 3);
+        let spike = bands
+            .iter()
+            .find(|band| band.max > 90.0)
+            .expect("spike survives as band max");
+        assert!(spike.avg < 30.0);
+        assert_eq!(spike.min, 20.0);
+        assert_eq!(spike.p95, 20.0);
+        assert!(
+            bands
+                .iter()
+                .all(|band| band.min <= band.p95 && band.p95 <= band.max)
+        );
     }
 
     #[test]
@@ -72,11 +106,11 @@ mod tests {
         }
 
         let timer = Instant::now();
-        let reduced = downsample_points(&samples, GraphMetric::Cpu, 1200);
+        let reduced = downsample_bands(&sample_buckets(&samples), GraphMetric::Cpu, 1200);
         let elapsed = timer.elapsed();
 
         assert!(!reduced.is_empty());
-        assert!(reduced.len() <= 2400);
+        assert!(reduced.len() <= 1200);
         assert!(elapsed.as_secs() < 5);
     }
 
@@ -1,13 +1,43 @@
-use crate::{config::Alerts, monitor::MetricSample};
+use chrono::{DateTime, Utc};
 
-#[derive(Clone, Copy)]
+use crate::{
+    config::{Alerts, GraphTheme, ReportMetric},
+    incidents::IncidentMetric,
+    monitor::MetricSample,
+};
+
+use super::super::time_range::TimeRange;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(super) enum GraphMetric {
     Cpu,
     Ram,
     Disk,
 }
 
+impl From<IncidentMetric> for GraphMetric {
+    fn from(metric: IncidentMetric) -> Self {
+        match metric {
+            IncidentMetric::Cpu => Self::Cpu,
+            IncidentMetric::Ram => Self::Ram,
+            IncidentMetric::Disk => Self::Disk,
+        }
+    }
+}
+
+impl From<ReportMetric> for GraphMetric {
+    fn from(metric: ReportMetric) -> Self {
+        match metric {
+            ReportMetric::Cpu => Self::Cpu,
+            ReportMetric::Ram => Self::Ram,
+            ReportMetric::Disk => Self::Disk,
+        }
+    }
+}
+
 impl GraphMetric {
+    pub(super) const ALL: [Self; 3] = [Self::Cpu, Self::Ram, Self::Disk];
+
     pub(super) fn parse(input: &str) -> Option<Self> {
         match input.trim().to_lowercase().as_str() {
             "cpu" => Some(Self::Cpu),
@@ -58,9 +88,11 @@ impl GraphMetric {
     }
 }
 
+/// Either the last N minutes or an absolute period read from the reporting store.
 #[derive(Clone, Copy)]
-pub(super) struct GraphWindow {
-    minutes: i64,
+pub(super) enum GraphWindow {
+    Recent { minutes: i64 },
+    Range(TimeRange),
 }
 
 impl GraphWindow {
@@ -69,24 +101,67 @@ impl GraphWindow {
             return None;
         }
 
-        Some(Self { minutes })
+        Some(Self::Recent { minutes })
     }
 
     pub(super) fn minutes(self) -> i64 {
-        self.minutes
+        match self {
+            Self::Recent { minutes } => minutes,
+            Self::Range(range) => range.minutes(),
+        }
+    }
+
+    /// Start and end of the window, with recent windows ending at `now`.
+    pub(super) fn bounds(self, now: DateTime<Utc>) -> (DateTime<Utc>, DateTime<Utc>) {
+        match self {
+            Self::Recent { minutes } => (now - chrono::Duration::minutes(minutes), now),
+            Self::Range(range) => (range.from, range.to),
+        }
     }
 
     pub(super) fn suffix(self) -> String {
-        if self.minutes % 60 == 0 {
-            format!("{}h", self.minutes / 60)
-        } else {
-            format!("{}m", self.minutes)
+        match self {
+            Self::Recent { minutes } if minutes % 60 == 0 => format!("{}h", minutes / 60),
+            Self::Recent { minutes } => format!("{minutes}m"),
+            Self::Range(range) => range.file_suffix(),
+        }
+    }
+
+    pub(super) fn label(self) -> String {
+        match self {
+            Self::Recent { .. } => self.suffix(),
+            Self::Range(range) => range.label(),
         }
     }
 }
 
-#[derive(Clone, Copy)]
+/// Output encoding of a rendered graph.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum GraphFormat {
+    /// Sent as a photo.
+    Png,
+    /// Sent as a document, for scaling without blur.
+    Svg,
+}
+
+impl GraphFormat {
+    pub(super) fn extension(self) -> &'static str {
+        match self {
+            Self::Png => "png",
+            Self::Svg => "svg",
+        }
+    }
+}
+
+/// One or more distinct metrics drawn over the same window, or one metric
+/// against the same window `compare` earlier. `theme` and `size` override
+/// the `[graph]` config for this request only.
+#[derive(Clone)]
 pub(super) struct GraphRequest {
-    pub(super) metric: GraphMetric,
+    pub(super) metrics: Vec<GraphMetric>,
     pub(super) window: GraphWindow,
+    pub(super) compare: Option<chrono::Duration>,
+    pub(super) theme: Option<GraphTheme>,
+    pub(super) size: Option<(u32, u32)>,
+    pub(super) format: GraphFormat,
 }
@@ -1,119 +1,259 @@
+use chrono::{Duration as ChronoDuration, Utc};
+
 use crate::app_context::AppContext;
+use crate::config::WeeklyCharts;
+use crate::incidents::IncidentStats;
+use crate::reporting_store::RollingMetricSummary;
+use crate::templates::{TemplateKind, render_template};
 
+use super::super::incidents::format_mttr;
+use super::super::time_range::TimeRange;
 use super::GeneratedGraphReport;
-use super::executor::{acquire_render_slot, run_render_task};
-use super::render::GRAPH_WIDTH_PX;
-use super::stats::{assess_anomaly_labels, compute_metric_summary, downsample_points};
+use super::annotations::collect_annotations;
+use super::executor::{
+    RENDER_EXECUTION_TIMEOUT_SECS, RENDER_SLOT_WAIT_TIMEOUT_SECS, acquire_render_slot,
+    run_render_task,
+};
+use super::handler::range_buckets;
+use super::render::{GraphLayout, GraphOptions, GraphSeries, render_graph};
+use super::stats::{
+    assess_anomaly_labels, compute_metric_summary, downsample_bands, sample_buckets,
+};
 use super::types::GraphMetric;
 
 const WEEKLY_WINDOW_MINUTES: i64 = 7 * 24 * 60;
-const RENDER_SLOT_WAIT_TIMEOUT_SECS: u64 = 3;
-const RENDER_EXECUTION_TIMEOUT_SECS: u64 = 8;
+/// One point per second: `latest_window` then reads raw samples whenever
+/// their retention covers the week, so anomaly labels see unsmoothed spikes.
+const WEEKLY_SAMPLE_POINTS: usize = WEEKLY_WINDOW_MINUTES as usize * 60;
 
-pub(crate) async fn build_weekly_cpu_report(
+/// One captioned chart per image of the weekly report, in send order.
+pub(crate) async fn build_weekly_report(
     app_context: &AppContext,
-) -> Result<GeneratedGraphReport, String> {
+) -> Result<Vec<GeneratedGraphReport>, String> {
     let graph_runtime = app_context.graph_runtime.read().await.clone();
     let runtime_config = app_context.runtime_config.read().await.clone();
     if !graph_runtime.enabled {
         return Err("graph feature is disabled in config".to_string());
     }
 
+    let options = GraphOptions::from_config(&graph_runtime);
+    let max_points = usize::from(graph_runtime.max_points).max(2);
+    let width_limit = usize::try_from(options.width).unwrap_or(max_points);
+    let points_limit = max_points.min(width_limit);
+
     let persisted = app_context
         .reporting_store
-        .latest_window(WEEKLY_WINDOW_MINUTES);
+        .latest_window(WEEKLY_WINDOW_MINUTES, WEEKLY_SAMPLE_POINTS);
     let samples = if persisted.len() >= 2 {
         persisted
     } else {
         let history = app_context.monitor.metric_history.lock().await;
         history.latest_window(WEEKLY_WINDOW_MINUTES)
     };
+    // The chart reads rollup buckets rather than their averages so peaks
+    // inside a bucket still show in the band.
+    let now = Utc::now();
+    let window_start = now - ChronoDuration::minutes(WEEKLY_WINDOW_MINUTES);
+    let persisted_buckets = TimeRange::new(window_start, now, app_context.config.tz())
+        .map(|range| range_buckets(app_context, range, points_limit))
+        .unwrap_or_default();
+    let buckets = if persisted_buckets.len() >= 2 {
+        persisted_buckets
+    } else {
+        sample_buckets(&samples)
+    };
 
     let persisted_rollup = app_context.reporting_store.rolling_summary_days(7);
+    let incidents = app_context.incident_store.incidents_since(window_start);
 
     if samples.len() < 2 {
         return Err("not enough samples yet".to_string());
     }
 
-    let summary = compute_metric_summary(GraphMetric::Cpu, &samples)
-        .ok_or_else(|| "not enough samples yet".to_string())?;
+    let weekly = &app_context.config.weekly_report;
+    let metrics = weekly
+        .report_metrics()
+        .into_iter()
+        .map(GraphMetric::from)
+        .collect::<Vec<_>>();
+    let mut captions = Vec::with_capacity(metrics.len());
+    for &metric in &metrics {
+        let summary = compute_metric_summary(metric, &samples)
+            .ok_or_else(|| "not enough samples yet".to_string())?;
+        let (min, max, avg, samples_count) = match &persisted_rollup {
+            Some(rollup) => {
+                let (min, max, avg) = rollup_stats(rollup, metric);
+                (min, max, avg, rollup.sample_count)
+            }
+            None => (summary.min, summary.max, summary.avg, samples.len() as u64),
+        };
+        let anomaly_labels =
+            assess_anomaly_labels(metric, &samples, metric.threshold(&runtime_config.alerts))
+                .map(|assessment| assessment.labels().join(" | "))
+                .unwrap_or_default();
+        let metric_incidents = incidents
+            .iter()
+            .filter(|incident| GraphMetric::from(incident.metric) == metric)
+            .cloned()
+            .collect::<Vec<_>>();
+        let incident_stats = IncidentStats::from_incidents(&metric_incidents);
+        // RAM/disk totals that have no chart of their own ride on the first caption.
+        let rollup_suffix = match (&persisted_rollup, captions.is_empty()) {
+            (Some(rollup), true) => rollup_suffix(rollup, &metrics),
+            _ => String::new(),
+        };
 
-    let threshold = runtime_config.alerts.cpu;
-    let anomaly_labels = assess_anomaly_labels(GraphMetric::Cpu, &samples, threshold)
-        .map(|assessment| assessment.labels().join(" | "))
-        .unwrap_or_default();
-    let max_points = usize::from(graph_runtime.max_points).max(2);
-    let width_limit = usize::try_from(GRAPH_WIDTH_PX).unwrap_or(max_points);
-    let points_limit = max_points.min(width_limit);
-    let points = downsample_points(&samples, GraphMetric::Cpu, points_limit);
-
-    let render_slot = acquire_render_slot(
-        app_context.bot_runtime.graph_render_slots.clone(),
-        RENDER_SLOT_WAIT_TIMEOUT_SECS,
-    )
-    .await
-    .map_err(|error| {
-        format!(
-            "weekly render slot failed code={} error={}",
-            error.code(),
-            error
+        captions.push(render_template(
+            TemplateKind::WeeklyCaption,
+            app_context
+                .config
+                .templates
+                .source(TemplateKind::WeeklyCaption),
+            &[
+                ("metric", metric.title().to_string()),
+                ("duration", "7d".to_string()),
+                ("samples", samples_count.to_string()),
+                ("min", format!("{min:.1}")),
+                ("max", format!("{max:.1}")),
+                ("avg", format!("{avg:.1}")),
+                (
+                    "labels",
+                    if anomaly_labels.is_empty() {
+                        String::new()
+                    } else {
+                        format!(" | {anomaly_labels}")
+                    },
+                ),
+                ("rollup", rollup_suffix),
+                ("incidents", incident_stats.count.to_string()),
+                ("mttr", format_mttr(incident_stats.mttr)),
+            ],
+        ));
+    }
+
+    let mut reports = Vec::new();
+    for (group, group_captions) in chart_groups(&metrics, weekly.charts, &captions) {
+        let layout = if group.len() > 1 {
+            GraphLayout::Stacked
+        } else {
+            GraphLayout::Overlay
+        };
+        let series = group
+            .iter()
+            .map(|&metric| GraphSeries {
+                metric,
+                bands: downsample_bands(&buckets, metric, points_limit),
+                threshold: metric.threshold(&runtime_config.alerts),
+                comparison: None,
+            })
+            .collect();
+        let annotations = collect_annotations(app_context, &group, window_start, now).await;
+
+        let render_slot = acquire_render_slot(
+            app_context.bot_runtime.graph_render_slots.clone(),
+            RENDER_SLOT_WAIT_TIMEOUT_SECS,
         )
-    })?;
-
-    let png_bytes = run_render_task(
-        points,
-        GraphMetric::Cpu,
-        threshold,
-        render_slot,
-        RENDER_EXECUTION_TIMEOUT_SECS,
-    )
-    .await
-    .map_err(|error| format!("weekly render failed code={} error={}", error.code(), error))?;
-
-    let (min_cpu, max_cpu, avg_cpu, samples_count, rollup_suffix) = if let Some(rollup) =
-        persisted_rollup
-    {
-        let suffix = format!(
-            "\nRAM avg/min/max: {:.1}% / {:.1}% / {:.1}% | Disk avg/min/max: {:.1}% / {:.1}% / {:.1}%",
-            rollup.ram_avg,
-            rollup.ram_min,
-            rollup.ram_max,
-            rollup.disk_avg,
-            rollup.disk_min,
-            rollup.disk_max
-        );
-        (
-            rollup.cpu_min,
-            rollup.cpu_max,
-            rollup.cpu_avg,
-            rollup.sample_count,
-            suffix,
+        .await
+        .map_err(|error| {
+            format!(
+                "weekly render slot failed code={} error={}",
+                error.code(),
+                error
+            )
+        })?;
+        let png_bytes = run_render_task(
+            move || render_graph(series, layout, annotations, options),
+            render_slot,
+            RENDER_EXECUTION_TIMEOUT_SECS,
         )
+        .await
+        .map_err(|error| format!("weekly render failed code={} error={}", error.code(), error))?;
+
+        let file_name = match group.as_slice() {
+            [metric] => format!("{}-weekly-7d.png", metric.file_name()),
+            _ => "weekly-7d.png".to_string(),
+        };
+        reports.push(GeneratedGraphReport {
+            png_bytes,
+            file_name,
+            caption: group_captions.join("\n\n"),
+        });
+    }
+
+    Ok(reports)
+}
+
+/// Metrics and their captions per image: all in one, or one image each.
+fn chart_groups<'a>(
+    metrics: &[GraphMetric],
+    charts: WeeklyCharts,
+    captions: &'a [String],
+) -> Vec<(Vec<GraphMetric>, &'a [String])> {
+    match charts {
+        WeeklyCharts::Combined => vec![(metrics.to_vec(), captions)],
+        WeeklyCharts::PerMetric => metrics
+            .iter()
+            .zip(captions.chunks(1))
+            .map(|(&metric, caption)| (vec![metric], caption))
+            .collect(),
+    }
+}
+
+fn rollup_stats(rollup: &RollingMetricSummary, metric: GraphMetric) -> (f32, f32, f32) {
+    match metric {
+        GraphMetric::Cpu => (rollup.cpu_min, rollup.cpu_max, rollup.cpu_avg),
+        GraphMetric::Ram => (rollup.ram_min, rollup.ram_max, rollup.ram_avg),
+        GraphMetric::Disk => (rollup.disk_min, rollup.disk_max, rollup.disk_avg),
+    }
+}
+
+/// 7-day rollup line for the metrics the report does not chart.
+fn rollup_suffix(rollup: &RollingMetricSummary, charted: &[GraphMetric]) -> String {
+    let lines = GraphMetric::ALL
+        .iter()
+        .filter(|metric| !charted.contains(metric))
+        .map(|&metric| {
+            let (min, max, avg) = rollup_stats(rollup, metric);
+            format!(
+                "{} avg/min/max: {avg:.1}% / {min:.1}% / {max:.1}%",
+                metric.title()
+            )
+        })
+        .collect::<Vec<_>>();
+    if lines.is_empty() {
+        String::new()
     } else {
-        (
-            summary.min,
-            summary.max,
-            summary.avg,
-            samples.len() as u64,
-            String::new(),
-        )
-    };
+        format!("\n{}", lines.join(" | "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::chart_groups;
+    use crate::commands::features::graph::types::GraphMetric;
+    use crate::config::WeeklyCharts;
 
-    Ok(GeneratedGraphReport {
-        png_bytes,
-        file_name: "cpu-weekly-7d.png".to_string(),
-        caption: format!(
-            "📈 Weekly CPU (7d) | samples: {} | min: {:.1}% | max: {:.1}% | avg: {:.1}%{}{}",
-            samples_count,
-            min_cpu,
-            max_cpu,
-            avg_cpu,
-            if anomaly_labels.is_empty() {
-                String::new()
-            } else {
-                format!(" | {anomaly_labels}")
-            },
-            rollup_suffix,
-        ),
-    })
+    #[test]
+    fn chart_groups_combine_metrics_or_split_them_with_their_captions() {
+        let metrics = [GraphMetric::Cpu, GraphMetric::Disk];
+        let captions = ["cpu".to_string(), "disk".to_string()];
+
+        let combined = chart_groups(&metrics, WeeklyCharts::Combined, &captions);
+        assert_eq!(combined.len(), 1);
+        assert_eq!(combined[0].0, metrics);
+        assert_eq!(combined[0].1, captions);
+
+        let split = chart_groups(&metrics, WeeklyCharts::PerMetric, &captions);
+        let split = split
+            .iter()
+            .map(|(group, captions)| (group.clone(), captions.to_vec()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            split,
+            [
+                (vec![GraphMetric::Cpu], vec!["cpu".to_string()]),
+                (vec![GraphMetric::Disk], vec!["disk".to_string()]),
+            ]
+        );
+    }
 }
@@ -2,6 +2,8 @@ use chrono::Utc;
 use teloxide::{prelude::*, types::ParseMode};
 
 use crate::app_context::AppContext;
+use crate::i18n::{t, t_with};
+use crate::time_format::format_local_time;
 
 use super::super::helpers::{as_html_card, escape_html_text};
 use super::menu::main_menu_keyboard;
@@ -11,16 +13,10 @@ pub(crate) async fn handle_help(
     msg: &Message,
     app_context: &AppContext,
 ) -> ResponseResult<()> {
-    bot.send_message(
-        msg.chat.id,
-        as_html_card(
-            "Control Center",
-            "• Use buttons below for common flows.\n\n<b>Main journeys</b>\n• Health &amp; status checks\n• System diagnostics\n• Monitoring, alerts and graphs\n• Recent anomalies and exports\n\nSlash commands still work if you prefer manual usage.",
-        ),
-    )
-    .reply_markup(main_menu_keyboard(&app_context.capabilities))
-    .parse_mode(ParseMode::Html)
-    .await?;
+    bot.send_message(msg.chat.id, as_html_card(t("help.title"), t("help.body")))
+        .reply_markup(main_menu_keyboard(&app_context.capabilities))
+        .parse_mode(ParseMode::Html)
+        .await?;
 
     Ok(())
 }
@@ -34,36 +30,44 @@ pub(crate) async fn handle_health(
     let last_tick = *app_context.monitor.last_monitor_tick.lock().await;
     let now = Utc::now();
     let threshold_secs = (runtime_config.monitor_interval * 2) as i64;
+    let tz = app_context.config.tz();
 
     let body = match last_tick {
         Some(tick) => {
             let lag_secs = now.signed_duration_since(tick).num_seconds().max(0);
-            let status_line = if lag_secs > threshold_secs {
-                format!(
-                    "⚠️ CRITICAL: Monitor loop is delayed. Last tick: {lag_secs}s ago (threshold: {threshold_secs}s)"
-                )
+            let status_key = if lag_secs > threshold_secs {
+                "health.delayed"
             } else {
-                format!(
-                    "✅ Healthy. Last monitor tick: {lag_secs}s ago (threshold: {threshold_secs}s)"
-                )
+                "health.healthy"
             };
+            let status_line = t_with(
+                status_key,
+                &[
+                    ("lag", lag_secs.to_string()),
+                    ("threshold", threshold_secs.to_string()),
+                ],
+            );
 
-            format!(
-                "{}\n\nMonitor interval: {}s\nCurrent time: {}\nLast tick: {}",
-                status_line,
-                runtime_config.monitor_interval,
-                now.to_rfc3339(),
-                tick.to_rfc3339()
+            t_with(
+                "health.details",
+                &[
+                    ("status", status_line),
+                    ("interval", runtime_config.monitor_interval.to_string()),
+                    ("now", format_local_time(now, tz)),
+                    ("tick", format_local_time(tick, tz)),
+                ],
             )
         }
-        None => format!(
-            "⏳ Warming up...\n\nMonitor loop has not produced the first tick yet.\nMonitor interval: {}s\nCurrent time: {}",
-            runtime_config.monitor_interval,
-            now.to_rfc3339()
+        None => t_with(
+            "health.warming_up",
+            &[
+                ("interval", runtime_config.monitor_interval.to_string()),
+                ("now", format_local_time(now, tz)),
+            ],
         ),
     };
 
-    let health_html = as_html_card("Bot Health", &escape_html_text(&body));
+    let health_html = as_html_card(t("health.title"), &escape_html_text(&body));
 
     bot.send_message(msg.chat.id, health_html)
         .reply_markup(main_menu_keyboard(&app_context.capabilities))
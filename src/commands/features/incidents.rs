@@ -0,0 +1,297 @@
+use chrono::{Duration as ChronoDuration, Utc};
+use teloxide::{prelude::*, types::InputFile, types::ParseMode};
+
+use crate::app_context::AppContext;
+use crate::architecture::use_cases::acknowledge_incident_use_case;
+use crate::i18n::{t, t_with};
+use crate::incidents::{Incident, IncidentEventKind, IncidentStats};
+use crate::templates::format_duration;
+use crate::time_format::format_local_rfc3339;
+
+use super::super::helpers::{acquire_command_slot, as_html_block, parse_mute_duration};
+use super::graph::build_incident_graph;
+use super::menu::main_menu_keyboard;
+
+const DEFAULT_WINDOW: &str = "7d";
+const MAX_WINDOW_DAYS: i64 = 365;
+const MAX_LISTED: usize = 25;
+
+pub(crate) async fn handle_incidents(
+    bot: &Bot,
+    msg: &Message,
+    app_context: &AppContext,
+    args: &str,
+) -> ResponseResult<()> {
+    let window_label = match args.trim() {
+        "" => DEFAULT_WINDOW.to_string(),
+        raw => raw.to_lowercase(),
+    };
+    let Some(window) = parse_mute_duration(&window_label)
+        .filter(|window| *window <= ChronoDuration::days(MAX_WINDOW_DAYS))
+    else {
+        return send_reply(
+            bot,
+            msg,
+            app_context,
+            t("incidents.title"),
+            t("incidents.usage"),
+        )
+        .await;
+    };
+
+    let incidents = app_context
+        .incident_store
+        .incidents_since(Utc::now() - window);
+    if incidents.is_empty() {
+        let body = t_with("incidents.empty", &[("window", window_label)]);
+        return send_reply(bot, msg, app_context, t("incidents.title"), &body).await;
+    }
+
+    let stats = IncidentStats::from_incidents(&incidents);
+    let mut lines = vec![t_with(
+        "incidents.summary",
+        &[
+            ("count", stats.count.to_string()),
+            ("window", window_label),
+            ("open", stats.open.to_string()),
+            ("mttr", format_mttr(stats.mttr)),
+        ],
+    )];
+    lines.push(String::new());
+    lines.extend(
+        incidents
+            .iter()
+            .take(MAX_LISTED)
+            .map(|incident| incident_line(app_context, incident)),
+    );
+
+    send_reply(
+        bot,
+        msg,
+        app_context,
+        t("incidents.title"),
+        &lines.join("\n"),
+    )
+    .await
+}
+
+pub(crate) async fn handle_incident(
+    bot: &Bot,
+    msg: &Message,
+    app_context: &AppContext,
+    args: &str,
+) -> ResponseResult<()> {
+    let mut parts = args.split_whitespace();
+    let id = parts
+        .next()
+        .map(|raw| raw.trim_start_matches('#'))
+        .and_then(|raw| raw.parse::<u64>().ok());
+    let action = parts.next().map(str::to_lowercase);
+    let (Some(id), None | Some("ack"), None) = (id, action.as_deref(), parts.next()) else {
+        return send_reply(
+            bot,
+            msg,
+            app_context,
+            t("incidents.title"),
+            t("incidents.usage"),
+        )
+        .await;
+    };
+    let title = t_with("incident.title", &[("id", id.to_string())]);
+
+    let incident = if action.is_some() {
+        acknowledge_incident_use_case(
+            &app_context.monitor.alert_state,
+            app_context.incident_store.as_ref(),
+            id,
+        )
+        .await
+        .unwrap_or_else(|error| {
+            log::warn!("incident_ack_failed id={id} error={error}");
+            None
+        })
+    } else {
+        open_or_stored_incident(app_context, id).await
+    };
+    let Some(incident) = incident else {
+        let body = t_with("incident.not_found", &[("id", id.to_string())]);
+        return send_reply(bot, msg, app_context, &title, &body).await;
+    };
+
+    send_reply(
+        bot,
+        msg,
+        app_context,
+        &title,
+        &incident_details(app_context, &incident),
+    )
+    .await?;
+    if action.is_some() {
+        return Ok(());
+    }
+
+    let Some(_permit) =
+        acquire_command_slot(&app_context.bot_runtime.command_slots, msg, bot).await?
+    else {
+        return Ok(());
+    };
+    match build_incident_graph(app_context, &incident).await {
+        Ok(report) => {
+            bot.send_photo(
+                msg.chat.id,
+                InputFile::memory(report.png_bytes).file_name(report.file_name),
+            )
+            .caption(report.caption)
+            .reply_markup(main_menu_keyboard(&app_context.capabilities))
+            .await?;
+        }
+        Err(error) => log::warn!("incident_graph_skipped id={id} error={error}"),
+    }
+    Ok(())
+}
+
+/// The in-memory copy of an open incident is newer than the stored one.
+async fn open_or_stored_incident(app_context: &AppContext, id: u64) -> Option<Incident> {
+    let open = {
+        let state = app_context.monitor.alert_state.lock().await;
+        state
+            .open_incidents
+            .iter()
+            .flatten()
+            .find(|incident| incident.id == id)
+            .cloned()
+    };
+    open.or_else(|| app_context.incident_store.incident(id))
+}
+
+fn incident_line(app_context: &AppContext, incident: &Incident) -> String {
+    let mut flags = Vec::new();
+    if incident.is_open() {
+        flags.push(t("incidents.flag.open"));
+    }
+    if incident.acked_at.is_some() {
+        flags.push(t("incidents.flag.acked"));
+    }
+    if incident.muted {
+        flags.push(t("incidents.flag.muted"));
+    }
+
+    t_with(
+        "incidents.line",
+        &[
+            ("id", incident.id.to_string()),
+            ("metric", incident.metric.label().to_string()),
+            (
+                "started",
+                format_local_rfc3339(&incident.started_at, app_context.config.tz()),
+            ),
+            ("duration", format_incident_duration(incident)),
+            ("peak", format!("{:.1}", incident.peak)),
+            ("threshold", format!("{:.1}", incident.threshold)),
+            ("alerts", incident.alerts_sent.to_string()),
+            (
+                "flags",
+                if flags.is_empty() {
+                    String::new()
+                } else {
+                    format!(" | {}", flags.join(", "))
+                },
+            ),
+        ],
+    )
+}
+
+fn incident_details(app_context: &AppContext, incident: &Incident) -> String {
+    let tz = app_context.config.tz();
+    let none = || t("incidents.none").to_string();
+    let timeline = incident
+        .timeline
+        .iter()
+        .map(|event| {
+            let value = event
+                .value
+                .map(|value| format!(" ({value:.1}%)"))
+                .unwrap_or_default();
+            format!(
+                "{} {}{value}",
+                format_local_rfc3339(&event.at, tz),
+                t(event_key(event.kind))
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    t_with(
+        "incident.body",
+        &[
+            ("metric", incident.metric.label().to_string()),
+            ("threshold", format!("{:.1}", incident.threshold)),
+            ("started", format_local_rfc3339(&incident.started_at, tz)),
+            (
+                "ended",
+                incident.ended_at.as_deref().map_or_else(
+                    || t("incident.ongoing").to_string(),
+                    |ended| format_local_rfc3339(ended, tz),
+                ),
+            ),
+            ("duration", format_incident_duration(incident)),
+            ("peak", format!("{:.1}", incident.peak)),
+            ("peak_at", format_local_rfc3339(&incident.peak_at, tz)),
+            ("alerts", incident.alerts_sent.to_string()),
+            (
+                "acked",
+                incident
+                    .acked_at
+                    .as_deref()
+                    .map_or_else(none, |acked| format_local_rfc3339(acked, tz)),
+            ),
+            (
+                "muted",
+                t(if incident.muted {
+                    "common.yes"
+                } else {
+                    "common.no"
+                })
+                .to_string(),
+            ),
+            ("timeline", timeline),
+        ],
+    )
+}
+
+fn event_key(kind: IncidentEventKind) -> &'static str {
+    match kind {
+        IncidentEventKind::Opened => "incident.event.opened",
+        IncidentEventKind::Alerted => "incident.event.alerted",
+        IncidentEventKind::Muted => "incident.event.muted",
+        IncidentEventKind::Acknowledged => "incident.event.acknowledged",
+        IncidentEventKind::Resolved => "incident.event.resolved",
+    }
+}
+
+fn format_incident_duration(incident: &Incident) -> String {
+    incident
+        .duration(Utc::now())
+        .and_then(|duration| duration.to_std().ok())
+        .map_or_else(|| t("incidents.none").to_string(), format_duration)
+}
+
+/// Mean time to resolve, or a placeholder when nothing has resolved yet.
+pub(crate) fn format_mttr(mttr: Option<ChronoDuration>) -> String {
+    mttr.and_then(|mttr| mttr.to_std().ok())
+        .map_or_else(|| t("incidents.none").to_string(), format_duration)
+}
+
+async fn send_reply(
+    bot: &Bot,
+    msg: &Message,
+    app_context: &AppContext,
+    title: &str,
+    body: &str,
+) -> ResponseResult<()> {
+    bot.send_message(msg.chat.id, as_html_block(title, body))
+        .reply_markup(main_menu_keyboard(&app_context.capabilities))
+        .parse_mode(ParseMode::Html)
+        .await?;
+    Ok(())
+}
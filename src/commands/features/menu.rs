@@ -6,6 +6,7 @@ use teloxide::{
 
 use crate::capabilities::Capabilities;
 use crate::commands::helpers::as_html_card;
+use crate::i18n::t;
 
 fn has_any_system_capability(capabilities: &Capabilities) -> bool {
     capabilities.has_free
@@ -19,23 +20,26 @@ fn has_any_system_capability(capabilities: &Capabilities) -> bool {
 
 pub(crate) fn main_menu_keyboard(capabilities: &Capabilities) -> InlineKeyboardMarkup {
     let mut rows = vec![vec![
-        InlineKeyboardButton::callback("📊 Status", "cmd:status"),
-        InlineKeyboardButton::callback("💓 Health", "cmd:health"),
-        InlineKeyboardButton::callback("🚨 Alerts", "cmd:alerts"),
+        InlineKeyboardButton::callback(t("menu.button.status"), "cmd:status"),
+        InlineKeyboardButton::callback(t("menu.button.health"), "cmd:health"),
+        InlineKeyboardButton::callback(t("menu.button.alerts"), "cmd:alerts"),
     ]];
 
     let mut second_row = vec![
-        InlineKeyboardButton::callback("📈 Monitor", "menu:monitor"),
-        InlineKeyboardButton::callback("📦 Data", "menu:data"),
+        InlineKeyboardButton::callback(t("menu.button.monitor"), "menu:monitor"),
+        InlineKeyboardButton::callback(t("menu.button.data"), "menu:data"),
     ];
     if has_any_system_capability(capabilities) {
         second_row.insert(
             0,
-            InlineKeyboardButton::callback("🖥️ System", "menu:system"),
+            InlineKeyboardButton::callback(t("menu.button.system"), "menu:system"),
         );
     }
     rows.push(second_row);
-    rows.push(vec![InlineKeyboardButton::callback("❓ Help", "menu:help")]);
+    rows.push(vec![InlineKeyboardButton::callback(
+        t("menu.button.help"),
+        "menu:help",
+    )]);
 
     InlineKeyboardMarkup::new(rows)
 }
@@ -46,15 +50,21 @@ fn system_menu_keyboard(capabilities: &Capabilities) -> InlineKeyboardMarkup {
     let mut resource_row: Vec<InlineKeyboardButton> = Vec::new();
     if capabilities.has_free {
         resource_row.push(InlineKeyboardButton::callback(
-            "📦 Sys Snapshot",
+            t("menu.button.sys_snapshot"),
             "cmd:sysstatus",
         ));
     }
     if capabilities.has_top {
-        resource_row.push(InlineKeyboardButton::callback("🧠 CPU", "cmd:cpu"));
+        resource_row.push(InlineKeyboardButton::callback(
+            t("menu.button.cpu"),
+            "cmd:cpu",
+        ));
     }
     if capabilities.has_sensors {
-        resource_row.push(InlineKeyboardButton::callback("🌡️ Temp", "cmd:temp"));
+        resource_row.push(InlineKeyboardButton::callback(
+            t("menu.button.temp"),
+            "cmd:temp",
+        ));
     }
     if !resource_row.is_empty() {
         rows.push(resource_row);
@@ -62,10 +72,16 @@ fn system_menu_keyboard(capabilities: &Capabilities) -> InlineKeyboardMarkup {
 
     let mut network_row: Vec<InlineKeyboardButton> = Vec::new();
     if capabilities.has_ip {
-        network_row.push(InlineKeyboardButton::callback("🌐 Network", "cmd:network"));
+        network_row.push(InlineKeyboardButton::callback(
+            t("menu.button.network"),
+            "cmd:network",
+        ));
     }
     if capabilities.has_uptime {
-        network_row.push(InlineKeyboardButton::callback("⏱️ Uptime", "cmd:uptime"));
+        network_row.push(InlineKeyboardButton::callback(
+            t("menu.button.uptime"),
+            "cmd:uptime",
+        ));
     }
     if !network_row.is_empty() {
         rows.push(network_row);
@@ -73,11 +89,14 @@ fn system_menu_keyboard(capabilities: &Capabilities) -> InlineKeyboardMarkup {
 
     let mut services_row: Vec<InlineKeyboardButton> = Vec::new();
     if capabilities.has_ss {
-        services_row.push(InlineKeyboardButton::callback("🔌 Ports", "cmd:ports"));
+        services_row.push(InlineKeyboardButton::callback(
+            t("menu.button.ports"),
+            "cmd:ports",
+        ));
     }
     if capabilities.is_systemd {
         services_row.push(InlineKeyboardButton::callback(
-            "🧩 Services",
+            t("menu.button.services"),
             "cmd:services",
         ));
     }
@@ -86,7 +105,7 @@ fn system_menu_keyboard(capabilities: &Capabilities) -> InlineKeyboardMarkup {
     }
 
     rows.push(vec![InlineKeyboardButton::callback(
-        "⬅️ Main Menu",
+        t("menu.button.main_menu"),
         "menu:main",
     )]);
 
@@ -96,52 +115,49 @@ fn system_menu_keyboard(capabilities: &Capabilities) -> InlineKeyboardMarkup {
 fn monitor_menu_keyboard() -> InlineKeyboardMarkup {
     InlineKeyboardMarkup::new(vec![
         vec![
-            InlineKeyboardButton::callback("📈 CPU 1h", "cmd:graph:cpu 1h"),
-            InlineKeyboardButton::callback("💾 RAM 1h", "cmd:graph:ram 1h"),
-            InlineKeyboardButton::callback("🧱 Disk 1h", "cmd:graph:disk 1h"),
+            InlineKeyboardButton::callback(t("menu.button.graph_cpu_1h"), "cmd:graph:cpu 1h"),
+            InlineKeyboardButton::callback(t("menu.button.graph_ram_1h"), "cmd:graph:ram 1h"),
+            InlineKeyboardButton::callback(t("menu.button.graph_disk_1h"), "cmd:graph:disk 1h"),
         ],
         vec![
-            InlineKeyboardButton::callback("🧾 Recent 6h", "cmd:recent:6h"),
-            InlineKeyboardButton::callback("📤 Export CPU", "cmd:export:cpu 1h csv"),
+            InlineKeyboardButton::callback(t("menu.button.recent_6h"), "cmd:recent:6h"),
+            InlineKeyboardButton::callback(t("menu.button.export_cpu"), "cmd:export:cpu 1h csv"),
         ],
         vec![
-            InlineKeyboardButton::callback("🔇 Mute 1h", "cmd:mute:1h"),
-            InlineKeyboardButton::callback("🔔 Unmute", "cmd:unmute"),
+            InlineKeyboardButton::callback(t("menu.button.mute_1h"), "cmd:mute:1h"),
+            InlineKeyboardButton::callback(t("menu.button.unmute"), "cmd:unmute"),
         ],
-        vec![InlineKeyboardButton::callback("⬅️ Main Menu", "menu:main")],
+        vec![InlineKeyboardButton::callback(
+            t("menu.button.main_menu"),
+            "menu:main",
+        )],
     ])
 }
 
 fn data_menu_keyboard() -> InlineKeyboardMarkup {
     InlineKeyboardMarkup::new(vec![
         vec![
-            InlineKeyboardButton::callback("🧾 Recent 24h", "cmd:recent:24h"),
-            InlineKeyboardButton::callback("📤 Export CPU", "cmd:export:cpu 6h csv"),
+            InlineKeyboardButton::callback(t("menu.button.recent_24h"), "cmd:recent:24h"),
+            InlineKeyboardButton::callback(t("menu.button.export_cpu"), "cmd:export:cpu 6h csv"),
         ],
         vec![
-            InlineKeyboardButton::callback("📤 Export RAM", "cmd:export:ram 6h csv"),
-            InlineKeyboardButton::callback("📤 Export Disk", "cmd:export:disk 6h csv"),
+            InlineKeyboardButton::callback(t("menu.button.export_ram"), "cmd:export:ram 6h csv"),
+            InlineKeyboardButton::callback(t("menu.button.export_disk"), "cmd:export:disk 6h csv"),
         ],
-        vec![InlineKeyboardButton::callback("⬅️ Main Menu", "menu:main")],
+        vec![InlineKeyboardButton::callback(
+            t("menu.button.main_menu"),
+            "menu:main",
+        )],
     ])
 }
 
 fn menu_screen(menu_name: &str) -> Option<(&'static str, &'static str)> {
     match menu_name {
-        "main" => Some((
-            "Main",
-            "Use buttons to run actions directly. No need to type commands manually.",
-        )),
-        "system" => Some(("Main › System", "Live host diagnostics and service checks.")),
-        "monitor" => Some((
-            "Main › Monitor",
-            "Alert controls, graphs and anomaly drill-down actions.",
-        )),
-        "data" => Some(("Main › Data", "Recent anomalies and export shortcuts.")),
-        "help" => Some((
-            "Main › Help",
-            "1) Open a menu\n2) Tap an action\n3) Use ⬅️ Main Menu to continue\n\nTip: Slash commands still work, but all common flows are available as buttons.",
-        )),
+        "main" => Some((t("menu.main.title"), t("menu.main.body"))),
+        "system" => Some((t("menu.system.title"), t("menu.system.body"))),
+        "monitor" => Some((t("menu.monitor.title"), t("menu.monitor.body"))),
+        "data" => Some((t("menu.data.title"), t("menu.data.body"))),
+        "help" => Some((t("menu.help.title"), t("menu.help.body"))),
         _ => None,
     }
 }
@@ -232,10 +248,7 @@ pub(crate) async fn send_navigation_hint(
 ) -> ResponseResult<()> {
     bot.send_message(
         chat_id,
-        as_html_card(
-            "Next",
-            "Continue from menu buttons below or use /help for full command list.",
-        ),
+        as_html_card(t("menu.next.title"), t("menu.next.body")),
     )
     .reply_markup(main_menu_keyboard(capabilities))
     .parse_mode(ParseMode::Html)
@@ -1,10 +1,15 @@
 pub(super) mod alerts;
+pub(super) mod backup;
+pub(super) mod dbcheck;
 pub(super) mod export;
 pub(crate) mod graph;
 pub(super) mod health;
+pub(super) mod incidents;
 pub(super) mod menu;
 pub(super) mod recent;
 pub(super) mod recent_query;
 pub(super) mod status;
+pub(super) mod storage;
 pub(super) mod system_info;
+pub(super) mod time_range;
 pub(super) mod update;
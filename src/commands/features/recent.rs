@@ -1,10 +1,12 @@
 use teloxide::{prelude::*, types::ParseMode};
 
 use crate::app_context::AppContext;
+use crate::i18n::{t, t_with};
+use crate::time_format::format_local_rfc3339;
 
 use super::super::helpers::as_html_block;
 use super::menu::main_menu_keyboard;
-use super::recent_query::{RecentQuery, apply_recent_query, parse_recent_query};
+use super::recent_query::{RecentQuery, parse_recent_query};
 
 pub(crate) async fn handle_recent_anomalies(
     bot: &Bot,
@@ -14,7 +16,6 @@ pub(crate) async fn handle_recent_anomalies(
 ) -> ResponseResult<()> {
     const DEFAULT_LIMIT: usize = 10;
     const MAX_LIMIT: usize = 100;
-    const SCAN_LIMIT: usize = 500;
 
     let parsed_query = match parse_recent_query(query) {
         Ok(parsed) => parsed,
@@ -22,10 +23,8 @@ pub(crate) async fn handle_recent_anomalies(
             bot.send_message(
                 msg.chat.id,
                 as_html_block(
-                    "Recent anomalies",
-                    &format!(
-                        "Invalid query: {error}\n\nUsage:\n/recent\n/recent 5\n/recent 6h\n/recent cpu>85\n/recent cpu>85 ram>80 6h"
-                    ),
+                    t("recent.title"),
+                    &t_with("recent.invalid", &[("error", error.to_string())]),
                 ),
             )
             .reply_markup(main_menu_keyboard(&app_context.capabilities))
@@ -35,25 +34,21 @@ pub(crate) async fn handle_recent_anomalies(
         }
     };
 
-    let desired_limit = match parsed_query {
-        RecentQuery::Default => DEFAULT_LIMIT,
-        RecentQuery::Limit(limit) => limit,
-        RecentQuery::Filters(_) => MAX_LIMIT,
+    let storage = &app_context.anomaly_storage;
+    let recent = match parsed_query {
+        RecentQuery::Default => storage.recent(&app_context.config, DEFAULT_LIMIT).await,
+        RecentQuery::Limit(limit) => storage.recent(&app_context.config, limit).await,
+        RecentQuery::Filters(filters) => {
+            storage
+                .query(&app_context.config, &filters.to_anomaly_query(MAX_LIMIT))
+                .await
+        }
     };
 
-    let mut recent = app_context
-        .anomaly_storage
-        .recent(&app_context.config, SCAN_LIMIT)
-        .await;
-    recent = apply_recent_query(recent, parsed_query);
-    if recent.len() > desired_limit {
-        recent.truncate(desired_limit);
-    }
-
     if recent.is_empty() {
         bot.send_message(
             msg.chat.id,
-            as_html_block("Recent anomalies", "No anomaly records found."),
+            as_html_block(t("recent.title"), t("recent.empty")),
         )
         .reply_markup(main_menu_keyboard(&app_context.capabilities))
         .parse_mode(ParseMode::Html)
@@ -61,6 +56,7 @@ pub(crate) async fn handle_recent_anomalies(
         return Ok(());
     }
 
+    let tz = app_context.config.tz();
     let lines = recent
         .iter()
         .enumerate()
@@ -68,7 +64,7 @@ pub(crate) async fn handle_recent_anomalies(
             format!(
                 "{}. {} | CPU {:.1}% (>{:.1}%: {}) | RAM {:.1}% (>{:.1}%: {}) | Disk {:.1}% (>{:.1}%: {})",
                 index + 1,
-                event.timestamp,
+                format_local_rfc3339(&event.timestamp, tz),
                 event.cpu,
                 event.cpu_threshold,
                 yes_no(event.cpu_over),
@@ -83,7 +79,7 @@ pub(crate) async fn handle_recent_anomalies(
         .collect::<Vec<_>>()
         .join("\n");
 
-    bot.send_message(msg.chat.id, as_html_block("Recent anomalies", &lines))
+    bot.send_message(msg.chat.id, as_html_block(t("recent.title"), &lines))
         .reply_markup(main_menu_keyboard(&app_context.capabilities))
         .parse_mode(ParseMode::Html)
         .await?;
@@ -92,5 +88,5 @@ pub(crate) async fn handle_recent_anomalies(
 }
 
 fn yes_no(value: bool) -> &'static str {
-    if value { "yes" } else { "no" }
+    t(if value { "common.yes" } else { "common.no" })
 }
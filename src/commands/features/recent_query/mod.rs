@@ -2,7 +2,6 @@ mod filter;
 mod model;
 mod parser;
 
-pub(super) use filter::apply_recent_query;
 pub(super) use model::RecentQuery;
 pub(super) use parser::parse_recent_query;
 
@@ -1,24 +1,4 @@
-#[derive(Clone, Copy)]
-pub(crate) enum MetricField {
-    Cpu,
-    Ram,
-    Disk,
-}
-
-#[derive(Clone, Copy)]
-pub(crate) enum Operator {
-    Gt,
-    Gte,
-    Lt,
-    Lte,
-}
-
-#[derive(Clone, Copy)]
-pub(crate) struct MetricCondition {
-    pub(crate) field: MetricField,
-    pub(crate) op: Operator,
-    pub(crate) threshold: f32,
-}
+pub(crate) use crate::anomaly_db::{MetricCondition, MetricField, Operator};
 
 #[derive(Clone)]
 pub(crate) struct RecentFilters {
@@ -1,9 +1,16 @@
 use teloxide::{prelude::*, types::ParseMode};
 
+use crate::anomaly_db::last_integrity_report;
 use crate::app_context::AppContext;
 use crate::architecture::use_cases::alert_snapshot_use_case;
+use crate::config::AnomalyDbBackend;
+use crate::i18n::{t, t_with};
+use crate::jobs::upcoming_runs;
+use crate::monitor::ALERT_SPARKLINE_MINUTES;
+use crate::time_format::format_local_time;
 
 use super::super::helpers::{as_html_card, escape_html_text};
+use super::graph::spark_lines;
 use super::menu::main_menu_keyboard;
 
 pub(crate) async fn handle_status_overview(
@@ -16,49 +23,131 @@ pub(crate) async fn handle_status_overview(
     let alert_state = alert_snapshot_use_case(&app_context.monitor.alert_state).await;
     let last_monitor_tick = *app_context.monitor.last_monitor_tick.lock().await;
     let now = chrono::Utc::now();
+    let tz = app_context.config.tz();
 
     let mute_state = match alert_state.muted_until {
-        Some(until) if now < until => format!("muted until {}", until.to_rfc3339()),
-        _ => "not muted".to_string(),
+        Some(until) if now < until => t_with(
+            "status.muted_until",
+            &[("until", format_local_time(until, tz))],
+        ),
+        _ => t("status.not_muted").to_string(),
     };
 
-    let last_tick_text =
-        last_monitor_tick.map_or_else(|| "not available yet".to_string(), |tick| tick.to_rfc3339());
+    let last_tick_text = last_monitor_tick.map_or_else(
+        || t("status.not_available").to_string(),
+        |tick| format_local_time(tick, tz),
+    );
+
+    let upcoming = upcoming_runs(&app_context.config, now);
+    let schedule_text = if upcoming.is_empty() {
+        format!("- {}", t("status.none"))
+    } else {
+        upcoming
+            .iter()
+            .map(|(job, at)| format!("- {}: {}", job.name(), format_local_time(*at, tz)))
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+
+    let integrity_text = if app_context.config.anomaly_db.backend == AnomalyDbBackend::Sled {
+        t("status.integrity.sled").to_string()
+    } else {
+        last_integrity_report(&app_context.config).map_or_else(
+            || t("status.integrity.unchecked").to_string(),
+            |report| {
+                let summary_key = if report.is_clean() {
+                    "status.integrity.clean"
+                } else if report.repaired {
+                    "status.integrity.repaired"
+                } else {
+                    "status.integrity.issues"
+                };
+                t_with(
+                    "status.integrity.checked",
+                    &[
+                        ("summary", t(summary_key).to_string()),
+                        ("at", report.checked_at_utc.to_string()),
+                    ],
+                )
+            },
+        )
+    };
+
+    let recent_samples = app_context
+        .monitor
+        .metric_history
+        .lock()
+        .await
+        .latest_window(ALERT_SPARKLINE_MINUTES);
+    let sparklines = spark_lines(&recent_samples);
+    let sparkline_text = if sparklines.is_empty() {
+        format!("- {}", t("error.not_enough_samples"))
+    } else {
+        sparklines
+            .iter()
+            .map(|line| format!("- {line}"))
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
 
     let capabilities = app_context.capabilities.as_ref();
 
-    let body = format!(
-        "Auth Mode: Owner Only (DM)\nStorage: Hierarchical JSONL + Indexed\nMaintenance: {}\nRetention: {} days\nAnomaly dir: {}\n\nRuntime:\n- Monitor interval: {}s\n- Last monitor tick: {}\n- Mute state: {}\n\nReporting Store:\n- enabled: {}\n- path: {}\n- retention: {} days\n\nSecurity:\n- redact_sensitive_output: {}\n\nSimulation:\n- enabled: {}\n- profile: {}\n\nGraph Runtime:\n- enabled: {}\n- default window: {}m\n- max window: {}h\n- max points: {}\n\nCapabilities:\n- is_systemd: {}\n- has_sensors: {}\n- has_free: {}\n- has_top: {}\n- has_ip: {}\n- has_ss: {}\n- has_uptime: {}\n\nButton-first UX:\n- Use menu buttons below to run actions directly.\n- Slash commands are optional for advanced queries.\n\nAdvanced examples:\n/recent\n/recent 5\n/recent 6h\n/recent cpu>85",
-        if app_context.config.anomaly_db.enabled {
-            "Active (Hourly)"
-        } else {
-            "Disabled"
-        },
-        app_context.config.anomaly_db.retention_days,
-        app_context.config.anomaly_db.dir,
-        runtime_config.monitor_interval,
-        last_tick_text,
-        mute_state,
-        app_context.config.reporting_store.enabled,
-        app_context.config.reporting_store.path,
-        app_context.config.reporting_store.retention_days,
-        app_context.config.security.redact_sensitive_output,
-        app_context.config.simulation.enabled,
-        app_context.config.simulation.profile,
-        graph_runtime.enabled,
-        graph_runtime.default_window_minutes,
-        graph_runtime.max_window_hours,
-        graph_runtime.max_points,
-        capabilities.is_systemd,
-        capabilities.has_sensors,
-        capabilities.has_free,
-        capabilities.has_top,
-        capabilities.has_ip,
-        capabilities.has_ss,
-        capabilities.has_uptime,
+    let config = &app_context.config;
+    let maintenance = if config.anomaly_db.enabled {
+        t("status.maintenance.active")
+    } else {
+        t("status.maintenance.disabled")
+    };
+
+    let body = t_with(
+        "status.body",
+        &[
+            ("maintenance", maintenance.to_string()),
+            (
+                "retention_days",
+                config.anomaly_db.retention_days.to_string(),
+            ),
+            ("anomaly_dir", config.anomaly_db.dir.to_string()),
+            ("integrity", integrity_text),
+            ("interval", runtime_config.monitor_interval.to_string()),
+            ("last_tick", last_tick_text),
+            ("mute_state", mute_state),
+            ("spark_minutes", ALERT_SPARKLINE_MINUTES.to_string()),
+            ("sparklines", sparkline_text),
+            ("schedule", schedule_text),
+            ("store_enabled", config.reporting_store.enabled.to_string()),
+            ("store_path", config.reporting_store.path.to_string()),
+            (
+                "store_retention_days",
+                config.reporting_store.retention_days.to_string(),
+            ),
+            (
+                "redact",
+                config.security.redact_sensitive_output.to_string(),
+            ),
+            ("simulation_enabled", config.simulation.enabled.to_string()),
+            ("simulation_profile", config.simulation.profile.to_string()),
+            ("graph_enabled", graph_runtime.enabled.to_string()),
+            (
+                "graph_default_window",
+                graph_runtime.default_window_minutes.to_string(),
+            ),
+            (
+                "graph_max_window",
+                graph_runtime.max_window_hours.to_string(),
+            ),
+            ("graph_max_points", graph_runtime.max_points.to_string()),
+            ("is_systemd", capabilities.is_systemd.to_string()),
+            ("has_sensors", capabilities.has_sensors.to_string()),
+            ("has_free", capabilities.has_free.to_string()),
+            ("has_top", capabilities.has_top.to_string()),
+            ("has_ip", capabilities.has_ip.to_string()),
+            ("has_ss", capabilities.has_ss.to_string()),
+            ("has_uptime", capabilities.has_uptime.to_string()),
+        ],
     );
 
-    let status_html = as_html_card("Bot Status", &escape_html_text(&body));
+    let status_html = as_html_card(t("status.title"), &escape_html_text(&body));
 
     bot.send_message(msg.chat.id, status_html)
         .reply_markup(main_menu_keyboard(&app_context.capabilities))
@@ -0,0 +1,234 @@
+use std::path::Path;
+use std::time::Duration;
+
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use chrono_tz::Tz;
+use teloxide::{prelude::*, types::ParseMode};
+
+use crate::anomaly_db::anomaly_db_stats;
+use crate::app_context::AppContext;
+use crate::config::Config;
+use crate::fs_usage::dir_size_bytes;
+use crate::i18n::{t, t_with};
+use crate::reporting_store::TreeStats;
+use crate::time_format::format_local_time;
+
+use super::super::command_def::MyCommands;
+use super::super::helpers::{acquire_command_slot, as_html_block, timeout_for};
+use super::menu::main_menu_keyboard;
+
+/// Projections never extrapolate from less than this much history.
+const MIN_PROJECTION_SPAN_SECS: i64 = 3600;
+
+pub(crate) async fn handle_storage(
+    bot: &Bot,
+    msg: &Message,
+    app_context: &AppContext,
+) -> ResponseResult<()> {
+    let Some(_permit) =
+        acquire_command_slot(&app_context.bot_runtime.command_slots, msg, bot).await?
+    else {
+        return Ok(());
+    };
+
+    let runtime_config = app_context.runtime_config.read().await.clone();
+    let timeout = timeout_for(&MyCommands::Storage, runtime_config.command_timeout_secs);
+    let config = app_context.config.clone();
+    let store = app_context.reporting_store.clone();
+    let incident_store = app_context.incident_store.clone();
+    let task = tokio::task::spawn_blocking(move || {
+        let tz = config.tz();
+        let mut trees = store.tree_stats();
+        trees.extend(incident_store.tree_stats());
+        let mut sections = vec![reporting_section(&config, &trees, tz)];
+        sections.push(anomaly_section(&config));
+        sections.join("\n\n")
+    });
+
+    let body = match tokio::time::timeout(Duration::from_secs(timeout), task).await {
+        Ok(Ok(body)) => body,
+        Ok(Err(error)) => t_with("storage.failed", &[("error", error.to_string())]),
+        Err(_) => t_with(
+            "storage.failed",
+            &[("error", format!("timed out after {timeout}s"))],
+        ),
+    };
+
+    bot.send_message(msg.chat.id, as_html_block(t("storage.title"), &body))
+        .reply_markup(main_menu_keyboard(&app_context.capabilities))
+        .parse_mode(ParseMode::Html)
+        .await?;
+
+    Ok(())
+}
+
+fn reporting_section(config: &Config, trees: &[TreeStats], tz: Tz) -> String {
+    if !config.reporting_store.enabled {
+        return t("storage.reporting.disabled").to_string();
+    }
+
+    let size = dir_size_bytes(Path::new(&config.reporting_store.path));
+    let samples = trees.iter().find(|tree| tree.name == "samples");
+    let samples_span = samples
+        .and_then(|tree| Some(tree.newest? - tree.oldest?))
+        .unwrap_or_else(ChronoDuration::zero);
+    // Only raw samples grow towards `retention_days`; rollups and incidents
+    // follow their own retention and are kept at their current size.
+    let raw_bytes = raw_share_bytes(size, samples.map_or(0, |tree| tree.data_bytes), trees);
+    let tree_lines = trees
+        .iter()
+        .map(|tree| {
+            t_with(
+                "storage.tree",
+                &[
+                    ("name", tree.name.to_string()),
+                    ("entries", tree.entries.to_string()),
+                    ("oldest", format_optional_time(tree.oldest, tz)),
+                    ("newest", format_optional_time(tree.newest, tz)),
+                ],
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    t_with(
+        "storage.reporting",
+        &[
+            ("path", config.reporting_store.path.clone()),
+            ("size", format_bytes(size)),
+            ("trees", tree_lines),
+            (
+                "retention_days",
+                config.reporting_store.retention_days.to_string(),
+            ),
+            (
+                "projected",
+                format_bytes(
+                    size - raw_bytes
+                        + projected_bytes(
+                            raw_bytes,
+                            samples_span,
+                            config.reporting_store.retention_days,
+                        ),
+                ),
+            ),
+        ],
+    )
+}
+
+fn anomaly_section(config: &Config) -> String {
+    if !config.anomaly_db.enabled {
+        return t("storage.anomaly.disabled").to_string();
+    }
+
+    let stats = anomaly_db_stats(config);
+    let total = stats.file_bytes + stats.store_bytes;
+    let span = match (stats.oldest_day, stats.newest_day) {
+        (Some(oldest), Some(newest)) => newest - oldest + ChronoDuration::days(1),
+        _ => ChronoDuration::zero(),
+    };
+    let day = |day: Option<chrono::NaiveDate>| {
+        day.map_or_else(|| t("storage.none").to_string(), |day| day.to_string())
+    };
+
+    t_with(
+        "storage.anomaly",
+        &[
+            ("dir", config.anomaly_db.dir.clone()),
+            ("event_files", stats.event_files.to_string()),
+            ("rotated", stats.rotated_files.to_string()),
+            ("index_files", stats.index_files.to_string()),
+            ("file_size", format_bytes(stats.file_bytes)),
+            ("store_size", format_bytes(stats.store_bytes)),
+            ("oldest", day(stats.oldest_day)),
+            ("newest", day(stats.newest_day)),
+            (
+                "retention_days",
+                config.anomaly_db.retention_days.to_string(),
+            ),
+            (
+                "projected",
+                format_bytes(projected_bytes(
+                    total,
+                    span,
+                    config.anomaly_db.retention_days,
+                )),
+            ),
+        ],
+    )
+}
+
+fn format_optional_time(time: Option<DateTime<Utc>>, tz: Tz) -> String {
+    time.map_or_else(
+        || t("storage.none").to_string(),
+        |time| format_local_time(time, tz),
+    )
+}
+
+/// Size once `retention_days` of history has accumulated at the rate seen over
+/// `span`. Stores already holding a full retention window keep their size.
+fn projected_bytes(current: u64, span: ChronoDuration, retention_days: u16) -> u64 {
+    let retention_secs = i64::from(retention_days) * 86_400;
+    let span_secs = span.num_seconds().max(MIN_PROJECTION_SPAN_SECS);
+    if current == 0 || span_secs >= retention_secs {
+        return current;
+    }
+    (current as f64 * retention_secs as f64 / span_secs as f64).round() as u64
+}
+
+/// Part of the on-disk `size` attributed to `raw_data_bytes`. sled does not
+/// report per-tree sizes, so the split follows each tree's key and value bytes.
+fn raw_share_bytes(size: u64, raw_data_bytes: u64, trees: &[TreeStats]) -> u64 {
+    let total_bytes = trees.iter().map(|tree| tree.data_bytes).sum::<u64>();
+    if total_bytes == 0 {
+        return 0;
+    }
+    (size as f64 * raw_data_bytes as f64 / total_bytes as f64).round() as u64
+}
+
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration as ChronoDuration;
+
+    use super::{format_bytes, projected_bytes, raw_share_bytes};
+    use crate::reporting_store::TreeStats;
+
+    #[test]
+    fn projects_partial_history_to_the_retention_window() {
+        assert_eq!(projected_bytes(1000, ChronoDuration::days(1), 7), 7000);
+        assert_eq!(projected_bytes(1000, ChronoDuration::days(10), 7), 1000);
+        // Very short histories are treated as one hour.
+        assert_eq!(projected_bytes(10, ChronoDuration::minutes(5), 1), 240);
+        assert_eq!(projected_bytes(0, ChronoDuration::zero(), 7), 0);
+
+        // Fewer but larger rollup entries still take their byte share.
+        let tree = |name, entries, data_bytes| TreeStats {
+            name,
+            entries,
+            data_bytes,
+            oldest: None,
+            newest: None,
+        };
+        let trees = [tree("samples", 300, 1000), tree("rollups_5m", 100, 3000)];
+        assert_eq!(raw_share_bytes(4000, 1000, &trees), 1000);
+        assert_eq!(raw_share_bytes(4000, 0, &[]), 0);
+
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(5 * 1024 * 1024), "5.0 MiB");
+    }
+}
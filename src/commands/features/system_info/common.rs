@@ -1,8 +1,12 @@
 use super::super::super::helpers::as_html_block;
+use crate::i18n::t_with;
 
 pub(super) fn unsupported_feature_message(feature: &str, command: &str) -> String {
     as_html_block(
         feature,
-        &format!("This feature is not supported on this system. Missing dependency: {command}"),
+        &t_with(
+            "error.unsupported_feature",
+            &[("command", command.to_string())],
+        ),
     )
 }
@@ -1,6 +1,7 @@
 use teloxide::{prelude::*, types::ParseMode};
 
 use crate::app_context::AppContext;
+use crate::i18n::{t, t_with};
 use crate::system::run_cmd;
 
 use super::super::super::{
@@ -41,10 +42,13 @@ fn summarize_interfaces(stdout: &str) -> String {
         .collect::<Vec<_>>();
 
     if interfaces.is_empty() {
-        return "Interfaces: no non-loopback interface detected".to_string();
+        return t("system.network.no_interfaces").to_string();
     }
 
-    format!("Interfaces: {}", interfaces.join(", "))
+    t_with(
+        "system.network.interfaces",
+        &[("interfaces", interfaces.join(", "))],
+    )
 }
 
 fn compact_lines(text: &str, max_lines: usize) -> String {
@@ -60,9 +64,12 @@ pub(crate) async fn handle_cpu(
     let runtime_config = config.runtime_config.read().await.clone();
 
     if !config.capabilities.has_top {
-        bot.send_message(msg.chat.id, unsupported_feature_message("CPU Usage", "top"))
-            .parse_mode(ParseMode::Html)
-            .await?;
+        bot.send_message(
+            msg.chat.id,
+            unsupported_feature_message(t("system.cpu.title"), "top"),
+        )
+        .parse_mode(ParseMode::Html)
+        .await?;
         return Ok(());
     }
 
@@ -85,11 +92,11 @@ pub(crate) async fn handle_cpu(
                 .collect::<Vec<_>>()
                 .join("\n");
             let body = if short.is_empty() {
-                "No CPU output."
+                t("system.cpu.empty")
             } else {
                 &short
             };
-            as_html_block("CPU Usage", body)
+            as_html_block(t("system.cpu.title"), body)
         }
         Err(error) => command_error_html(&error),
     };
@@ -113,7 +120,7 @@ pub(crate) async fn handle_network(
     if !config.capabilities.has_ip {
         bot.send_message(
             msg.chat.id,
-            unsupported_feature_message("Network Statistics", "ip"),
+            unsupported_feature_message(t("system.network.title"), "ip"),
         )
         .parse_mode(ParseMode::Html)
         .await?;
@@ -139,7 +146,7 @@ pub(crate) async fn handle_network(
                 &format!("{}\n\nDetails:\n{}", summary, compact),
                 config.config.security.redact_sensitive_output,
             );
-            send_html_or_file(bot, msg.chat.id, "Network Statistics", &body).await?;
+            send_html_or_file(bot, msg.chat.id, t("system.network.title"), &body).await?;
             send_navigation_hint(bot, msg.chat.id, &config.capabilities).await?;
         }
         Err(error) => {
@@ -163,7 +170,7 @@ pub(crate) async fn handle_uptime(
     if !config.capabilities.has_uptime {
         bot.send_message(
             msg.chat.id,
-            unsupported_feature_message("System Uptime", "uptime"),
+            unsupported_feature_message(t("system.uptime.title"), "uptime"),
         )
         .parse_mode(ParseMode::Html)
         .await?;
@@ -183,7 +190,7 @@ pub(crate) async fn handle_uptime(
     {
         Ok(output) => {
             let body = command_body(&output);
-            send_html_or_file(bot, msg.chat.id, "System Uptime", &body).await?;
+            send_html_or_file(bot, msg.chat.id, t("system.uptime.title"), &body).await?;
             send_navigation_hint(bot, msg.chat.id, &config.capabilities).await?;
         }
         Err(error) => {
@@ -207,7 +214,7 @@ pub(crate) async fn handle_temp(
     if !config.capabilities.has_sensors {
         bot.send_message(
             msg.chat.id,
-            unsupported_feature_message("Temperature Sensors", "sensors"),
+            unsupported_feature_message(t("system.temp.title"), "sensors"),
         )
         .parse_mode(ParseMode::Html)
         .await?;
@@ -227,7 +234,7 @@ pub(crate) async fn handle_temp(
     {
         Ok(output) => {
             let body = command_body(&output);
-            send_html_or_file(bot, msg.chat.id, "Temperature Sensors", &body).await?;
+            send_html_or_file(bot, msg.chat.id, t("system.temp.title"), &body).await?;
             send_navigation_hint(bot, msg.chat.id, &config.capabilities).await?;
         }
         Err(error) => {
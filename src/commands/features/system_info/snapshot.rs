@@ -1,6 +1,7 @@
 use teloxide::{prelude::*, types::ParseMode};
 
 use crate::app_context::AppContext;
+use crate::i18n::{t, t_with};
 use crate::system::run_cmd;
 
 use super::super::super::{
@@ -22,9 +23,13 @@ fn summarize_memory(stdout: &str) -> Option<String> {
         return None;
     }
 
-    Some(format!(
-        "Memory: used {} / total {} (free {})",
-        cols[2], cols[1], cols[3]
+    Some(t_with(
+        "system.snapshot.memory",
+        &[
+            ("used", cols[2].to_string()),
+            ("total", cols[1].to_string()),
+            ("free", cols[3].to_string()),
+        ],
     ))
 }
 
@@ -37,9 +42,14 @@ fn summarize_root_disk(stdout: &str) -> Option<String> {
         return None;
     }
 
-    Some(format!(
-        "Disk (/): used {} / size {} ({} used, avail {})",
-        cols[2], cols[1], cols[4], cols[3]
+    Some(t_with(
+        "system.snapshot.disk",
+        &[
+            ("used", cols[2].to_string()),
+            ("size", cols[1].to_string()),
+            ("percent", cols[4].to_string()),
+            ("avail", cols[3].to_string()),
+        ],
     ))
 }
 
@@ -58,7 +68,7 @@ pub(crate) async fn handle_sys_status(
     if !config.capabilities.has_free {
         bot.send_message(
             msg.chat.id,
-            unsupported_feature_message("System Snapshot", "free"),
+            unsupported_feature_message(t("system.snapshot.title"), "free"),
         )
         .parse_mode(ParseMode::Html)
         .await?;
@@ -77,18 +87,20 @@ pub(crate) async fn handle_sys_status(
     match (ram, disk) {
         (Ok(ram_out), Ok(disk_out)) => {
             let memory_summary = summarize_memory(&ram_out.stdout)
-                .unwrap_or_else(|| "Memory summary unavailable".to_string());
+                .unwrap_or_else(|| t("system.snapshot.memory_unavailable").to_string());
             let disk_summary = summarize_root_disk(&disk_out.stdout)
-                .unwrap_or_else(|| "Disk summary unavailable".to_string());
-
-            let body = format!(
-                "Summary:\n- {}\n- {}\n\nRAM (details):\n{}\n\nDisk (details):\n{}",
-                memory_summary,
-                disk_summary,
-                compact_lines(&command_body(&ram_out), 12),
-                compact_lines(&command_body(&disk_out), 12)
+                .unwrap_or_else(|| t("system.snapshot.disk_unavailable").to_string());
+
+            let body = t_with(
+                "system.snapshot.body",
+                &[
+                    ("memory", memory_summary),
+                    ("disk", disk_summary),
+                    ("memory_details", compact_lines(&command_body(&ram_out), 12)),
+                    ("disk_details", compact_lines(&command_body(&disk_out), 12)),
+                ],
             );
-            send_html_or_file(bot, msg.chat.id, "System Snapshot", &body).await?;
+            send_html_or_file(bot, msg.chat.id, t("system.snapshot.title"), &body).await?;
             send_navigation_hint(bot, msg.chat.id, &config.capabilities).await?;
         }
         (Err(error), _) | (_, Err(error)) => {
@@ -110,9 +122,12 @@ pub(crate) async fn handle_ports(
     let runtime_config = config.runtime_config.read().await.clone();
 
     if !config.capabilities.has_ss {
-        bot.send_message(msg.chat.id, unsupported_feature_message("Open Ports", "ss"))
-            .parse_mode(ParseMode::Html)
-            .await?;
+        bot.send_message(
+            msg.chat.id,
+            unsupported_feature_message(t("system.ports.title"), "ss"),
+        )
+        .parse_mode(ParseMode::Html)
+        .await?;
         return Ok(());
     }
 
@@ -133,7 +148,7 @@ pub(crate) async fn handle_ports(
                 &raw_body,
                 config.config.security.redact_sensitive_output,
             );
-            send_html_or_file(bot, msg.chat.id, "Open Ports", &body).await?;
+            send_html_or_file(bot, msg.chat.id, t("system.ports.title"), &body).await?;
             send_navigation_hint(bot, msg.chat.id, &config.capabilities).await?;
         }
         Err(error) => {
@@ -157,7 +172,7 @@ pub(crate) async fn handle_services(
     if !config.capabilities.is_systemd {
         bot.send_message(
             msg.chat.id,
-            unsupported_feature_message("Active Services", "systemctl + systemd"),
+            unsupported_feature_message(t("system.services.title"), "systemctl + systemd"),
         )
         .parse_mode(ParseMode::Html)
         .await?;
@@ -191,13 +206,13 @@ pub(crate) async fn handle_services(
                 .collect::<Vec<_>>()
                 .join("\n");
             let body = if short.is_empty() {
-                "No service output."
+                t("system.services.empty")
             } else {
                 &short
             };
             let redacted =
                 maybe_redact_sensitive_output(body, config.config.security.redact_sensitive_output);
-            send_html_or_file(bot, msg.chat.id, "Active Services", &redacted).await?;
+            send_html_or_file(bot, msg.chat.id, t("system.services.title"), &redacted).await?;
             send_navigation_hint(bot, msg.chat.id, &config.capabilities).await?;
         }
         Err(error) => {
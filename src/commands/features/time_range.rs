@@ -0,0 +1,125 @@
+use chrono::{DateTime, Days, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc};
+use chrono_tz::Tz;
+
+/// Longest absolute period a single query may cover.
+const MAX_RANGE_DAYS: i64 = 366;
+
+/// Absolute `[from, to)` period typed by the user in the configured timezone.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct TimeRange {
+    pub(super) from: DateTime<Utc>,
+    pub(super) to: DateTime<Utc>,
+    tz: Tz,
+}
+
+impl TimeRange {
+    pub(super) fn new(from: DateTime<Utc>, to: DateTime<Utc>, tz: Tz) -> Option<Self> {
+        if to <= from || (to - from).num_days() > MAX_RANGE_DAYS {
+            return None;
+        }
+
+        Some(Self { from, to, tz })
+    }
+
+    /// Parses `2026-10-01..2026-10-03` or `2026-10-01T06:00..2026-10-01T18:00`.
+    /// A date-only end includes that whole day.
+    pub(super) fn parse(input: &str, tz: Tz) -> Option<Self> {
+        let (from, to) = input.split_once("..")?;
+        Self::new(
+            parse_time_point(from, tz, false)?,
+            parse_time_point(to, tz, true)?,
+            tz,
+        )
+    }
+
+    pub(super) fn minutes(self) -> i64 {
+        (self.to - self.from).num_minutes()
+    }
+
+    /// End of the period in the timezone it was typed in.
+    pub(super) fn local_end(self) -> DateTime<Tz> {
+        self.to.with_timezone(&self.tz)
+    }
+
+    /// Human-readable period, e.g. `2026-10-01..2026-10-03`.
+    pub(super) fn label(self) -> String {
+        let from = self.from.with_timezone(&self.tz);
+        let to = self.to.with_timezone(&self.tz);
+        if from.time() == NaiveTime::MIN && to.time() == NaiveTime::MIN {
+            let last_day = to.date_naive() - Days::new(1);
+            format!(
+                "{}..{}",
+                from.format("%Y-%m-%d"),
+                last_day.format("%Y-%m-%d")
+            )
+        } else {
+            format!(
+                "{}..{}",
+                from.format("%Y-%m-%d %H:%M"),
+                to.format("%Y-%m-%d %H:%M")
+            )
+        }
+    }
+
+    /// File-name friendly variant of [`Self::label`].
+    pub(super) fn file_suffix(self) -> String {
+        self.label().replace("..", "_").replace([' ', ':'], "")
+    }
+}
+
+/// Parses `YYYY-MM-DD` or `YYYY-MM-DDTHH:MM` in `tz`. With `end_of_day`, a
+/// date-only value resolves to the start of the following day.
+pub(super) fn parse_time_point(input: &str, tz: Tz, end_of_day: bool) -> Option<DateTime<Utc>> {
+    let input = input.trim();
+    let naive = if let Ok(date) = NaiveDate::parse_from_str(input, "%Y-%m-%d") {
+        let date = if end_of_day {
+            date.checked_add_days(Days::new(1))?
+        } else {
+            date
+        };
+        date.and_time(NaiveTime::MIN)
+    } else {
+        NaiveDateTime::parse_from_str(input, "%Y-%m-%dT%H:%M").ok()?
+    };
+
+    tz.from_local_datetime(&naive)
+        .earliest()
+        .map(|value| value.with_timezone(&Utc))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+    use chrono_tz::Tz;
+
+    use super::TimeRange;
+
+    #[test]
+    fn parses_inclusive_date_ranges_in_local_time() {
+        let tz: Tz = "Europe/Istanbul".parse().unwrap();
+        let range = TimeRange::parse("2026-10-01..2026-10-03", tz).expect("range parses");
+        assert_eq!(
+            range.from,
+            Utc.with_ymd_and_hms(2026, 9, 30, 21, 0, 0).unwrap()
+        );
+        assert_eq!(
+            range.to,
+            Utc.with_ymd_and_hms(2026, 10, 3, 21, 0, 0).unwrap()
+        );
+        assert_eq!(range.label(), "2026-10-01..2026-10-03");
+        assert_eq!(range.file_suffix(), "2026-10-01_2026-10-03");
+    }
+
+    #[test]
+    fn parses_datetime_ranges_and_rejects_invalid_ones() {
+        let range =
+            TimeRange::parse("2026-10-01T06:00..2026-10-01T18:30", Tz::UTC).expect("range parses");
+        assert_eq!(range.minutes(), 12 * 60 + 30);
+        assert_eq!(range.label(), "2026-10-01 06:00..2026-10-01 18:30");
+
+        assert!(TimeRange::parse("2026-10-03..2026-10-01", Tz::UTC).is_none());
+        assert!(TimeRange::parse("2026-10-01", Tz::UTC).is_none());
+        assert!(TimeRange::parse("2024-01-01..2026-01-01", Tz::UTC).is_none());
+        assert!(TimeRange::parse("yesterday..today", Tz::UTC).is_none());
+    }
+}
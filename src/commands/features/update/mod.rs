@@ -4,6 +4,7 @@ use teloxide::{prelude::*, types::ParseMode};
 
 use crate::app_context::AppContext;
 use crate::capabilities::Capabilities;
+use crate::i18n::{t, t_with};
 
 use super::super::{
     command_def::MyCommands,
@@ -55,7 +56,7 @@ pub(crate) async fn handle_update(
     if mode != "apply" {
         bot.send_message(
             msg.chat.id,
-            as_html_block("Update Usage", "Usage:\n/update check\n/update apply"),
+            as_html_block(t("update.usage.title"), t("update.usage")),
         )
         .reply_markup(main_menu_keyboard(&app_context.capabilities))
         .parse_mode(ParseMode::Html)
@@ -67,8 +68,11 @@ pub(crate) async fn handle_update(
         bot.send_message(
             msg.chat.id,
             as_html_block(
-                "Update",
-                &format!("No newer release found. Current v{CURRENT_VERSION} is up to date."),
+                t("update.title"),
+                &t_with(
+                    "update.up_to_date",
+                    &[("version", CURRENT_VERSION.to_string())],
+                ),
             ),
         )
         .reply_markup(main_menu_keyboard(&app_context.capabilities))
@@ -80,10 +84,7 @@ pub(crate) async fn handle_update(
     if !app_context.capabilities.is_systemd {
         bot.send_message(
             msg.chat.id,
-            as_html_block(
-                "Update",
-                "Controlled restart is unavailable on this host (systemd not detected).",
-            ),
+            as_html_block(t("update.title"), t("update.no_systemd")),
         )
         .reply_markup(main_menu_keyboard(&app_context.capabilities))
         .parse_mode(ParseMode::Html)
@@ -99,8 +100,8 @@ pub(crate) async fn handle_update(
         bot.send_message(
             msg.chat.id,
             as_html_block(
-                "Update",
-                &format!("Update apply is blocked by pre-checks.\n{detail}"),
+                t("update.title"),
+                &t_with("update.blocked", &[("detail", detail)]),
             ),
         )
         .reply_markup(main_menu_keyboard(&app_context.capabilities))
@@ -112,8 +113,8 @@ pub(crate) async fn handle_update(
     bot.send_message(
         msg.chat.id,
         as_html_block(
-            "Update",
-            &format!("Starting update to v{latest_version}. Service may restart during apply."),
+            t("update.title"),
+            &t_with("update.starting", &[("version", latest_version.clone())]),
         ),
     )
     .reply_markup(main_menu_keyboard(&app_context.capabilities))
@@ -128,7 +129,10 @@ pub(crate) async fn handle_update(
             // After restart, this process will be terminated by SIGTERM.
             bot.send_message(
                 msg.chat.id,
-                as_html_block("Update", &format!("{message}\nService is restarting...")),
+                as_html_block(
+                    t("update.title"),
+                    &t_with("update.restarting", &[("message", message)]),
+                ),
             )
             .reply_markup(main_menu_keyboard(&app_context.capabilities))
             .parse_mode(ParseMode::Html)
@@ -139,10 +143,8 @@ pub(crate) async fn handle_update(
                 bot.send_message(
                     msg.chat.id,
                     as_html_block(
-                        "Update",
-                        &format!(
-                            "Binary installed but service restart failed: {error}\nManual restart required: systemctl restart kars-bot"
-                        ),
+                        t("update.title"),
+                        &t_with("update.restart_failed", &[("error", error)]),
                     ),
                 )
                 .reply_markup(main_menu_keyboard(&app_context.capabilities))
@@ -153,7 +155,10 @@ pub(crate) async fn handle_update(
         Err(error) => {
             bot.send_message(
                 msg.chat.id,
-                as_html_block("Update", &format!("Update apply failed: {error}")),
+                as_html_block(
+                    t("update.title"),
+                    &t_with("update.apply_failed", &[("error", error)]),
+                ),
             )
             .reply_markup(main_menu_keyboard(&app_context.capabilities))
             .parse_mode(ParseMode::Html)
@@ -173,22 +178,26 @@ async fn send_update_check(
     capabilities: &Capabilities,
 ) -> ResponseResult<()> {
     let readiness_line = match readiness {
-        Ok((true, details)) => format!("Apply readiness: ready\n{details}"),
-        Ok((false, details)) => format!("Apply readiness: blocked\n{details}"),
-        Err(error) => format!("Apply readiness: check failed ({error})"),
+        Ok((true, details)) => t_with("update.readiness.ready", &[("details", details)]),
+        Ok((false, details)) => t_with("update.readiness.blocked", &[("details", details)]),
+        Err(error) => t_with("update.readiness.failed", &[("error", error)]),
     };
 
-    let body = if compare == Ordering::Less {
-        format!(
-            "Current: v{CURRENT_VERSION}\nLatest: v{latest_version}\n\nUpdate available.\nRun /update apply to trigger controlled restart.\n\n{readiness_line}"
-        )
+    let key = if compare == Ordering::Less {
+        "update.check.available"
     } else {
-        format!(
-            "Current: v{CURRENT_VERSION}\nLatest: v{latest_version}\n\nYou are up to date.\n\n{readiness_line}"
-        )
+        "update.check.current"
     };
+    let body = t_with(
+        key,
+        &[
+            ("current", CURRENT_VERSION.to_string()),
+            ("latest", latest_version.to_string()),
+            ("readiness", readiness_line),
+        ],
+    );
 
-    bot.send_message(msg.chat.id, as_html_block("Update Check", &body))
+    bot.send_message(msg.chat.id, as_html_block(t("update.check.title"), &body))
         .reply_markup(main_menu_keyboard(capabilities))
         .parse_mode(ParseMode::Html)
         .await?;
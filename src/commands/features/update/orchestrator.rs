@@ -8,6 +8,7 @@ use tokio::{
 
 use super::super::super::helpers::as_html_block;
 use super::self_update;
+use crate::i18n::{t, t_with};
 
 const UPDATE_LOCK_TIMEOUT_SECS: u64 = 2;
 
@@ -19,7 +20,7 @@ pub(super) async fn run_update_check(
 ) -> Result<(bool, String), String> {
     let info = self_update::fetch_latest_release().await?;
     if !self_update::update_available(current_version, &info.version) {
-        return Ok((true, "No update available for current version.".to_string()));
+        return Ok((true, t("update.no_update").to_string()));
     }
 
     Ok(self_update::summarize_release_readiness(&info))
@@ -36,8 +37,8 @@ pub(super) async fn extract_readiness(
             bot.send_message(
                 msg.chat.id,
                 as_html_block(
-                    "Update",
-                    &format!("Update check failed before apply: {error}"),
+                    t("update.title"),
+                    &t_with("update.check_failed", &[("error", error)]),
                 ),
             )
             .parse_mode(ParseMode::Html)
@@ -65,9 +66,7 @@ pub(super) async fn run_update_apply(
 
     drop(permit);
 
-    output.map(|maybe_message| {
-        maybe_message.unwrap_or_else(|| "No update was applied (already up to date).".to_string())
-    })
+    output.map(|maybe_message| maybe_message.unwrap_or_else(|| t("update.not_applied").to_string()))
 }
 
 /// Phase 2: trigger systemd restart. Must be called AFTER the final Telegram
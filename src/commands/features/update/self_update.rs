@@ -5,6 +5,8 @@ use std::path::{Path, PathBuf};
 use serde::Deserialize;
 use sha2::{Digest, Sha256};
 
+use crate::i18n::t_with;
+
 const GITHUB_API_LATEST_RELEASE: &str =
     "https://api.github.com/repos/recregt/kars_bot/releases/latest";
 const BINARY_NAME: &str = "kars_bot";
@@ -190,9 +192,13 @@ pub(super) async fn prepare_update(current_version: &str) -> Result<Option<Strin
     let target_path = install_dir.join(BINARY_NAME);
     atomic_install(&extracted_binary, &target_path)?;
 
-    Ok(Some(format!(
-        "Updated: v{current_version} -> v{} ({})",
-        info.version, info.tag
+    Ok(Some(t_with(
+        "update.applied",
+        &[
+            ("current", current_version.to_string()),
+            ("latest", info.version),
+            ("tag", info.tag),
+        ],
     )))
 }
 
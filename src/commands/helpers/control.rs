@@ -9,9 +9,12 @@ use tokio::sync::{OwnedSemaphorePermit, Semaphore};
 
 use super::formatting::as_html_block;
 use crate::commands::command_def::MyCommands;
+use crate::i18n::t;
 
 const FAST_TIMEOUT_SECS: u64 = 5;
 const TELEGRAM_FILE_FALLBACK_THRESHOLD: usize = 3900;
+/// Bot API upload limit for documents.
+pub(crate) const TELEGRAM_DOCUMENT_LIMIT_BYTES: usize = 50 * 1024 * 1024;
 
 pub(crate) fn timeout_for(cmd: &MyCommands, command_timeout_secs: u64) -> u64 {
     match cmd {
@@ -25,13 +28,20 @@ pub(crate) fn timeout_for(cmd: &MyCommands, command_timeout_secs: u64) -> u64 {
         | MyCommands::Uptime
         | MyCommands::Health
         | MyCommands::Alerts
+        | MyCommands::Incidents(_)
+        | MyCommands::Incident(_)
         | MyCommands::Graph(_)
+        | MyCommands::Heatmap(_)
+        | MyCommands::Spark(_)
         | MyCommands::Export(_)
         | MyCommands::Recent(_)
         | MyCommands::Mute(_)
         | MyCommands::Unmute
         | MyCommands::Help => FAST_TIMEOUT_SECS,
-        MyCommands::Update(_) => command_timeout_secs,
+        MyCommands::Update(_)
+        | MyCommands::Backup
+        | MyCommands::Dbcheck(_)
+        | MyCommands::Storage => command_timeout_secs,
         MyCommands::Services | MyCommands::Temp => command_timeout_secs,
     }
 }
@@ -69,8 +79,8 @@ pub(crate) async fn acquire_command_slot(
             bot.send_message(
                 msg.chat.id,
                 as_html_block(
-                    "Command queue error",
-                    "Could not acquire command slot. Please try again.",
+                    t("error.command_queue.title"),
+                    t("error.command_queue.body"),
                 ),
             )
             .parse_mode(ParseMode::Html)
@@ -94,15 +104,9 @@ pub(crate) async fn send_html_or_file(
         return Ok(());
     }
 
-    bot.send_message(
-        chat_id,
-        as_html_block(
-            title,
-            "Output is too long for a Telegram message. Sent as file attachment.",
-        ),
-    )
-    .parse_mode(ParseMode::Html)
-    .await?;
+    bot.send_message(chat_id, as_html_block(title, t("error.output_too_long")))
+        .parse_mode(ParseMode::Html)
+        .await?;
 
     let file_name = format!(
         "{}-output.txt",
@@ -55,6 +55,15 @@ pub(crate) fn as_html_card(title: &str, body_html: &str) -> String {
     format!("<b>{escaped_title}</b>\n{body_html}")
 }
 
+/// `90` -> `90m`, `120` -> `2h`.
+pub(crate) fn format_window_suffix(window_minutes: i64) -> String {
+    if window_minutes % 60 == 0 {
+        format!("{}h", window_minutes / 60)
+    } else {
+        format!("{window_minutes}m")
+    }
+}
+
 pub(crate) fn escape_html_text(text: &str) -> String {
     html_escape::encode_text(text).into_owned()
 }
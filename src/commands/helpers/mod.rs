@@ -5,9 +5,11 @@ mod redaction;
 
 pub(super) use auth::is_authorized;
 pub(super) use control::{
-    acquire_command_slot, parse_mute_duration, send_html_or_file, timeout_for,
+    TELEGRAM_DOCUMENT_LIMIT_BYTES, acquire_command_slot, parse_mute_duration, send_html_or_file,
+    timeout_for,
 };
 pub(super) use formatting::{
     as_html_block, as_html_card, command_body, command_error_html, escape_html_text,
+    format_window_suffix,
 };
 pub(super) use redaction::maybe_redact_sensitive_output;
@@ -4,6 +4,6 @@ mod handler;
 mod helpers;
 mod router;
 pub use command_def::MyCommands;
-pub(crate) use features::graph::build_weekly_cpu_report;
+pub(crate) use features::graph::{build_monthly_capacity_report, build_weekly_report};
 pub use handler::answer;
 pub use handler::answer_callback;
@@ -5,11 +5,15 @@ use crate::app_context::AppContext;
 use super::command_def::MyCommands;
 use super::features::{
     alerts::{handle_alerts, handle_mute, handle_unmute},
+    backup::handle_backup,
+    dbcheck::handle_dbcheck,
     export::handle_export,
-    graph::handle_graph,
+    graph::{handle_graph, handle_heatmap, handle_spark},
     health::{handle_health, handle_help},
+    incidents::{handle_incident, handle_incidents},
     recent::handle_recent_anomalies,
     status::handle_status_overview,
+    storage::handle_storage,
     system_info::{
         handle_cpu, handle_network, handle_ports, handle_services, handle_sys_status, handle_temp,
         handle_uptime,
@@ -37,7 +41,11 @@ pub(super) async fn route_command(
         MyCommands::Temp => handle_temp(&bot, &msg, app_context, &cmd).await?,
         MyCommands::Health => handle_health(&bot, &msg, app_context).await?,
         MyCommands::Alerts => handle_alerts(&bot, &msg, app_context).await?,
+        MyCommands::Incidents(args) => handle_incidents(&bot, &msg, app_context, &args).await?,
+        MyCommands::Incident(args) => handle_incident(&bot, &msg, app_context, &args).await?,
         MyCommands::Graph(query) => handle_graph(&bot, &msg, app_context, &query).await?,
+        MyCommands::Heatmap(query) => handle_heatmap(&bot, &msg, app_context, &query).await?,
+        MyCommands::Spark(query) => handle_spark(&bot, &msg, app_context, &query).await?,
         MyCommands::Export(query) => handle_export(&bot, &msg, app_context, &query).await?,
         MyCommands::Recent(query) => {
             handle_recent_anomalies(&bot, &msg, app_context, normalize_recent_query(&query))
@@ -47,6 +55,9 @@ pub(super) async fn route_command(
             handle_mute(&bot, &msg, app_context, &duration_str).await?;
         }
         MyCommands::Unmute => handle_unmute(&bot, &msg, app_context).await?,
+        MyCommands::Backup => handle_backup(&bot, &msg, app_context).await?,
+        MyCommands::Storage => handle_storage(&bot, &msg, app_context).await?,
+        MyCommands::Dbcheck(args) => handle_dbcheck(&bot, &msg, app_context, &args).await?,
         MyCommands::Update(args) => handle_update(&bot, &msg, app_context, &args).await?,
     }
 
@@ -1,5 +1,7 @@
 use super::schema::{
-    Alerts, AnomalyDb, DailySummary, Graph, ReportingStoreConfig, Simulation, WeeklyReport,
+    Alerts, AnomalyCompression, AnomalyDb, AnomalyDbBackend, Backup, DailySummary, Graph,
+    GraphTheme, HourlyDigest, MonthlyReport, QuietHours, QuietHoursMode, ReportMetric,
+    ReportingStoreConfig, Scheduler, Simulation, WeeklyCharts, WeeklyReport,
 };
 
 pub(super) fn default_monitor_interval() -> u64 {
@@ -42,6 +44,18 @@ pub(super) fn default_graph_max_window_hours() -> u64 {
     24
 }
 
+pub(super) fn default_graph_theme() -> GraphTheme {
+    GraphTheme::Light
+}
+
+pub(super) fn default_graph_width() -> u32 {
+    1200
+}
+
+pub(super) fn default_graph_height() -> u32 {
+    480
+}
+
 pub(super) fn default_graph_max_points() -> u16 {
     1200
 }
@@ -62,6 +76,22 @@ pub(super) fn default_weekly_report_minute() -> u8 {
     0
 }
 
+pub(super) fn default_weekly_report_metrics() -> Vec<ReportMetric> {
+    vec![ReportMetric::Cpu]
+}
+
+pub(super) fn default_monthly_report_day() -> u8 {
+    1
+}
+
+pub(super) fn default_monthly_report_hour() -> u8 {
+    9
+}
+
+pub(super) fn default_monthly_report_minute() -> u8 {
+    0
+}
+
 pub(super) fn default_daily_summary_hour() -> u8 {
     9
 }
@@ -90,6 +120,18 @@ pub(super) fn default_anomaly_db_retention_days() -> u16 {
     7
 }
 
+pub(super) fn default_anomaly_db_backend() -> AnomalyDbBackend {
+    AnomalyDbBackend::Jsonl
+}
+
+pub(super) fn default_anomaly_db_compression() -> AnomalyCompression {
+    AnomalyCompression::Gzip
+}
+
+pub(super) fn default_anomaly_db_max_total_bytes() -> u64 {
+    256 * 1024 * 1024
+}
+
 pub(super) fn default_simulation_profile() -> String {
     "wave".to_string()
 }
@@ -102,6 +144,14 @@ pub(super) fn default_reporting_store_retention_days() -> u16 {
     30
 }
 
+pub(super) fn default_reporting_store_rollup_5m_retention_days() -> u16 {
+    90
+}
+
+pub(super) fn default_reporting_store_rollup_1h_retention_days() -> u16 {
+    730
+}
+
 pub(super) fn default_release_notifier_changelog_path() -> String {
     "CHANGELOG.md".to_string()
 }
@@ -114,6 +164,50 @@ pub(super) fn default_redact_sensitive_output() -> bool {
     false
 }
 
+pub(super) fn default_quiet_hours_start() -> String {
+    "23:00".to_string()
+}
+
+pub(super) fn default_quiet_hours_end() -> String {
+    "07:00".to_string()
+}
+
+pub(super) fn default_quiet_hours_state_path() -> String {
+    "data/quiet_hours/deferred.json".to_string()
+}
+
+pub(super) fn default_hourly_digest_cron() -> String {
+    "0 * * * *".to_string()
+}
+
+pub(super) fn default_scheduler_state_path() -> String {
+    "data/scheduler/state.json".to_string()
+}
+
+pub(super) fn default_scheduler_catch_up() -> bool {
+    true
+}
+
+pub(super) fn default_backup_dir() -> String {
+    "data/backups".to_string()
+}
+
+pub(super) fn default_backup_keep_last() -> usize {
+    5
+}
+
+pub(super) fn default_timezone() -> String {
+    "UTC".to_string()
+}
+
+pub(super) fn default_quiet_hours_mode() -> QuietHoursMode {
+    QuietHoursMode::Digest
+}
+
+pub(super) fn default_quiet_hours_critical_threshold() -> f32 {
+    95.0
+}
+
 impl Default for Alerts {
     fn default() -> Self {
         Self {
@@ -130,8 +224,9 @@ impl Default for DailySummary {
     fn default() -> Self {
         Self {
             enabled: default_daily_summary_enabled(),
-            hour_utc: default_daily_summary_hour(),
-            minute_utc: default_daily_summary_minute(),
+            hour: default_daily_summary_hour(),
+            minute: default_daily_summary_minute(),
+            cron: None,
         }
     }
 }
@@ -140,9 +235,52 @@ impl Default for WeeklyReport {
     fn default() -> Self {
         Self {
             enabled: default_weekly_report_enabled(),
-            weekday_utc: default_weekly_report_weekday(),
-            hour_utc: default_weekly_report_hour(),
-            minute_utc: default_weekly_report_minute(),
+            weekday: default_weekly_report_weekday(),
+            hour: default_weekly_report_hour(),
+            minute: default_weekly_report_minute(),
+            cron: None,
+            metrics: default_weekly_report_metrics(),
+            charts: WeeklyCharts::default(),
+            stacked_panels: false,
+        }
+    }
+}
+
+impl Default for MonthlyReport {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            day: default_monthly_report_day(),
+            hour: default_monthly_report_hour(),
+            minute: default_monthly_report_minute(),
+            cron: None,
+        }
+    }
+}
+
+impl Default for HourlyDigest {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            cron: default_hourly_digest_cron(),
+        }
+    }
+}
+
+impl Default for Backup {
+    fn default() -> Self {
+        Self {
+            dir: default_backup_dir(),
+            keep_last: default_backup_keep_last(),
+        }
+    }
+}
+
+impl Default for Scheduler {
+    fn default() -> Self {
+        Self {
+            state_path: default_scheduler_state_path(),
+            catch_up: default_scheduler_catch_up(),
         }
     }
 }
@@ -154,6 +292,9 @@ impl Default for Graph {
             default_window_minutes: default_graph_window_minutes(),
             max_window_hours: default_graph_max_window_hours(),
             max_points: default_graph_max_points(),
+            theme: default_graph_theme(),
+            width: default_graph_width(),
+            height: default_graph_height(),
         }
     }
 }
@@ -165,6 +306,9 @@ impl Default for AnomalyDb {
             dir: default_anomaly_db_dir(),
             max_file_size_bytes: default_anomaly_db_max_file_size_bytes(),
             retention_days: default_anomaly_db_retention_days(),
+            backend: default_anomaly_db_backend(),
+            compression: default_anomaly_db_compression(),
+            max_total_bytes: default_anomaly_db_max_total_bytes(),
         }
     }
 }
@@ -184,6 +328,22 @@ impl Default for ReportingStoreConfig {
             enabled: false,
             path: default_reporting_store_path(),
             retention_days: default_reporting_store_retention_days(),
+            rollup_5m_retention_days: default_reporting_store_rollup_5m_retention_days(),
+            rollup_1h_retention_days: default_reporting_store_rollup_1h_retention_days(),
+        }
+    }
+}
+
+impl Default for QuietHours {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            start: default_quiet_hours_start(),
+            end: default_quiet_hours_end(),
+            timezone: None,
+            mode: default_quiet_hours_mode(),
+            critical_threshold: default_quiet_hours_critical_threshold(),
+            state_path: default_quiet_hours_state_path(),
         }
     }
 }
@@ -9,10 +9,17 @@ pub fn load_config(path: impl AsRef<Path>) -> Result<Config, ConfigError> {
         path: path_str.clone(),
         source,
     })?;
-    let config: Config = toml::from_str(&raw).map_err(|source| ConfigError::Parse {
+    let mut config: Config = toml::from_str(&raw).map_err(|source| ConfigError::Parse {
         path: path_str,
         source,
     })?;
+    config
+        .templates
+        .load_dir()
+        .map_err(|source| ConfigError::Read {
+            path: config.templates.dir.clone().unwrap_or_default(),
+            source,
+        })?;
     config.validate()?;
     Ok(config)
 }
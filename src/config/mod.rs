@@ -6,6 +6,8 @@ mod validate;
 pub use io::load_config;
 #[allow(unused_imports)]
 pub use schema::{
-    Alerts, AnomalyDb, Config, DailySummary, Graph, ReleaseNotifierConfig, ReportingStoreConfig,
-    RuntimeConfig, Security, Simulation, WeeklyReport,
+    Alerts, AnomalyCompression, AnomalyDb, AnomalyDbBackend, Backup, Config, DailySummary, Graph,
+    GraphTheme, HourlyDigest, MonthlyReport, QuietHours, QuietHoursMode, ReleaseNotifierConfig,
+    ReportMetric, ReportingStoreConfig, RuntimeConfig, Scheduler, Security, Simulation, Templates,
+    WeeklyCharts, WeeklyReport,
 };
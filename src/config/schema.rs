@@ -1,17 +1,30 @@
+use chrono::NaiveTime;
+use chrono_tz::Tz;
 use serde::Deserialize;
 
+use crate::i18n::Language;
+use crate::templates::TemplateKind;
+
 use super::defaults::{
-    default_alert_cpu, default_alert_disk, default_alert_ram, default_anomaly_db_dir,
-    default_anomaly_db_enabled, default_anomaly_db_max_file_size_bytes,
-    default_anomaly_db_retention_days, default_command_timeout_secs, default_cooldown_secs,
-    default_daily_summary_enabled, default_daily_summary_hour, default_daily_summary_minute,
-    default_graph_enabled, default_graph_max_points, default_graph_max_window_hours,
-    default_graph_window_minutes, default_hysteresis, default_monitor_interval,
-    default_redact_sensitive_output, default_release_notifier_changelog_path,
-    default_release_notifier_state_path, default_reporting_store_path,
-    default_reporting_store_retention_days, default_simulation_profile,
-    default_weekly_report_enabled, default_weekly_report_hour, default_weekly_report_minute,
-    default_weekly_report_weekday,
+    default_alert_cpu, default_alert_disk, default_alert_ram, default_anomaly_db_backend,
+    default_anomaly_db_compression, default_anomaly_db_dir, default_anomaly_db_enabled,
+    default_anomaly_db_max_file_size_bytes, default_anomaly_db_max_total_bytes,
+    default_anomaly_db_retention_days, default_backup_dir, default_backup_keep_last,
+    default_command_timeout_secs, default_cooldown_secs, default_daily_summary_enabled,
+    default_daily_summary_hour, default_daily_summary_minute, default_graph_enabled,
+    default_graph_height, default_graph_max_points, default_graph_max_window_hours,
+    default_graph_theme, default_graph_width, default_graph_window_minutes,
+    default_hourly_digest_cron, default_hysteresis, default_monitor_interval,
+    default_monthly_report_day, default_monthly_report_hour, default_monthly_report_minute,
+    default_quiet_hours_critical_threshold, default_quiet_hours_end, default_quiet_hours_mode,
+    default_quiet_hours_start, default_quiet_hours_state_path, default_redact_sensitive_output,
+    default_release_notifier_changelog_path, default_release_notifier_state_path,
+    default_reporting_store_path, default_reporting_store_retention_days,
+    default_reporting_store_rollup_1h_retention_days,
+    default_reporting_store_rollup_5m_retention_days, default_scheduler_catch_up,
+    default_scheduler_state_path, default_simulation_profile, default_timezone,
+    default_weekly_report_enabled, default_weekly_report_hour, default_weekly_report_metrics,
+    default_weekly_report_minute, default_weekly_report_weekday,
 };
 
 #[derive(Debug, Clone, Deserialize)]
@@ -23,12 +36,22 @@ pub struct Config {
     #[serde(default = "default_command_timeout_secs")]
     pub command_timeout_secs: u64,
     #[serde(default)]
+    pub language: Language,
+    #[serde(default = "default_timezone")]
+    pub timezone: String,
+    #[serde(default)]
     pub alerts: Alerts,
     #[serde(default)]
     pub daily_summary: DailySummary,
     #[serde(default)]
     pub weekly_report: WeeklyReport,
     #[serde(default)]
+    pub monthly_report: MonthlyReport,
+    #[serde(default)]
+    pub hourly_digest: HourlyDigest,
+    #[serde(default)]
+    pub scheduler: Scheduler,
+    #[serde(default)]
     pub graph: Graph,
     #[serde(default, alias = "anomaly_journal")]
     pub anomaly_db: AnomalyDb,
@@ -40,6 +63,12 @@ pub struct Config {
     pub release_notifier: ReleaseNotifierConfig,
     #[serde(default)]
     pub security: Security,
+    #[serde(default)]
+    pub quiet_hours: QuietHours,
+    #[serde(default)]
+    pub templates: Templates,
+    #[serde(default)]
+    pub backup: Backup,
 }
 
 #[derive(Debug, Clone)]
@@ -79,22 +108,130 @@ pub struct Alerts {
 pub struct DailySummary {
     #[serde(default = "default_daily_summary_enabled")]
     pub enabled: bool,
-    #[serde(default = "default_daily_summary_hour")]
-    pub hour_utc: u8,
-    #[serde(default = "default_daily_summary_minute")]
-    pub minute_utc: u8,
+    #[serde(default = "default_daily_summary_hour", alias = "hour_utc")]
+    pub hour: u8,
+    #[serde(default = "default_daily_summary_minute", alias = "minute_utc")]
+    pub minute: u8,
+    /// Cron expression overriding `hour`/`minute`.
+    #[serde(default)]
+    pub cron: Option<String>,
+}
+
+impl DailySummary {
+    pub fn schedule_expression(&self) -> String {
+        self.cron
+            .clone()
+            .unwrap_or_else(|| format!("{} {} * * *", self.minute, self.hour))
+    }
 }
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct WeeklyReport {
     #[serde(default = "default_weekly_report_enabled")]
     pub enabled: bool,
-    #[serde(default = "default_weekly_report_weekday")]
-    pub weekday_utc: u8,
-    #[serde(default = "default_weekly_report_hour")]
-    pub hour_utc: u8,
-    #[serde(default = "default_weekly_report_minute")]
-    pub minute_utc: u8,
+    #[serde(default = "default_weekly_report_weekday", alias = "weekday_utc")]
+    pub weekday: u8,
+    #[serde(default = "default_weekly_report_hour", alias = "hour_utc")]
+    pub hour: u8,
+    #[serde(default = "default_weekly_report_minute", alias = "minute_utc")]
+    pub minute: u8,
+    /// Cron expression overriding `weekday`/`hour`/`minute`.
+    #[serde(default)]
+    pub cron: Option<String>,
+    /// Metrics charted in the report, each with its own caption line.
+    #[serde(default = "default_weekly_report_metrics")]
+    pub metrics: Vec<ReportMetric>,
+    #[serde(default)]
+    pub charts: WeeklyCharts,
+    /// Legacy switch for `metrics = ["cpu", "ram", "disk"]` as one image.
+    #[serde(default)]
+    pub stacked_panels: bool,
+}
+
+/// How the weekly report splits its metrics into images.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WeeklyCharts {
+    /// One image, with a panel per metric when there are several.
+    #[default]
+    Combined,
+    /// One image per metric, sent as a media group.
+    PerMetric,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportMetric {
+    Cpu,
+    Ram,
+    Disk,
+}
+
+impl WeeklyReport {
+    /// Configured metrics, or all of them when `stacked_panels` is set.
+    pub fn report_metrics(&self) -> Vec<ReportMetric> {
+        if self.stacked_panels {
+            vec![ReportMetric::Cpu, ReportMetric::Ram, ReportMetric::Disk]
+        } else {
+            self.metrics.clone()
+        }
+    }
+
+    pub fn schedule_expression(&self) -> String {
+        self.cron.clone().unwrap_or_else(|| {
+            // `weekday` is 1 (Monday) to 7 (Sunday); cron accepts 7 as Sunday.
+            format!("{} {} * * {}", self.minute, self.hour, self.weekday)
+        })
+    }
+}
+
+/// Capacity report over the previous calendar month.
+#[derive(Debug, Clone, Deserialize)]
+pub struct MonthlyReport {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Day of the month, 1 to 28 so every month has it.
+    #[serde(default = "default_monthly_report_day")]
+    pub day: u8,
+    #[serde(default = "default_monthly_report_hour")]
+    pub hour: u8,
+    #[serde(default = "default_monthly_report_minute")]
+    pub minute: u8,
+    /// Cron expression overriding `day`/`hour`/`minute`.
+    #[serde(default)]
+    pub cron: Option<String>,
+}
+
+impl MonthlyReport {
+    pub fn schedule_expression(&self) -> String {
+        self.cron
+            .clone()
+            .unwrap_or_else(|| format!("{} {} {} * *", self.minute, self.hour, self.day))
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct HourlyDigest {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_hourly_digest_cron")]
+    pub cron: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Scheduler {
+    #[serde(default = "default_scheduler_state_path")]
+    pub state_path: String,
+    #[serde(default = "default_scheduler_catch_up")]
+    pub catch_up: bool,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Backup {
+    #[serde(default = "default_backup_dir")]
+    pub dir: String,
+    #[serde(default = "default_backup_keep_last")]
+    pub keep_last: usize,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -107,6 +244,24 @@ pub struct Graph {
     pub max_window_hours: u64,
     #[serde(default = "default_graph_max_points")]
     pub max_points: u16,
+    #[serde(default = "default_graph_theme")]
+    pub theme: GraphTheme,
+    #[serde(default = "default_graph_width")]
+    pub width: u32,
+    #[serde(default = "default_graph_height")]
+    pub height: u32,
+}
+
+impl Graph {
+    pub const WIDTH_RANGE: std::ops::RangeInclusive<u32> = 320..=3840;
+    pub const HEIGHT_RANGE: std::ops::RangeInclusive<u32> = 200..=2160;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GraphTheme {
+    Light,
+    Dark,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -119,6 +274,29 @@ pub struct AnomalyDb {
     pub max_file_size_bytes: u64,
     #[serde(default = "default_anomaly_db_retention_days")]
     pub retention_days: u16,
+    #[serde(default = "default_anomaly_db_backend")]
+    pub backend: AnomalyDbBackend,
+    #[serde(default = "default_anomaly_db_compression")]
+    pub compression: AnomalyCompression,
+    #[serde(default = "default_anomaly_db_max_total_bytes")]
+    pub max_total_bytes: u64,
+}
+
+/// Where anomaly events are persisted. `sled` keeps them in `<dir>/store` and
+/// imports existing JSONL events on first open.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AnomalyDbBackend {
+    Jsonl,
+    Sled,
+}
+
+/// Compression applied to event files once they are rotated out by size.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AnomalyCompression {
+    None,
+    Gzip,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -137,6 +315,10 @@ pub struct ReportingStoreConfig {
     pub path: String,
     #[serde(default = "default_reporting_store_retention_days")]
     pub retention_days: u16,
+    #[serde(default = "default_reporting_store_rollup_5m_retention_days")]
+    pub rollup_5m_retention_days: u16,
+    #[serde(default = "default_reporting_store_rollup_1h_retention_days")]
+    pub rollup_1h_retention_days: u16,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -155,6 +337,110 @@ pub struct Security {
     pub redact_sensitive_output: bool,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum QuietHoursMode {
+    Digest,
+    Silent,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct QuietHours {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_quiet_hours_start")]
+    pub start: String,
+    #[serde(default = "default_quiet_hours_end")]
+    pub end: String,
+    /// IANA name; unset follows the top-level `timezone`.
+    #[serde(default)]
+    pub timezone: Option<String>,
+    #[serde(default = "default_quiet_hours_mode")]
+    pub mode: QuietHoursMode,
+    #[serde(default = "default_quiet_hours_critical_threshold")]
+    pub critical_threshold: f32,
+    /// Alerts held for the digest are kept here across restarts.
+    #[serde(default = "default_quiet_hours_state_path")]
+    pub state_path: String,
+}
+
+impl QuietHours {
+    pub fn start_time(&self) -> Option<NaiveTime> {
+        parse_clock_time(&self.start)
+    }
+
+    pub fn end_time(&self) -> Option<NaiveTime> {
+        parse_clock_time(&self.end)
+    }
+
+    /// The configured timezone, or `fallback` when none is set; `None` for an
+    /// invalid name.
+    pub fn tz(&self, fallback: Tz) -> Option<Tz> {
+        match &self.timezone {
+            Some(name) => name.trim().parse::<Tz>().ok(),
+            None => Some(fallback),
+        }
+    }
+}
+
+fn parse_clock_time(value: &str) -> Option<NaiveTime> {
+    NaiveTime::parse_from_str(value.trim(), "%H:%M").ok()
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct Templates {
+    #[serde(default)]
+    pub dir: Option<String>,
+    #[serde(default)]
+    pub alert: Option<String>,
+    #[serde(default)]
+    pub daily_summary: Option<String>,
+    #[serde(default)]
+    pub weekly_caption: Option<String>,
+}
+
+impl Templates {
+    pub fn source(&self, kind: TemplateKind) -> Option<&str> {
+        match kind {
+            TemplateKind::Alert => self.alert.as_deref(),
+            TemplateKind::DailySummary => self.daily_summary.as_deref(),
+            TemplateKind::WeeklyCaption => self.weekly_caption.as_deref(),
+        }
+    }
+
+    /// Fills templates that are not set inline from `<dir>/<key>.txt`.
+    pub(super) fn load_dir(&mut self) -> Result<(), std::io::Error> {
+        let Some(dir) = self
+            .dir
+            .as_deref()
+            .map(str::trim)
+            .filter(|dir| !dir.is_empty())
+        else {
+            return Ok(());
+        };
+        let dir = std::path::PathBuf::from(dir);
+
+        for (kind, slot) in [
+            (TemplateKind::Alert, &mut self.alert),
+            (TemplateKind::DailySummary, &mut self.daily_summary),
+            (TemplateKind::WeeklyCaption, &mut self.weekly_caption),
+        ] {
+            if slot.is_some() {
+                continue;
+            }
+
+            let path = dir.join(format!("{}.txt", kind.config_key()));
+            match std::fs::read_to_string(&path) {
+                Ok(content) => *slot = Some(content.trim_end_matches('\n').to_string()),
+                Err(error) if error.kind() == std::io::ErrorKind::NotFound => {}
+                Err(error) => return Err(error),
+            }
+        }
+
+        Ok(())
+    }
+}
+
 impl Default for ReleaseNotifierConfig {
     fn default() -> Self {
         Self {
@@ -1,7 +1,10 @@
+use chrono_tz::Tz;
 use teloxide::types::{ChatId, UserId};
 use thiserror::Error;
 
-use super::schema::Config;
+use super::schema::{Config, Graph};
+use crate::jobs::CronSchedule;
+use crate::templates::{TemplateKind, validate_template};
 
 #[derive(Debug, Error)]
 pub enum ConfigError {
@@ -41,6 +44,11 @@ impl Config {
                 "command_timeout_secs must be greater than 0".to_string(),
             ));
         }
+        if self.timezone.trim().parse::<Tz>().is_err() {
+            return Err(ConfigError::Validation(
+                "timezone must be a valid IANA timezone name".to_string(),
+            ));
+        }
         validate_percentage("alerts.cpu", self.alerts.cpu)?;
         validate_percentage("alerts.ram", self.alerts.ram)?;
         validate_percentage("alerts.disk", self.alerts.disk)?;
@@ -54,29 +62,83 @@ impl Config {
                 "alerts.hysteresis must be non-negative".to_string(),
             ));
         }
-        if self.daily_summary.hour_utc > 23 {
+        if self.daily_summary.hour > 23 {
+            return Err(ConfigError::Validation(
+                "daily_summary.hour must be between 0 and 23".to_string(),
+            ));
+        }
+        if self.daily_summary.minute > 59 {
+            return Err(ConfigError::Validation(
+                "daily_summary.minute must be between 0 and 59".to_string(),
+            ));
+        }
+        if !(1..=7).contains(&self.weekly_report.weekday) {
+            return Err(ConfigError::Validation(
+                "weekly_report.weekday must be between 1 and 7".to_string(),
+            ));
+        }
+        if self.weekly_report.hour > 23 {
+            return Err(ConfigError::Validation(
+                "weekly_report.hour must be between 0 and 23".to_string(),
+            ));
+        }
+        if self.weekly_report.minute > 59 {
+            return Err(ConfigError::Validation(
+                "weekly_report.minute must be between 0 and 59".to_string(),
+            ));
+        }
+        let weekly_metrics = &self.weekly_report.metrics;
+        if weekly_metrics.is_empty() {
+            return Err(ConfigError::Validation(
+                "weekly_report.metrics must list at least one metric".to_string(),
+            ));
+        }
+        if weekly_metrics
+            .iter()
+            .enumerate()
+            .any(|(index, metric)| weekly_metrics[..index].contains(metric))
+        {
             return Err(ConfigError::Validation(
-                "daily_summary.hour_utc must be between 0 and 23".to_string(),
+                "weekly_report.metrics must not repeat a metric".to_string(),
             ));
         }
-        if self.daily_summary.minute_utc > 59 {
+        if !(1..=28).contains(&self.monthly_report.day) {
             return Err(ConfigError::Validation(
-                "daily_summary.minute_utc must be between 0 and 59".to_string(),
+                "monthly_report.day must be between 1 and 28".to_string(),
             ));
         }
-        if !(1..=7).contains(&self.weekly_report.weekday_utc) {
+        if self.monthly_report.hour > 23 {
             return Err(ConfigError::Validation(
-                "weekly_report.weekday_utc must be between 1 and 7".to_string(),
+                "monthly_report.hour must be between 0 and 23".to_string(),
             ));
         }
-        if self.weekly_report.hour_utc > 23 {
+        if self.monthly_report.minute > 59 {
             return Err(ConfigError::Validation(
-                "weekly_report.hour_utc must be between 0 and 23".to_string(),
+                "monthly_report.minute must be between 0 and 59".to_string(),
             ));
         }
-        if self.weekly_report.minute_utc > 59 {
+        for (key, expression) in [
+            (
+                "daily_summary.cron",
+                self.daily_summary.schedule_expression(),
+            ),
+            (
+                "weekly_report.cron",
+                self.weekly_report.schedule_expression(),
+            ),
+            (
+                "monthly_report.cron",
+                self.monthly_report.schedule_expression(),
+            ),
+            ("hourly_digest.cron", self.hourly_digest.cron.clone()),
+        ] {
+            if let Err(error) = CronSchedule::parse(&expression) {
+                return Err(ConfigError::Validation(format!("{key}: {error}")));
+            }
+        }
+        if self.scheduler.state_path.trim().is_empty() {
             return Err(ConfigError::Validation(
-                "weekly_report.minute_utc must be between 0 and 59".to_string(),
+                "scheduler.state_path must not be empty".to_string(),
             ));
         }
         if self.graph.default_window_minutes == 0 {
@@ -94,6 +156,20 @@ impl Config {
                 "graph.max_points must be at least 10".to_string(),
             ));
         }
+        if !Graph::WIDTH_RANGE.contains(&self.graph.width) {
+            return Err(ConfigError::Validation(format!(
+                "graph.width must be between {} and {}",
+                Graph::WIDTH_RANGE.start(),
+                Graph::WIDTH_RANGE.end()
+            )));
+        }
+        if !Graph::HEIGHT_RANGE.contains(&self.graph.height) {
+            return Err(ConfigError::Validation(format!(
+                "graph.height must be between {} and {}",
+                Graph::HEIGHT_RANGE.start(),
+                Graph::HEIGHT_RANGE.end()
+            )));
+        }
 
         if self.anomaly_db.enabled && self.anomaly_db.dir.trim().is_empty() {
             return Err(ConfigError::Validation(
@@ -110,6 +186,12 @@ impl Config {
                 "anomaly_db.retention_days must be greater than 0".to_string(),
             ));
         }
+        if self.anomaly_db.max_total_bytes < self.anomaly_db.max_file_size_bytes {
+            return Err(ConfigError::Validation(
+                "anomaly_db.max_total_bytes must be at least anomaly_db.max_file_size_bytes"
+                    .to_string(),
+            ));
+        }
 
         if self.simulation.profile.trim().is_empty() {
             return Err(ConfigError::Validation(
@@ -128,6 +210,13 @@ impl Config {
                 "reporting_store.retention_days must be greater than 0".to_string(),
             ));
         }
+        if self.reporting_store.rollup_5m_retention_days == 0
+            || self.reporting_store.rollup_1h_retention_days == 0
+        {
+            return Err(ConfigError::Validation(
+                "reporting_store rollup retention days must be greater than 0".to_string(),
+            ));
+        }
         if self.release_notifier.enabled && self.release_notifier.changelog_path.trim().is_empty() {
             return Err(ConfigError::Validation(
                 "release_notifier.changelog_path must not be empty when release_notifier.enabled is true"
@@ -140,9 +229,66 @@ impl Config {
                     .to_string(),
             ));
         }
+        if self.quiet_hours.enabled && self.quiet_hours.state_path.trim().is_empty() {
+            return Err(ConfigError::Validation(
+                "quiet_hours.state_path must not be empty when quiet_hours.enabled is true"
+                    .to_string(),
+            ));
+        }
+        if self.quiet_hours.start_time().is_none() {
+            return Err(ConfigError::Validation(
+                "quiet_hours.start must use HH:MM format".to_string(),
+            ));
+        }
+        if self.quiet_hours.end_time().is_none() {
+            return Err(ConfigError::Validation(
+                "quiet_hours.end must use HH:MM format".to_string(),
+            ));
+        }
+        if self.quiet_hours.start_time() == self.quiet_hours.end_time() {
+            return Err(ConfigError::Validation(
+                "quiet_hours.start and quiet_hours.end must differ".to_string(),
+            ));
+        }
+        if self.quiet_hours.tz(self.tz()).is_none() {
+            return Err(ConfigError::Validation(
+                "quiet_hours.timezone must be a valid IANA timezone name".to_string(),
+            ));
+        }
+        validate_percentage(
+            "quiet_hours.critical_threshold",
+            self.quiet_hours.critical_threshold,
+        )?;
+        if self.backup.dir.trim().is_empty() {
+            return Err(ConfigError::Validation(
+                "backup.dir must not be empty".to_string(),
+            ));
+        }
+        if self.backup.keep_last == 0 {
+            return Err(ConfigError::Validation(
+                "backup.keep_last must be greater than 0".to_string(),
+            ));
+        }
+        for kind in [
+            TemplateKind::Alert,
+            TemplateKind::DailySummary,
+            TemplateKind::WeeklyCaption,
+        ] {
+            if let Some(source) = self.templates.source(kind) {
+                validate_template(kind, source).map_err(|error| {
+                    ConfigError::Validation(format!("templates.{}: {error}", kind.config_key()))
+                })?;
+            }
+        }
         Ok(())
     }
 
+    /// Timezone for schedules and user-facing timestamps. Falls back to UTC
+    /// when the configured name is invalid (rejected by `validate`).
+    pub fn tz(&self) -> Tz {
+        self.timezone.trim().parse().unwrap_or(Tz::UTC)
+    }
+
     pub fn owner_chat_id(&self) -> Result<ChatId, ConfigError> {
         if self.owner_id == 0 {
             return Err(ConfigError::Validation(
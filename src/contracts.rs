@@ -1,3 +1,3 @@
 pub use crate::anomaly_db::AnomalyStorage;
 pub use crate::monitor::{MetricsProvider, Notifier};
-pub use crate::reporting_store::ReportingStorage;
+pub use crate::reporting_store::{IncidentStorage, ReportingStorage};
@@ -0,0 +1,25 @@
+//! Filesystem size helpers shared by the stores' `/storage` reporting.
+
+use std::fs;
+use std::path::Path;
+
+/// Total size of the regular files under `root`.
+pub(crate) fn dir_size_bytes(root: &Path) -> u64 {
+    let mut total = 0;
+    let mut pending = vec![root.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            match entry.file_type() {
+                Ok(file_type) if file_type.is_dir() => pending.push(entry.path()),
+                Ok(file_type) if file_type.is_file() => {
+                    total += entry.metadata().map_or(0, |meta| meta.len());
+                }
+                _ => {}
+            }
+        }
+    }
+    total
+}
@@ -0,0 +1,477 @@
+pub(super) const ENTRIES: &[(&str, &str)] = &[
+    ("common.yes", "yes"),
+    ("common.no", "no"),
+    // Menus
+    ("menu.button.status", "📊 Status"),
+    ("menu.button.health", "💓 Health"),
+    ("menu.button.alerts", "🚨 Alerts"),
+    ("menu.button.monitor", "📈 Monitor"),
+    ("menu.button.data", "📦 Data"),
+    ("menu.button.system", "🖥️ System"),
+    ("menu.button.help", "❓ Help"),
+    ("menu.button.sys_snapshot", "📦 Sys Snapshot"),
+    ("menu.button.cpu", "🧠 CPU"),
+    ("menu.button.temp", "🌡️ Temp"),
+    ("menu.button.network", "🌐 Network"),
+    ("menu.button.uptime", "⏱️ Uptime"),
+    ("menu.button.ports", "🔌 Ports"),
+    ("menu.button.services", "🧩 Services"),
+    ("menu.button.main_menu", "⬅️ Main Menu"),
+    ("menu.button.graph_cpu_1h", "📈 CPU 1h"),
+    ("menu.button.graph_ram_1h", "💾 RAM 1h"),
+    ("menu.button.graph_disk_1h", "🧱 Disk 1h"),
+    ("menu.button.recent_6h", "🧾 Recent 6h"),
+    ("menu.button.recent_24h", "🧾 Recent 24h"),
+    ("menu.button.export_cpu", "📤 Export CPU"),
+    ("menu.button.export_ram", "📤 Export RAM"),
+    ("menu.button.export_disk", "📤 Export Disk"),
+    ("menu.button.mute_1h", "🔇 Mute 1h"),
+    ("menu.button.unmute", "🔔 Unmute"),
+    ("menu.main.title", "Main"),
+    (
+        "menu.main.body",
+        "Use buttons to run actions directly. No need to type commands manually.",
+    ),
+    ("menu.system.title", "Main › System"),
+    (
+        "menu.system.body",
+        "Live host diagnostics and service checks.",
+    ),
+    ("menu.monitor.title", "Main › Monitor"),
+    (
+        "menu.monitor.body",
+        "Alert controls, graphs and anomaly drill-down actions.",
+    ),
+    ("menu.data.title", "Main › Data"),
+    ("menu.data.body", "Recent anomalies and export shortcuts."),
+    ("menu.help.title", "Main › Help"),
+    (
+        "menu.help.body",
+        "1) Open a menu\n2) Tap an action\n3) Use ⬅️ Main Menu to continue\n\nTip: Slash commands still work, but all common flows are available as buttons.",
+    ),
+    ("menu.next.title", "Next"),
+    (
+        "menu.next.body",
+        "Continue from menu buttons below or use /help for full command list.",
+    ),
+    // Help and health
+    ("help.title", "Control Center"),
+    (
+        "help.body",
+        "• Use buttons below for common flows.\n\n<b>Main journeys</b>\n• Health &amp; status checks\n• System diagnostics\n• Monitoring, alerts and graphs\n• Recent anomalies and exports\n\nSlash commands still work if you prefer manual usage.",
+    ),
+    ("health.title", "Bot Health"),
+    (
+        "health.delayed",
+        "⚠️ CRITICAL: Monitor loop is delayed. Last tick: {lag}s ago (threshold: {threshold}s)",
+    ),
+    (
+        "health.healthy",
+        "✅ Healthy. Last monitor tick: {lag}s ago (threshold: {threshold}s)",
+    ),
+    (
+        "health.details",
+        "{status}\n\nMonitor interval: {interval}s\nCurrent time: {now}\nLast tick: {tick}",
+    ),
+    (
+        "health.warming_up",
+        "⏳ Warming up...\n\nMonitor loop has not produced the first tick yet.\nMonitor interval: {interval}s\nCurrent time: {now}",
+    ),
+    // Status
+    ("status.title", "Bot Status"),
+    ("status.muted_until", "muted until {until}"),
+    ("status.not_muted", "not muted"),
+    ("status.not_available", "not available yet"),
+    ("status.none", "none"),
+    ("status.integrity.unchecked", "not checked yet"),
+    ("status.integrity.checked", "{summary} at {at}"),
+    ("status.integrity.clean", "clean"),
+    ("status.integrity.issues", "issues found"),
+    ("status.integrity.repaired", "issues found and repaired"),
+    ("status.integrity.sled", "not used by the sled backend"),
+    ("status.maintenance.active", "Active (Hourly)"),
+    ("status.maintenance.disabled", "Disabled"),
+    (
+        "status.body",
+        "Auth Mode: Owner Only (DM)\nStorage: Hierarchical JSONL + Indexed\nMaintenance: {maintenance}\nRetention: {retention_days} days\nAnomaly dir: {anomaly_dir}\nIntegrity: {integrity}\n\nRuntime:\n- Monitor interval: {interval}s\n- Last monitor tick: {last_tick}\n- Mute state: {mute_state}\n\nLast {spark_minutes}m:\n{sparklines}\n\nUpcoming Reports:\n{schedule}\n\nReporting Store:\n- enabled: {store_enabled}\n- path: {store_path}\n- retention: {store_retention_days} days\n\nSecurity:\n- redact_sensitive_output: {redact}\n\nSimulation:\n- enabled: {simulation_enabled}\n- profile: {simulation_profile}\n\nGraph Runtime:\n- enabled: {graph_enabled}\n- default window: {graph_default_window}m\n- max window: {graph_max_window}h\n- max points: {graph_max_points}\n\nCapabilities:\n- is_systemd: {is_systemd}\n- has_sensors: {has_sensors}\n- has_free: {has_free}\n- has_top: {has_top}\n- has_ip: {has_ip}\n- has_ss: {has_ss}\n- has_uptime: {has_uptime}\n\nButton-first UX:\n- Use menu buttons below to run actions directly.\n- Slash commands are optional for advanced queries.\n\nAdvanced examples:\n/recent\n/recent 5\n/recent 6h\n/recent cpu>85",
+    ),
+    // Alerts
+    ("alerts.title", "Alert Configuration"),
+    (
+        "alerts.body",
+        "Thresholds:\n- CPU: {cpu}%\n- RAM: {ram}%\n- Disk: {disk}%\n\nControl:\n- Cooldown: {cooldown}s\n- Hysteresis: {hysteresis}%\n- Mute: {mute}\n- Quiet hours: {quiet_hours}\n- Last daily summary: {last_summary}\n\nCurrent State:\n- CPU alerting: {cpu_alerting}\n- RAM alerting: {ram_alerting}\n- Disk alerting: {disk_alerting}",
+    ),
+    (
+        "alerts.muted_remaining",
+        "muted ({remaining}s remaining until {until})",
+    ),
+    ("alerts.not_muted", "not muted"),
+    ("alerts.disabled", "disabled"),
+    ("alerts.not_generated", "not generated yet"),
+    (
+        "alerts.quiet_hours",
+        "{start}-{end} {timezone} ({mode}, critical >= {critical}%, held: {held})",
+    ),
+    ("alerts.mute_failed.title", "Mute failed"),
+    (
+        "alerts.mute_failed.body",
+        "Invalid duration. Use format like: <b>30s</b>, <b>15m</b>, <b>2h</b>, <b>1d</b>.",
+    ),
+    ("alerts.mute_cooldown.title", "Mute cooldown"),
+    ("alerts.unmute_cooldown.title", "Unmute cooldown"),
+    (
+        "alerts.cooldown.body",
+        "Please wait <b>{secs}s</b> before changing mute state again.",
+    ),
+    ("alerts.muted.title", "Alerts muted ✅"),
+    (
+        "alerts.muted.body",
+        "Alerts are muted until <b>{until}</b>.\n\nYou can continue from the Monitor menu below.",
+    ),
+    ("alerts.unmuted.title", "Alerts unmuted ✅"),
+    (
+        "alerts.unmuted.body",
+        "Alerts are active again.\n\nYou can continue from the Monitor menu below.",
+    ),
+    // Alert and report texts (defaults for `[templates]`)
+    (
+        "template.alert",
+        "⚠️ ALERT: {metric} usage is high ({value}%)",
+    ),
+    (
+        "template.daily_summary",
+        "📅 Daily Summary\n\nSamples: {samples}\nAlerts triggered: {alerts}\n\nCPU avg/min/max: {cpu_avg}% / {cpu_min}% / {cpu_max}%\nRAM avg/min/max: {ram_avg}% / {ram_min}% / {ram_max}%\nDisk avg/min/max: {disk_avg}% / {disk_min}% / {disk_max}%\n\nGenerated at: {generated_at}",
+    ),
+    (
+        "template.weekly_caption",
+        "📈 Weekly {metric} ({duration}) | samples: {samples} | min: {min}% | max: {max}% | avg: {avg}%{labels}{rollup}\nIncidents: {incidents} | MTTR: {mttr}",
+    ),
+    // Summaries
+    (
+        "summary.daily.empty",
+        "📅 Daily Summary\n\nNo monitoring samples were collected since the last summary window.\nGenerated at: {generated_at}",
+    ),
+    (
+        "summary.weekly.failed",
+        "📈 Weekly Report\n\nCould not generate chart this cycle: {error}",
+    ),
+    (
+        "summary.monthly.caption",
+        "📊 Capacity Report {month}\nCPU p95: {cpu_p95}% | RAM p95: {ram_p95}% | Disk max: {disk_max}%\nAlerts: {alerts} | Incidents: {incidents} | MTTR: {mttr}\n{growth}",
+    ),
+    (
+        "summary.monthly.document",
+        "Capacity Report {month} ({timezone}, {days} days)\nPeriod: {from} to {to} {timezone}; daily rows are UTC days\n\nPercentiles\n{metrics}\n\nAlerts sent: {alerts}\nIncidents: {incidents} ({by_metric})\nMTTR: {mttr}\n\n{growth}\nBusiest hours (CPU avg): {busiest}\n\nDaily avg/max\n{daily}\n",
+    ),
+    (
+        "summary.monthly.document.caption",
+        "📄 Full capacity report for {month}",
+    ),
+    (
+        "summary.monthly.growth",
+        "Disk growth: {rate}%/day, projected {projected}% in 30 days, full in ~{full_days} days",
+    ),
+    (
+        "summary.monthly.growth.flat",
+        "Disk growth: {rate}%/day, not filling up",
+    ),
+    (
+        "summary.monthly.failed",
+        "📊 Capacity Report\n\nCould not generate the monthly report: {error}",
+    ),
+    (
+        "summary.quiet_digest",
+        "🌙 Quiet Hours Digest\n\n{count} alert(s) were held during quiet hours:\n{alerts}",
+    ),
+    (
+        "summary.hourly_digest",
+        "🕐 Hourly Digest\n\nSamples: {samples}\nCPU avg/max: {cpu_avg}% / {cpu_max}%\nRAM avg/max: {ram_avg}% / {ram_max}%\nDisk avg/max: {disk_avg}% / {disk_max}%",
+    ),
+    (
+        "summary.hourly_digest.empty",
+        "🕐 Hourly Digest\n\nNo monitoring samples were collected in the last hour.",
+    ),
+    // System info and updates
+    ("update.title", "Update"),
+    ("update.check.title", "Update Check"),
+    ("update.usage.title", "Update Usage"),
+    ("update.usage", "Usage:\n/update check\n/update apply"),
+    (
+        "update.up_to_date",
+        "No newer release found. Current v{version} is up to date.",
+    ),
+    (
+        "update.no_systemd",
+        "Controlled restart is unavailable on this host (systemd not detected).",
+    ),
+    (
+        "update.blocked",
+        "Update apply is blocked by pre-checks.\n{detail}",
+    ),
+    (
+        "update.starting",
+        "Starting update to v{version}. Service may restart during apply.",
+    ),
+    ("update.restarting", "{message}\nService is restarting..."),
+    (
+        "update.restart_failed",
+        "Binary installed but service restart failed: {error}\nManual restart required: systemctl restart kars-bot",
+    ),
+    ("update.apply_failed", "Update apply failed: {error}"),
+    (
+        "update.check_failed",
+        "Update check failed before apply: {error}",
+    ),
+    (
+        "update.readiness.ready",
+        "Apply readiness: ready\n{details}",
+    ),
+    (
+        "update.readiness.blocked",
+        "Apply readiness: blocked\n{details}",
+    ),
+    (
+        "update.readiness.failed",
+        "Apply readiness: check failed ({error})",
+    ),
+    (
+        "update.check.available",
+        "Current: v{current}\nLatest: v{latest}\n\nUpdate available.\nRun /update apply to trigger controlled restart.\n\n{readiness}",
+    ),
+    (
+        "update.check.current",
+        "Current: v{current}\nLatest: v{latest}\n\nYou are up to date.\n\n{readiness}",
+    ),
+    (
+        "update.no_update",
+        "No update available for current version.",
+    ),
+    (
+        "update.not_applied",
+        "No update was applied (already up to date).",
+    ),
+    ("update.applied", "Updated: v{current} -> v{latest} ({tag})"),
+    ("system.cpu.title", "CPU Usage"),
+    ("system.cpu.empty", "No CPU output."),
+    ("system.network.title", "Network Statistics"),
+    ("system.network.interfaces", "Interfaces: {interfaces}"),
+    (
+        "system.network.no_interfaces",
+        "Interfaces: no non-loopback interface detected",
+    ),
+    ("system.uptime.title", "System Uptime"),
+    ("system.temp.title", "Temperature Sensors"),
+    ("system.snapshot.title", "System Snapshot"),
+    (
+        "system.snapshot.memory",
+        "Memory: used {used} / total {total} (free {free})",
+    ),
+    (
+        "system.snapshot.memory_unavailable",
+        "Memory summary unavailable",
+    ),
+    (
+        "system.snapshot.disk",
+        "Disk (/): used {used} / size {size} ({percent} used, avail {avail})",
+    ),
+    (
+        "system.snapshot.disk_unavailable",
+        "Disk summary unavailable",
+    ),
+    (
+        "system.snapshot.body",
+        "Summary:\n- {memory}\n- {disk}\n\nRAM (details):\n{memory_details}\n\nDisk (details):\n{disk_details}",
+    ),
+    ("system.ports.title", "Open Ports"),
+    ("system.services.title", "Active Services"),
+    ("system.services.empty", "No service output."),
+    // Errors
+    ("error.command_queue.title", "Command queue error"),
+    (
+        "error.command_queue.body",
+        "Could not acquire command slot. Please try again.",
+    ),
+    (
+        "error.output_too_long",
+        "Output is too long for a Telegram message. Sent as file attachment.",
+    ),
+    (
+        "error.unsupported_feature",
+        "This feature is not supported on this system. Missing dependency: {command}",
+    ),
+    ("error.not_enough_samples", "not enough samples yet"),
+    ("graph.disabled.title", "Graph Disabled"),
+    (
+        "graph.disabled.body",
+        "Graph feature is disabled (runtime config). Enable graph in config if needed.",
+    ),
+    ("graph.usage.title", "Graph Usage"),
+    (
+        "graph.usage",
+        "Usage: /graph cpu|ram|disk[,ram,...] [<Nm|Nh>|<YYYY-MM-DD>..<YYYY-MM-DD>] [vs <N(m|h|d)>-ago|compare=yesterday|lastweek] [light|dark] [<W>x<H>] [png|svg]",
+    ),
+    ("graph.cooldown.title", "Graph Cooldown"),
+    (
+        "graph.cooldown.body",
+        "Please wait {secs}s before using /graph again.",
+    ),
+    ("graph.title", "{metric} Graph"),
+    (
+        "graph.compare.no_data",
+        "Not enough stored samples for the same window {period} earlier.",
+    ),
+    ("graph.compare.ago", "{period} ago"),
+    (
+        "graph.compare.caption",
+        "vs {period}: avg {avg}% ({avg_delta}) | max {max}% ({max_delta})",
+    ),
+    ("graph.axis.now", "now"),
+    (
+        "graph.caption.summary",
+        "min: {min}% | max: {max}% | avg: {avg}%",
+    ),
+    (
+        "graph.caption.metric_summary",
+        "{metric} min/max/avg: {min}% / {max}% / {avg}%",
+    ),
+    ("graph.render.title", "Graph Render"),
+    ("heatmap.usage.title", "Heatmap Usage"),
+    (
+        "heatmap.usage",
+        "Usage: /heatmap cpu|ram|disk [<Nd|Nw>] [avg|p95] [light|dark] [png|svg]",
+    ),
+    ("spark.usage.title", "Sparkline Usage"),
+    ("spark.usage", "Usage: /spark [<Nm|Nh>]"),
+    ("spark.title", "Sparklines (last {window})"),
+    ("alert.sparkline", "last {window}: {sparkline}"),
+    ("heatmap.title", "{metric} Heatmap"),
+    (
+        "heatmap.caption",
+        "{metric} heatmap ({window}, hourly {stat}, {timezone}) | busiest: {busiest} | quietest: {quietest}",
+    ),
+    (
+        "graph.error.font",
+        "Graph engine embedded font failed to initialize. Please check build assets and restart.",
+    ),
+    (
+        "graph.error.busy",
+        "Graph renderer is busy. Please retry shortly.",
+    ),
+    (
+        "graph.error.timeout",
+        "Graph render took too long and was cancelled. Please try a shorter window.",
+    ),
+    (
+        "graph.error.generic",
+        "Could not render graph right now. Please try again.",
+    ),
+    ("export.disabled.title", "Export Disabled"),
+    (
+        "export.disabled.body",
+        "Export feature is disabled in config.",
+    ),
+    ("export.usage.title", "Export Usage"),
+    (
+        "export.usage",
+        "Usage: /export cpu|ram|disk[,ram,...]|all [<Nm|Nh>] [csv|json|ndjson|openmetrics|influx]\n/export cpu|ram|disk[,ram,...]|all from=<YYYY-MM-DD[THH:MM]> [to=<YYYY-MM-DD[THH:MM]>] [format]",
+    ),
+    ("export.title", "Export"),
+    ("export.failed", "Could not build export: {error}"),
+    (
+        "export.too_large",
+        "export is over Telegram's 50 MB limit even gzipped; pick a shorter period",
+    ),
+    (
+        "export.compressed",
+        "Gzipped to fit Telegram's 50 MB upload limit.",
+    ),
+    (
+        "export.rollup_averages",
+        "The period starts before raw samples are kept, so rows are {bucket} averages.",
+    ),
+    (
+        "export.caption",
+        "Exported {count} samples for {metric} ({window})",
+    ),
+    ("backup.title", "Backup"),
+    (
+        "backup.caption",
+        "🗄️ Backup: {files} files, {bytes} bytes ({components})",
+    ),
+    (
+        "backup.too_large",
+        "Backup created ({bytes} bytes) but it exceeds the Telegram upload limit.\nSaved on the server: {path}",
+    ),
+    ("backup.failed", "Could not create backup: {error}"),
+    ("storage.title", "Storage Usage"),
+    ("storage.failed", "Could not collect storage usage: {error}"),
+    ("storage.none", "-"),
+    ("storage.reporting.disabled", "Reporting store: disabled"),
+    (
+        "storage.reporting",
+        "Reporting store ({path})\nSize on disk: {size}\n{trees}\nProjected at {retention_days}d raw retention: {projected}",
+    ),
+    (
+        "storage.tree",
+        "- {name}: {entries} entries, {oldest} → {newest}",
+    ),
+    ("storage.anomaly.disabled", "Anomaly DB: disabled"),
+    (
+        "storage.anomaly",
+        "Anomaly DB ({dir})\nFiles: {event_files} events ({rotated} rotated), {index_files} index\nFile size: {file_size}, sled store: {store_size}\nDays: {oldest} → {newest}\nProjected at {retention_days}d retention: {projected}",
+    ),
+    ("dbcheck.title", "Anomaly DB Check"),
+    ("dbcheck.usage", "Usage: /dbcheck or /dbcheck repair"),
+    ("dbcheck.disabled", "Anomaly DB is disabled in config."),
+    (
+        "dbcheck.sled",
+        "The anomaly DB uses the sled backend; integrity checks only cover JSONL files.",
+    ),
+    ("dbcheck.failed", "Integrity check failed: {error}"),
+    ("dbcheck.status.clean", "✅ No issues found"),
+    (
+        "dbcheck.status.issues",
+        "⚠️ Issues found, run /dbcheck repair to fix",
+    ),
+    ("dbcheck.status.repaired", "🛠️ Issues found and repaired"),
+    (
+        "dbcheck.result",
+        "{status}\n\nFiles: {event_files} events / {index_files} index\nLines: {event_lines} events / {index_lines} index\nCorrupt lines: {corrupt_events} events / {corrupt_index} index\nIndex entries without event: {index_only}\nEvents missing from index: {unindexed}\nPartial trailing lines: {partial}\nIndex days rebuilt: {rebuilt}",
+    ),
+    ("incidents.title", "Incidents"),
+    (
+        "incidents.usage",
+        "Usage:\n/incidents [7d]\n/incident <id> [ack]",
+    ),
+    ("incidents.empty", "No incidents in the last {window}."),
+    (
+        "incidents.summary",
+        "{count} incidents in the last {window} | open: {open} | MTTR: {mttr}",
+    ),
+    (
+        "incidents.line",
+        "#{id} {metric} | {started} | {duration} | peak {peak}% (>{threshold}%) | alerts: {alerts}{flags}",
+    ),
+    ("incidents.flag.open", "open"),
+    ("incidents.flag.acked", "acked"),
+    ("incidents.flag.muted", "muted"),
+    ("incidents.none", "-"),
+    ("incident.title", "Incident #{id}"),
+    ("incident.not_found", "Incident #{id} was not found."),
+    ("incident.ongoing", "ongoing"),
+    (
+        "incident.body",
+        "Metric: {metric} (threshold {threshold}%)\nStarted: {started}\nEnded: {ended}\nDuration: {duration}\nPeak: {peak}% at {peak_at}\nAlerts sent: {alerts}\nAcknowledged: {acked}\nMuted: {muted}\n\nTimeline:\n{timeline}",
+    ),
+    ("incident.event.opened", "opened"),
+    ("incident.event.alerted", "alert repeated"),
+    ("incident.event.muted", "alerts muted"),
+    ("incident.event.acknowledged", "acknowledged"),
+    ("incident.event.resolved", "resolved"),
+    ("recent.title", "Recent anomalies"),
+    ("recent.empty", "No anomaly records found."),
+    (
+        "recent.invalid",
+        "Invalid query: {error}\n\nUsage:\n/recent\n/recent 5\n/recent 6h\n/recent cpu>85\n/recent cpu>85 ram>80 6h",
+    ),
+];
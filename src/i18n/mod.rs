@@ -0,0 +1,151 @@
+mod en;
+mod tr;
+
+use std::sync::OnceLock;
+
+use serde::Deserialize;
+
+static ACTIVE_LANGUAGE: OnceLock<Language> = OnceLock::new();
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Language {
+    #[default]
+    En,
+    Tr,
+}
+
+impl Language {
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::En => "en",
+            Self::Tr => "tr",
+        }
+    }
+}
+
+/// Selects the process-wide message language. Only the first call wins, so
+/// a language change requires a restart.
+pub fn set_language(language: Language) {
+    if ACTIVE_LANGUAGE.set(language).is_err() && language != active_language() {
+        log::warn!(
+            "language_change_ignored requested={} active={} reason=restart_required",
+            language.code(),
+            active_language().code()
+        );
+    }
+}
+
+pub fn active_language() -> Language {
+    ACTIVE_LANGUAGE.get().copied().unwrap_or_default()
+}
+
+/// Looks up `key` in the active language, falling back to English and then
+/// to the key itself.
+pub fn t(key: &'static str) -> &'static str {
+    lookup(active_language(), key)
+}
+
+/// Like [`t`] but replaces `{name}` placeholders with the given values.
+pub fn t_with(key: &'static str, vars: &[(&str, String)]) -> String {
+    fill(t(key), vars)
+}
+
+/// Substitutes placeholders in one pass over `template`, so braces inside a
+/// value are never treated as placeholders. Unknown names are kept as-is.
+fn fill(template: &str, vars: &[(&str, String)]) -> String {
+    let mut text = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(open) = rest.find('{') {
+        text.push_str(&rest[..open]);
+        let after = &rest[open + 1..];
+        let value = after.find('}').and_then(|close| {
+            vars.iter()
+                .find(|(name, _)| *name == &after[..close])
+                .map(|(_, value)| (close, value))
+        });
+        match value {
+            Some((close, value)) => {
+                text.push_str(value);
+                rest = &after[close + 1..];
+            }
+            None => {
+                text.push('{');
+                rest = after;
+            }
+        }
+    }
+    text.push_str(rest);
+    text
+}
+
+pub fn lookup(language: Language, key: &'static str) -> &'static str {
+    let translated = match language {
+        Language::En => None,
+        Language::Tr => find(tr::ENTRIES, key),
+    };
+
+    translated.or_else(|| find(en::ENTRIES, key)).unwrap_or(key)
+}
+
+fn find(entries: &'static [(&'static str, &'static str)], key: &str) -> Option<&'static str> {
+    entries
+        .iter()
+        .find(|(entry_key, _)| *entry_key == key)
+        .map(|(_, text)| *text)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::{Language, en, fill, find, lookup, tr};
+
+    fn placeholders(text: &str) -> Vec<&str> {
+        let mut names: Vec<&str> = text
+            .split('{')
+            .skip(1)
+            .filter_map(|rest| rest.split_once('}').map(|(name, _)| name))
+            .collect();
+        names.sort_unstable();
+        names
+    }
+
+    #[test]
+    fn catalogs_have_unique_keys() {
+        for entries in [en::ENTRIES, tr::ENTRIES] {
+            let mut seen = HashSet::new();
+            for (key, _) in entries {
+                assert!(seen.insert(*key), "duplicate catalog key {key}");
+            }
+        }
+    }
+
+    #[test]
+    fn turkish_entries_match_english_keys_and_placeholders() {
+        for (key, text) in tr::ENTRIES {
+            let english =
+                find(en::ENTRIES, key).unwrap_or_else(|| panic!("tr key {key} is missing in en"));
+            assert_eq!(placeholders(text), placeholders(english), "key {key}");
+        }
+    }
+
+    #[test]
+    fn falls_back_to_english_then_key() {
+        assert_eq!(lookup(Language::Tr, "common.yes"), "evet");
+        assert_eq!(lookup(Language::En, "common.yes"), "yes");
+        assert_eq!(lookup(Language::Tr, "no.such.key"), "no.such.key");
+    }
+
+    #[test]
+    fn placeholders_are_filled_in_one_pass() {
+        let vars = [
+            ("name", "{detail}".to_string()),
+            ("detail", "disk".to_string()),
+        ];
+        assert_eq!(
+            fill("{name} / {detail} / {unknown}", &vars),
+            "{detail} / disk / {unknown}"
+        );
+    }
+}
@@ -0,0 +1,480 @@
+pub(super) const ENTRIES: &[(&str, &str)] = &[
+    ("common.yes", "evet"),
+    ("common.no", "hayır"),
+    // Menüler
+    ("menu.button.status", "📊 Durum"),
+    ("menu.button.health", "💓 Sağlık"),
+    ("menu.button.alerts", "🚨 Alarmlar"),
+    ("menu.button.monitor", "📈 İzleme"),
+    ("menu.button.data", "📦 Veri"),
+    ("menu.button.system", "🖥️ Sistem"),
+    ("menu.button.help", "❓ Yardım"),
+    ("menu.button.sys_snapshot", "📦 Sistem Özeti"),
+    ("menu.button.cpu", "🧠 CPU"),
+    ("menu.button.temp", "🌡️ Sıcaklık"),
+    ("menu.button.network", "🌐 Ağ"),
+    ("menu.button.uptime", "⏱️ Çalışma Süresi"),
+    ("menu.button.ports", "🔌 Portlar"),
+    ("menu.button.services", "🧩 Servisler"),
+    ("menu.button.main_menu", "⬅️ Ana Menü"),
+    ("menu.button.graph_cpu_1h", "📈 CPU 1sa"),
+    ("menu.button.graph_ram_1h", "💾 RAM 1sa"),
+    ("menu.button.graph_disk_1h", "🧱 Disk 1sa"),
+    ("menu.button.recent_6h", "🧾 Son 6sa"),
+    ("menu.button.recent_24h", "🧾 Son 24sa"),
+    ("menu.button.export_cpu", "📤 CPU Dışa Aktar"),
+    ("menu.button.export_ram", "📤 RAM Dışa Aktar"),
+    ("menu.button.export_disk", "📤 Disk Dışa Aktar"),
+    ("menu.button.mute_1h", "🔇 1sa Sustur"),
+    ("menu.button.unmute", "🔔 Sesi Aç"),
+    ("menu.main.title", "Ana Menü"),
+    (
+        "menu.main.body",
+        "İşlemleri doğrudan butonlarla çalıştırın. Komut yazmanıza gerek yok.",
+    ),
+    ("menu.system.title", "Ana Menü › Sistem"),
+    (
+        "menu.system.body",
+        "Canlı sunucu tanılaması ve servis kontrolleri.",
+    ),
+    ("menu.monitor.title", "Ana Menü › İzleme"),
+    (
+        "menu.monitor.body",
+        "Alarm kontrolleri, grafikler ve anomali ayrıntıları.",
+    ),
+    ("menu.data.title", "Ana Menü › Veri"),
+    (
+        "menu.data.body",
+        "Son anomaliler ve dışa aktarma kısayolları.",
+    ),
+    ("menu.help.title", "Ana Menü › Yardım"),
+    (
+        "menu.help.body",
+        "1) Bir menü açın\n2) Bir işleme dokunun\n3) Devam etmek için ⬅️ Ana Menü'yü kullanın\n\nİpucu: Eğik çizgi komutları hâlâ çalışır, ancak tüm yaygın işlemler butonlarla da yapılabilir.",
+    ),
+    ("menu.next.title", "Sonraki"),
+    (
+        "menu.next.body",
+        "Aşağıdaki menü butonlarıyla devam edin veya tüm komutlar için /help kullanın.",
+    ),
+    // Yardım ve sağlık
+    ("help.title", "Kontrol Merkezi"),
+    (
+        "help.body",
+        "• Yaygın işlemler için aşağıdaki butonları kullanın.\n\n<b>Ana akışlar</b>\n• Sağlık ve durum kontrolleri\n• Sistem tanılaması\n• İzleme, alarmlar ve grafikler\n• Son anomaliler ve dışa aktarımlar\n\nİsterseniz eğik çizgi komutlarını elle de kullanabilirsiniz.",
+    ),
+    ("health.title", "Bot Sağlığı"),
+    (
+        "health.delayed",
+        "⚠️ KRİTİK: İzleme döngüsü gecikiyor. Son tik: {lag}sn önce (eşik: {threshold}sn)",
+    ),
+    (
+        "health.healthy",
+        "✅ Sağlıklı. Son izleme tiki: {lag}sn önce (eşik: {threshold}sn)",
+    ),
+    (
+        "health.details",
+        "{status}\n\nİzleme aralığı: {interval}sn\nŞu anki zaman: {now}\nSon tik: {tick}",
+    ),
+    (
+        "health.warming_up",
+        "⏳ Hazırlanıyor...\n\nİzleme döngüsü henüz ilk tikini üretmedi.\nİzleme aralığı: {interval}sn\nŞu anki zaman: {now}",
+    ),
+    // Durum
+    ("status.title", "Bot Durumu"),
+    ("status.muted_until", "{until} tarihine kadar sessiz"),
+    ("status.not_muted", "sessiz değil"),
+    ("status.not_available", "henüz yok"),
+    ("status.none", "yok"),
+    ("status.integrity.unchecked", "henüz kontrol edilmedi"),
+    ("status.integrity.checked", "{summary} ({at})"),
+    ("status.integrity.clean", "temiz"),
+    ("status.integrity.issues", "sorun bulundu"),
+    ("status.integrity.repaired", "sorunlar bulundu ve onarıldı"),
+    ("status.integrity.sled", "sled altyapısında kullanılmıyor"),
+    ("status.maintenance.active", "Etkin (Saatlik)"),
+    ("status.maintenance.disabled", "Kapalı"),
+    (
+        "status.body",
+        "Yetki Modu: Yalnızca Sahip (DM)\nDepolama: Hiyerarşik JSONL + İndeksli\nBakım: {maintenance}\nSaklama: {retention_days} gün\nAnomali dizini: {anomaly_dir}\nBütünlük: {integrity}\n\nÇalışma Zamanı:\n- İzleme aralığı: {interval}sn\n- Son izleme turu: {last_tick}\n- Sessiz durumu: {mute_state}\n\nSon {spark_minutes}dk:\n{sparklines}\n\nYaklaşan Raporlar:\n{schedule}\n\nRapor Deposu:\n- etkin: {store_enabled}\n- yol: {store_path}\n- saklama: {store_retention_days} gün\n\nGüvenlik:\n- redact_sensitive_output: {redact}\n\nSimülasyon:\n- etkin: {simulation_enabled}\n- profil: {simulation_profile}\n\nGrafik Ayarları:\n- etkin: {graph_enabled}\n- varsayılan aralık: {graph_default_window}dk\n- en uzun aralık: {graph_max_window}sa\n- en fazla nokta: {graph_max_points}\n\nYetenekler:\n- is_systemd: {is_systemd}\n- has_sensors: {has_sensors}\n- has_free: {has_free}\n- has_top: {has_top}\n- has_ip: {has_ip}\n- has_ss: {has_ss}\n- has_uptime: {has_uptime}\n\nDüğme öncelikli kullanım:\n- İşlemleri doğrudan aşağıdaki menü düğmeleriyle çalıştırın.\n- Eğik çizgi komutları gelişmiş sorgular için isteğe bağlıdır.\n\nGelişmiş örnekler:\n/recent\n/recent 5\n/recent 6h\n/recent cpu>85",
+    ),
+    // Alarmlar
+    ("alerts.title", "Alarm Yapılandırması"),
+    (
+        "alerts.body",
+        "Eşikler:\n- CPU: {cpu}%\n- RAM: {ram}%\n- Disk: {disk}%\n\nKontrol:\n- Bekleme süresi: {cooldown}sn\n- Histerezis: {hysteresis}%\n- Sessize alma: {mute}\n- Sessiz saatler: {quiet_hours}\n- Son günlük özet: {last_summary}\n\nMevcut Durum:\n- CPU alarmı: {cpu_alerting}\n- RAM alarmı: {ram_alerting}\n- Disk alarmı: {disk_alerting}",
+    ),
+    (
+        "alerts.muted_remaining",
+        "sessizde ({until} zamanına kadar {remaining}sn kaldı)",
+    ),
+    ("alerts.not_muted", "sessizde değil"),
+    ("alerts.disabled", "kapalı"),
+    ("alerts.not_generated", "henüz oluşturulmadı"),
+    (
+        "alerts.quiet_hours",
+        "{start}-{end} {timezone} ({mode}, kritik >= {critical}%, bekleyen: {held})",
+    ),
+    ("alerts.mute_failed.title", "Sessize alma başarısız"),
+    (
+        "alerts.mute_failed.body",
+        "Geçersiz süre. Şu biçimi kullanın: <b>30s</b>, <b>15m</b>, <b>2h</b>, <b>1d</b>.",
+    ),
+    ("alerts.mute_cooldown.title", "Sessize alma beklemesi"),
+    ("alerts.unmute_cooldown.title", "Sesi açma beklemesi"),
+    (
+        "alerts.cooldown.body",
+        "Sessize alma durumunu tekrar değiştirmeden önce lütfen <b>{secs}sn</b> bekleyin.",
+    ),
+    ("alerts.muted.title", "Alarmlar sessize alındı ✅"),
+    (
+        "alerts.muted.body",
+        "Alarmlar <b>{until}</b> zamanına kadar sessizde.\n\nAşağıdaki İzleme menüsünden devam edebilirsiniz.",
+    ),
+    ("alerts.unmuted.title", "Alarmların sesi açıldı ✅"),
+    (
+        "alerts.unmuted.body",
+        "Alarmlar yeniden etkin.\n\nAşağıdaki İzleme menüsünden devam edebilirsiniz.",
+    ),
+    // Alarm ve rapor metinleri
+    (
+        "template.alert",
+        "⚠️ ALARM: {metric} kullanımı yüksek ({value}%)",
+    ),
+    (
+        "template.daily_summary",
+        "📅 Günlük Özet\n\nÖrnek sayısı: {samples}\nTetiklenen alarmlar: {alerts}\n\nCPU ort/min/maks: {cpu_avg}% / {cpu_min}% / {cpu_max}%\nRAM ort/min/maks: {ram_avg}% / {ram_min}% / {ram_max}%\nDisk ort/min/maks: {disk_avg}% / {disk_min}% / {disk_max}%\n\nOluşturulma zamanı: {generated_at}",
+    ),
+    (
+        "template.weekly_caption",
+        "📈 Haftalık {metric} ({duration}) | örnek: {samples} | min: {min}% | maks: {max}% | ort: {avg}%{labels}{rollup}\nOlaylar: {incidents} | MTTR: {mttr}",
+    ),
+    // Özetler
+    (
+        "summary.daily.empty",
+        "📅 Günlük Özet\n\nSon özet aralığından bu yana izleme örneği toplanmadı.\nOluşturulma zamanı: {generated_at}",
+    ),
+    (
+        "summary.weekly.failed",
+        "📈 Haftalık Rapor\n\nBu döngüde grafik oluşturulamadı: {error}",
+    ),
+    (
+        "summary.monthly.caption",
+        "📊 Kapasite Raporu {month}\nCPU p95: {cpu_p95}% | RAM p95: {ram_p95}% | Disk maks: {disk_max}%\nAlarmlar: {alerts} | Olaylar: {incidents} | MTTR: {mttr}\n{growth}",
+    ),
+    (
+        "summary.monthly.document",
+        "Kapasite Raporu {month} ({timezone}, {days} gün)\nDönem: {from} - {to} {timezone}; günlük satırlar UTC günleridir\n\nYüzdelikler\n{metrics}\n\nGönderilen alarmlar: {alerts}\nOlaylar: {incidents} ({by_metric})\nMTTR: {mttr}\n\n{growth}\nEn yoğun saatler (CPU ort): {busiest}\n\nGünlük ort/maks\n{daily}\n",
+    ),
+    (
+        "summary.monthly.document.caption",
+        "📄 {month} için tam kapasite raporu",
+    ),
+    (
+        "summary.monthly.growth",
+        "Disk büyümesi: günde {rate}%, 30 günde tahmini {projected}%, ~{full_days} günde dolu",
+    ),
+    (
+        "summary.monthly.growth.flat",
+        "Disk büyümesi: günde {rate}%, dolmuyor",
+    ),
+    (
+        "summary.monthly.failed",
+        "📊 Kapasite Raporu\n\nAylık rapor oluşturulamadı: {error}",
+    ),
+    (
+        "summary.quiet_digest",
+        "🌙 Sessiz Saatler Özeti\n\nSessiz saatlerde {count} alarm bekletildi:\n{alerts}",
+    ),
+    (
+        "summary.hourly_digest",
+        "🕐 Saatlik Özet\n\nÖrnek sayısı: {samples}\nCPU ort/maks: {cpu_avg}% / {cpu_max}%\nRAM ort/maks: {ram_avg}% / {ram_max}%\nDisk ort/maks: {disk_avg}% / {disk_max}%",
+    ),
+    (
+        "summary.hourly_digest.empty",
+        "🕐 Saatlik Özet\n\nSon bir saatte izleme örneği toplanmadı.",
+    ),
+    // Sistem bilgisi ve güncellemeler
+    ("update.title", "Güncelleme"),
+    ("update.check.title", "Güncelleme Kontrolü"),
+    ("update.usage.title", "Güncelleme Kullanımı"),
+    ("update.usage", "Kullanım:\n/update check\n/update apply"),
+    (
+        "update.up_to_date",
+        "Daha yeni bir sürüm bulunamadı. Mevcut v{version} güncel.",
+    ),
+    (
+        "update.no_systemd",
+        "Bu sunucuda kontrollü yeniden başlatma kullanılamıyor (systemd bulunamadı).",
+    ),
+    (
+        "update.blocked",
+        "Güncelleme ön kontroller tarafından engellendi.\n{detail}",
+    ),
+    (
+        "update.starting",
+        "v{version} sürümüne güncelleme başlatılıyor. Servis bu sırada yeniden başlayabilir.",
+    ),
+    (
+        "update.restarting",
+        "{message}\nServis yeniden başlatılıyor...",
+    ),
+    (
+        "update.restart_failed",
+        "Yeni sürüm kuruldu ancak servis yeniden başlatılamadı: {error}\nElle yeniden başlatma gerekli: systemctl restart kars-bot",
+    ),
+    ("update.apply_failed", "Güncelleme uygulanamadı: {error}"),
+    (
+        "update.check_failed",
+        "Uygulamadan önce güncelleme kontrolü başarısız: {error}",
+    ),
+    (
+        "update.readiness.ready",
+        "Uygulama durumu: hazır\n{details}",
+    ),
+    (
+        "update.readiness.blocked",
+        "Uygulama durumu: engellendi\n{details}",
+    ),
+    (
+        "update.readiness.failed",
+        "Uygulama durumu: kontrol başarısız ({error})",
+    ),
+    (
+        "update.check.available",
+        "Mevcut: v{current}\nEn son: v{latest}\n\nGüncelleme mevcut.\nKontrollü yeniden başlatma için /update apply çalıştırın.\n\n{readiness}",
+    ),
+    (
+        "update.check.current",
+        "Mevcut: v{current}\nEn son: v{latest}\n\nSürümünüz güncel.\n\n{readiness}",
+    ),
+    ("update.no_update", "Mevcut sürüm için güncelleme yok."),
+    (
+        "update.not_applied",
+        "Güncelleme uygulanmadı (zaten güncel).",
+    ),
+    (
+        "update.applied",
+        "Güncellendi: v{current} -> v{latest} ({tag})",
+    ),
+    ("system.cpu.title", "CPU Kullanımı"),
+    ("system.cpu.empty", "CPU çıktısı yok."),
+    ("system.network.title", "Ağ İstatistikleri"),
+    ("system.network.interfaces", "Arayüzler: {interfaces}"),
+    (
+        "system.network.no_interfaces",
+        "Arayüzler: loopback dışında arayüz bulunamadı",
+    ),
+    ("system.uptime.title", "Sistem Çalışma Süresi"),
+    ("system.temp.title", "Sıcaklık Sensörleri"),
+    ("system.snapshot.title", "Sistem Özeti"),
+    (
+        "system.snapshot.memory",
+        "Bellek: kullanılan {used} / toplam {total} (boş {free})",
+    ),
+    (
+        "system.snapshot.memory_unavailable",
+        "Bellek özeti alınamadı",
+    ),
+    (
+        "system.snapshot.disk",
+        "Disk (/): kullanılan {used} / boyut {size} ({percent} dolu, boş {avail})",
+    ),
+    ("system.snapshot.disk_unavailable", "Disk özeti alınamadı"),
+    (
+        "system.snapshot.body",
+        "Özet:\n- {memory}\n- {disk}\n\nRAM (ayrıntılar):\n{memory_details}\n\nDisk (ayrıntılar):\n{disk_details}",
+    ),
+    ("system.ports.title", "Açık Portlar"),
+    ("system.services.title", "Aktif Servisler"),
+    ("system.services.empty", "Servis çıktısı yok."),
+    // Hatalar
+    ("error.command_queue.title", "Komut kuyruğu hatası"),
+    (
+        "error.command_queue.body",
+        "Komut sırası alınamadı. Lütfen tekrar deneyin.",
+    ),
+    (
+        "error.output_too_long",
+        "Çıktı bir Telegram mesajı için çok uzun. Dosya eki olarak gönderildi.",
+    ),
+    (
+        "error.unsupported_feature",
+        "Bu özellik bu sistemde desteklenmiyor. Eksik bağımlılık: {command}",
+    ),
+    ("error.not_enough_samples", "henüz yeterli örnek yok"),
+    ("graph.disabled.title", "Grafik Kapalı"),
+    (
+        "graph.disabled.body",
+        "Grafik özelliği kapalı (çalışma zamanı yapılandırması). Gerekirse yapılandırmadan etkinleştirin.",
+    ),
+    ("graph.usage.title", "Grafik Kullanımı"),
+    (
+        "graph.usage",
+        "Kullanım: /graph cpu|ram|disk[,ram,...] [<Nm|Nh>|<YYYY-MM-DD>..<YYYY-MM-DD>] [vs <N(m|h|d)>-ago|compare=yesterday|lastweek] [light|dark] [<W>x<H>] [png|svg]",
+    ),
+    ("graph.cooldown.title", "Grafik Beklemesi"),
+    (
+        "graph.cooldown.body",
+        "/graph komutunu tekrar kullanmadan önce lütfen {secs}sn bekleyin.",
+    ),
+    ("graph.title", "{metric} Grafiği"),
+    (
+        "graph.compare.no_data",
+        "{period} önceki aynı aralık için yeterli kayıtlı örnek yok.",
+    ),
+    ("graph.compare.ago", "{period} önce"),
+    (
+        "graph.compare.caption",
+        "{period} ile: ort. %{avg} ({avg_delta}) | en yüksek %{max} ({max_delta})",
+    ),
+    ("graph.axis.now", "şimdi"),
+    (
+        "graph.caption.summary",
+        "en düşük: %{min} | en yüksek: %{max} | ort.: %{avg}",
+    ),
+    (
+        "graph.caption.metric_summary",
+        "{metric} en düşük/en yüksek/ort.: %{min} / %{max} / %{avg}",
+    ),
+    ("graph.render.title", "Grafik Çizimi"),
+    ("heatmap.usage.title", "Isı Haritası Kullanımı"),
+    (
+        "heatmap.usage",
+        "Kullanım: /heatmap cpu|ram|disk [<Nd|Nw>] [avg|p95] [light|dark] [png|svg]",
+    ),
+    ("spark.usage.title", "Mini Grafik Kullanımı"),
+    ("spark.usage", "Kullanım: /spark [<Nm|Nh>]"),
+    ("spark.title", "Mini Grafikler (son {window})"),
+    ("alert.sparkline", "son {window}: {sparkline}"),
+    ("heatmap.title", "{metric} Isı Haritası"),
+    (
+        "heatmap.caption",
+        "{metric} ısı haritası ({window}, saatlik {stat}, {timezone}) | en yoğun: {busiest} | en sakin: {quietest}",
+    ),
+    (
+        "graph.error.font",
+        "Grafik motorunun gömülü yazı tipi yüklenemedi. Lütfen derleme dosyalarını kontrol edip yeniden başlatın.",
+    ),
+    (
+        "graph.error.busy",
+        "Grafik çizici meşgul. Lütfen birazdan tekrar deneyin.",
+    ),
+    (
+        "graph.error.timeout",
+        "Grafik çizimi çok uzun sürdü ve iptal edildi. Lütfen daha kısa bir aralık deneyin.",
+    ),
+    (
+        "graph.error.generic",
+        "Grafik şu anda çizilemedi. Lütfen tekrar deneyin.",
+    ),
+    ("export.disabled.title", "Dışa Aktarma Kapalı"),
+    (
+        "export.disabled.body",
+        "Dışa aktarma özelliği yapılandırmada kapalı.",
+    ),
+    ("export.usage.title", "Dışa Aktarma Kullanımı"),
+    (
+        "export.usage",
+        "Kullanım: /export cpu|ram|disk[,ram,...]|all [<Nm|Nh>] [csv|json|ndjson|openmetrics|influx]\n/export cpu|ram|disk[,ram,...]|all from=<YYYY-MM-DD[THH:MM]> [to=<YYYY-MM-DD[THH:MM]>] [biçim]",
+    ),
+    ("export.title", "Dışa Aktarma"),
+    ("export.failed", "Dışa aktarma oluşturulamadı: {error}"),
+    (
+        "export.too_large",
+        "dışa aktarma gzip ile bile Telegram'ın 50 MB sınırını aşıyor; daha kısa bir dönem seçin",
+    ),
+    (
+        "export.compressed",
+        "Telegram'ın 50 MB yükleme sınırına sığması için gzip ile sıkıştırıldı.",
+    ),
+    (
+        "export.rollup_averages",
+        "Dönem ham örneklerin saklandığı süreden önce başlıyor; satırlar {bucket} ortalamalarıdır.",
+    ),
+    (
+        "export.caption",
+        "{metric} için {count} örnek dışa aktarıldı ({window})",
+    ),
+    ("backup.title", "Yedek"),
+    (
+        "backup.caption",
+        "🗄️ Yedek: {files} dosya, {bytes} bayt ({components})",
+    ),
+    (
+        "backup.too_large",
+        "Yedek oluşturuldu ({bytes} bayt) ancak Telegram yükleme sınırını aşıyor.\nSunucuda kaydedildi: {path}",
+    ),
+    ("backup.failed", "Yedek oluşturulamadı: {error}"),
+    ("storage.title", "Depolama Kullanımı"),
+    ("storage.failed", "Depolama kullanımı alınamadı: {error}"),
+    ("storage.none", "-"),
+    ("storage.reporting.disabled", "Raporlama deposu: kapalı"),
+    (
+        "storage.reporting",
+        "Raporlama deposu ({path})\nDisk boyutu: {size}\n{trees}\n{retention_days} günlük ham saklamada tahmini boyut: {projected}",
+    ),
+    (
+        "storage.tree",
+        "- {name}: {entries} kayıt, {oldest} → {newest}",
+    ),
+    ("storage.anomaly.disabled", "Anomali DB: kapalı"),
+    (
+        "storage.anomaly",
+        "Anomali DB ({dir})\nDosyalar: {event_files} olay ({rotated} döndürülmüş), {index_files} indeks\nDosya boyutu: {file_size}, sled deposu: {store_size}\nGünler: {oldest} → {newest}\n{retention_days} günlük saklamada tahmini boyut: {projected}",
+    ),
+    ("dbcheck.title", "Anomali DB Kontrolü"),
+    ("dbcheck.usage", "Kullanım: /dbcheck veya /dbcheck repair"),
+    ("dbcheck.disabled", "Anomali DB yapılandırmada kapalı."),
+    (
+        "dbcheck.sled",
+        "Anomali DB sled altyapısını kullanıyor; bütünlük kontrolü yalnızca JSONL dosyalarını kapsar.",
+    ),
+    ("dbcheck.failed", "Bütünlük kontrolü başarısız: {error}"),
+    ("dbcheck.status.clean", "✅ Sorun bulunamadı"),
+    (
+        "dbcheck.status.issues",
+        "⚠️ Sorun bulundu, düzeltmek için /dbcheck repair çalıştırın",
+    ),
+    ("dbcheck.status.repaired", "🛠️ Sorunlar bulundu ve onarıldı"),
+    (
+        "dbcheck.result",
+        "{status}\n\nDosyalar: {event_files} olay / {index_files} indeks\nSatırlar: {event_lines} olay / {index_lines} indeks\nBozuk satırlar: {corrupt_events} olay / {corrupt_index} indeks\nOlayı olmayan indeks kayıtları: {index_only}\nİndekste olmayan olaylar: {unindexed}\nYarım kalmış son satırlar: {partial}\nYeniden oluşturulan indeks günleri: {rebuilt}",
+    ),
+    ("incidents.title", "Olaylar"),
+    (
+        "incidents.usage",
+        "Kullanım:\n/incidents [7d]\n/incident <id> [ack]",
+    ),
+    ("incidents.empty", "Son {window} içinde olay yok."),
+    (
+        "incidents.summary",
+        "Son {window} içinde {count} olay | açık: {open} | MTTR: {mttr}",
+    ),
+    (
+        "incidents.line",
+        "#{id} {metric} | {started} | {duration} | zirve {peak}% (>{threshold}%) | uyarı: {alerts}{flags}",
+    ),
+    ("incidents.flag.open", "açık"),
+    ("incidents.flag.acked", "onaylandı"),
+    ("incidents.flag.muted", "sessize alındı"),
+    ("incidents.none", "-"),
+    ("incident.title", "Olay #{id}"),
+    ("incident.not_found", "#{id} numaralı olay bulunamadı."),
+    ("incident.ongoing", "devam ediyor"),
+    (
+        "incident.body",
+        "Metrik: {metric} (eşik {threshold}%)\nBaşlangıç: {started}\nBitiş: {ended}\nSüre: {duration}\nZirve: {peak}% ({peak_at})\nGönderilen uyarı: {alerts}\nOnay: {acked}\nSessize alındı: {muted}\n\nZaman çizelgesi:\n{timeline}",
+    ),
+    ("incident.event.opened", "başladı"),
+    ("incident.event.alerted", "uyarı tekrarlandı"),
+    ("incident.event.muted", "uyarılar sessize alındı"),
+    ("incident.event.acknowledged", "onaylandı"),
+    ("incident.event.resolved", "çözüldü"),
+    ("recent.title", "Son anomaliler"),
+    ("recent.empty", "Anomali kaydı bulunamadı."),
+    (
+        "recent.invalid",
+        "Geçersiz sorgu: {error}\n\nKullanım:\n/recent\n/recent 5\n/recent 6h\n/recent cpu>85\n/recent cpu>85 ram>80 6h",
+    ),
+];
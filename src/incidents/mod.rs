@@ -0,0 +1,268 @@
+//! Incidents derived from alert state transitions: one per metric from the
+//! moment it crosses its threshold until it clears the hysteresis band.
+
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::versioned::Versioned;
+
+/// Repeat alerts beyond this many timeline entries are only counted.
+const MAX_TIMELINE_EVENTS: usize = 50;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IncidentMetric {
+    Cpu,
+    Ram,
+    Disk,
+}
+
+impl IncidentMetric {
+    pub const ALL: [Self; 3] = [Self::Cpu, Self::Ram, Self::Disk];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Cpu => "CPU",
+            Self::Ram => "RAM",
+            Self::Disk => "Disk",
+        }
+    }
+
+    pub(crate) fn index(self) -> usize {
+        match self {
+            Self::Cpu => 0,
+            Self::Ram => 1,
+            Self::Disk => 2,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IncidentEventKind {
+    Opened,
+    Alerted,
+    Muted,
+    Acknowledged,
+    Resolved,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IncidentEvent {
+    pub at: String,
+    pub kind: IncidentEventKind,
+    #[serde(default)]
+    pub value: Option<f32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Incident {
+    pub id: u64,
+    pub metric: IncidentMetric,
+    pub threshold: f32,
+    pub started_at: String,
+    #[serde(default)]
+    pub ended_at: Option<String>,
+    pub peak: f32,
+    pub peak_at: String,
+    pub alerts_sent: u32,
+    #[serde(default)]
+    pub acked_at: Option<String>,
+    #[serde(default)]
+    pub muted: bool,
+    #[serde(default)]
+    pub timeline: Vec<IncidentEvent>,
+}
+
+impl Versioned for Incident {
+    const KIND: &'static str = "incident";
+    const VERSION: u32 = 1;
+}
+
+fn parse_time(raw: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(raw)
+        .ok()
+        .map(|time| time.with_timezone(&Utc))
+}
+
+impl Incident {
+    pub fn open(
+        id: u64,
+        metric: IncidentMetric,
+        threshold: f32,
+        value: f32,
+        now: DateTime<Utc>,
+    ) -> Self {
+        let mut incident = Self {
+            id,
+            metric,
+            threshold,
+            started_at: now.to_rfc3339(),
+            ended_at: None,
+            peak: value,
+            peak_at: now.to_rfc3339(),
+            alerts_sent: 0,
+            acked_at: None,
+            muted: false,
+            timeline: Vec::new(),
+        };
+        incident.push_event(now, IncidentEventKind::Opened, Some(value));
+        incident
+    }
+
+    pub fn started(&self) -> Option<DateTime<Utc>> {
+        parse_time(&self.started_at)
+    }
+
+    pub fn ended(&self) -> Option<DateTime<Utc>> {
+        self.ended_at.as_deref().and_then(parse_time)
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.ended_at.is_none()
+    }
+
+    /// Time from start to resolution, or to `now` while still open.
+    pub fn duration(&self, now: DateTime<Utc>) -> Option<ChronoDuration> {
+        let started = self.started()?;
+        let ended = if self.is_open() { now } else { self.ended()? };
+        Some((ended - started).max(ChronoDuration::zero()))
+    }
+
+    /// Records `value` as the new peak when it is higher. Returns whether it was.
+    pub fn observe(&mut self, value: f32, now: DateTime<Utc>) -> bool {
+        if value <= self.peak {
+            return false;
+        }
+        self.peak = value;
+        self.peak_at = now.to_rfc3339();
+        true
+    }
+
+    pub fn record_alert(&mut self, value: f32, now: DateTime<Utc>) {
+        self.alerts_sent = self.alerts_sent.saturating_add(1);
+        if self.alerts_sent > 1 {
+            self.push_event(now, IncidentEventKind::Alerted, Some(value));
+        }
+    }
+
+    /// Marks the incident as muted once. Returns whether this call changed it.
+    pub fn record_mute(&mut self, now: DateTime<Utc>) -> bool {
+        if self.muted {
+            return false;
+        }
+        self.muted = true;
+        self.push_event(now, IncidentEventKind::Muted, None);
+        true
+    }
+
+    /// Acknowledges the incident once. Returns whether this call changed it.
+    pub fn acknowledge(&mut self, now: DateTime<Utc>) -> bool {
+        if self.acked_at.is_some() {
+            return false;
+        }
+        self.acked_at = Some(now.to_rfc3339());
+        self.push_event(now, IncidentEventKind::Acknowledged, None);
+        true
+    }
+
+    pub fn resolve(&mut self, value: Option<f32>, now: DateTime<Utc>) {
+        self.ended_at = Some(now.to_rfc3339());
+        self.push_event(now, IncidentEventKind::Resolved, value);
+    }
+
+    fn push_event(&mut self, now: DateTime<Utc>, kind: IncidentEventKind, value: Option<f32>) {
+        if kind == IncidentEventKind::Alerted && self.timeline.len() >= MAX_TIMELINE_EVENTS {
+            return;
+        }
+        self.timeline.push(IncidentEvent {
+            at: now.to_rfc3339(),
+            kind,
+            value,
+        });
+    }
+}
+
+/// Counts and mean time to resolve over a set of incidents.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct IncidentStats {
+    pub count: usize,
+    pub open: usize,
+    pub mttr: Option<ChronoDuration>,
+}
+
+impl IncidentStats {
+    pub fn from_incidents(incidents: &[Incident]) -> Self {
+        let resolved = incidents
+            .iter()
+            .filter_map(|incident| Some(incident.ended()? - incident.started()?))
+            .collect::<Vec<_>>();
+        let mttr = (!resolved.is_empty()).then(|| {
+            let total_secs = resolved
+                .iter()
+                .map(ChronoDuration::num_seconds)
+                .sum::<i64>();
+            ChronoDuration::seconds(total_secs / resolved.len() as i64)
+        });
+
+        Self {
+            count: incidents.len(),
+            open: incidents
+                .iter()
+                .filter(|incident| incident.is_open())
+                .count(),
+            mttr,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, TimeZone, Utc};
+
+    use super::{Incident, IncidentEventKind, IncidentMetric, IncidentStats};
+    use crate::versioned::{decode, encode};
+
+    #[test]
+    fn lifecycle_builds_timeline_and_stats() {
+        let start = Utc.with_ymd_and_hms(2026, 10, 12, 8, 0, 0).unwrap();
+        let mut first = Incident::open(1, IncidentMetric::Cpu, 85.0, 91.0, start);
+        first.record_alert(91.0, start);
+        assert!(first.observe(97.5, start + Duration::minutes(2)));
+        assert!(!first.observe(93.0, start + Duration::minutes(3)));
+        first.record_alert(95.0, start + Duration::minutes(5));
+        assert!(first.record_mute(start + Duration::minutes(6)));
+        assert!(!first.record_mute(start + Duration::minutes(7)));
+        assert!(first.acknowledge(start + Duration::minutes(8)));
+        first.resolve(Some(70.0), start + Duration::minutes(20));
+
+        let kinds = first
+            .timeline
+            .iter()
+            .map(|event| event.kind)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            kinds,
+            [
+                IncidentEventKind::Opened,
+                IncidentEventKind::Alerted,
+                IncidentEventKind::Muted,
+                IncidentEventKind::Acknowledged,
+                IncidentEventKind::Resolved,
+            ]
+        );
+        assert_eq!(first.alerts_sent, 2);
+        assert_eq!(first.peak, 97.5);
+
+        let round_trip = decode::<Incident>(&encode(&first).unwrap()).unwrap();
+        assert_eq!(round_trip.duration(Utc::now()), Some(Duration::minutes(20)));
+
+        let mut second = Incident::open(2, IncidentMetric::Disk, 90.0, 92.0, start);
+        second.resolve(None, start + Duration::minutes(10));
+        let open = Incident::open(3, IncidentMetric::Ram, 90.0, 95.0, start);
+        let stats = IncidentStats::from_incidents(&[first, second, open]);
+        assert_eq!(stats.count, 3);
+        assert_eq!(stats.open, 1);
+        assert_eq!(stats.mttr, Some(Duration::minutes(15)));
+    }
+}
@@ -1,4 +1,5 @@
 use std::fs;
+use std::path::Path;
 
 use tempfile::tempdir;
 
@@ -10,7 +11,13 @@ use crate::{
 
 use super::apply_runtime_reload_from_path;
 
-fn config_toml(monitor_interval: u64, timeout_secs: u64, cpu_threshold: f32) -> String {
+fn config_toml(
+    monitor_interval: u64,
+    timeout_secs: u64,
+    cpu_threshold: f32,
+    anomaly_dir: &Path,
+) -> String {
+    let anomaly_dir = anomaly_dir.display();
     format!(
         r#"bot_token = "123456:abc"
 owner_id = 123456789
@@ -43,7 +50,7 @@ max_points = 1200
 
 [anomaly_db]
 enabled = true
-dir = "logs"
+dir = "{anomaly_dir}"
 max_file_size_bytes = 10485760
 retention_days = 7
 
@@ -68,7 +75,11 @@ state_path = "logs/release_notifier.state"
 async fn hot_reload_applies_valid_runtime_changes_without_restart() {
     let temp = tempdir().expect("tempdir should be created");
     let config_path = temp.path().join("config.toml");
-    fs::write(&config_path, config_toml(30, 30, 85.0)).expect("initial config should be written");
+    fs::write(
+        &config_path,
+        config_toml(30, 30, 85.0, &temp.path().join("anomaly_db")),
+    )
+    .expect("initial config should be written");
 
     let initial = load_config(&config_path).expect("initial config should load");
     let app = AppContext::new(
@@ -76,9 +87,14 @@ async fn hot_reload_applies_valid_runtime_changes_without_restart() {
         2,
         config_path.to_string_lossy().to_string(),
         Capabilities::detect(),
-    );
+    )
+    .expect("app context");
 
-    fs::write(&config_path, config_toml(12, 45, 72.5)).expect("updated config should be written");
+    fs::write(
+        &config_path,
+        config_toml(12, 45, 72.5, &temp.path().join("anomaly_db")),
+    )
+    .expect("updated config should be written");
 
     let applied = apply_runtime_reload_from_path(&app, &config_path.to_string_lossy())
         .await
@@ -97,7 +113,11 @@ async fn hot_reload_applies_valid_runtime_changes_without_restart() {
 async fn hot_reload_rejects_invalid_config_and_preserves_last_runtime() {
     let temp = tempdir().expect("tempdir should be created");
     let config_path = temp.path().join("config.toml");
-    fs::write(&config_path, config_toml(30, 30, 85.0)).expect("initial config should be written");
+    fs::write(
+        &config_path,
+        config_toml(30, 30, 85.0, &temp.path().join("anomaly_db")),
+    )
+    .expect("initial config should be written");
 
     let initial = load_config(&config_path).expect("initial config should load");
     let expected_runtime = RuntimeConfig::from_config(&initial);
@@ -106,9 +126,14 @@ async fn hot_reload_rejects_invalid_config_and_preserves_last_runtime() {
         2,
         config_path.to_string_lossy().to_string(),
         Capabilities::detect(),
-    );
+    )
+    .expect("app context");
 
-    fs::write(&config_path, config_toml(0, 45, 72.5)).expect("invalid config should be written");
+    fs::write(
+        &config_path,
+        config_toml(0, 45, 72.5, &temp.path().join("anomaly_db")),
+    )
+    .expect("invalid config should be written");
 
     let error = apply_runtime_reload_from_path(&app, &config_path.to_string_lossy())
         .await
@@ -1,12 +1,17 @@
 use teloxide::prelude::*;
 
 use crate::app_context::AppContext;
+use crate::config::QuietHoursMode;
 
 mod config_reload;
 mod monitor;
 mod release_notify;
+mod scheduler;
 mod schedules;
 
+pub(crate) use release_notify::deploy_history;
+pub(crate) use scheduler::{CronSchedule, upcoming_runs};
+
 pub fn start_background_jobs(bot: Bot, app_context: AppContext) {
     monitor::start_monitor_job(bot.clone(), app_context.clone());
     config_reload::start_config_hot_reload_job(app_context.clone());
@@ -16,11 +21,11 @@ pub fn start_background_jobs(bot: Bot, app_context: AppContext) {
         schedules::start_maintenance_job(app_context.clone());
     }
 
-    if app_context.config.daily_summary.enabled {
-        schedules::start_daily_summary_job(bot.clone(), app_context.clone());
+    if app_context.config.quiet_hours.enabled
+        && app_context.config.quiet_hours.mode == QuietHoursMode::Digest
+    {
+        schedules::start_quiet_hours_digest_job(bot.clone(), app_context.clone());
     }
 
-    if app_context.config.weekly_report.enabled {
-        schedules::start_weekly_report_job(bot, app_context);
-    }
+    scheduler::start_report_scheduler(bot, app_context);
 }
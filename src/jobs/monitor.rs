@@ -20,6 +20,7 @@ pub(super) fn start_monitor_job(bot: Bot, app_context: AppContext) {
         }
         let mut previous_tick = None;
         let reporting_store = app_context.reporting_store.clone();
+        let incident_store = app_context.incident_store.clone();
 
         loop {
             let runtime_config = app_context.runtime_config.read().await.clone();
@@ -48,6 +49,7 @@ pub(super) fn start_monitor_job(bot: Bot, app_context: AppContext) {
                     config: &app_context.config,
                     runtime_config: &runtime_config,
                     reporting_store: reporting_store.as_ref(),
+                    incident_store: incident_store.as_ref(),
                     anomaly_storage: app_context.anomaly_storage.as_ref(),
                     state: &app_context.monitor.alert_state,
                     metric_history: &app_context.monitor.metric_history,
@@ -1,7 +1,7 @@
 use std::{fs, path::Path, path::PathBuf};
 
-use chrono::Utc;
-use serde_json::json;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
 use teloxide::prelude::*;
 use tokio::time::{Duration, sleep};
 
@@ -9,6 +9,34 @@ use crate::app_context::AppContext;
 use crate::architecture::{adapters::TeloxideNotifier, ports::NotifierPort};
 use crate::release_notes::release_notes_for_version;
 
+/// Deploys kept in the state file for graph annotations.
+const MAX_DEPLOY_HISTORY: usize = 20;
+
+/// A version the bot announced after starting, i.e. a deploy or self-update.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct DeployRecord {
+    pub(crate) version: String,
+    pub(crate) at: String,
+}
+
+impl DeployRecord {
+    pub(crate) fn time(&self) -> Option<DateTime<Utc>> {
+        DateTime::parse_from_rfc3339(&self.at)
+            .ok()
+            .map(|time| time.with_timezone(&Utc))
+    }
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct ReleaseNotifyState {
+    #[serde(default)]
+    last_notified_version: Option<String>,
+    #[serde(default)]
+    updated_at_utc: Option<String>,
+    #[serde(default)]
+    deploys: Vec<DeployRecord>,
+}
+
 pub(super) fn start_release_notify_job(bot: Bot, app_context: AppContext) {
     if !app_context.config.release_notifier.enabled {
         return;
@@ -55,13 +83,28 @@ async fn perform_release_notify<N: NotifierPort>(notifier: &N, config: &crate::c
     }
 }
 
-fn read_notified_version(path: &Path) -> Option<String> {
+fn read_state(path: &Path) -> Option<ReleaseNotifyState> {
     let content = fs::read_to_string(path).ok()?;
-    serde_json::from_str::<serde_json::Value>(&content)
-        .ok()?
-        .get("last_notified_version")?
-        .as_str()
-        .map(std::string::ToString::to_string)
+    serde_json::from_str(&content).ok()
+}
+
+fn read_notified_version(path: &Path) -> Option<String> {
+    read_state(path)?.last_notified_version
+}
+
+/// Announced deploys, oldest first. State written before the history existed
+/// yields its single last version.
+pub(crate) fn deploy_history(path: &Path) -> Vec<DeployRecord> {
+    let Some(state) = read_state(path) else {
+        return Vec::new();
+    };
+    if !state.deploys.is_empty() {
+        return state.deploys;
+    }
+    match (state.last_notified_version, state.updated_at_utc) {
+        (Some(version), Some(at)) => vec![DeployRecord { version, at }],
+        _ => Vec::new(),
+    }
 }
 
 fn write_notified_version(path: &Path, version: &str) -> Result<(), std::io::Error> {
@@ -69,12 +112,22 @@ fn write_notified_version(path: &Path, version: &str) -> Result<(), std::io::Err
         fs::create_dir_all(parent)?;
     }
 
-    let payload = json!({
-        "last_notified_version": version,
-        "updated_at_utc": Utc::now().to_rfc3339(),
+    let now = Utc::now().to_rfc3339();
+    let mut deploys = deploy_history(path);
+    deploys.push(DeployRecord {
+        version: version.to_string(),
+        at: now.clone(),
     });
+    let excess = deploys.len().saturating_sub(MAX_DEPLOY_HISTORY);
+    deploys.drain(..excess);
 
-    fs::write(path, payload.to_string())
+    let state = ReleaseNotifyState {
+        last_notified_version: Some(version.to_string()),
+        updated_at_utc: Some(now),
+        deploys,
+    };
+    let payload = serde_json::to_string(&state).map_err(std::io::Error::other)?;
+    fs::write(path, payload)
 }
 
 #[cfg(test)]
@@ -112,4 +165,32 @@ mod tests {
         }
         assert!(fs::metadata(&config.release_notifier.state_path).is_ok());
     }
+
+    #[test]
+    fn deploy_history_upgrades_legacy_state_and_is_capped() {
+        let dir = tempdir().expect("temp dir");
+        let path = dir.path().join("state.json");
+        fs::write(
+            &path,
+            r#"{"last_notified_version":"1.8.1","updated_at_utc":"2026-10-01T08:00:00+00:00"}"#,
+        )
+        .unwrap();
+
+        write_notified_version(&path, "1.8.2").unwrap();
+        let history = deploy_history(&path);
+        let versions = history
+            .iter()
+            .map(|deploy| deploy.version.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(versions, ["1.8.1", "1.8.2"]);
+        assert!(history.iter().all(|deploy| deploy.time().is_some()));
+        assert_eq!(read_notified_version(&path).as_deref(), Some("1.8.2"));
+
+        for minor in 0..MAX_DEPLOY_HISTORY {
+            write_notified_version(&path, &format!("2.{minor}.0")).unwrap();
+        }
+        let history = deploy_history(&path);
+        assert_eq!(history.len(), MAX_DEPLOY_HISTORY);
+        assert_eq!(history[0].version, "2.0.0");
+    }
 }
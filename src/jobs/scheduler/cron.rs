@@ -0,0 +1,302 @@
+use chrono::{DateTime, Datelike, Days, NaiveDate, TimeZone, Utc};
+use chrono_tz::Tz;
+use thiserror::Error;
+
+/// Upper bound for the forward search. `parse` only accepts expressions
+/// that match a day in every year, so a run is always within a year.
+const SEARCH_DAYS: u64 = 367;
+/// A non-leap year; an expression matching none of its days (`31 2`, or
+/// `29 2` which only runs in leap years) is rejected.
+const PROBE_YEAR: i32 = 2025;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub(crate) enum CronError {
+    #[error("expected 5 fields (minute hour day-of-month month day-of-week), got {0}")]
+    FieldCount(usize),
+    #[error("invalid {field} field '{value}'")]
+    InvalidField { field: &'static str, value: String },
+    #[error("{field} value {value} is out of range {min}-{max}")]
+    OutOfRange {
+        field: &'static str,
+        value: u32,
+        min: u32,
+        max: u32,
+    },
+    #[error("expression has no run within a year")]
+    NeverRuns,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct FieldSet {
+    mask: u64,
+    restricted: bool,
+}
+
+impl FieldSet {
+    fn contains(self, value: u32) -> bool {
+        self.mask & (1u64 << value) != 0
+    }
+}
+
+/// Standard five-field cron expression evaluated in a given timezone.
+///
+/// Supports `*`, lists, ranges, steps and the `@hourly`, `@daily`, `@weekly`,
+/// `@monthly` and `@yearly` shorthands. As in classic cron, when both
+/// day-of-month and day-of-week are restricted a day matching either runs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct CronSchedule {
+    minutes: FieldSet,
+    hours: FieldSet,
+    days_of_month: FieldSet,
+    months: FieldSet,
+    days_of_week: FieldSet,
+}
+
+impl CronSchedule {
+    pub(crate) fn parse(expression: &str) -> Result<Self, CronError> {
+        let expression = match expression.trim() {
+            "@hourly" => "0 * * * *",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@weekly" => "0 0 * * 0",
+            "@monthly" => "0 0 1 * *",
+            "@yearly" | "@annually" => "0 0 1 1 *",
+            other => other,
+        };
+
+        let fields: Vec<&str> = expression.split_whitespace().collect();
+        let [minute, hour, day_of_month, month, day_of_week] = fields.as_slice() else {
+            return Err(CronError::FieldCount(fields.len()));
+        };
+
+        let mut days_of_week = parse_field(day_of_week, "day-of-week", 0, 7)?;
+        // 7 is an alias for Sunday.
+        if days_of_week.contains(7) {
+            days_of_week.mask = (days_of_week.mask & !(1 << 7)) | 1;
+        }
+
+        let schedule = Self {
+            minutes: parse_field(minute, "minute", 0, 59)?,
+            hours: parse_field(hour, "hour", 0, 23)?,
+            days_of_month: parse_field(day_of_month, "day-of-month", 1, 31)?,
+            months: parse_field(month, "month", 1, 12)?,
+            days_of_week,
+        };
+        let matches_every_year = NaiveDate::from_ymd_opt(PROBE_YEAR, 1, 1)
+            .into_iter()
+            .flat_map(|first| first.iter_days())
+            .take_while(|date| date.year() == PROBE_YEAR)
+            .any(|date| schedule.matches_date(date));
+        if !matches_every_year {
+            return Err(CronError::NeverRuns);
+        }
+        Ok(schedule)
+    }
+
+    /// First matching instant strictly after `after`. Local times that fall
+    /// into a DST gap run one hour later; repeated local times run once.
+    pub(crate) fn next_after(&self, after: DateTime<Utc>, tz: Tz) -> Option<DateTime<Utc>> {
+        let local_start = after.with_timezone(&tz).date_naive();
+
+        for offset in 0..SEARCH_DAYS {
+            let date = local_start.checked_add_days(Days::new(offset))?;
+            if !self.matches_date(date) {
+                continue;
+            }
+
+            for hour in (0..24).filter(|hour| self.hours.contains(*hour)) {
+                for minute in (0..60).filter(|minute| self.minutes.contains(*minute)) {
+                    let Some(naive) = date.and_hms_opt(hour, minute, 0) else {
+                        continue;
+                    };
+                    let candidate = tz
+                        .from_local_datetime(&naive)
+                        .earliest()
+                        .or_else(|| {
+                            tz.from_local_datetime(&(naive + chrono::Duration::hours(1)))
+                                .earliest()
+                        })
+                        .map(|value| value.with_timezone(&Utc));
+
+                    if let Some(candidate) = candidate
+                        && candidate > after
+                    {
+                        return Some(candidate);
+                    }
+                }
+            }
+        }
+
+        None
+    }
+
+    fn matches_date(&self, date: NaiveDate) -> bool {
+        if !self.months.contains(date.month()) {
+            return false;
+        }
+
+        let day_of_month = self.days_of_month.contains(date.day());
+        let day_of_week = self
+            .days_of_week
+            .contains(date.weekday().num_days_from_sunday());
+
+        if self.days_of_month.restricted && self.days_of_week.restricted {
+            day_of_month || day_of_week
+        } else {
+            day_of_month && day_of_week
+        }
+    }
+}
+
+fn parse_field(spec: &str, field: &'static str, min: u32, max: u32) -> Result<FieldSet, CronError> {
+    let invalid = || CronError::InvalidField {
+        field,
+        value: spec.to_string(),
+    };
+    let parse_value = |raw: &str| -> Result<u32, CronError> {
+        let value = raw.parse::<u32>().map_err(|_| invalid())?;
+        if value < min || value > max {
+            return Err(CronError::OutOfRange {
+                field,
+                value,
+                min,
+                max,
+            });
+        }
+        Ok(value)
+    };
+
+    let mut mask = 0u64;
+    for part in spec.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => {
+                let step = step.parse::<u32>().map_err(|_| invalid())?;
+                if step == 0 {
+                    return Err(invalid());
+                }
+                (range, Some(step))
+            }
+            None => (part, None),
+        };
+
+        let (start, end) = if range == "*" {
+            (min, max)
+        } else if let Some((start, end)) = range.split_once('-') {
+            (parse_value(start)?, parse_value(end)?)
+        } else {
+            let start = parse_value(range)?;
+            (start, if step.is_some() { max } else { start })
+        };
+        if start > end {
+            return Err(invalid());
+        }
+
+        for value in (start..=end).step_by(step.unwrap_or(1) as usize) {
+            mask |= 1u64 << value;
+        }
+    }
+
+    Ok(FieldSet {
+        mask,
+        restricted: !spec.starts_with('*'),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+    use chrono_tz::Tz;
+
+    use super::{CronError, CronSchedule};
+
+    fn next(expression: &str, tz: &str, after: chrono::DateTime<Utc>) -> chrono::DateTime<Utc> {
+        let tz: Tz = tz.parse().expect("valid tz");
+        CronSchedule::parse(expression)
+            .expect("valid expression")
+            .next_after(after, tz)
+            .expect("should have a next run")
+    }
+
+    #[test]
+    fn parses_lists_ranges_steps_and_shorthands() {
+        assert!(CronSchedule::parse("*/15 9-17 * * 1-5").is_ok());
+        assert!(CronSchedule::parse("0 0,12 1 */2 7").is_ok());
+        assert_eq!(
+            CronSchedule::parse("@hourly"),
+            CronSchedule::parse("0 * * * *")
+        );
+        assert_eq!(
+            CronSchedule::parse("0 9 * *").unwrap_err(),
+            CronError::FieldCount(4)
+        );
+        assert!(matches!(
+            CronSchedule::parse("60 * * * *").unwrap_err(),
+            CronError::OutOfRange { value: 60, .. }
+        ));
+        assert!(CronSchedule::parse("*/0 * * * *").is_err());
+        assert!(CronSchedule::parse("5-1 * * * *").is_err());
+    }
+
+    #[test]
+    fn rejects_expressions_without_a_run_every_year() {
+        for expression in [
+            "0 0 31 2 *",
+            "0 0 30 2 *",
+            "0 0 31 4,6,9,11 *",
+            "0 0 29 2 *",
+        ] {
+            assert_eq!(
+                CronSchedule::parse(expression).unwrap_err(),
+                CronError::NeverRuns,
+                "{expression}"
+            );
+        }
+        // Either day field may match when both are restricted.
+        assert!(CronSchedule::parse("0 0 31 2 1").is_ok());
+        assert!(CronSchedule::parse("0 0 28-31 2 *").is_ok());
+    }
+
+    #[test]
+    fn daily_run_uses_local_time() {
+        // 07:00 UTC is 10:00 in Istanbul; 09:00 local tomorrow is 06:00 UTC.
+        let after = Utc.with_ymd_and_hms(2026, 10, 18, 7, 0, 0).unwrap();
+        assert_eq!(
+            next("0 9 * * *", "Europe/Istanbul", after),
+            Utc.with_ymd_and_hms(2026, 10, 19, 6, 0, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn weekly_run_accounts_for_dst_change() {
+        // Berlin leaves DST on Sunday 2026-10-25, so Monday 09:00 CET is
+        // 08:00 UTC rather than 07:00.
+        let after = Utc.with_ymd_and_hms(2026, 10, 24, 12, 0, 0).unwrap();
+        assert_eq!(
+            next("0 9 * * 1", "Europe/Berlin", after),
+            Utc.with_ymd_and_hms(2026, 10, 26, 8, 0, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn dst_gap_runs_one_hour_later() {
+        // 2026-03-29 02:30 does not exist in Berlin; 03:30 CEST is used.
+        let after = Utc.with_ymd_and_hms(2026, 3, 28, 23, 0, 0).unwrap();
+        assert_eq!(
+            next("30 2 * * *", "Europe/Berlin", after),
+            Utc.with_ymd_and_hms(2026, 3, 29, 1, 30, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn restricted_day_fields_match_either() {
+        // 2026-10-18 is a Sunday: "1st of month or Friday" next fires Friday 23rd.
+        let after = Utc.with_ymd_and_hms(2026, 10, 18, 12, 0, 0).unwrap();
+        assert_eq!(
+            next("0 0 1 * 5", "UTC", after),
+            Utc.with_ymd_and_hms(2026, 10, 23, 0, 0, 0).unwrap()
+        );
+        assert_eq!(
+            next("@monthly", "UTC", after),
+            Utc.with_ymd_and_hms(2026, 11, 1, 0, 0, 0).unwrap()
+        );
+    }
+}
@@ -0,0 +1,302 @@
+mod cron;
+mod state;
+
+use std::path::PathBuf;
+
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use teloxide::prelude::*;
+use tokio::time::{Duration, sleep};
+
+use crate::app_context::AppContext;
+use crate::architecture::adapters::TeloxideNotifier;
+use crate::config::Config;
+
+pub(crate) use cron::CronSchedule;
+use state::SchedulerState;
+
+use super::schedules::run_report_job;
+
+/// Longest single sleep, so wall-clock jumps (suspend, NTP steps) are noticed.
+const MAX_SLEEP_SECS: u64 = 300;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ReportJob {
+    DailySummary,
+    WeeklyReport,
+    MonthlyReport,
+    HourlyDigest,
+}
+
+impl ReportJob {
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            Self::DailySummary => "daily_summary",
+            Self::WeeklyReport => "weekly_report",
+            Self::MonthlyReport => "monthly_report",
+            Self::HourlyDigest => "hourly_digest",
+        }
+    }
+}
+
+struct ScheduledJob {
+    job: ReportJob,
+    schedule: CronSchedule,
+    next_due: DateTime<Utc>,
+}
+
+fn configured_jobs(config: &Config) -> Vec<(ReportJob, CronSchedule)> {
+    let candidates = [
+        (
+            config.daily_summary.enabled,
+            ReportJob::DailySummary,
+            config.daily_summary.schedule_expression(),
+        ),
+        (
+            config.weekly_report.enabled,
+            ReportJob::WeeklyReport,
+            config.weekly_report.schedule_expression(),
+        ),
+        (
+            config.monthly_report.enabled,
+            ReportJob::MonthlyReport,
+            config.monthly_report.schedule_expression(),
+        ),
+        (
+            config.hourly_digest.enabled,
+            ReportJob::HourlyDigest,
+            config.hourly_digest.cron.clone(),
+        ),
+    ];
+
+    candidates
+        .into_iter()
+        .filter(|(enabled, _, _)| *enabled)
+        .filter_map(
+            |(_, job, expression)| match CronSchedule::parse(&expression) {
+                Ok(schedule) => Some((job, schedule)),
+                Err(error) => {
+                    log::warn!(
+                        "scheduler_job_skipped job={} cron={expression:?} error={error}",
+                        job.name()
+                    );
+                    None
+                }
+            },
+        )
+        .collect()
+}
+
+/// Next run of every enabled report job, soonest first.
+pub(crate) fn upcoming_runs(
+    config: &Config,
+    now: DateTime<Utc>,
+) -> Vec<(ReportJob, DateTime<Utc>)> {
+    let tz = config.tz();
+    let mut runs: Vec<_> = configured_jobs(config)
+        .into_iter()
+        .filter_map(|(job, schedule)| schedule.next_after(now, tz).map(|at| (job, at)))
+        .collect();
+    runs.sort_by_key(|(_, at)| *at);
+    runs
+}
+
+/// A run missed since `last_run` is due immediately (once, however many were
+/// missed); otherwise the job waits for its next occurrence.
+fn initial_due(
+    schedule: &CronSchedule,
+    tz: Tz,
+    last_run: Option<DateTime<Utc>>,
+    now: DateTime<Utc>,
+    catch_up: bool,
+) -> Option<DateTime<Utc>> {
+    if catch_up
+        && let Some(last_run) = last_run
+        && schedule
+            .next_after(last_run, tz)
+            .is_some_and(|missed| missed <= now)
+    {
+        return Some(now);
+    }
+
+    schedule.next_after(now, tz)
+}
+
+pub(super) fn start_report_scheduler(bot: Bot, app_context: AppContext) {
+    let tz = app_context.config.tz();
+    let catch_up = app_context.config.scheduler.catch_up;
+    let state_path = PathBuf::from(&app_context.config.scheduler.state_path);
+    let mut state = SchedulerState::load(&state_path);
+    let now = Utc::now();
+
+    let mut jobs: Vec<ScheduledJob> = configured_jobs(&app_context.config)
+        .into_iter()
+        .filter_map(|(job, schedule)| {
+            let last_run = state.last_run(job.name());
+            let next_due = initial_due(&schedule, tz, last_run, now, catch_up)?;
+            if next_due <= now {
+                log::info!(
+                    "scheduler_catch_up job={} last_run={}",
+                    job.name(),
+                    last_run.map_or_else(|| "none".to_string(), |time| time.to_rfc3339())
+                );
+            }
+            Some(ScheduledJob {
+                job,
+                schedule,
+                next_due,
+            })
+        })
+        .collect();
+    if jobs.is_empty() {
+        return;
+    }
+
+    let notifier = TeloxideNotifier(bot);
+    tokio::spawn(async move {
+        while let Some(next_due) = jobs.iter().map(|job| job.next_due).min() {
+            let now = Utc::now();
+            if next_due > now {
+                let wait = (next_due - now)
+                    .to_std()
+                    .unwrap_or_else(|_| Duration::from_secs(1))
+                    .min(Duration::from_secs(MAX_SLEEP_SECS));
+                sleep(wait).await;
+                continue;
+            }
+
+            let mut index = 0;
+            while index < jobs.len() {
+                if jobs[index].next_due > now {
+                    index += 1;
+                    continue;
+                }
+
+                let job = jobs[index].job;
+                run_report_job(job, &notifier, &app_context).await;
+                let finished_at = Utc::now();
+                state.record_run(job.name(), finished_at);
+                if let Err(error) = state.save(&state_path) {
+                    log::warn!(
+                        "scheduler_state_write_failed job={} error={error}",
+                        job.name()
+                    );
+                }
+
+                match jobs[index].schedule.next_after(finished_at, tz) {
+                    Some(next_due) => {
+                        jobs[index].next_due = next_due;
+                        index += 1;
+                    }
+                    None => {
+                        log::warn!("scheduler_job_exhausted job={}", job.name());
+                        jobs.remove(index);
+                    }
+                }
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+    use chrono_tz::Tz;
+
+    use super::{CronSchedule, ReportJob, initial_due, upcoming_runs};
+
+    #[test]
+    fn missed_run_is_caught_up_once() {
+        let schedule = CronSchedule::parse("0 9 * * *").expect("valid cron");
+        let now = Utc.with_ymd_and_hms(2026, 10, 18, 12, 0, 0).unwrap();
+        let three_days_ago = Utc.with_ymd_and_hms(2026, 10, 15, 9, 0, 0).unwrap();
+
+        assert_eq!(
+            initial_due(&schedule, Tz::UTC, Some(three_days_ago), now, true),
+            Some(now)
+        );
+        assert_eq!(
+            initial_due(&schedule, Tz::UTC, Some(three_days_ago), now, false),
+            Some(Utc.with_ymd_and_hms(2026, 10, 19, 9, 0, 0).unwrap())
+        );
+    }
+
+    #[test]
+    fn first_start_and_up_to_date_state_wait_for_next_run() {
+        let schedule = CronSchedule::parse("0 9 * * *").expect("valid cron");
+        let now = Utc.with_ymd_and_hms(2026, 10, 18, 12, 0, 0).unwrap();
+        let next = Utc.with_ymd_and_hms(2026, 10, 19, 9, 0, 0).unwrap();
+        let this_morning = Utc.with_ymd_and_hms(2026, 10, 18, 9, 0, 5).unwrap();
+
+        assert_eq!(initial_due(&schedule, Tz::UTC, None, now, true), Some(next));
+        assert_eq!(
+            initial_due(&schedule, Tz::UTC, Some(this_morning), now, true),
+            Some(next)
+        );
+    }
+
+    #[test]
+    fn daily_summary_uses_configured_timezone() {
+        let mut config = crate::test_utils::base_test_config();
+        config.timezone = "Europe/Istanbul".to_string();
+        config.daily_summary.enabled = true;
+        config.daily_summary.cron = None;
+        config.daily_summary.hour = 9;
+        config.daily_summary.minute = 0;
+        config.weekly_report.enabled = false;
+
+        // 07:00 UTC is 10:00 in Istanbul; 09:00 local tomorrow is 06:00 UTC.
+        let now = Utc.with_ymd_and_hms(2026, 10, 18, 7, 0, 0).unwrap();
+        let runs = upcoming_runs(&config, now);
+        assert_eq!(
+            runs,
+            vec![(ReportJob::DailySummary, now + chrono::Duration::hours(23))]
+        );
+    }
+
+    #[test]
+    fn weekly_report_accounts_for_dst_change() {
+        let mut config = crate::test_utils::base_test_config();
+        config.timezone = "Europe/Berlin".to_string();
+        config.daily_summary.enabled = false;
+        config.weekly_report.enabled = true;
+        config.weekly_report.cron = None;
+        config.weekly_report.weekday = 1;
+        config.weekly_report.hour = 9;
+        config.weekly_report.minute = 0;
+
+        // Saturday 12:00 UTC; Berlin leaves DST on Sunday, so Monday 09:00
+        // CET is 08:00 UTC (44h away rather than 43h).
+        let now = Utc.with_ymd_and_hms(2026, 10, 24, 12, 0, 0).unwrap();
+        let runs = upcoming_runs(&config, now);
+        assert_eq!(
+            runs,
+            vec![(ReportJob::WeeklyReport, now + chrono::Duration::hours(44))]
+        );
+    }
+
+    #[test]
+    fn upcoming_runs_lists_enabled_jobs_in_order() {
+        let mut config = crate::test_utils::base_test_config();
+        config.daily_summary.enabled = true;
+        config.daily_summary.cron = Some("0 9 * * *".to_string());
+        config.weekly_report.enabled = false;
+        config.hourly_digest.enabled = true;
+
+        let now = Utc.with_ymd_and_hms(2026, 10, 18, 12, 30, 0).unwrap();
+        let runs = upcoming_runs(&config, now);
+        assert_eq!(
+            runs,
+            vec![
+                (
+                    ReportJob::HourlyDigest,
+                    Utc.with_ymd_and_hms(2026, 10, 18, 13, 0, 0).unwrap()
+                ),
+                (
+                    ReportJob::DailySummary,
+                    Utc.with_ymd_and_hms(2026, 10, 19, 9, 0, 0).unwrap()
+                ),
+            ]
+        );
+    }
+}
@@ -0,0 +1,82 @@
+use std::collections::BTreeMap;
+use std::{fs, path::Path};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Last successful run per job, persisted so missed runs can be caught up
+/// after downtime.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub(super) struct SchedulerState {
+    #[serde(default)]
+    last_runs_utc: BTreeMap<String, String>,
+}
+
+impl SchedulerState {
+    pub(super) fn load(path: &Path) -> Self {
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Self::default(),
+            Err(error) => {
+                log::warn!(
+                    "scheduler_state_read_failed path={} error={error}",
+                    path.display()
+                );
+                return Self::default();
+            }
+        };
+
+        serde_json::from_str(&content).unwrap_or_else(|error| {
+            log::warn!(
+                "scheduler_state_parse_failed path={} error={error}",
+                path.display()
+            );
+            Self::default()
+        })
+    }
+
+    pub(super) fn save(&self, path: &Path) -> Result<(), std::io::Error> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let payload = serde_json::to_string_pretty(self).map_err(std::io::Error::other)?;
+        let tmp_path = path.with_extension("json.tmp");
+        fs::write(&tmp_path, payload)?;
+        fs::rename(tmp_path, path)
+    }
+
+    pub(super) fn last_run(&self, job: &str) -> Option<DateTime<Utc>> {
+        let raw = self.last_runs_utc.get(job)?;
+        DateTime::parse_from_rfc3339(raw)
+            .ok()
+            .map(|time| time.with_timezone(&Utc))
+    }
+
+    pub(super) fn record_run(&mut self, job: &str, at: DateTime<Utc>) {
+        self.last_runs_utc.insert(job.to_string(), at.to_rfc3339());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+    use tempfile::tempdir;
+
+    use super::SchedulerState;
+
+    #[test]
+    fn round_trips_last_runs() {
+        let dir = tempdir().expect("temp dir");
+        let path = dir.path().join("scheduler").join("state.json");
+        let at = Utc.with_ymd_and_hms(2026, 10, 18, 9, 0, 0).unwrap();
+
+        let mut state = SchedulerState::load(&path);
+        assert!(state.last_run("daily_summary").is_none());
+        state.record_run("daily_summary", at);
+        state.save(&path).expect("state should save");
+
+        let reloaded = SchedulerState::load(&path);
+        assert_eq!(reloaded.last_run("daily_summary"), Some(at));
+    }
+}
@@ -1,38 +1,244 @@
-use chrono::{Datelike, Days, TimeZone, Utc};
+use std::path::Path;
+
+use chrono::{DateTime, Days, NaiveTime, TimeZone, Utc};
+use chrono_tz::Tz;
 use teloxide::prelude::*;
 use tokio::time::{Duration, interval, sleep};
 
-use crate::anomaly_db::run_maintenance;
+use super::scheduler::ReportJob;
+
 use crate::app_context::AppContext;
 use crate::architecture::{
     adapters::TeloxideNotifier,
-    ports::NotifierPort,
-    use_cases::{DailySummaryReport, take_daily_summary_report_use_case},
+    ports::{MediaPhoto, NotifierPort},
+    use_cases::{
+        DailySummaryReport, DeferredAlert, take_daily_summary_report_use_case,
+        take_deferred_alerts_use_case,
+    },
 };
-use crate::commands::build_weekly_cpu_report;
+use crate::commands::{build_monthly_capacity_report, build_weekly_report};
+use crate::config::Templates;
+use crate::i18n::{t, t_with};
+use crate::monitor::MetricSample;
+use crate::templates::{TemplateKind, render_template};
+use crate::time_format::format_local_time;
+
+/// Telegram rejects photo captions longer than this many characters.
+const TELEGRAM_CAPTION_LIMIT: usize = 1024;
+
+pub(super) async fn run_report_job<N: NotifierPort>(
+    job: ReportJob,
+    notifier: &N,
+    app_context: &AppContext,
+) {
+    let owner_chat_id = match app_context.config.owner_chat_id() {
+        Ok(chat_id) => chat_id,
+        Err(error) => {
+            log::error!("{} skipped: invalid owner chat id: {error}", job.name());
+            return;
+        }
+    };
+
+    match job {
+        ReportJob::DailySummary => send_daily_summary(notifier, owner_chat_id, app_context).await,
+        ReportJob::WeeklyReport => send_weekly_report(notifier, owner_chat_id, app_context).await,
+        ReportJob::HourlyDigest => send_hourly_digest(notifier, owner_chat_id, app_context).await,
+        ReportJob::MonthlyReport => send_monthly_report(notifier, owner_chat_id, app_context).await,
+    }
+}
+
+async fn send_daily_summary<N: NotifierPort>(
+    notifier: &N,
+    owner_chat_id: ChatId,
+    app_context: &AppContext,
+) {
+    let report = take_daily_summary_report_use_case(&app_context.monitor.alert_state).await;
+    let message = format_daily_summary_message(
+        report,
+        &app_context.config.templates,
+        app_context.config.tz(),
+    );
+
+    if let Err(error) = notifier.send_message(owner_chat_id, message).await {
+        log::error!("failed to send daily summary: {error}");
+    }
+}
+
+async fn send_weekly_report<N: NotifierPort>(
+    notifier: &N,
+    owner_chat_id: ChatId,
+    app_context: &AppContext,
+) {
+    match build_weekly_report(app_context).await {
+        Ok(reports) => {
+            let mut overflow = Vec::new();
+            let mut photos = reports
+                .into_iter()
+                .map(|report| {
+                    let (caption, rest) = fit_caption(report.caption);
+                    overflow.extend(rest);
+                    MediaPhoto {
+                        bytes: report.png_bytes,
+                        file_name: report.file_name,
+                        caption,
+                    }
+                })
+                .collect::<Vec<_>>();
+            let sent = if photos.len() == 1 {
+                let photo = photos.remove(0);
+                notifier
+                    .send_photo(owner_chat_id, photo.bytes, photo.file_name, photo.caption)
+                    .await
+            } else {
+                notifier.send_media_group(owner_chat_id, photos).await
+            };
+            if let Err(error) = sent {
+                log::error!("failed to send weekly report chart: {error}");
+            } else if !overflow.is_empty()
+                && let Err(error) = notifier
+                    .send_message(owner_chat_id, overflow.join("\n\n"))
+                    .await
+            {
+                log::error!("failed to send weekly report caption overflow: {error}");
+            }
+        }
+        Err(error) => {
+            log::warn!("weekly report skipped: {error}");
+            if let Err(send_error) = notifier
+                .send_message(
+                    owner_chat_id,
+                    t_with("summary.weekly.failed", &[("error", error.to_string())]),
+                )
+                .await
+            {
+                log::error!("failed to send weekly report fallback: {send_error}");
+            }
+        }
+    }
+}
+
+/// Cuts `caption` at the last line break within Telegram's caption limit and
+/// returns the remainder, which is sent as a message after the photos.
+fn fit_caption(caption: String) -> (String, Option<String>) {
+    let Some((limit, _)) = caption.char_indices().nth(TELEGRAM_CAPTION_LIMIT) else {
+        return (caption, None);
+    };
+    let cut = caption[..limit]
+        .rfind('\n')
+        .filter(|&index| index > 0)
+        .unwrap_or(limit);
+    let (head, rest) = caption.split_at(cut);
+    (
+        head.trim_end().to_string(),
+        Some(rest.trim_start().to_string()),
+    )
+}
+
+async fn send_monthly_report<N: NotifierPort>(
+    notifier: &N,
+    owner_chat_id: ChatId,
+    app_context: &AppContext,
+) {
+    match build_monthly_capacity_report(app_context).await {
+        Ok(report) => {
+            let chart = report.chart;
+            if let Err(error) = notifier
+                .send_photo(
+                    owner_chat_id,
+                    chart.png_bytes,
+                    chart.file_name,
+                    chart.caption,
+                )
+                .await
+            {
+                log::error!("failed to send monthly report chart: {error}");
+            }
+            if let Err(error) = notifier
+                .send_document(
+                    owner_chat_id,
+                    report.document,
+                    report.document_name,
+                    report.document_caption,
+                )
+                .await
+            {
+                log::error!("failed to send monthly report document: {error}");
+            }
+        }
+        Err(error) => {
+            log::warn!("monthly report skipped: {error}");
+            if let Err(send_error) = notifier
+                .send_message(
+                    owner_chat_id,
+                    t_with("summary.monthly.failed", &[("error", error)]),
+                )
+                .await
+            {
+                log::error!("failed to send monthly report fallback: {send_error}");
+            }
+        }
+    }
+}
+
+async fn send_hourly_digest<N: NotifierPort>(
+    notifier: &N,
+    owner_chat_id: ChatId,
+    app_context: &AppContext,
+) {
+    let samples = app_context
+        .monitor
+        .metric_history
+        .lock()
+        .await
+        .latest_window(60);
+
+    if let Err(error) = notifier
+        .send_message(owner_chat_id, format_hourly_digest_message(&samples))
+        .await
+    {
+        log::error!("failed to send hourly digest: {error}");
+    }
+}
+
+pub(super) fn start_quiet_hours_digest_job(bot: Bot, app_context: AppContext) {
+    let quiet_hours = app_context.config.quiet_hours.clone();
+    let (Some(end_time), Some(tz)) = (
+        quiet_hours.end_time(),
+        quiet_hours.tz(app_context.config.tz()),
+    ) else {
+        log::warn!("quiet hours digest disabled: invalid end time or timezone");
+        return;
+    };
 
-pub(super) fn start_daily_summary_job(bot: Bot, app_context: AppContext) {
     let notifier = TeloxideNotifier(bot.clone());
     tokio::spawn(async move {
         loop {
-            let wait = duration_until_next_daily_summary(
-                app_context.config.daily_summary.hour_utc,
-                app_context.config.daily_summary.minute_utc,
-            );
+            let now = Utc::now();
+            let wait = (next_local_occurrence(now, end_time, tz) - now)
+                .to_std()
+                .unwrap_or_else(|_| Duration::from_secs(60));
             sleep(wait).await;
 
-            let report = take_daily_summary_report_use_case(&app_context.monitor.alert_state).await;
-            let message = format_daily_summary_message(report);
+            let deferred = take_deferred_alerts_use_case(
+                &app_context.monitor.alert_state,
+                Path::new(&quiet_hours.state_path),
+            )
+            .await;
+            if deferred.is_empty() {
+                continue;
+            }
+
             let owner_chat_id = match app_context.config.owner_chat_id() {
                 Ok(chat_id) => chat_id,
                 Err(error) => {
-                    log::error!("daily summary skipped: invalid owner chat id: {error}");
+                    log::error!("quiet hours digest skipped: invalid owner chat id: {error}");
                     continue;
                 }
             };
 
+            let message = format_quiet_hours_digest_message(&deferred, tz);
             if let Err(error) = notifier.send_message(owner_chat_id, message).await {
-                log::error!("failed to send daily summary: {error}");
+                log::error!("failed to send quiet hours digest: {error}");
             }
         }
     });
@@ -44,141 +250,251 @@ pub(super) fn start_maintenance_job(app_context: AppContext) {
 
         loop {
             ticker.tick().await;
-            run_maintenance(&app_context.config);
+            app_context
+                .anomaly_storage
+                .run_maintenance(&app_context.config)
+                .await;
         }
     });
 }
 
-pub(super) fn start_weekly_report_job(bot: Bot, app_context: AppContext) {
-    let notifier = TeloxideNotifier(bot.clone());
-    tokio::spawn(async move {
-        loop {
-            let wait = duration_until_next_weekly_report(
-                app_context.config.weekly_report.weekday_utc,
-                app_context.config.weekly_report.hour_utc,
-                app_context.config.weekly_report.minute_utc,
-            );
-            sleep(wait).await;
+fn next_local_occurrence(now: DateTime<Utc>, time: NaiveTime, tz: Tz) -> DateTime<Utc> {
+    let local_today = now.with_timezone(&tz).date_naive();
 
-            let owner_chat_id = match app_context.config.owner_chat_id() {
-                Ok(chat_id) => chat_id,
-                Err(error) => {
-                    log::error!("weekly report skipped: invalid owner chat id: {error}");
-                    continue;
-                }
-            };
+    for offset in 0..=2 {
+        let Some(date) = local_today.checked_add_days(Days::new(offset)) else {
+            break;
+        };
 
-            match build_weekly_cpu_report(&app_context).await {
-                Ok(report) => {
-                    if let Err(error) = notifier
-                        .send_photo(
-                            owner_chat_id,
-                            report.png_bytes,
-                            report.file_name,
-                            report.caption,
-                        )
-                        .await
-                    {
-                        log::error!("failed to send weekly report chart: {error}");
-                    }
-                }
-                Err(error) => {
-                    log::warn!("weekly report skipped: {error}");
-                    if let Err(send_error) = notifier
-                        .send_message(
-                            owner_chat_id,
-                            format!(
-                                "📈 Weekly Report\n\nCould not generate chart this cycle: {error}"
-                            ),
-                        )
-                        .await
-                    {
-                        log::error!("failed to send weekly report fallback: {send_error}");
-                    }
-                }
-            }
+        // A wall-clock time that falls into a DST gap does not exist; the
+        // next valid instant one hour later is used instead.
+        let candidate = tz
+            .from_local_datetime(&date.and_time(time))
+            .earliest()
+            .or_else(|| {
+                tz.from_local_datetime(&(date.and_time(time) + chrono::Duration::hours(1)))
+                    .earliest()
+            })
+            .map(|value| value.with_timezone(&Utc));
+
+        if let Some(candidate) = candidate
+            && candidate > now
+        {
+            return candidate;
         }
-    });
+    }
+
+    now + chrono::Duration::days(1)
 }
 
-fn duration_until_next_daily_summary(hour_utc: u8, minute_utc: u8) -> Duration {
-    let now = Utc::now();
+fn format_quiet_hours_digest_message(deferred: &[DeferredAlert], tz: Tz) -> String {
+    let lines = deferred
+        .iter()
+        .map(|alert| {
+            format!(
+                "- {} {}",
+                alert.raised_at.with_timezone(&tz).format("%H:%M"),
+                alert.text
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
 
-    let today = now.date_naive();
-    let Some(scheduled_today_naive) =
-        today.and_hms_opt(u32::from(hour_utc), u32::from(minute_utc), 0)
-    else {
-        return Duration::from_secs(60);
-    };
+    t_with(
+        "summary.quiet_digest",
+        &[("count", deferred.len().to_string()), ("alerts", lines)],
+    )
+}
 
-    let mut scheduled = Utc.from_utc_datetime(&scheduled_today_naive);
-    if scheduled <= now {
-        let tomorrow = today.checked_add_days(Days::new(1)).unwrap_or(today);
-        let Some(scheduled_tomorrow_naive) =
-            tomorrow.and_hms_opt(u32::from(hour_utc), u32::from(minute_utc), 0)
-        else {
-            return Duration::from_secs(60);
-        };
-        scheduled = Utc.from_utc_datetime(&scheduled_tomorrow_naive);
+fn format_hourly_digest_message(samples: &[MetricSample]) -> String {
+    if samples.is_empty() {
+        return t("summary.hourly_digest.empty").to_string();
     }
 
-    (scheduled - now)
-        .to_std()
-        .unwrap_or_else(|_| Duration::from_secs(60))
+    let avg_max = |value: fn(&MetricSample) -> f32| {
+        let sum: f32 = samples.iter().map(value).sum();
+        let max = samples.iter().map(value).fold(f32::MIN, f32::max);
+        (sum / samples.len() as f32, max)
+    };
+    let (cpu_avg, cpu_max) = avg_max(|sample| sample.cpu);
+    let (ram_avg, ram_max) = avg_max(|sample| sample.ram);
+    let (disk_avg, disk_max) = avg_max(|sample| sample.disk);
+
+    t_with(
+        "summary.hourly_digest",
+        &[
+            ("samples", samples.len().to_string()),
+            ("cpu_avg", format!("{cpu_avg:.1}")),
+            ("cpu_max", format!("{cpu_max:.1}")),
+            ("ram_avg", format!("{ram_avg:.1}")),
+            ("ram_max", format!("{ram_max:.1}")),
+            ("disk_avg", format!("{disk_avg:.1}")),
+            ("disk_max", format!("{disk_max:.1}")),
+        ],
+    )
 }
 
-fn duration_until_next_weekly_report(weekday_utc: u8, hour_utc: u8, minute_utc: u8) -> Duration {
-    let now = Utc::now();
-    let today = now.date_naive();
+fn format_daily_summary_message(
+    report: Option<DailySummaryReport>,
+    templates: &Templates,
+    tz: Tz,
+) -> String {
+    match report {
+        Some(report) => render_template(
+            TemplateKind::DailySummary,
+            templates.source(TemplateKind::DailySummary),
+            &[
+                ("samples", report.sample_count.to_string()),
+                ("alerts", report.alert_count.to_string()),
+                ("cpu_avg", format!("{:.1}", report.cpu_avg)),
+                ("cpu_min", format!("{:.1}", report.cpu_min)),
+                ("cpu_max", format!("{:.1}", report.cpu_max)),
+                ("ram_avg", format!("{:.1}", report.ram_avg)),
+                ("ram_min", format!("{:.1}", report.ram_min)),
+                ("ram_max", format!("{:.1}", report.ram_max)),
+                ("disk_avg", format!("{:.1}", report.disk_avg)),
+                ("disk_min", format!("{:.1}", report.disk_min)),
+                ("disk_max", format!("{:.1}", report.disk_max)),
+                ("generated_at", format_local_time(report.generated_at, tz)),
+            ],
+        ),
+        None => t_with(
+            "summary.daily.empty",
+            &[("generated_at", format_local_time(Utc::now(), tz))],
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{NaiveTime, TimeZone, Utc};
+    use chrono_tz::Tz;
+    use teloxide::types::ChatId;
 
-    let current_weekday = i64::from(now.weekday().num_days_from_monday());
-    let target_weekday = i64::from(weekday_utc.saturating_sub(1));
+    use crate::monitor::MetricSample;
 
-    let mut days_ahead = (target_weekday - current_weekday).rem_euclid(7);
-    let scheduled_today = match today.and_hms_opt(u32::from(hour_utc), u32::from(minute_utc), 0) {
-        Some(value) => Utc.from_utc_datetime(&value),
-        None => return Duration::from_secs(60),
+    use super::{
+        DeferredAlert, fit_caption, format_hourly_digest_message,
+        format_quiet_hours_digest_message, next_local_occurrence, send_weekly_report,
     };
+    use crate::app_context::AppContext;
+    use crate::capabilities::Capabilities;
+    use crate::config::{ReportMetric, WeeklyCharts};
+    use crate::monitor::{SentItem, SpyNotifier};
+    use crate::test_utils::base_test_config;
 
-    if days_ahead == 0 && scheduled_today <= now {
-        days_ahead = 7;
+    async fn weekly_context(metrics: Vec<ReportMetric>, charts: WeeklyCharts) -> AppContext {
+        let mut config = base_test_config();
+        config.reporting_store.enabled = false;
+        config.anomaly_db.enabled = false;
+        config.weekly_report.metrics = metrics;
+        config.weekly_report.charts = charts;
+        let app =
+            AppContext::new(config, 2, "config.toml", Capabilities::detect()).expect("app context");
+        let now = Utc::now();
+        let mut history = app.monitor.metric_history.lock().await;
+        for minute in 0..60 {
+            history.push(MetricSample {
+                timestamp: now - chrono::Duration::minutes(60 - minute),
+                cpu: 20.0 + minute as f32,
+                ram: 50.0,
+                disk: 40.0,
+            });
+        }
+        drop(history);
+        app
     }
 
-    let target_date = match today.checked_add_days(Days::new(days_ahead as u64)) {
-        Some(value) => value,
-        None => return Duration::from_secs(60),
-    };
+    #[tokio::test]
+    async fn weekly_report_sends_one_photo_or_one_media_group() {
+        let single = weekly_context(vec![ReportMetric::Cpu], WeeklyCharts::PerMetric).await;
+        let notifier = SpyNotifier::new();
+        send_weekly_report(&notifier, ChatId(1), &single).await;
+        let sent = notifier.sent.lock().await;
+        assert!(matches!(sent.as_slice(), [SentItem::Photo(..)]), "{sent:?}");
+        drop(sent);
 
-    let scheduled = match target_date.and_hms_opt(u32::from(hour_utc), u32::from(minute_utc), 0) {
-        Some(value) => Utc.from_utc_datetime(&value),
-        None => return Duration::from_secs(60),
-    };
+        let split = weekly_context(
+            vec![ReportMetric::Cpu, ReportMetric::Ram],
+            WeeklyCharts::PerMetric,
+        )
+        .await;
+        let notifier = SpyNotifier::new();
+        send_weekly_report(&notifier, ChatId(1), &split).await;
+        let sent = notifier.sent.lock().await;
+        match sent.as_slice() {
+            [SentItem::MediaGroup(_, photos)] => assert_eq!(photos.len(), 2),
+            other => panic!("expected one media group, got {other:?}"),
+        }
+    }
 
-    (scheduled - now)
-        .to_std()
-        .unwrap_or_else(|_| Duration::from_secs(60))
-}
+    #[test]
+    fn long_captions_are_cut_at_a_line_break() {
+        let caption = format!("{}\n{}", "a".repeat(1000), "b".repeat(100));
+        let (head, rest) = fit_caption(caption);
+        assert_eq!(head, "a".repeat(1000));
+        assert_eq!(rest, Some("b".repeat(100)));
+        assert_eq!(
+            fit_caption("short".to_string()),
+            ("short".to_string(), None)
+        );
+    }
 
-fn format_daily_summary_message(report: Option<DailySummaryReport>) -> String {
-    match report {
-        Some(report) => format!(
-            "📅 Daily Summary\n\nSamples: {}\nAlerts triggered: {}\n\nCPU avg/min/max: {:.1}% / {:.1}% / {:.1}%\nRAM avg/min/max: {:.1}% / {:.1}% / {:.1}%\nDisk avg/min/max: {:.1}% / {:.1}% / {:.1}%\n\nGenerated at (UTC): {}",
-            report.sample_count,
-            report.alert_count,
-            report.cpu_avg,
-            report.cpu_min,
-            report.cpu_max,
-            report.ram_avg,
-            report.ram_min,
-            report.ram_max,
-            report.disk_avg,
-            report.disk_min,
-            report.disk_max,
-            report.generated_at.to_rfc3339(),
-        ),
-        None => format!(
-            "📅 Daily Summary\n\nNo monitoring samples were collected since the last summary window.\nGenerated at (UTC): {}",
-            Utc::now().to_rfc3339()
-        ),
+    #[test]
+    fn next_local_occurrence_rolls_over_to_next_day() {
+        let tz: Tz = "Europe/Istanbul".parse().expect("valid tz");
+        let time = NaiveTime::from_hms_opt(7, 0, 0).expect("valid time");
+
+        // 05:00 UTC is 08:00 in Istanbul, so the next 07:00 is tomorrow.
+        let now = Utc.with_ymd_and_hms(2026, 10, 18, 5, 0, 0).unwrap();
+        let next = next_local_occurrence(now, time, tz);
+        assert_eq!(next, Utc.with_ymd_and_hms(2026, 10, 19, 4, 0, 0).unwrap());
+
+        let early = Utc.with_ymd_and_hms(2026, 10, 18, 1, 0, 0).unwrap();
+        let next = next_local_occurrence(early, time, tz);
+        assert_eq!(next, Utc.with_ymd_and_hms(2026, 10, 18, 4, 0, 0).unwrap());
+    }
+
+    #[test]
+    fn next_local_occurrence_skips_dst_gap() {
+        let tz: Tz = "Europe/Berlin".parse().expect("valid tz");
+        let time = NaiveTime::from_hms_opt(2, 30, 0).expect("valid time");
+
+        // 2026-03-29 02:30 does not exist in Berlin; 03:30 CEST is used.
+        let now = Utc.with_ymd_and_hms(2026, 3, 28, 23, 0, 0).unwrap();
+        let next = next_local_occurrence(now, time, tz);
+        assert_eq!(next, Utc.with_ymd_and_hms(2026, 3, 29, 1, 30, 0).unwrap());
+    }
+
+    #[test]
+    fn hourly_digest_summarizes_last_hour() {
+        let samples = [(40.0, 50.0, 60.0), (80.0, 70.0, 61.0)]
+            .into_iter()
+            .map(|(cpu, ram, disk)| MetricSample {
+                timestamp: Utc::now(),
+                cpu,
+                ram,
+                disk,
+            })
+            .collect::<Vec<_>>();
+
+        let message = format_hourly_digest_message(&samples);
+        assert!(message.contains("Samples: 2"));
+        assert!(message.contains("CPU avg/max: 60.0% / 80.0%"));
+        assert!(message.contains("Disk avg/max: 60.5% / 61.0%"));
+        assert!(format_hourly_digest_message(&[]).contains("No monitoring samples"));
+    }
+
+    #[test]
+    fn digest_message_lists_held_alerts() {
+        let tz: Tz = "UTC".parse().expect("valid tz");
+        let deferred = vec![DeferredAlert {
+            raised_at: Utc.with_ymd_and_hms(2026, 10, 18, 2, 15, 0).unwrap(),
+            text: "⚠️ ALERT: CPU usage is high (88.0%)".to_string(),
+        }];
+
+        let message = format_quiet_hours_digest_message(&deferred, tz);
+        assert!(message.contains("1 alert(s)"));
+        assert!(message.contains("02:15 ⚠️ ALERT: CPU usage is high"));
     }
 }
@@ -2,19 +2,28 @@ mod anomaly_db;
 mod app_builder;
 mod app_context;
 mod architecture;
+mod backup;
 mod bot_runtime;
 mod capabilities;
+mod cli;
 mod commands;
 mod config;
 mod contracts;
+mod fs_usage;
+mod i18n;
+mod incidents;
 mod jobs;
 mod monitor;
 mod monitor_context;
 mod release_notes;
 mod reporting_store;
 mod system;
+mod templates;
 #[cfg(test)]
 mod test_utils;
+mod time_bands;
+mod time_format;
+mod versioned;
 
 use teloxide::dispatching::UpdateFilterExt;
 use teloxide::prelude::*;
@@ -79,6 +88,11 @@ async fn wait_for_shutdown_signal() {
 
 #[tokio::main]
 async fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    if let Some(code) = cli::run_subcommand(&args, CONFIG_PATH) {
+        std::process::exit(code);
+    }
+
     init_json_logging();
     log::info!("Kars Server Bot is starting...");
 
@@ -3,49 +3,108 @@ use std::{sync::Arc, time::Instant};
 use tokio::sync::Mutex;
 
 use crate::config::Config;
+use crate::incidents::IncidentMetric;
+use crate::templates::{TemplateKind, format_duration, render_template};
 
 use super::{provider::Metrics, state::AlertState};
 
+#[derive(Debug, Clone)]
+pub(super) struct AlertNotification {
+    pub(super) metric: IncidentMetric,
+    pub(super) value: f32,
+    pub(super) text: String,
+}
+
+impl AlertNotification {
+    fn render(
+        config: &Config,
+        metric: IncidentMetric,
+        value: f32,
+        threshold: f32,
+        alerting_since: Option<Instant>,
+        now: Instant,
+    ) -> Self {
+        let duration = alerting_since.map_or_else(
+            || std::time::Duration::from_secs(0),
+            |since| now.saturating_duration_since(since),
+        );
+        let text = render_template(
+            TemplateKind::Alert,
+            config.templates.source(TemplateKind::Alert),
+            &[
+                ("metric", metric.label().to_string()),
+                ("value", format!("{value:.1}")),
+                ("threshold", format!("{threshold:.1}")),
+                ("duration", format_duration(duration)),
+            ],
+        );
+
+        Self {
+            metric,
+            value,
+            text,
+        }
+    }
+}
+
 pub(super) async fn evaluate_alerts_at(
     config: &Config,
     state: &Arc<Mutex<AlertState>>,
     metrics: Metrics,
     now: Instant,
-) -> Vec<String> {
+) -> Vec<AlertNotification> {
     let mut notifications = Vec::new();
 
     {
         let mut alert_state = state.lock().await;
-        if alert_state.cpu_should_alert(
+        if alert_state.cpu.should_alert(
             metrics.cpu,
             config.alerts.cpu,
             config.alerts.cooldown_secs,
             config.alerts.hysteresis,
             now,
         ) {
-            notifications.push(format!("⚠️ ALERT: CPU usage is high ({:.1}%)", metrics.cpu));
+            notifications.push(AlertNotification::render(
+                config,
+                IncidentMetric::Cpu,
+                metrics.cpu,
+                config.alerts.cpu,
+                alert_state.cpu.alerting_since,
+                now,
+            ));
         }
 
-        if alert_state.ram_should_alert(
+        if alert_state.ram.should_alert(
             metrics.ram,
             config.alerts.ram,
             config.alerts.cooldown_secs,
             config.alerts.hysteresis,
             now,
         ) {
-            notifications.push(format!("⚠️ ALERT: RAM usage is high ({:.1}%)", metrics.ram));
+            notifications.push(AlertNotification::render(
+                config,
+                IncidentMetric::Ram,
+                metrics.ram,
+                config.alerts.ram,
+                alert_state.ram.alerting_since,
+                now,
+            ));
         }
 
-        if alert_state.disk_should_alert(
+        if alert_state.disk.should_alert(
             metrics.disk,
             config.alerts.disk,
             config.alerts.cooldown_secs,
             config.alerts.hysteresis,
             now,
         ) {
-            notifications.push(format!(
-                "⚠️ ALERT: Disk usage is high ({:.1}%)",
-                metrics.disk
+            notifications.push(AlertNotification::render(
+                config,
+                IncidentMetric::Disk,
+                metrics.disk,
+                config.alerts.disk,
+                alert_state.disk.alerting_since,
+                now,
             ));
         }
     }
@@ -161,7 +220,7 @@ mod tests {
 
             if notifications
                 .iter()
-                .any(|n| n.contains("CPU usage is high"))
+                .any(|n| n.text.contains("CPU usage is high"))
             {
                 cpu_alert_observed = true;
                 break;
@@ -173,4 +232,27 @@ mod tests {
             "expected simulated CPU spike to trigger alert"
         );
     }
+
+    #[tokio::test]
+    async fn custom_alert_template_reports_duration_since_first_alert() {
+        let mut config = test_config();
+        config.alerts.cooldown_secs = 60;
+        config.templates.alert = Some("{metric} {value}>{threshold} for {duration}".to_string());
+
+        let state = Arc::new(Mutex::new(AlertState::default()));
+        let start = Instant::now();
+
+        let first =
+            evaluate_alerts_at(&config, &state, Metrics::new(90.0, 10.0, 10.0), start).await;
+        assert_eq!(first[0].text, "CPU 90.0>80.0 for 0s");
+
+        let repeat = evaluate_alerts_at(
+            &config,
+            &state,
+            Metrics::new(91.0, 10.0, 10.0),
+            start + Duration::from_secs(330),
+        )
+        .await;
+        assert_eq!(repeat[0].text, "CPU 91.0>80.0 for 5m 30s");
+    }
 }
@@ -25,11 +25,9 @@ impl MetricHistory {
     }
 
     pub fn with_retention_secs(monitor_interval_secs: u64, retention_secs: u64) -> Self {
-        let computed_capacity = if monitor_interval_secs == 0 {
-            1
-        } else {
-            (retention_secs / monitor_interval_secs).max(1)
-        } as usize;
+        let computed_capacity = retention_secs
+            .checked_div(monitor_interval_secs)
+            .map_or(1, |capacity| capacity.max(1)) as usize;
 
         Self {
             samples: VecDeque::with_capacity(computed_capacity),
@@ -45,8 +43,12 @@ impl MetricHistory {
     }
 
     pub fn latest_window(&self, minutes: i64) -> Vec<MetricSample> {
-        let now = Utc::now();
-        let cutoff = now - Duration::minutes(minutes.max(1));
+        self.window_ending(Utc::now(), minutes)
+    }
+
+    /// Samples from the `minutes` before `end`.
+    pub fn window_ending(&self, end: DateTime<Utc>, minutes: i64) -> Vec<MetricSample> {
+        let cutoff = end - Duration::minutes(minutes.max(1));
 
         self.samples
             .iter()
@@ -2,19 +2,24 @@ mod evaluator;
 mod history;
 mod notify;
 mod provider;
+mod quiet_hours;
 mod service;
+mod sparkline;
 mod state;
 
 pub use history::{MetricHistory, MetricSample};
 pub use provider::{MetricsProvider, new_metrics_provider};
+pub(crate) use quiet_hours::QuietHoursPolicy;
 pub use service::{
-    CheckAlertsContext, MuteActionError, alert_snapshot, check_alerts, mute_alerts_for,
-    take_daily_summary_report, unmute_alerts,
+    CheckAlertsContext, MuteActionError, acknowledge_incident, alert_snapshot, check_alerts,
+    mute_alerts_for, take_daily_summary_report, take_deferred_alerts, unmute_alerts,
 };
+pub(crate) use sparkline::{ALERT_SPARKLINE_MINUTES, SPARKLINE_WIDTH, metric_sparkline};
 
 #[cfg(test)]
 pub use notify::{SentItem, SpyNotifier};
 
-pub use notify::{Notifier, TeloxideNotifier};
+pub use notify::{MediaPhoto, Notifier, TeloxideNotifier};
 
-pub use state::{AlertState, DailySummaryReport};
+pub(crate) use state::load_deferred_alerts;
+pub use state::{AlertState, DailySummaryReport, DeferredAlert};
@@ -1,6 +1,6 @@
 use async_trait::async_trait;
 use teloxide::prelude::*;
-use teloxide::types::InputFile;
+use teloxide::types::{InputFile, InputMedia, InputMediaPhoto};
 
 /// General messaging interface for the bot.  Provides both text and
 /// photo/graphic delivery so that scheduled reports, release notices and
@@ -10,6 +10,9 @@ pub trait Notifier: Send + Sync {
     /// send a plain-text message
     async fn send_message(&self, chat_id: ChatId, text: String) -> Result<(), String>;
 
+    /// send a plain-text message without triggering a notification sound
+    async fn send_silent_message(&self, chat_id: ChatId, text: String) -> Result<(), String>;
+
     /// send a photo with given bytes, filename and caption
     async fn send_photo(
         &self,
@@ -18,6 +21,30 @@ pub trait Notifier: Send + Sync {
         file_name: String,
         caption: String,
     ) -> Result<(), String>;
+
+    /// send a file attachment with given bytes, filename and caption
+    async fn send_document(
+        &self,
+        chat_id: ChatId,
+        bytes: Vec<u8>,
+        file_name: String,
+        caption: String,
+    ) -> Result<(), String>;
+
+    /// send photos as one album; Telegram accepts 2 to 10 per group
+    async fn send_media_group(
+        &self,
+        chat_id: ChatId,
+        photos: Vec<MediaPhoto>,
+    ) -> Result<(), String>;
+}
+
+/// One photo of a media group, captioned on its own.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MediaPhoto {
+    pub bytes: Vec<u8>,
+    pub file_name: String,
+    pub caption: String,
 }
 
 /// adapter that delegates to a live `teloxide::Bot`.
@@ -33,6 +60,15 @@ impl Notifier for TeloxideNotifier {
             .map_err(|e| e.to_string())
     }
 
+    async fn send_silent_message(&self, chat_id: ChatId, text: String) -> Result<(), String> {
+        self.0
+            .send_message(chat_id, text)
+            .disable_notification(true)
+            .await
+            .map(|_| ())
+            .map_err(|e| e.to_string())
+    }
+
     async fn send_photo(
         &self,
         chat_id: ChatId,
@@ -47,6 +83,39 @@ impl Notifier for TeloxideNotifier {
             .map(|_| ())
             .map_err(|e| e.to_string())
     }
+
+    async fn send_document(
+        &self,
+        chat_id: ChatId,
+        bytes: Vec<u8>,
+        file_name: String,
+        caption: String,
+    ) -> Result<(), String> {
+        self.0
+            .send_document(chat_id, InputFile::memory(bytes).file_name(file_name))
+            .caption(caption)
+            .await
+            .map(|_| ())
+            .map_err(|e| e.to_string())
+    }
+
+    async fn send_media_group(
+        &self,
+        chat_id: ChatId,
+        photos: Vec<MediaPhoto>,
+    ) -> Result<(), String> {
+        let media = photos.into_iter().map(|photo| {
+            InputMedia::Photo(
+                InputMediaPhoto::new(InputFile::memory(photo.bytes).file_name(photo.file_name))
+                    .caption(photo.caption),
+            )
+        });
+        self.0
+            .send_media_group(chat_id, media)
+            .await
+            .map(|_| ())
+            .map_err(|e| e.to_string())
+    }
 }
 
 #[cfg(test)]
@@ -60,7 +129,10 @@ pub struct SpyNotifier {
 #[allow(dead_code)]
 pub enum SentItem {
     Message(ChatId, String),
+    SilentMessage(ChatId, String),
     Photo(ChatId, Vec<u8>, String, String),
+    Document(ChatId, Vec<u8>, String, String),
+    MediaGroup(ChatId, Vec<MediaPhoto>),
 }
 
 #[cfg(test)]
@@ -81,6 +153,12 @@ impl Notifier for SpyNotifier {
         Ok(())
     }
 
+    async fn send_silent_message(&self, chat_id: ChatId, text: String) -> Result<(), String> {
+        let mut guard = self.sent.lock().await;
+        guard.push(SentItem::SilentMessage(chat_id, text));
+        Ok(())
+    }
+
     async fn send_photo(
         &self,
         chat_id: ChatId,
@@ -92,4 +170,26 @@ impl Notifier for SpyNotifier {
         guard.push(SentItem::Photo(chat_id, bytes, file_name, caption));
         Ok(())
     }
+
+    async fn send_document(
+        &self,
+        chat_id: ChatId,
+        bytes: Vec<u8>,
+        file_name: String,
+        caption: String,
+    ) -> Result<(), String> {
+        let mut guard = self.sent.lock().await;
+        guard.push(SentItem::Document(chat_id, bytes, file_name, caption));
+        Ok(())
+    }
+
+    async fn send_media_group(
+        &self,
+        chat_id: ChatId,
+        photos: Vec<MediaPhoto>,
+    ) -> Result<(), String> {
+        let mut guard = self.sent.lock().await;
+        guard.push(SentItem::MediaGroup(chat_id, photos));
+        Ok(())
+    }
 }
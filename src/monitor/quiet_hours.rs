@@ -0,0 +1,187 @@
+use chrono::{DateTime, Days, NaiveDate, NaiveTime, TimeZone, Utc};
+use chrono_tz::Tz;
+
+use crate::config::{QuietHours, QuietHoursMode};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum AlertDelivery {
+    Immediate,
+    Silent,
+    Deferred,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct QuietHoursPolicy {
+    start: NaiveTime,
+    end: NaiveTime,
+    tz: Tz,
+    mode: QuietHoursMode,
+    critical_threshold: f32,
+}
+
+impl QuietHoursPolicy {
+    /// `default_tz` applies when `quiet_hours.timezone` is unset.
+    pub(crate) fn from_config(config: &QuietHours, default_tz: Tz) -> Option<Self> {
+        if !config.enabled {
+            return None;
+        }
+
+        Some(Self {
+            start: config.start_time()?,
+            end: config.end_time()?,
+            tz: config.tz(default_tz)?,
+            mode: config.mode,
+            critical_threshold: config.critical_threshold,
+        })
+    }
+
+    pub(crate) fn is_quiet_at(&self, now: DateTime<Utc>) -> bool {
+        let local_time = now.with_timezone(&self.tz).time();
+        if self.start < self.end {
+            local_time >= self.start && local_time < self.end
+        } else {
+            local_time >= self.start || local_time < self.end
+        }
+    }
+
+    /// Quiet periods overlapping `from..to`, clipped to it.
+    pub(crate) fn windows_between(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Vec<(DateTime<Utc>, DateTime<Utc>)> {
+        let mut windows = Vec::new();
+        let last_day = to.with_timezone(&self.tz).date_naive();
+        let mut day = from.with_timezone(&self.tz).date_naive() - Days::new(1);
+        while day <= last_day {
+            let end_day = if self.start < self.end {
+                day
+            } else {
+                day + Days::new(1)
+            };
+            if let (Some(start), Some(end)) =
+                (self.local(day, self.start), self.local(end_day, self.end))
+            {
+                let (start, end) = (start.max(from), end.min(to));
+                if start < end {
+                    windows.push((start, end));
+                }
+            }
+            day = day + Days::new(1);
+        }
+        windows
+    }
+
+    fn local(&self, day: NaiveDate, time: NaiveTime) -> Option<DateTime<Utc>> {
+        self.tz
+            .from_local_datetime(&day.and_time(time))
+            .earliest()
+            .map(|local| local.with_timezone(&Utc))
+    }
+
+    pub(crate) fn delivery_for(&self, value: f32, now: DateTime<Utc>) -> AlertDelivery {
+        if value >= self.critical_threshold || !self.is_quiet_at(now) {
+            return AlertDelivery::Immediate;
+        }
+
+        match self.mode {
+            QuietHoursMode::Digest => AlertDelivery::Deferred,
+            QuietHoursMode::Silent => AlertDelivery::Silent,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+    use chrono_tz::Tz;
+
+    use crate::config::{QuietHours, QuietHoursMode};
+
+    use super::{AlertDelivery, QuietHoursPolicy};
+
+    fn policy(start: &str, end: &str, timezone: &str, mode: QuietHoursMode) -> QuietHoursPolicy {
+        QuietHoursPolicy::from_config(
+            &QuietHours {
+                enabled: true,
+                start: start.to_string(),
+                end: end.to_string(),
+                timezone: Some(timezone.to_string()),
+                mode,
+                critical_threshold: 95.0,
+                ..QuietHours::default()
+            },
+            Tz::UTC,
+        )
+        .expect("policy should build")
+    }
+
+    #[test]
+    fn window_wraps_past_midnight() {
+        let policy = policy("22:00", "07:00", "UTC", QuietHoursMode::Digest);
+
+        assert!(policy.is_quiet_at(Utc.with_ymd_and_hms(2026, 10, 18, 23, 30, 0).unwrap()));
+        assert!(policy.is_quiet_at(Utc.with_ymd_and_hms(2026, 10, 18, 6, 59, 0).unwrap()));
+        assert!(!policy.is_quiet_at(Utc.with_ymd_and_hms(2026, 10, 18, 7, 0, 0).unwrap()));
+        assert!(!policy.is_quiet_at(Utc.with_ymd_and_hms(2026, 10, 18, 12, 0, 0).unwrap()));
+    }
+
+    #[test]
+    fn window_uses_configured_timezone() {
+        let policy = policy("22:00", "07:00", "Europe/Istanbul", QuietHoursMode::Digest);
+
+        // 20:30 UTC is 23:30 in Istanbul (UTC+3).
+        assert!(policy.is_quiet_at(Utc.with_ymd_and_hms(2026, 10, 18, 20, 30, 0).unwrap()));
+        // 05:00 UTC is 08:00 in Istanbul.
+        assert!(!policy.is_quiet_at(Utc.with_ymd_and_hms(2026, 10, 18, 5, 0, 0).unwrap()));
+
+        // Without its own timezone the policy follows the top-level one.
+        let config = QuietHours {
+            enabled: true,
+            start: "22:00".to_string(),
+            end: "07:00".to_string(),
+            ..QuietHours::default()
+        };
+        let inherited =
+            QuietHoursPolicy::from_config(&config, Tz::Europe__Istanbul).expect("policy");
+        assert!(inherited.is_quiet_at(Utc.with_ymd_and_hms(2026, 10, 18, 20, 30, 0).unwrap()));
+    }
+
+    #[test]
+    fn windows_between_clips_wrapping_periods() {
+        let policy = policy("22:00", "07:00", "UTC", QuietHoursMode::Digest);
+        let from = Utc.with_ymd_and_hms(2026, 10, 17, 3, 0, 0).unwrap();
+        let to = Utc.with_ymd_and_hms(2026, 10, 18, 23, 0, 0).unwrap();
+
+        assert_eq!(
+            policy.windows_between(from, to),
+            [
+                (from, Utc.with_ymd_and_hms(2026, 10, 17, 7, 0, 0).unwrap()),
+                (
+                    Utc.with_ymd_and_hms(2026, 10, 17, 22, 0, 0).unwrap(),
+                    Utc.with_ymd_and_hms(2026, 10, 18, 7, 0, 0).unwrap()
+                ),
+                (Utc.with_ymd_and_hms(2026, 10, 18, 22, 0, 0).unwrap(), to),
+            ]
+        );
+    }
+
+    #[test]
+    fn critical_alerts_bypass_quiet_hours() {
+        let quiet_at = Utc.with_ymd_and_hms(2026, 10, 18, 23, 0, 0).unwrap();
+        let digest = policy("22:00", "07:00", "UTC", QuietHoursMode::Digest);
+        let silent = policy("22:00", "07:00", "UTC", QuietHoursMode::Silent);
+
+        assert_eq!(
+            digest.delivery_for(97.0, quiet_at),
+            AlertDelivery::Immediate
+        );
+        assert_eq!(digest.delivery_for(88.0, quiet_at), AlertDelivery::Deferred);
+        assert_eq!(silent.delivery_for(88.0, quiet_at), AlertDelivery::Silent);
+    }
+
+    #[test]
+    fn disabled_config_has_no_policy() {
+        assert!(QuietHoursPolicy::from_config(&QuietHours::default(), Tz::UTC).is_none());
+    }
+}
@@ -1,16 +1,21 @@
+use std::path::Path;
 use std::sync::Arc;
 
 use tokio::sync::Mutex;
 
 use crate::architecture::ports::{
-    AnomalyStoragePort, MetricsProviderPort, NotifierPort, ReportingStoragePort,
+    AnomalyStoragePort, IncidentStoragePort, MetricsProviderPort, NotifierPort,
+    ReportingStoragePort,
 };
 use crate::config::{Config, RuntimeConfig};
+use crate::i18n::t_with;
 
 use super::super::{
     evaluator::evaluate_alerts_at,
     history::{MetricHistory, MetricSample},
-    state::AlertState,
+    quiet_hours::{AlertDelivery, QuietHoursPolicy},
+    sparkline::{ALERT_SPARKLINE_MINUTES, SPARKLINE_WIDTH, metric_sparkline},
+    state::{AlertState, save_deferred_alerts},
 };
 
 use super::clock::{Clock, SystemClock};
@@ -20,6 +25,7 @@ pub struct CheckAlertsContext<'a, N: NotifierPort> {
     pub config: &'a Config,
     pub runtime_config: &'a RuntimeConfig,
     pub reporting_store: &'a dyn ReportingStoragePort,
+    pub incident_store: &'a dyn IncidentStoragePort,
     pub anomaly_storage: &'a dyn AnomalyStoragePort,
     pub state: &'a Arc<Mutex<AlertState>>,
     pub metric_history: &'a Arc<Mutex<MetricHistory>>,
@@ -47,6 +53,7 @@ pub(super) async fn check_alerts_with_clock<
         config,
         runtime_config,
         reporting_store,
+        incident_store,
         anomaly_storage,
         state,
         metric_history,
@@ -84,11 +91,31 @@ pub(super) async fn check_alerts_with_clock<
 
     let notifications =
         evaluate_alerts_at(&effective_config, state, metrics, clock.now_instant()).await;
+    let alerted = notifications
+        .iter()
+        .map(|notification| notification.metric)
+        .collect::<Vec<_>>();
 
-    {
+    let (muted_until, changed_incidents) = {
         let mut state = state.lock().await;
         state.record_metrics(metrics);
         state.record_alerts(notifications.len() as u64);
+        let now_utc = clock.now_utc();
+        let muted = state.muted_until.is_some_and(|until| now_utc < until);
+        let changed = state.track_incidents(
+            now_utc,
+            metrics,
+            &effective_config.alerts,
+            &alerted,
+            muted,
+            || incident_store.next_incident_id(),
+        );
+        (state.muted_until, changed)
+    };
+    for incident in &changed_incidents {
+        if let Err(error) = incident_store.save_incident(incident) {
+            log::warn!("incident_write_failed id={} error={error}", incident.id);
+        }
     }
 
     let sample = MetricSample {
@@ -98,10 +125,16 @@ pub(super) async fn check_alerts_with_clock<
         disk: metrics.disk,
     };
 
-    {
+    // Alerts carry a sparkline of the window that led up to them.
+    let recent_samples = {
         let mut history = metric_history.lock().await;
         history.push(sample);
-    }
+        if notifications.is_empty() {
+            Vec::new()
+        } else {
+            history.window_ending(sample.timestamp, ALERT_SPARKLINE_MINUTES)
+        }
+    };
 
     if let Err(error) = reporting_store.record_sample(sample) {
         log::warn!("reporting_store_write_failed error={error}");
@@ -115,18 +148,59 @@ pub(super) async fn check_alerts_with_clock<
         }
     };
 
-    let muted_until = {
-        let state = state.lock().await;
-        state.muted_until
-    };
     if let Some(until) = muted_until
         && clock.now_utc() < until
     {
         return;
     }
 
-    for notification in notifications {
-        if let Err(error) = notifier.send_message(owner_chat_id, notification).await {
+    let quiet_hours = QuietHoursPolicy::from_config(&config.quiet_hours, config.tz());
+    let now_utc = clock.now_utc();
+
+    for mut notification in notifications {
+        if let Some(sparkline) =
+            metric_sparkline(&recent_samples, notification.metric, SPARKLINE_WIDTH)
+        {
+            notification.text.push('\n');
+            notification.text.push_str(&t_with(
+                "alert.sparkline",
+                &[
+                    ("window", format!("{ALERT_SPARKLINE_MINUTES}m")),
+                    ("sparkline", sparkline),
+                ],
+            ));
+        }
+        let delivery = quiet_hours
+            .as_ref()
+            .map_or(AlertDelivery::Immediate, |policy| {
+                policy.delivery_for(notification.value, now_utc)
+            });
+
+        let result = match delivery {
+            AlertDelivery::Immediate => {
+                notifier
+                    .send_message(owner_chat_id, notification.text)
+                    .await
+            }
+            AlertDelivery::Silent => {
+                notifier
+                    .send_silent_message(owner_chat_id, notification.text)
+                    .await
+            }
+            AlertDelivery::Deferred => {
+                let mut state = state.lock().await;
+                state.defer_alert(now_utc, notification.text);
+                if let Err(error) = save_deferred_alerts(
+                    Path::new(&config.quiet_hours.state_path),
+                    &state.deferred_alerts,
+                ) {
+                    log::warn!("deferred_alerts_save_failed error={error}");
+                }
+                Ok(())
+            }
+        };
+
+        if let Err(error) = result {
             log::error!(
                 "CRITICAL: Failed to send alert to {}: {}",
                 owner_chat_id.0,
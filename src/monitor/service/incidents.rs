@@ -0,0 +1,33 @@
+use std::sync::Arc;
+
+use chrono::Utc;
+use tokio::sync::Mutex;
+
+use crate::architecture::ports::IncidentStoragePort;
+use crate::incidents::Incident;
+
+use super::super::state::AlertState;
+
+/// Acknowledges incident `id`, whether still open in the alert state or
+/// already resolved in the store. Returns `None` when it does not exist.
+pub async fn acknowledge_incident(
+    state: &Arc<Mutex<AlertState>>,
+    store: &dyn IncidentStoragePort,
+    id: u64,
+) -> Result<Option<Incident>, String> {
+    let now = Utc::now();
+    let open = {
+        let mut state = state.lock().await;
+        state.acknowledge_open_incident(id, now)
+    };
+    let Some(incident) = open.or_else(|| {
+        let mut stored = store.incident(id)?;
+        stored.acknowledge(now);
+        Some(stored)
+    }) else {
+        return Ok(None);
+    };
+
+    store.save_incident(&incident)?;
+    Ok(Some(incident))
+}
@@ -1,11 +1,13 @@
 mod clock;
 mod core;
+mod incidents;
 mod mute;
 mod snapshot;
 
 pub use core::{CheckAlertsContext, check_alerts};
+pub use incidents::acknowledge_incident;
 pub use mute::{MuteActionError, mute_alerts_for, unmute_alerts};
-pub use snapshot::{alert_snapshot, take_daily_summary_report};
+pub use snapshot::{alert_snapshot, take_daily_summary_report, take_deferred_alerts};
 
 #[cfg(test)]
 mod tests;
@@ -1,18 +1,22 @@
+use std::path::Path;
 use std::sync::Arc;
 
 use chrono::Utc;
 use tokio::sync::Mutex;
 
-use super::super::state::{AlertSnapshot, AlertState, DailySummaryReport};
+use super::super::state::{
+    AlertSnapshot, AlertState, DailySummaryReport, DeferredAlert, save_deferred_alerts,
+};
 
 pub async fn alert_snapshot(state: &Arc<Mutex<AlertState>>) -> AlertSnapshot {
     let state = state.lock().await;
     AlertSnapshot {
-        cpu_alerting: state.cpu_alerting,
-        ram_alerting: state.ram_alerting,
-        disk_alerting: state.disk_alerting,
+        cpu_alerting: state.cpu.alerting,
+        ram_alerting: state.ram.alerting,
+        disk_alerting: state.disk.alerting,
         muted_until: state.muted_until,
         last_daily_summary_at: state.last_daily_summary_at(),
+        deferred_alert_count: state.deferred_alerts.len(),
     }
 }
 
@@ -22,3 +26,18 @@ pub async fn take_daily_summary_report(
     let mut state = state.lock().await;
     state.take_daily_summary_report(Utc::now())
 }
+
+/// Hands the held alerts to the digest and clears the copy at `state_path`.
+pub async fn take_deferred_alerts(
+    state: &Arc<Mutex<AlertState>>,
+    state_path: &Path,
+) -> Vec<DeferredAlert> {
+    let mut state = state.lock().await;
+    let deferred = state.take_deferred_alerts();
+    if !deferred.is_empty()
+        && let Err(error) = save_deferred_alerts(state_path, &[])
+    {
+        log::warn!("deferred_alerts_clear_failed error={error}");
+    }
+    deferred
+}
@@ -3,17 +3,20 @@ use std::sync::Arc;
 use chrono::{Duration as ChronoDuration, Utc};
 use tokio::sync::Mutex;
 
+use crate::incidents::IncidentMetric;
 use crate::monitor::{
     AlertState, CheckAlertsContext, check_alerts,
     provider::{Metrics, MockMetricsProvider},
 };
+use crate::reporting_store::IncidentStorage;
 use crate::test_utils::{base_test_config, test_alert_state, test_metric_history};
 
 use super::clock::{Clock, MockClock};
 use super::core::check_alerts_with_clock;
 use super::mute::{mute_alerts_for_with_clock, unmute_alerts_with_clock};
 use super::{
-    MuteActionError, alert_snapshot, mute_alerts_for, take_daily_summary_report, unmute_alerts,
+    MuteActionError, alert_snapshot, mute_alerts_for, take_daily_summary_report,
+    take_deferred_alerts, unmute_alerts,
 };
 
 #[tokio::test]
@@ -96,6 +99,7 @@ async fn notifications_triggered_when_threshold_exceeded() {
     runtime.alerts.cpu = 0.0;
 
     let store = crate::reporting_store::NullReportingStorage;
+    let incidents = crate::reporting_store::InMemoryIncidentStorage::default();
     let state = test_alert_state();
     let history = test_metric_history(1);
     let mut provider = MockMetricsProvider::new(vec![Metrics::new(50.0, 0.0, 0.0)]);
@@ -108,6 +112,7 @@ async fn notifications_triggered_when_threshold_exceeded() {
             config: &config,
             runtime_config: &runtime,
             reporting_store: &store,
+            incident_store: &incidents,
             anomaly_storage: &anomaly_store,
             state: &state,
             metric_history: &history,
@@ -165,6 +170,7 @@ async fn alert_cooldown_supports_time_travel_without_waiting() {
 
     let runtime = crate::config::RuntimeConfig::from_config(&config);
     let store = crate::reporting_store::NullReportingStorage;
+    let incidents = crate::reporting_store::InMemoryIncidentStorage::default();
     let state = test_alert_state();
     let history = test_metric_history(1);
     let notifier = crate::monitor::SpyNotifier::new();
@@ -183,6 +189,7 @@ async fn alert_cooldown_supports_time_travel_without_waiting() {
             config: &config,
             runtime_config: &runtime,
             reporting_store: &store,
+            incident_store: &incidents,
             anomaly_storage: &anomaly_store,
             state: &state,
             metric_history: &history,
@@ -199,6 +206,7 @@ async fn alert_cooldown_supports_time_travel_without_waiting() {
             config: &config,
             runtime_config: &runtime,
             reporting_store: &store,
+            incident_store: &incidents,
             anomaly_storage: &anomaly_store,
             state: &state,
             metric_history: &history,
@@ -215,6 +223,7 @@ async fn alert_cooldown_supports_time_travel_without_waiting() {
             config: &config,
             runtime_config: &runtime,
             reporting_store: &store,
+            incident_store: &incidents,
             anomaly_storage: &anomaly_store,
             state: &state,
             metric_history: &history,
@@ -231,3 +240,218 @@ async fn alert_cooldown_supports_time_travel_without_waiting() {
         "expected one immediate + one post-cooldown alert"
     );
 }
+
+#[tokio::test]
+async fn alert_message_ends_with_recent_sparkline() {
+    let mut config = base_test_config();
+    config.owner_id = 42;
+    config.alerts.cpu = 80.0;
+    config.alerts.ram = 100.0;
+    config.alerts.disk = 100.0;
+    config.alerts.hysteresis = 0.0;
+
+    let runtime = crate::config::RuntimeConfig::from_config(&config);
+    let store = crate::reporting_store::NullReportingStorage;
+    let incidents = crate::reporting_store::InMemoryIncidentStorage::default();
+    let state = test_alert_state();
+    let history = test_metric_history(1);
+    let notifier = crate::monitor::SpyNotifier::new();
+    let anomaly_store = crate::anomaly_db::InMemoryAnomalyStorage::new();
+    let clock = MockClock::new(Utc::now());
+    let mut provider = MockMetricsProvider::new(vec![
+        Metrics::new(50.0, 0.0, 0.0),
+        Metrics::new(60.0, 0.0, 0.0),
+        Metrics::new(90.0, 0.0, 0.0),
+    ]);
+
+    for _ in 0..3 {
+        check_alerts_with_clock(
+            CheckAlertsContext {
+                notifier: &notifier,
+                config: &config,
+                runtime_config: &runtime,
+                reporting_store: &store,
+                incident_store: &incidents,
+                anomaly_storage: &anomaly_store,
+                state: &state,
+                metric_history: &history,
+            },
+            &mut provider,
+            &clock,
+        )
+        .await;
+        clock.advance(std::time::Duration::from_secs(60));
+    }
+
+    let sent = notifier.sent.lock().await;
+    assert_eq!(sent.len(), 1);
+    match &sent[0] {
+        crate::monitor::SentItem::Message(_, text) => {
+            assert!(text.ends_with("▁▃█"), "{text}");
+        }
+        other => panic!("expected message, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn quiet_hours_defer_non_critical_and_ring_critical_alerts() {
+    use chrono::TimeZone;
+
+    let mut config = base_test_config();
+    config.owner_id = 42;
+    config.alerts.cpu = 80.0;
+    config.alerts.ram = 80.0;
+    config.alerts.disk = 100.0;
+    config.alerts.hysteresis = 0.0;
+    config.quiet_hours.enabled = true;
+    config.quiet_hours.start = "22:00".to_string();
+    config.quiet_hours.end = "07:00".to_string();
+    config.quiet_hours.critical_threshold = 95.0;
+    let temp = tempfile::tempdir().expect("temp dir");
+    let state_path = temp.path().join("deferred.json");
+    config.quiet_hours.state_path = state_path.display().to_string();
+
+    let runtime = crate::config::RuntimeConfig::from_config(&config);
+    let store = crate::reporting_store::NullReportingStorage;
+    let incidents = crate::reporting_store::InMemoryIncidentStorage::default();
+    let state = test_alert_state();
+    let history = test_metric_history(1);
+    let notifier = crate::monitor::SpyNotifier::new();
+    let anomaly_store = crate::anomaly_db::InMemoryAnomalyStorage::new();
+    let clock = MockClock::new(Utc.with_ymd_and_hms(2026, 10, 18, 23, 30, 0).unwrap());
+    let mut provider = MockMetricsProvider::new(vec![Metrics::new(85.0, 97.0, 0.0)]);
+
+    check_alerts_with_clock(
+        CheckAlertsContext {
+            notifier: &notifier,
+            config: &config,
+            runtime_config: &runtime,
+            reporting_store: &store,
+            incident_store: &incidents,
+            anomaly_storage: &anomaly_store,
+            state: &state,
+            metric_history: &history,
+        },
+        &mut provider,
+        &clock,
+    )
+    .await;
+
+    let sent = notifier.sent.lock().await;
+    assert_eq!(sent.len(), 1, "only the critical RAM alert should ring");
+    match &sent[0] {
+        crate::monitor::SentItem::Message(_, text) => assert!(text.contains("RAM")),
+        other => panic!("expected message, got {other:?}"),
+    }
+
+    // Held alerts survive a restart until the digest takes them.
+    assert_eq!(crate::monitor::load_deferred_alerts(&state_path).len(), 1);
+    let deferred = take_deferred_alerts(&state, &state_path).await;
+    assert_eq!(deferred.len(), 1);
+    assert!(deferred[0].text.contains("CPU"));
+    assert!(crate::monitor::load_deferred_alerts(&state_path).is_empty());
+}
+
+#[tokio::test]
+async fn alert_transitions_open_update_and_resolve_incidents() {
+    let mut config = base_test_config();
+    config.owner_id = 42;
+    config.alerts.cpu = 80.0;
+    config.alerts.ram = 100.0;
+    config.alerts.disk = 100.0;
+    config.alerts.cooldown_secs = 60;
+    config.alerts.hysteresis = 5.0;
+
+    let runtime = crate::config::RuntimeConfig::from_config(&config);
+    let store = crate::reporting_store::InMemoryReportingStore::new();
+    let incidents = crate::reporting_store::InMemoryIncidentStorage::default();
+    let state = test_alert_state();
+    let history = test_metric_history(1);
+    let notifier = crate::monitor::SpyNotifier::new();
+    let anomaly_store = crate::anomaly_db::InMemoryAnomalyStorage::new();
+    let clock = MockClock::new(Utc::now());
+    let mut provider = MockMetricsProvider::new(vec![
+        Metrics::new(90.0, 0.0, 0.0),
+        Metrics::new(97.0, 0.0, 0.0),
+        Metrics::new(60.0, 0.0, 0.0),
+    ]);
+
+    for _ in 0..3 {
+        check_alerts_with_clock(
+            CheckAlertsContext {
+                notifier: &notifier,
+                config: &config,
+                runtime_config: &runtime,
+                reporting_store: &store,
+                incident_store: &incidents,
+                anomaly_storage: &anomaly_store,
+                state: &state,
+                metric_history: &history,
+            },
+            &mut provider,
+            &clock,
+        )
+        .await;
+        clock.advance(std::time::Duration::from_secs(120));
+    }
+
+    let incidents = incidents.incidents_since(Utc::now() - ChronoDuration::hours(1));
+    assert_eq!(incidents.len(), 1);
+    let incident = &incidents[0];
+    assert_eq!(incident.metric, IncidentMetric::Cpu);
+    assert_eq!(incident.peak, 97.0);
+    assert_eq!(incident.alerts_sent, 2);
+    assert_eq!(
+        incident.duration(Utc::now()),
+        Some(ChronoDuration::minutes(4))
+    );
+    assert!(
+        state
+            .lock()
+            .await
+            .open_incidents
+            .iter()
+            .all(Option::is_none)
+    );
+}
+
+#[tokio::test]
+async fn metrics_opening_in_one_tick_get_distinct_incidents() {
+    let mut config = base_test_config();
+    config.owner_id = 42;
+    config.alerts.cpu = 80.0;
+    config.alerts.ram = 80.0;
+    config.alerts.disk = 100.0;
+
+    let runtime = crate::config::RuntimeConfig::from_config(&config);
+    let store = crate::reporting_store::NullReportingStorage;
+    let incidents = crate::reporting_store::InMemoryIncidentStorage::default();
+    let state = test_alert_state();
+    let history = test_metric_history(1);
+    let notifier = crate::monitor::SpyNotifier::new();
+    let anomaly_store = crate::anomaly_db::InMemoryAnomalyStorage::new();
+    let mut provider = MockMetricsProvider::new(vec![Metrics::new(90.0, 95.0, 0.0)]);
+
+    check_alerts(
+        CheckAlertsContext {
+            notifier: &notifier,
+            config: &config,
+            runtime_config: &runtime,
+            reporting_store: &store,
+            incident_store: &incidents,
+            anomaly_storage: &anomaly_store,
+            state: &state,
+            metric_history: &history,
+        },
+        &mut provider,
+    )
+    .await;
+
+    let stored = incidents.incidents_since(Utc::now() - ChronoDuration::hours(1));
+    let metrics = stored
+        .iter()
+        .map(|incident| incident.metric)
+        .collect::<Vec<_>>();
+    assert_eq!(metrics, [IncidentMetric::Ram, IncidentMetric::Cpu]);
+    assert_ne!(stored[0].id, stored[1].id);
+}
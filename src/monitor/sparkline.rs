@@ -0,0 +1,104 @@
+//! Unicode sparklines (▁▂▃▄▅▆▇█) from the in-memory metric history: cheap
+//! to build and readable in notification previews, unlike rendered charts.
+
+use crate::incidents::IncidentMetric;
+use crate::time_bands::time_bands;
+
+use super::history::MetricSample;
+
+const LEVELS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+/// Smallest value range spread over the levels, in percentage points, so a
+/// flat line does not turn into full-height noise.
+const MIN_SPAN: f32 = 10.0;
+pub(crate) const SPARKLINE_WIDTH: usize = 16;
+/// Window drawn under alert messages.
+pub(crate) const ALERT_SPARKLINE_MINUTES: i64 = 30;
+
+/// Sparkline of `metric` over `samples`, one glyph per band of at most
+/// `width`; `None` with fewer than two bands.
+pub(crate) fn metric_sparkline(
+    samples: &[MetricSample],
+    metric: IncidentMetric,
+    width: usize,
+) -> Option<String> {
+    let averages = band_averages(samples, metric, width);
+    (averages.len() >= 2).then(|| sparkline(&averages))
+}
+
+/// Averages time-ordered samples into at most `width` bands of equal time
+/// span. Short inputs keep one band per sample.
+fn band_averages(samples: &[MetricSample], metric: IncidentMetric, width: usize) -> Vec<f32> {
+    time_bands(samples, width, |sample| sample.timestamp)
+        .into_iter()
+        .map(|band| {
+            let band = &samples[band];
+            let sum = band
+                .iter()
+                .map(|sample| f64::from(sample_value(sample, metric)))
+                .sum::<f64>();
+            (sum / band.len() as f64) as f32
+        })
+        .collect()
+}
+
+fn sample_value(sample: &MetricSample, metric: IncidentMetric) -> f32 {
+    match metric {
+        IncidentMetric::Cpu => sample.cpu,
+        IncidentMetric::Ram => sample.ram,
+        IncidentMetric::Disk => sample.disk,
+    }
+}
+
+fn sparkline(values: &[f32]) -> String {
+    let min = values.iter().copied().fold(f32::INFINITY, f32::min);
+    let max = values.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+    // Centre narrow ranges inside `MIN_SPAN`, clamped to the 0-100% scale.
+    let span = (max - min).max(MIN_SPAN);
+    let floor = (min - (span - (max - min)) / 2.0).clamp(0.0, (100.0 - span).max(0.0));
+    let top = (LEVELS.len() - 1) as f32;
+    values
+        .iter()
+        .map(|value| {
+            let level = ((value - floor) / span * top).round().clamp(0.0, top);
+            LEVELS[level as usize]
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+
+    use super::{metric_sparkline, sparkline};
+    use crate::incidents::IncidentMetric;
+    use crate::monitor::MetricSample;
+
+    #[test]
+    fn sparkline_scales_to_range_with_a_minimum_span() {
+        assert_eq!(sparkline(&[0.0, 50.0, 100.0]), "▁▅█");
+        // Within 2 points of each other: a mostly flat line, not ▁ to █.
+        assert_eq!(sparkline(&[40.0, 41.0, 42.0]), "▄▅▅");
+        assert_eq!(sparkline(&[99.0, 100.0]), "▇█");
+    }
+
+    #[test]
+    fn metric_sparkline_buckets_samples_to_width() {
+        let start = Utc::now();
+        let samples = (0..40)
+            .map(|index| MetricSample {
+                timestamp: start + Duration::seconds(30 * index),
+                cpu: index as f32 * 2.5,
+                ram: 50.0,
+                disk: 50.0,
+            })
+            .collect::<Vec<_>>();
+
+        let cpu = metric_sparkline(&samples, IncidentMetric::Cpu, 8).expect("sparkline");
+        assert_eq!(cpu.chars().count(), 8);
+        assert!(cpu.starts_with('▁') && cpu.ends_with('█'));
+        assert_eq!(
+            metric_sparkline(&samples[..1], IncidentMetric::Cpu, 8),
+            None
+        );
+    }
+}
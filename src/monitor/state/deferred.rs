@@ -0,0 +1,100 @@
+use std::fs;
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::DeferredAlert;
+
+/// On-disk form of a held alert, so a restart inside quiet hours still
+/// delivers it in the digest.
+#[derive(Serialize, Deserialize)]
+struct StoredDeferredAlert {
+    raised_at_utc: String,
+    text: String,
+}
+
+/// Alerts saved by [`save_deferred_alerts`]; a missing or unreadable file
+/// yields none.
+pub(crate) fn load_deferred_alerts(path: &Path) -> Vec<DeferredAlert> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Vec::new(),
+        Err(error) => {
+            log::warn!(
+                "deferred_alerts_read_failed path={} error={error}",
+                path.display()
+            );
+            return Vec::new();
+        }
+    };
+
+    let stored: Vec<StoredDeferredAlert> = serde_json::from_str(&content).unwrap_or_else(|error| {
+        log::warn!(
+            "deferred_alerts_parse_failed path={} error={error}",
+            path.display()
+        );
+        Vec::new()
+    });
+    stored
+        .into_iter()
+        .filter_map(|alert| {
+            let raised_at = DateTime::parse_from_rfc3339(&alert.raised_at_utc).ok()?;
+            Some(DeferredAlert {
+                raised_at: raised_at.with_timezone(&Utc),
+                text: alert.text,
+            })
+        })
+        .collect()
+}
+
+pub(crate) fn save_deferred_alerts(
+    path: &Path,
+    alerts: &[DeferredAlert],
+) -> Result<(), std::io::Error> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let stored = alerts
+        .iter()
+        .map(|alert| StoredDeferredAlert {
+            raised_at_utc: alert.raised_at.to_rfc3339(),
+            text: alert.text.clone(),
+        })
+        .collect::<Vec<_>>();
+    let payload = serde_json::to_string_pretty(&stored).map_err(std::io::Error::other)?;
+    let tmp_path = path.with_extension("json.tmp");
+    fs::write(&tmp_path, payload)?;
+    fs::rename(tmp_path, path)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+    use tempfile::tempdir;
+
+    use super::{load_deferred_alerts, save_deferred_alerts};
+    use crate::monitor::DeferredAlert;
+
+    #[test]
+    fn round_trips_held_alerts() {
+        let dir = tempdir().expect("temp dir");
+        let path = dir.path().join("quiet_hours").join("deferred.json");
+        assert!(load_deferred_alerts(&path).is_empty());
+
+        let raised_at = Utc.with_ymd_and_hms(2026, 10, 18, 23, 30, 0).unwrap();
+        let alerts = vec![DeferredAlert {
+            raised_at,
+            text: "CPU 91%".to_string(),
+        }];
+        save_deferred_alerts(&path, &alerts).expect("save");
+        let loaded = load_deferred_alerts(&path);
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded[0].raised_at, raised_at);
+        assert_eq!(loaded[0].text, "CPU 91%");
+
+        save_deferred_alerts(&path, &[]).expect("clear");
+        assert!(load_deferred_alerts(&path).is_empty());
+    }
+}
@@ -0,0 +1,70 @@
+use chrono::{DateTime, Utc};
+
+use crate::config::Alerts;
+use crate::incidents::{Incident, IncidentMetric};
+
+use super::super::provider::Metrics;
+use super::AlertState;
+
+impl AlertState {
+    /// Opens, updates and resolves one incident per metric from the alerting
+    /// flags set by the last evaluation. Returns the incidents that changed.
+    pub(crate) fn track_incidents(
+        &mut self,
+        now: DateTime<Utc>,
+        metrics: Metrics,
+        alerts: &Alerts,
+        alerted: &[IncidentMetric],
+        muted: bool,
+        mut next_id: impl FnMut() -> u64,
+    ) -> Vec<Incident> {
+        let mut changed = Vec::new();
+        for metric in IncidentMetric::ALL {
+            let (alerting, value, threshold) = match metric {
+                IncidentMetric::Cpu => (self.cpu.alerting, metrics.cpu, alerts.cpu),
+                IncidentMetric::Ram => (self.ram.alerting, metrics.ram, alerts.ram),
+                IncidentMetric::Disk => (self.disk.alerting, metrics.disk, alerts.disk),
+            };
+            let slot = &mut self.open_incidents[metric.index()];
+
+            if !alerting {
+                if let Some(mut incident) = slot.take() {
+                    incident.resolve(Some(value), now);
+                    changed.push(incident);
+                }
+                continue;
+            }
+
+            let opened = slot.is_none();
+            let incident = slot
+                .get_or_insert_with(|| Incident::open(next_id(), metric, threshold, value, now));
+            let mut dirty = opened | incident.observe(value, now);
+            if alerted.contains(&metric) {
+                incident.record_alert(value, now);
+                dirty = true;
+            }
+            if muted {
+                dirty |= incident.record_mute(now);
+            }
+            if dirty {
+                changed.push(incident.clone());
+            }
+        }
+        changed
+    }
+
+    /// Acknowledges the open incident with `id`, if any, and returns it.
+    pub(crate) fn acknowledge_open_incident(
+        &mut self,
+        id: u64,
+        now: DateTime<Utc>,
+    ) -> Option<Incident> {
+        let incident = self
+            .open_incidents
+            .iter_mut()
+            .flatten()
+            .find(|incident| incident.id == id)?;
+        incident.acknowledge(now);
+        Some(incident.clone())
+    }
+}
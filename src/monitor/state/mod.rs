@@ -2,22 +2,44 @@ use std::time::Instant;
 
 use chrono::{DateTime, Utc};
 
+use crate::incidents::Incident;
+
 use super::provider::Metrics;
 
 mod alert_logic;
 mod daily_summary;
+mod deferred;
+mod incidents;
+
+pub(crate) use deferred::{load_deferred_alerts, save_deferred_alerts};
+
+const MAX_DEFERRED_ALERTS: usize = 100;
 
 #[derive(Debug, Default)]
 pub struct AlertState {
-    pub(crate) cpu_alerting: bool,
-    pub(crate) ram_alerting: bool,
-    pub(crate) disk_alerting: bool,
-    pub(crate) last_cpu_alert: Option<Instant>,
-    pub(crate) last_ram_alert: Option<Instant>,
-    pub(crate) last_disk_alert: Option<Instant>,
+    pub(crate) cpu: MetricAlertState,
+    pub(crate) ram: MetricAlertState,
+    pub(crate) disk: MetricAlertState,
     pub(crate) muted_until: Option<DateTime<Utc>>,
     pub(crate) last_mute_action_at: Option<DateTime<Utc>>,
     pub(crate) daily_summary: DailySummaryAccumulator,
+    pub(crate) deferred_alerts: Vec<DeferredAlert>,
+    /// Open incident per metric, indexed by `IncidentMetric::index`.
+    pub(crate) open_incidents: [Option<Incident>; 3],
+}
+
+/// Threshold crossing state of a single metric.
+#[derive(Debug, Default)]
+pub(crate) struct MetricAlertState {
+    pub(crate) alerting: bool,
+    pub(crate) last_sent: Option<Instant>,
+    pub(crate) alerting_since: Option<Instant>,
+}
+
+#[derive(Debug, Clone)]
+pub struct DeferredAlert {
+    pub raised_at: DateTime<Utc>,
+    pub text: String,
 }
 
 #[derive(Debug, Clone)]
@@ -27,6 +49,7 @@ pub struct AlertSnapshot {
     pub disk_alerting: bool,
     pub muted_until: Option<DateTime<Utc>>,
     pub last_daily_summary_at: Option<DateTime<Utc>>,
+    pub deferred_alert_count: usize,
 }
 
 #[derive(Debug, Clone)]
@@ -80,4 +103,15 @@ impl AlertState {
     pub(crate) fn last_daily_summary_at(&self) -> Option<DateTime<Utc>> {
         self.daily_summary.last_generated_at
     }
+
+    pub(crate) fn defer_alert(&mut self, raised_at: DateTime<Utc>, text: String) {
+        if self.deferred_alerts.len() >= MAX_DEFERRED_ALERTS {
+            self.deferred_alerts.remove(0);
+        }
+        self.deferred_alerts.push(DeferredAlert { raised_at, text });
+    }
+
+    pub(crate) fn take_deferred_alerts(&mut self) -> Vec<DeferredAlert> {
+        std::mem::take(&mut self.deferred_alerts)
+    }
 }
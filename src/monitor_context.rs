@@ -13,9 +13,9 @@ pub struct MonitorContext {
 }
 
 impl MonitorContext {
-    pub fn new(monitor_interval: u64) -> Self {
+    pub fn new(monitor_interval: u64, alert_state: AlertState) -> Self {
         Self {
-            alert_state: Arc::new(Mutex::new(AlertState::default())),
+            alert_state: Arc::new(Mutex::new(alert_state)),
             metric_history: Arc::new(Mutex::new(MetricHistory::with_monitor_interval_secs(
                 monitor_interval,
             ))),
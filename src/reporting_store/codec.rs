@@ -0,0 +1,122 @@
+use chrono::{DateTime, Utc};
+
+use crate::monitor::MetricSample;
+
+use crate::versioned::{VersionError, decode};
+
+use super::model::StoredMetricSample;
+
+/// First byte of a binary sample value. Legacy JSON values start with `{`.
+pub(super) const SAMPLE_FORMAT_V1: u8 = 1;
+/// Version byte followed by cpu, ram and disk as little-endian `f32`.
+pub(super) const SAMPLE_V1_LEN: usize = 1 + 3 * 4;
+
+/// Encodes the metric values only; the timestamp already lives in the key.
+pub(super) fn encode_sample(sample: &MetricSample) -> [u8; SAMPLE_V1_LEN] {
+    let mut value = [0u8; SAMPLE_V1_LEN];
+    value[0] = SAMPLE_FORMAT_V1;
+    value[1..5].copy_from_slice(&sample.cpu.to_le_bytes());
+    value[5..9].copy_from_slice(&sample.ram.to_le_bytes());
+    value[9..13].copy_from_slice(&sample.disk.to_le_bytes());
+    value
+}
+
+/// Decodes a sample from either the binary format or legacy JSON.
+pub(super) fn decode_sample(key: &[u8], value: &[u8]) -> Option<MetricSample> {
+    match value.first()? {
+        &SAMPLE_FORMAT_V1 if value.len() == SAMPLE_V1_LEN => {
+            let millis = i64::from_be_bytes(key.get(0..8)?.try_into().ok()?);
+            let read_f32 = |at: usize| {
+                value
+                    .get(at..at + 4)
+                    .and_then(|bytes| bytes.try_into().ok())
+                    .map(f32::from_le_bytes)
+            };
+            Some(MetricSample {
+                timestamp: DateTime::<Utc>::from_timestamp_millis(millis)?,
+                cpu: read_f32(1)?,
+                ram: read_f32(5)?,
+                disk: read_f32(9)?,
+            })
+        }
+        b'{' => decode_json_sample(value).ok(),
+        _ => None,
+    }
+}
+
+pub(super) fn is_legacy_json(value: &[u8]) -> bool {
+    value.first() == Some(&b'{')
+}
+
+/// Decodes a JSON sample, bare (pre-envelope) or inside a versioned envelope.
+pub(super) fn decode_json_sample(value: &[u8]) -> Result<MetricSample, VersionError> {
+    let item = decode::<StoredMetricSample>(value)?;
+    let timestamp = DateTime::parse_from_rfc3339(&item.timestamp_utc).map_err(|error| {
+        VersionError::Malformed {
+            kind: "metric_sample",
+            reason: error.to_string(),
+        }
+    })?;
+    Ok(MetricSample {
+        timestamp: timestamp.with_timezone(&Utc),
+        cpu: item.cpu,
+        ram: item.ram,
+        disk: item.disk,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use super::{SAMPLE_V1_LEN, decode_sample, encode_sample};
+    use crate::monitor::MetricSample;
+
+    fn key_for(sample: &MetricSample) -> Vec<u8> {
+        let mut key = sample.timestamp.timestamp_millis().to_be_bytes().to_vec();
+        key.extend_from_slice(&7u32.to_be_bytes());
+        key
+    }
+
+    #[test]
+    fn binary_round_trip_takes_timestamp_from_key() {
+        let sample = MetricSample {
+            timestamp: Utc.timestamp_millis_opt(1_792_000_000_123).unwrap(),
+            cpu: 12.5,
+            ram: 48.25,
+            disk: 91.0,
+        };
+        let value = encode_sample(&sample);
+        assert_eq!(value.len(), SAMPLE_V1_LEN);
+
+        let decoded = decode_sample(&key_for(&sample), &value).expect("decodes");
+        assert_eq!(decoded.timestamp, sample.timestamp);
+        assert_eq!(
+            (decoded.cpu, decoded.ram, decoded.disk),
+            (12.5, 48.25, 91.0)
+        );
+    }
+
+    #[test]
+    fn decodes_legacy_json_and_rejects_unknown_versions() {
+        let sample = MetricSample {
+            timestamp: Utc.with_ymd_and_hms(2026, 10, 1, 12, 0, 0).unwrap(),
+            cpu: 1.0,
+            ram: 2.0,
+            disk: 3.0,
+        };
+        let legacy =
+            br#"{"timestamp_utc":"2026-10-01T12:00:00+00:00","cpu":1.0,"ram":2.0,"disk":3.0}"#;
+        let decoded = decode_sample(&key_for(&sample), legacy).expect("legacy decodes");
+        assert_eq!(decoded.timestamp, sample.timestamp);
+        assert_eq!(decoded.disk, 3.0);
+        let enveloped = br#"{"v":1,"data":{"timestamp_utc":"2026-10-01T12:00:00+00:00","cpu":1.0,"ram":2.0,"disk":3.0}}"#;
+        assert!(decode_sample(&key_for(&sample), enveloped).is_some());
+        let future = br#"{"v":9,"data":{"timestamp_utc":"2026-10-01T12:00:00+00:00","cpu":1.0,"ram":2.0,"disk":3.0}}"#;
+        assert!(decode_sample(&key_for(&sample), future).is_none());
+
+        let mut future = encode_sample(&sample);
+        future[0] = 2;
+        assert!(decode_sample(&key_for(&sample), &future).is_none());
+    }
+}
@@ -0,0 +1,265 @@
+use std::sync::{
+    Arc, Mutex,
+    atomic::{AtomicU64, Ordering},
+};
+
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+
+use crate::{incidents::Incident, versioned};
+
+use super::{TreeStats, tree_data_bytes};
+
+/// Incident persistence, kept apart from the metric tiers so incidents
+/// survive with `reporting_store.enabled = false`.
+pub trait IncidentStorage: Send + Sync {
+    /// Allocates the id of a newly opened incident.
+    fn next_incident_id(&self) -> u64;
+    /// Inserts or replaces the incident stored under `incident.id`.
+    fn save_incident(&self, incident: &Incident) -> Result<(), String>;
+    fn incident(&self, id: u64) -> Option<Incident>;
+    /// Incidents started at or after `since`, newest first.
+    fn incidents_since(&self, since: DateTime<Utc>) -> Vec<Incident>;
+    /// Persists buffered writes, e.g. before the data directory is archived.
+    fn flush(&self) -> Result<(), String>;
+    /// Entry count and key range of the incidents tree, for `/storage`.
+    fn tree_stats(&self) -> Option<TreeStats>;
+}
+
+/// Keeps incidents for the lifetime of the process when the sled database
+/// cannot be opened. Incidents are kept ordered by id.
+pub struct InMemoryIncidentStorage {
+    incidents: Mutex<Vec<Incident>>,
+    next_id: AtomicU64,
+}
+
+impl Default for InMemoryIncidentStorage {
+    fn default() -> Self {
+        Self {
+            incidents: Mutex::new(Vec::new()),
+            next_id: AtomicU64::new(1),
+        }
+    }
+}
+
+impl IncidentStorage for InMemoryIncidentStorage {
+    fn next_incident_id(&self) -> u64 {
+        // Ids are handed out before the incidents are saved, so several
+        // metrics opening in one tick must not see the same stored maximum.
+        self.next_id.fetch_add(1, Ordering::Relaxed)
+    }
+
+    fn save_incident(&self, incident: &Incident) -> Result<(), String> {
+        self.next_id.fetch_max(incident.id + 1, Ordering::Relaxed);
+        let mut guard = self.incidents.lock().unwrap();
+        match guard.binary_search_by_key(&incident.id, |stored| stored.id) {
+            Ok(index) => guard[index] = incident.clone(),
+            Err(index) => guard.insert(index, incident.clone()),
+        }
+        Ok(())
+    }
+
+    fn incident(&self, id: u64) -> Option<Incident> {
+        let guard = self.incidents.lock().unwrap();
+        let index = guard
+            .binary_search_by_key(&id, |incident| incident.id)
+            .ok()?;
+        Some(guard[index].clone())
+    }
+
+    fn incidents_since(&self, since: DateTime<Utc>) -> Vec<Incident> {
+        let guard = self.incidents.lock().unwrap();
+        guard
+            .iter()
+            .rev()
+            .filter(|incident| incident.started().is_some_and(|started| started >= since))
+            .cloned()
+            .collect()
+    }
+
+    fn flush(&self) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn tree_stats(&self) -> Option<TreeStats> {
+        None
+    }
+}
+
+/// Incidents in the `incidents` tree of the reporting sled database, keyed by
+/// big-endian id.
+#[derive(Clone)]
+pub struct IncidentStore {
+    incidents: sled::Tree,
+    incident_ids: Arc<AtomicU64>,
+    retention_days: u16,
+}
+
+impl IncidentStore {
+    /// Opens the tree, closes incidents left open by a previous run and
+    /// drops those older than `retention_days`; afterwards the reporting
+    /// store prunes it along with the metric trees.
+    pub(super) fn open(db: &sled::Db, retention_days: u16) -> Result<Self, sled::Error> {
+        let store = Self {
+            incidents: db.open_tree("incidents")?,
+            incident_ids: Arc::new(AtomicU64::new(1)),
+            retention_days,
+        };
+        store.prune_old()?;
+        store.close_interrupted_incidents()?;
+        Ok(store)
+    }
+
+    /// Incidents still open from a previous run cannot resolve through the
+    /// alert state any more; they are closed at their last recorded event.
+    pub(super) fn close_interrupted_incidents(&self) -> Result<(), sled::Error> {
+        let next_id = self
+            .incidents
+            .last()?
+            .and_then(|(key, _)| Some(u64::from_be_bytes(key.as_ref().try_into().ok()?) + 1))
+            .unwrap_or(1);
+        self.incident_ids.store(next_id, Ordering::Relaxed);
+
+        for entry in self.incidents.iter() {
+            let (key, raw) = entry?;
+            let Ok(mut incident) = versioned::decode::<Incident>(&raw) else {
+                continue;
+            };
+            if !incident.is_open() {
+                continue;
+            }
+            let last_event_at = incident
+                .timeline
+                .last()
+                .and_then(|event| chrono::DateTime::parse_from_rfc3339(&event.at).ok())
+                .map_or_else(Utc::now, |time| time.with_timezone(&Utc));
+            incident.resolve(None, last_event_at);
+            if let Ok(value) = versioned::encode(&incident) {
+                self.incidents.insert(key, value)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Incidents annotate charts, so they are kept as long as the hourly tier.
+    pub(super) fn prune_old(&self) -> Result<(), sled::Error> {
+        let cutoff = Utc::now() - ChronoDuration::days(i64::from(self.retention_days));
+        let to_remove = self
+            .incidents
+            .iter()
+            .filter_map(std::result::Result::ok)
+            .take_while(|(_, raw)| {
+                versioned::decode::<Incident>(raw)
+                    .ok()
+                    .and_then(|incident| incident.started())
+                    .is_some_and(|started| started < cutoff)
+            })
+            .map(|(key, _)| key)
+            .collect::<Vec<_>>();
+        for key in to_remove {
+            self.incidents.remove(key)?;
+        }
+        Ok(())
+    }
+}
+
+impl IncidentStorage for IncidentStore {
+    fn next_incident_id(&self) -> u64 {
+        self.incident_ids.fetch_add(1, Ordering::Relaxed)
+    }
+
+    fn save_incident(&self, incident: &Incident) -> Result<(), String> {
+        let value = versioned::encode(incident).map_err(|error| error.to_string())?;
+        self.incidents
+            .insert(incident.id.to_be_bytes(), value)
+            .map(|_| ())
+            .map_err(|error| error.to_string())
+    }
+
+    fn incident(&self, id: u64) -> Option<Incident> {
+        let raw = self.incidents.get(id.to_be_bytes()).ok()??;
+        versioned::decode(&raw).ok()
+    }
+
+    fn incidents_since(&self, since: DateTime<Utc>) -> Vec<Incident> {
+        // Ids grow with start time, so the newest-first scan can stop early.
+        self.incidents
+            .iter()
+            .values()
+            .rev()
+            .filter_map(std::result::Result::ok)
+            .filter_map(|raw| versioned::decode::<Incident>(&raw).ok())
+            .take_while(|incident| incident.started().is_some_and(|started| started >= since))
+            .collect()
+    }
+
+    fn flush(&self) -> Result<(), String> {
+        self.incidents
+            .flush()
+            .map(|_| ())
+            .map_err(|error| error.to_string())
+    }
+
+    fn tree_stats(&self) -> Option<TreeStats> {
+        let started = |key: &[u8]| {
+            let raw = self.incidents.get(key).ok()??;
+            versioned::decode::<Incident>(&raw).ok()?.started()
+        };
+        Some(TreeStats {
+            name: "incidents",
+            entries: self.incidents.len(),
+            data_bytes: tree_data_bytes(&self.incidents),
+            oldest: self
+                .incidents
+                .first()
+                .ok()
+                .flatten()
+                .and_then(|(key, _)| started(&key)),
+            newest: self
+                .incidents
+                .last()
+                .ok()
+                .flatten()
+                .and_then(|(key, _)| started(&key)),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+
+    use super::{InMemoryIncidentStorage, IncidentStorage};
+    use crate::incidents::{Incident, IncidentMetric};
+
+    #[test]
+    fn in_memory_store_keeps_newest_first_after_updates() {
+        let store = InMemoryIncidentStorage::default();
+        let now = Utc::now();
+        let mut first = Incident::open(
+            store.next_incident_id(),
+            IncidentMetric::Cpu,
+            80.0,
+            90.0,
+            now - Duration::minutes(10),
+        );
+        let second = Incident::open(
+            store.next_incident_id(),
+            IncidentMetric::Ram,
+            80.0,
+            90.0,
+            now - Duration::minutes(5),
+        );
+        store.save_incident(&first).expect("save first");
+        store.save_incident(&second).expect("save second");
+
+        first.resolve(Some(50.0), now);
+        store.save_incident(&first).expect("update first");
+        let ids = store
+            .incidents_since(now - Duration::hours(1))
+            .iter()
+            .map(|incident| incident.id)
+            .collect::<Vec<_>>();
+        assert_eq!(ids, [second.id, first.id]);
+        assert_eq!(store.next_incident_id(), second.id + 1);
+    }
+}
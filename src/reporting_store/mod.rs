@@ -3,23 +3,57 @@ use std::sync::{
     atomic::{AtomicU32, Ordering},
 };
 
-use chrono::{Duration as ChronoDuration, Utc};
+use chrono::{DateTime, Duration as ChronoDuration, NaiveDate, Utc};
 
-use crate::{config::Config, monitor::MetricSample};
+use crate::{
+    config::{Config, ReportingStoreConfig},
+    monitor::MetricSample,
+    versioned::{self, StoreOpenError, VersionError, check_format_tree},
+};
 
+mod codec;
+mod incidents;
 mod model;
-pub use model::RollingMetricSummary;
+pub use incidents::{InMemoryIncidentStorage, IncidentStorage, IncidentStore};
+pub use model::{MetricBucket, RollingMetricSummary, TreeStats};
 
-use model::{DailyRollup, StoredMetricSample};
+use codec::{decode_json_sample, decode_sample, encode_sample, is_legacy_json};
+use model::{BucketRollup, DailyRollup};
 
 pub trait ReportingStorage: Send + Sync {
     fn record_sample(&self, sample: MetricSample) -> Result<(), String>;
-    fn latest_window(&self, minutes: i64) -> Vec<MetricSample>;
+    /// Samples from the last `minutes`, read from the coarsest tier that still
+    /// yields about `max_points` points (rollup tiers return bucket averages).
+    fn latest_window(&self, minutes: i64, max_points: usize) -> Vec<MetricSample>;
     fn rolling_summary_days(&self, days: i64) -> Option<RollingMetricSummary>;
+    /// Raw samples with `from <= timestamp < to`.
+    fn range(&self, from: DateTime<Utc>, to: DateTime<Utc>) -> Vec<MetricSample>;
+    /// Per-`bucket` aggregates over `[from, to)`, built from the coarsest
+    /// stored tier that still divides `bucket` and covers `from`. Tier
+    /// buckets starting before `from` are skipped.
+    fn rollup_range(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        bucket: ChronoDuration,
+    ) -> Vec<MetricBucket>;
+    /// One bucket per UTC day with `from <= day < to`, read from the daily
+    /// rollups, which are kept as long as the hourly tier.
+    fn daily_range(&self, from: NaiveDate, to: NaiveDate) -> Vec<MetricBucket>;
+    /// Persists buffered writes, e.g. before the data directory is archived.
+    fn flush(&self) -> Result<(), String>;
+    /// Entry count and key range of each persisted tree, for `/storage`.
+    fn tree_stats(&self) -> Vec<TreeStats>;
 }
 
 pub struct NullReportingStorage;
 
+/// Stores backed by the sled database at `reporting_store.path`.
+pub struct StoreHandles {
+    pub reporting: Arc<dyn ReportingStorage>,
+    pub incidents: Arc<dyn IncidentStorage>,
+}
+
 #[cfg(test)]
 pub struct InMemoryReportingStore {
     samples: std::sync::Mutex<Vec<MetricSample>>,
@@ -42,7 +76,7 @@ impl ReportingStorage for InMemoryReportingStore {
         Ok(())
     }
 
-    fn latest_window(&self, minutes: i64) -> Vec<MetricSample> {
+    fn latest_window(&self, minutes: i64, _max_points: usize) -> Vec<MetricSample> {
         let cutoff = Utc::now() - ChronoDuration::minutes(minutes.max(1));
         let guard = self.samples.lock().unwrap();
         guard
@@ -74,57 +108,342 @@ impl ReportingStorage for InMemoryReportingStore {
         }
         Some(summary.finalize())
     }
+
+    fn range(&self, from: DateTime<Utc>, to: DateTime<Utc>) -> Vec<MetricSample> {
+        let guard = self.samples.lock().unwrap();
+        guard
+            .iter()
+            .copied()
+            .filter(|s| s.timestamp >= from && s.timestamp < to)
+            .collect()
+    }
+
+    fn rollup_range(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        bucket: ChronoDuration,
+    ) -> Vec<MetricBucket> {
+        let rollups = self
+            .range(from, to)
+            .into_iter()
+            .map(|sample| BucketRollup::new(sample.timestamp.timestamp_millis(), sample));
+        aggregate_buckets(rollups, bucket)
+    }
+
+    fn daily_range(&self, from: NaiveDate, to: NaiveDate) -> Vec<MetricBucket> {
+        self.rollup_range(
+            from.and_time(chrono::NaiveTime::MIN).and_utc(),
+            to.and_time(chrono::NaiveTime::MIN).and_utc(),
+            ChronoDuration::days(1),
+        )
+    }
+
+    fn flush(&self) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn tree_stats(&self) -> Vec<TreeStats> {
+        let guard = self.samples.lock().unwrap();
+        vec![TreeStats {
+            name: "samples",
+            entries: guard.len(),
+            data_bytes: (guard.len() * std::mem::size_of::<MetricSample>()) as u64,
+            oldest: guard.iter().map(|sample| sample.timestamp).min(),
+            newest: guard.iter().map(|sample| sample.timestamp).max(),
+        }]
+    }
 }
 
 impl ReportingStorage for NullReportingStorage {
     fn record_sample(&self, _: MetricSample) -> Result<(), String> {
         Ok(())
     }
-    fn latest_window(&self, _: i64) -> Vec<MetricSample> {
+    fn latest_window(&self, _: i64, _: usize) -> Vec<MetricSample> {
         vec![]
     }
     fn rolling_summary_days(&self, _: i64) -> Option<RollingMetricSummary> {
         None
     }
+    fn range(&self, _: DateTime<Utc>, _: DateTime<Utc>) -> Vec<MetricSample> {
+        vec![]
+    }
+    fn rollup_range(
+        &self,
+        _: DateTime<Utc>,
+        _: DateTime<Utc>,
+        _: ChronoDuration,
+    ) -> Vec<MetricBucket> {
+        vec![]
+    }
+    fn daily_range(&self, _: NaiveDate, _: NaiveDate) -> Vec<MetricBucket> {
+        vec![]
+    }
+    fn flush(&self) -> Result<(), String> {
+        Ok(())
+    }
+    fn tree_stats(&self) -> Vec<TreeStats> {
+        vec![]
+    }
+}
+
+/// Record format stamped into the `meta` tree; bump when a record's
+/// [`versioned::Versioned::VERSION`] changes.
+const STORE_FORMAT_VERSION: u32 = 1;
+/// `meta` key set once legacy JSON samples have been rewritten as binary.
+const SAMPLES_MIGRATED_KEY: &[u8] = b"samples_migrated_binary_v1";
+/// `meta` key tracking the 5m/1h rollup backfill: `pending` while it runs,
+/// `done` once every stored sample is covered.
+const ROLLUP_BACKFILL_KEY: &[u8] = b"rollup_backfill";
+const BACKFILL_PENDING: &[u8] = b"pending";
+const BACKFILL_DONE: &[u8] = b"done";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum StorageTier {
+    Raw,
+    FiveMinute,
+    Hourly,
+}
+
+impl StorageTier {
+    const ALL: [Self; 3] = [Self::Raw, Self::FiveMinute, Self::Hourly];
+
+    fn bucket_secs(self) -> i64 {
+        match self {
+            Self::Raw => 0,
+            Self::FiveMinute => 300,
+            Self::Hourly => 3600,
+        }
+    }
+
+    fn bucket_start_millis(self, timestamp: DateTime<Utc>) -> i64 {
+        let bucket_millis = (self.bucket_secs() * 1000).max(1);
+        timestamp.timestamp_millis().div_euclid(bucket_millis) * bucket_millis
+    }
+}
+
+/// Coarsest tier whose bucket is no wider than `window / max_points`, among
+/// the tiers whose retention covers the window. When none covers it, the
+/// longest-retained tier is used.
+fn select_tier(window_minutes: i64, max_points: usize, retention_days: [u16; 3]) -> StorageTier {
+    let required_secs = window_minutes.max(1) * 60 / max_points.max(1) as i64;
+    let covering = StorageTier::ALL
+        .into_iter()
+        .zip(retention_days)
+        .filter(|(_, days)| i64::from(*days) * 24 * 60 >= window_minutes)
+        .map(|(tier, _)| tier)
+        .collect::<Vec<_>>();
+
+    let Some(finest) = covering.first().copied() else {
+        return StorageTier::ALL
+            .into_iter()
+            .zip(retention_days)
+            .max_by_key(|(_, days)| *days)
+            .map_or(StorageTier::Raw, |(tier, _)| tier);
+    };
+
+    covering
+        .into_iter()
+        .rfind(|tier| tier.bucket_secs() <= required_secs)
+        .unwrap_or(finest)
+}
+
+/// Coarsest tier whose bucket evenly divides `bucket_secs` and whose retention
+/// still reaches back to `from_age_minutes`; falls back to the longest-retained
+/// dividing tier.
+fn select_range_tier(
+    from_age_minutes: i64,
+    bucket_secs: i64,
+    retention_days: [u16; 3],
+) -> StorageTier {
+    let divides = |tier: StorageTier| {
+        let tier_secs = tier.bucket_secs();
+        tier_secs == 0 || (tier_secs <= bucket_secs && bucket_secs % tier_secs == 0)
+    };
+    covering_tiers(from_age_minutes, retention_days)
+        .filter(|tier| divides(*tier))
+        .last()
+        .or_else(|| longest_retained(retention_days, divides))
+        .unwrap_or(StorageTier::Raw)
+}
+
+/// Finest tier whose retention still reaches back to `from_age_minutes`;
+/// falls back to the longest-retained tier.
+fn finest_covering_tier(from_age_minutes: i64, retention_days: [u16; 3]) -> StorageTier {
+    covering_tiers(from_age_minutes, retention_days)
+        .next()
+        .or_else(|| longest_retained(retention_days, |_| true))
+        .unwrap_or(StorageTier::Raw)
+}
+
+/// Tiers whose retention reaches back to `from_age_minutes`, finest first.
+fn covering_tiers(
+    from_age_minutes: i64,
+    retention_days: [u16; 3],
+) -> impl Iterator<Item = StorageTier> {
+    StorageTier::ALL
+        .into_iter()
+        .zip(retention_days)
+        .filter(move |(_, days)| i64::from(*days) * 24 * 60 >= from_age_minutes)
+        .map(|(tier, _)| tier)
+}
+
+fn longest_retained(
+    retention_days: [u16; 3],
+    eligible: impl Fn(StorageTier) -> bool,
+) -> Option<StorageTier> {
+    StorageTier::ALL
+        .into_iter()
+        .zip(retention_days)
+        .filter(|(tier, _)| eligible(*tier))
+        .max_by_key(|(_, days)| *days)
+        .map(|(tier, _)| tier)
+}
+
+/// Bucket width of the finest stored tier that still reaches back to `from`,
+/// or `None` while raw samples do. Lets callers label rollup averages.
+pub fn rollup_bucket_for(
+    from: DateTime<Utc>,
+    config: &ReportingStoreConfig,
+) -> Option<ChronoDuration> {
+    let tier = finest_covering_tier(
+        (Utc::now() - from).num_minutes(),
+        [
+            config.retention_days,
+            config.rollup_5m_retention_days,
+            config.rollup_1h_retention_days,
+        ],
+    );
+    (tier != StorageTier::Raw).then(|| ChronoDuration::seconds(tier.bucket_secs()))
+}
+
+/// Merges time-ordered rollups into epoch-aligned buckets of `bucket` width.
+fn aggregate_buckets(
+    rollups: impl Iterator<Item = BucketRollup>,
+    bucket: ChronoDuration,
+) -> Vec<MetricBucket> {
+    let bucket_millis = bucket.num_milliseconds().max(1000);
+    let mut merged: Vec<BucketRollup> = Vec::new();
+    for mut rollup in rollups {
+        let start = rollup.bucket_start_millis.div_euclid(bucket_millis) * bucket_millis;
+        match merged.last_mut() {
+            Some(current) if current.bucket_start_millis == start => current.merge(&rollup),
+            _ => {
+                rollup.bucket_start_millis = start;
+                merged.push(rollup);
+            }
+        }
+    }
+
+    merged
+        .iter()
+        .filter_map(BucketRollup::to_metric_bucket)
+        .collect()
+}
+
+fn sample_key(timestamp: DateTime<Utc>) -> [u8; 12] {
+    let mut key = [0u8; 12];
+    key[0..8].copy_from_slice(&timestamp.timestamp_millis().to_be_bytes());
+    key
 }
 
 #[derive(Clone)]
 pub struct ReportingStore {
     samples: sled::Tree,
+    rollups_5m: sled::Tree,
+    rollups_1h: sled::Tree,
     daily_rollups: sled::Tree,
     sequence: Arc<AtomicU32>,
     retention_days: u16,
+    rollup_5m_retention_days: u16,
+    rollup_1h_retention_days: u16,
+    /// Pruned together with the metric trees.
+    incidents: Option<IncidentStore>,
 }
 
 impl ReportingStore {
-    pub fn open_from_config(config: &Config) -> Result<Option<Self>, sled::Error> {
-        if !config.reporting_store.enabled {
-            return Ok(None);
-        }
-
-        let db = sled::open(&config.reporting_store.path)?;
-        let samples = db.open_tree("samples")?;
-        let daily_rollups = db.open_tree("daily_rollups")?;
-        Ok(Some(Self {
-            samples,
-            daily_rollups,
+    fn open(
+        db: &sled::Db,
+        config: &Config,
+        incidents: Option<IncidentStore>,
+    ) -> Result<Self, sled::Error> {
+        let store = Self {
+            samples: db.open_tree("samples")?,
+            rollups_5m: db.open_tree("rollups_5m")?,
+            rollups_1h: db.open_tree("rollups_1h")?,
+            daily_rollups: db.open_tree("daily_rollups")?,
             sequence: Arc::new(AtomicU32::new(0)),
             retention_days: config.reporting_store.retention_days,
-        }))
+            rollup_5m_retention_days: config.reporting_store.rollup_5m_retention_days,
+            rollup_1h_retention_days: config.reporting_store.rollup_1h_retention_days,
+            incidents,
+        };
+        let meta = db.open_tree("meta")?;
+        store.migrate_legacy_samples(&meta)?;
+        store.start_rollup_backfill(meta)?;
+        Ok(store)
     }
 
-    pub fn new_arc_from_config(config: &Config) -> Arc<dyn ReportingStorage> {
-        match Self::open_from_config(config) {
-            Ok(Some(store)) => Arc::new(store),
-            Ok(None) => Arc::new(NullReportingStorage),
-            Err(error) => {
+    /// Opens the sled database at `reporting_store.path` and the stores in
+    /// it. When the store is disabled or sled fails, metrics fall back to
+    /// [`NullReportingStorage`] and incidents are kept in memory until
+    /// restart. Data written by a newer build is an error so the bot refuses
+    /// to start instead of rewriting it.
+    pub fn new_arcs_from_config(config: &Config) -> Result<StoreHandles, VersionError> {
+        if !config.reporting_store.enabled {
+            return Ok(StoreHandles {
+                reporting: Arc::new(NullReportingStorage),
+                incidents: Arc::new(InMemoryIncidentStorage::default()),
+            });
+        }
+
+        let db = match open_database(config) {
+            Ok(db) => db,
+            Err(StoreOpenError::Version(error)) => return Err(error),
+            Err(StoreOpenError::Sled(error)) => {
                 log::warn!("reporting_store_disabled reason=open_failed error={error}");
-                Arc::new(NullReportingStorage)
+                return Ok(StoreHandles {
+                    reporting: Arc::new(NullReportingStorage),
+                    incidents: Arc::new(InMemoryIncidentStorage::default()),
+                });
             }
-        }
+        };
+
+        let incident_store =
+            IncidentStore::open(&db, config.reporting_store.rollup_1h_retention_days)
+                .inspect_err(|error| {
+                    log::warn!("incident_store_in_memory reason=open_failed error={error}");
+                })
+                .ok();
+        let reporting: Arc<dyn ReportingStorage> =
+            match Self::open(&db, config, incident_store.clone()) {
+                Ok(store) => Arc::new(store),
+                Err(error) => {
+                    log::warn!("reporting_store_disabled reason=open_failed error={error}");
+                    Arc::new(NullReportingStorage)
+                }
+            };
+        let incidents: Arc<dyn IncidentStorage> = match incident_store {
+            Some(store) => Arc::new(store),
+            None => Arc::new(InMemoryIncidentStorage::default()),
+        };
+        Ok(StoreHandles {
+            reporting,
+            incidents,
+        })
     }
 }
 
+fn open_database(config: &Config) -> Result<sled::Db, StoreOpenError> {
+    let db = sled::open(&config.reporting_store.path)?;
+    check_format_tree(
+        &db.open_tree("meta")?,
+        "reporting_store",
+        STORE_FORMAT_VERSION,
+    )?;
+    Ok(db)
+}
+
 impl ReportingStorage for ReportingStore {
     fn record_sample(&self, sample: MetricSample) -> Result<(), String> {
         let mut key = Vec::with_capacity(12);
@@ -132,19 +451,16 @@ impl ReportingStorage for ReportingStore {
         let seq = self.sequence.fetch_add(1, Ordering::Relaxed);
         key.extend_from_slice(&seq.to_be_bytes());
 
-        let payload = StoredMetricSample {
-            timestamp_utc: sample.timestamp.to_rfc3339(),
-            cpu: sample.cpu,
-            ram: sample.ram,
-            disk: sample.disk,
-        };
-
-        if let Ok(value) = serde_json::to_vec(&payload) {
-            self.samples.insert(key, value).map_err(|e| e.to_string())?;
-        }
+        self.samples
+            .insert(key, &encode_sample(&sample))
+            .map_err(|e| e.to_string())?;
 
         self.update_daily_rollup(sample)
             .map_err(|e| e.to_string())?;
+        for tier in [StorageTier::FiveMinute, StorageTier::Hourly] {
+            self.update_bucket_rollup(tier, sample)
+                .map_err(|e| e.to_string())?;
+        }
 
         if seq.is_multiple_of(120) {
             self.prune_old().map_err(|e| e.to_string())?;
@@ -153,28 +469,34 @@ impl ReportingStorage for ReportingStore {
         Ok(())
     }
 
-    fn latest_window(&self, minutes: i64) -> Vec<MetricSample> {
+    fn latest_window(&self, minutes: i64, max_points: usize) -> Vec<MetricSample> {
         let now = Utc::now();
         let cutoff = now - ChronoDuration::minutes(minutes.max(1));
 
-        let mut start_key = Vec::with_capacity(12);
-        start_key.extend_from_slice(&cutoff.timestamp_millis().to_be_bytes());
-        start_key.extend_from_slice(&0u32.to_be_bytes());
+        let tier = select_tier(
+            minutes,
+            max_points,
+            [
+                self.retention_days,
+                self.rollup_5m_retention_days,
+                self.rollup_1h_retention_days,
+            ],
+        );
+        if let Some(tree) = self.rollup_tree(tier) {
+            let bucket_millis = tier.bucket_secs() * 1000;
+            let start = cutoff.timestamp_millis().div_euclid(bucket_millis) * bucket_millis;
+            return tree
+                .range(start.to_be_bytes()..)
+                .filter_map(std::result::Result::ok)
+                .filter_map(|(_, value)| versioned::decode::<BucketRollup>(&value).ok())
+                .filter_map(|rollup| rollup.average_sample())
+                .collect();
+        }
 
         self.samples
-            .range(start_key..)
+            .range(sample_key(cutoff)..)
             .filter_map(std::result::Result::ok)
-            .filter_map(|(_, value)| serde_json::from_slice::<StoredMetricSample>(&value).ok())
-            .filter_map(|item| {
-                chrono::DateTime::parse_from_rfc3339(&item.timestamp_utc)
-                    .ok()
-                    .map(|ts| MetricSample {
-                        timestamp: ts.with_timezone(&Utc),
-                        cpu: item.cpu,
-                        ram: item.ram,
-                        disk: item.disk,
-                    })
-            })
+            .filter_map(|(key, value)| decode_sample(&key, &value))
             .collect()
     }
 
@@ -187,7 +509,7 @@ impl ReportingStorage for ReportingStore {
         let mut summary = RollingMetricSummary::empty();
         for item in self.daily_rollups.range(start_day.as_bytes()..) {
             let Ok((_, value)) = item else { continue };
-            let Ok(rollup) = serde_json::from_slice::<DailyRollup>(&value) else {
+            let Ok(rollup) = versioned::decode::<DailyRollup>(&value) else {
                 continue;
             };
             summary.accumulate_rollup(&rollup);
@@ -199,15 +521,314 @@ impl ReportingStorage for ReportingStore {
 
         Some(summary.finalize())
     }
+
+    fn range(&self, from: DateTime<Utc>, to: DateTime<Utc>) -> Vec<MetricSample> {
+        if to <= from {
+            return Vec::new();
+        }
+
+        self.samples
+            .range(sample_key(from)..sample_key(to))
+            .filter_map(std::result::Result::ok)
+            .filter_map(|(key, value)| decode_sample(&key, &value))
+            .collect()
+    }
+
+    fn rollup_range(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        bucket: ChronoDuration,
+    ) -> Vec<MetricBucket> {
+        if to <= from {
+            return Vec::new();
+        }
+
+        let tier = select_range_tier(
+            (Utc::now() - from).num_minutes(),
+            bucket.num_seconds(),
+            [
+                self.retention_days,
+                self.rollup_5m_retention_days,
+                self.rollup_1h_retention_days,
+            ],
+        );
+        let Some(tree) = self.rollup_tree(tier) else {
+            let rollups = self
+                .range(from, to)
+                .into_iter()
+                .map(|sample| BucketRollup::new(sample.timestamp.timestamp_millis(), sample));
+            return aggregate_buckets(rollups, bucket);
+        };
+
+        // Keys are bucket starts, so a tier bucket that began before `from`
+        // (and would pull in earlier samples) is left out.
+        let rollups = tree
+            .range(from.timestamp_millis().to_be_bytes()..to.timestamp_millis().to_be_bytes())
+            .filter_map(std::result::Result::ok)
+            .filter_map(|(_, value)| versioned::decode::<BucketRollup>(&value).ok());
+        aggregate_buckets(rollups, bucket)
+    }
+
+    fn daily_range(&self, from: NaiveDate, to: NaiveDate) -> Vec<MetricBucket> {
+        let (from, to) = (
+            from.format("%Y-%m-%d").to_string(),
+            to.format("%Y-%m-%d").to_string(),
+        );
+        self.daily_rollups
+            .range(from.as_bytes()..to.as_bytes())
+            .filter_map(std::result::Result::ok)
+            .filter_map(|(_, value)| versioned::decode::<DailyRollup>(&value).ok())
+            .filter_map(|rollup| rollup.to_metric_bucket())
+            .collect()
+    }
+
+    fn flush(&self) -> Result<(), String> {
+        // All trees share one sled database, so flushing one flushes them all.
+        self.samples
+            .flush()
+            .map(|_| ())
+            .map_err(|error| error.to_string())
+    }
+
+    fn tree_stats(&self) -> Vec<TreeStats> {
+        let millis_key = |key: &[u8]| {
+            let millis = i64::from_be_bytes(key.get(0..8)?.try_into().ok()?);
+            DateTime::<Utc>::from_timestamp_millis(millis)
+        };
+        let day_key = |key: &[u8]| {
+            let day = std::str::from_utf8(key).ok()?;
+            let date = chrono::NaiveDate::parse_from_str(day, "%Y-%m-%d").ok()?;
+            Some(date.and_hms_opt(0, 0, 0)?.and_utc())
+        };
+
+        [
+            (
+                "samples",
+                &self.samples,
+                &millis_key as &dyn Fn(&[u8]) -> Option<DateTime<Utc>>,
+            ),
+            ("rollups_5m", &self.rollups_5m, &millis_key),
+            ("rollups_1h", &self.rollups_1h, &millis_key),
+            ("daily_rollups", &self.daily_rollups, &day_key),
+        ]
+        .into_iter()
+        .map(|(name, tree, key_time)| TreeStats {
+            name,
+            entries: tree.len(),
+            data_bytes: tree_data_bytes(tree),
+            oldest: tree
+                .first()
+                .ok()
+                .flatten()
+                .and_then(|(key, _)| key_time(&key)),
+            newest: tree
+                .last()
+                .ok()
+                .flatten()
+                .and_then(|(key, _)| key_time(&key)),
+        })
+        .collect()
+    }
+}
+
+/// Sum of key and value lengths in `tree`; sled has no per-tree size.
+pub(super) fn tree_data_bytes(tree: &sled::Tree) -> u64 {
+    tree.iter()
+        .filter_map(std::result::Result::ok)
+        .map(|(key, value)| (key.len() + value.len()) as u64)
+        .sum()
 }
 
 impl ReportingStore {
+    fn rollup_tree(&self, tier: StorageTier) -> Option<&sled::Tree> {
+        match tier {
+            StorageTier::Raw => None,
+            StorageTier::FiveMinute => Some(&self.rollups_5m),
+            StorageTier::Hourly => Some(&self.rollups_1h),
+        }
+    }
+
+    fn update_bucket_rollup(
+        &self,
+        tier: StorageTier,
+        sample: MetricSample,
+    ) -> Result<(), sled::Error> {
+        let Some(tree) = self.rollup_tree(tier) else {
+            return Ok(());
+        };
+        let bucket_start = tier.bucket_start_millis(sample.timestamp);
+        let key = bucket_start.to_be_bytes();
+
+        let current = match tree.get(key)? {
+            Some(value) => match read_for_update::<BucketRollup>(&value) {
+                Some(current) => current,
+                None => return Ok(()),
+            },
+            None => None,
+        };
+        let updated = match current {
+            Some(mut rollup) => {
+                rollup.update_with_sample(sample);
+                rollup
+            }
+            None => BucketRollup::new(bucket_start, sample),
+        };
+
+        if let Ok(value) = versioned::encode(&updated) {
+            tree.insert(key, value)?;
+        }
+
+        Ok(())
+    }
+
+    /// Rewrites samples stored as JSON by older releases in the binary format.
+    /// Rewrites pre-binary JSON samples once; the `meta` marker skips the
+    /// full scan on later starts. Undecodable values are counted and kept.
+    fn migrate_legacy_samples(&self, meta: &sled::Tree) -> Result<(), sled::Error> {
+        if meta.contains_key(SAMPLES_MIGRATED_KEY)? {
+            return Ok(());
+        }
+
+        let mut migrated = 0usize;
+        let mut undecodable = 0usize;
+        for item in self.samples.iter() {
+            let (key, value) = item?;
+            if !is_legacy_json(&value) {
+                continue;
+            }
+            match decode_json_sample(&value) {
+                Ok(sample) => {
+                    self.samples.insert(key, &encode_sample(&sample))?;
+                    migrated += 1;
+                }
+                // Left as is: a newer build wrote it.
+                Err(VersionError::Future { .. }) => {}
+                Err(_) => undecodable += 1,
+            }
+        }
+
+        if migrated > 0 {
+            log::info!("reporting_store_samples_migrated format=binary_v1 samples={migrated}");
+        }
+        if undecodable > 0 {
+            log::warn!("reporting_store_samples_undecodable format=json samples={undecodable}");
+        }
+        meta.insert(SAMPLES_MIGRATED_KEY, &[])?;
+        Ok(())
+    }
+
+    /// Builds the 5m/1h tiers from raw samples written before they existed.
+    /// The current hour is rebuilt before the store is handed out; older
+    /// buckets are rebuilt on a background thread, which live writes never
+    /// touch. The `meta` marker keeps later opens from scanning again, and a
+    /// run cut short by a restart is simply repeated. Returns the thread so
+    /// tests can wait for it.
+    fn start_rollup_backfill(
+        &self,
+        meta: sled::Tree,
+    ) -> Result<Option<std::thread::JoinHandle<()>>, sled::Error> {
+        match meta.get(ROLLUP_BACKFILL_KEY)? {
+            Some(state) if state == BACKFILL_PENDING => {}
+            Some(_) => return Ok(None),
+            None if self.rollups_5m.is_empty()
+                && self.rollups_1h.is_empty()
+                && !self.samples.is_empty() => {}
+            None => {
+                meta.insert(ROLLUP_BACKFILL_KEY, BACKFILL_DONE)?;
+                return Ok(None);
+            }
+        }
+
+        meta.insert(ROLLUP_BACKFILL_KEY, BACKFILL_PENDING)?;
+        let split = StorageTier::Hourly
+            .bucket_start_millis(Utc::now())
+            .to_be_bytes();
+        self.rebuild_rollups(self.samples.range(split..))?;
+
+        let store = self.clone();
+        let handle = std::thread::Builder::new()
+            .name("rollup-backfill".to_string())
+            .spawn(move || {
+                let result = store
+                    .rebuild_rollups(store.samples.range(..split))
+                    .and_then(|samples| {
+                        meta.insert(ROLLUP_BACKFILL_KEY, BACKFILL_DONE)?;
+                        Ok(samples)
+                    });
+                match result {
+                    Ok(samples) => {
+                        log::info!("reporting_store_rollups_backfilled samples={samples}");
+                    }
+                    Err(error) => {
+                        log::warn!("reporting_store_rollup_backfill_failed error={error}")
+                    }
+                }
+            })?;
+        Ok(Some(handle))
+    }
+
+    /// Recomputes the 5m/1h buckets of time-ordered `samples` from scratch and
+    /// overwrites them, so a repeated run does not count samples twice.
+    fn rebuild_rollups(&self, samples: sled::Iter) -> Result<usize, sled::Error> {
+        let tiers = [StorageTier::FiveMinute, StorageTier::Hourly];
+        let mut open: [Option<BucketRollup>; 2] = [None, None];
+        let mut rebuilt = 0usize;
+        for item in samples {
+            let (key, value) = item?;
+            let Some(sample) = decode_sample(&key, &value) else {
+                continue;
+            };
+            for (tier, slot) in tiers.into_iter().zip(&mut open) {
+                let start = tier.bucket_start_millis(sample.timestamp);
+                if let Some(rollup) = slot
+                    .as_mut()
+                    .filter(|rollup| rollup.bucket_start_millis == start)
+                {
+                    rollup.update_with_sample(sample);
+                } else if let Some(done) = slot.replace(BucketRollup::new(start, sample)) {
+                    self.replace_bucket_rollup(tier, &done)?;
+                }
+            }
+            rebuilt += 1;
+        }
+        for (tier, slot) in tiers.into_iter().zip(open) {
+            if let Some(done) = slot {
+                self.replace_bucket_rollup(tier, &done)?;
+            }
+        }
+        Ok(rebuilt)
+    }
+
+    fn replace_bucket_rollup(
+        &self,
+        tier: StorageTier,
+        rollup: &BucketRollup,
+    ) -> Result<(), sled::Error> {
+        let Some(tree) = self.rollup_tree(tier) else {
+            return Ok(());
+        };
+        let key = rollup.bucket_start_millis.to_be_bytes();
+        if let Some(current) = tree.get(key)?
+            && read_for_update::<BucketRollup>(&current).is_none()
+        {
+            return Ok(());
+        }
+        if let Ok(value) = versioned::encode(rollup) {
+            tree.insert(key, value)?;
+        }
+        Ok(())
+    }
+
     fn update_daily_rollup(&self, sample: MetricSample) -> Result<(), sled::Error> {
         let day_key = sample.timestamp.format("%Y-%m-%d").to_string();
-        let current = self
-            .daily_rollups
-            .get(day_key.as_bytes())?
-            .and_then(|value| serde_json::from_slice::<DailyRollup>(&value).ok());
+        let current = match self.daily_rollups.get(day_key.as_bytes())? {
+            Some(value) => match read_for_update::<DailyRollup>(&value) {
+                Some(current) => current,
+                None => return Ok(()),
+            },
+            None => None,
+        };
 
         let updated = if let Some(mut rollup) = current {
             rollup.update_with_sample(sample);
@@ -216,7 +837,7 @@ impl ReportingStore {
             DailyRollup::new(day_key.clone(), sample)
         };
 
-        if let Ok(value) = serde_json::to_vec(&updated) {
+        if let Ok(value) = versioned::encode(&updated) {
             self.daily_rollups.insert(day_key.as_bytes(), value)?;
         }
 
@@ -224,40 +845,60 @@ impl ReportingStore {
     }
 
     fn prune_old(&self) -> Result<(), sled::Error> {
-        let cutoff = Utc::now() - ChronoDuration::days(i64::from(self.retention_days));
-        let cutoff_key = cutoff.timestamp_millis().to_be_bytes();
-
-        let keys_to_remove = self
-            .samples
-            .iter()
-            .keys()
-            .filter_map(std::result::Result::ok)
-            .take_while(|key| {
-                key.as_ref().len() >= 8 && &key.as_ref()[0..8] < cutoff_key.as_slice()
-            })
-            .collect::<Vec<_>>();
-
-        for key in keys_to_remove {
-            self.samples.remove(key)?;
+        let now = Utc::now();
+        let cutoff = now - ChronoDuration::days(i64::from(self.retention_days));
+        prune_tree_before(&self.samples, cutoff.timestamp_millis())?;
+
+        for (tree, retention_days) in [
+            (&self.rollups_5m, self.rollup_5m_retention_days),
+            (&self.rollups_1h, self.rollup_1h_retention_days),
+        ] {
+            let tier_cutoff = now - ChronoDuration::days(i64::from(retention_days));
+            prune_tree_before(tree, tier_cutoff.timestamp_millis())?;
         }
 
-        let cutoff_day = cutoff.format("%Y-%m-%d").to_string();
-        let rollups_to_remove = self
-            .daily_rollups
-            .iter()
-            .keys()
-            .filter_map(std::result::Result::ok)
-            .filter_map(|key| String::from_utf8(key.to_vec()).ok())
-            .take_while(|day| day < &cutoff_day)
-            .collect::<Vec<_>>();
+        // Daily rollups back the monthly report, so they follow the hourly tier.
+        let cutoff_day = (now - ChronoDuration::days(i64::from(self.rollup_1h_retention_days)))
+            .format("%Y-%m-%d")
+            .to_string();
+        remove_keys_before(&self.daily_rollups, cutoff_day.as_bytes())?;
 
-        for day in rollups_to_remove {
-            self.daily_rollups.remove(day.as_bytes())?;
+        if let Some(incidents) = &self.incidents {
+            incidents.prune_old()?;
         }
 
         Ok(())
     }
 }
 
+/// Removes entries whose key starts with a big-endian millis timestamp older
+/// than `cutoff_millis`.
+fn prune_tree_before(tree: &sled::Tree, cutoff_millis: i64) -> Result<(), sled::Error> {
+    remove_keys_before(tree, &cutoff_millis.to_be_bytes())
+}
+
+/// Removes every key ordered before `cutoff` in one batch.
+fn remove_keys_before(tree: &sled::Tree, cutoff: &[u8]) -> Result<(), sled::Error> {
+    let mut batch = sled::Batch::default();
+    for key in tree.range(..cutoff).keys() {
+        batch.remove(key?);
+    }
+    tree.apply_batch(batch)
+}
+
+/// Decodes a stored rollup before a read-modify-write. `Some(None)` means the
+/// value is unreadable and may be replaced; `None` means a newer build wrote it
+/// and it must be left untouched.
+fn read_for_update<T: versioned::Versioned>(value: &[u8]) -> Option<Option<T>> {
+    match versioned::decode::<T>(value) {
+        Ok(record) => Some(Some(record)),
+        Err(VersionError::Future { .. }) => {
+            log::warn!("reporting_store_skip_newer_record kind={}", T::KIND);
+            None
+        }
+        Err(_) => Some(None),
+    }
+}
+
 #[cfg(test)]
 mod tests;
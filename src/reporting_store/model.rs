@@ -1,6 +1,8 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
 use crate::monitor::MetricSample;
+use crate::versioned::Versioned;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub(super) struct StoredMetricSample {
@@ -10,6 +12,11 @@ pub(super) struct StoredMetricSample {
     pub disk: f32,
 }
 
+impl Versioned for StoredMetricSample {
+    const KIND: &'static str = "metric_sample";
+    const VERSION: u32 = 1;
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub(super) struct DailyRollup {
     pub day_utc: String,
@@ -25,6 +32,11 @@ pub(super) struct DailyRollup {
     pub disk_max: f32,
 }
 
+impl Versioned for DailyRollup {
+    const KIND: &'static str = "daily_rollup";
+    const VERSION: u32 = 1;
+}
+
 impl DailyRollup {
     pub(super) fn new(day_utc: String, sample: MetricSample) -> Self {
         Self {
@@ -54,6 +66,151 @@ impl DailyRollup {
         self.disk_min = self.disk_min.min(sample.disk);
         self.disk_max = self.disk_max.max(sample.disk);
     }
+
+    /// The day as a bucket starting at its UTC midnight.
+    pub(super) fn to_metric_bucket(&self) -> Option<MetricBucket> {
+        let day = chrono::NaiveDate::parse_from_str(&self.day_utc, "%Y-%m-%d").ok()?;
+        BucketRollup {
+            bucket_start_millis: day
+                .and_time(chrono::NaiveTime::MIN)
+                .and_utc()
+                .timestamp_millis(),
+            sample_count: self.sample_count,
+            cpu_sum: self.cpu_sum,
+            cpu_min: self.cpu_min,
+            cpu_max: self.cpu_max,
+            ram_sum: self.ram_sum,
+            ram_min: self.ram_min,
+            ram_max: self.ram_max,
+            disk_sum: self.disk_sum,
+            disk_min: self.disk_min,
+            disk_max: self.disk_max,
+        }
+        .to_metric_bucket()
+    }
+}
+
+/// Fixed-width time bucket (5m or 1h tier) keyed by its start in millis.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(super) struct BucketRollup {
+    pub bucket_start_millis: i64,
+    pub sample_count: u64,
+    pub cpu_sum: f64,
+    pub cpu_min: f32,
+    pub cpu_max: f32,
+    pub ram_sum: f64,
+    pub ram_min: f32,
+    pub ram_max: f32,
+    pub disk_sum: f64,
+    pub disk_min: f32,
+    pub disk_max: f32,
+}
+
+impl Versioned for BucketRollup {
+    const KIND: &'static str = "bucket_rollup";
+    const VERSION: u32 = 1;
+}
+
+impl BucketRollup {
+    pub(super) fn new(bucket_start_millis: i64, sample: MetricSample) -> Self {
+        Self {
+            bucket_start_millis,
+            sample_count: 1,
+            cpu_sum: f64::from(sample.cpu),
+            cpu_min: sample.cpu,
+            cpu_max: sample.cpu,
+            ram_sum: f64::from(sample.ram),
+            ram_min: sample.ram,
+            ram_max: sample.ram,
+            disk_sum: f64::from(sample.disk),
+            disk_min: sample.disk,
+            disk_max: sample.disk,
+        }
+    }
+
+    pub(super) fn update_with_sample(&mut self, sample: MetricSample) {
+        self.sample_count = self.sample_count.saturating_add(1);
+        self.cpu_sum += f64::from(sample.cpu);
+        self.ram_sum += f64::from(sample.ram);
+        self.disk_sum += f64::from(sample.disk);
+        self.cpu_min = self.cpu_min.min(sample.cpu);
+        self.cpu_max = self.cpu_max.max(sample.cpu);
+        self.ram_min = self.ram_min.min(sample.ram);
+        self.ram_max = self.ram_max.max(sample.ram);
+        self.disk_min = self.disk_min.min(sample.disk);
+        self.disk_max = self.disk_max.max(sample.disk);
+    }
+
+    pub(super) fn merge(&mut self, other: &Self) {
+        self.sample_count = self.sample_count.saturating_add(other.sample_count);
+        self.cpu_sum += other.cpu_sum;
+        self.ram_sum += other.ram_sum;
+        self.disk_sum += other.disk_sum;
+        self.cpu_min = self.cpu_min.min(other.cpu_min);
+        self.cpu_max = self.cpu_max.max(other.cpu_max);
+        self.ram_min = self.ram_min.min(other.ram_min);
+        self.ram_max = self.ram_max.max(other.ram_max);
+        self.disk_min = self.disk_min.min(other.disk_min);
+        self.disk_max = self.disk_max.max(other.disk_max);
+    }
+
+    pub(super) fn to_metric_bucket(&self) -> Option<MetricBucket> {
+        let avg = self.average_sample()?;
+        Some(MetricBucket {
+            start: avg.timestamp,
+            sample_count: self.sample_count,
+            avg,
+            min: MetricSample {
+                timestamp: avg.timestamp,
+                cpu: self.cpu_min,
+                ram: self.ram_min,
+                disk: self.disk_min,
+            },
+            max: MetricSample {
+                timestamp: avg.timestamp,
+                cpu: self.cpu_max,
+                ram: self.ram_max,
+                disk: self.disk_max,
+            },
+        })
+    }
+
+    /// Bucket average as a sample stamped at the bucket start.
+    pub(super) fn average_sample(&self) -> Option<MetricSample> {
+        if self.sample_count == 0 {
+            return None;
+        }
+
+        let count = self.sample_count as f64;
+        Some(MetricSample {
+            timestamp: DateTime::<Utc>::from_timestamp_millis(self.bucket_start_millis)?,
+            cpu: (self.cpu_sum / count) as f32,
+            ram: (self.ram_sum / count) as f32,
+            disk: (self.disk_sum / count) as f32,
+        })
+    }
+}
+
+/// Aggregated metrics for one bucket of a [`super::ReportingStorage::rollup_range`] query.
+/// `avg`, `min` and `max` are stamped at the bucket start.
+#[derive(Debug, Clone, Copy)]
+pub struct MetricBucket {
+    pub start: DateTime<Utc>,
+    pub sample_count: u64,
+    pub avg: MetricSample,
+    pub min: MetricSample,
+    pub max: MetricSample,
+}
+
+/// Size and time span of one sled tree.
+#[derive(Debug, Clone)]
+pub struct TreeStats {
+    pub name: &'static str,
+    pub entries: usize,
+    /// Key plus value bytes of all entries, before sled's own overhead.
+    pub data_bytes: u64,
+    pub oldest: Option<DateTime<Utc>>,
+    pub newest: Option<DateTime<Utc>>,
 }
 
 #[derive(Debug, Clone)]
@@ -1,24 +1,31 @@
 use crate::reporting_store::ReportingStorage;
 use std::sync::{Arc, atomic::AtomicU32};
 
-use chrono::{Duration, Utc};
+use chrono::{Duration, DurationRound, Utc};
 
+use crate::incidents::{Incident, IncidentMetric};
 use crate::monitor::MetricSample;
 
-use super::ReportingStore;
+use super::{
+    IncidentStorage, IncidentStore, ReportingStore, StorageTier, StoreHandles,
+    finest_covering_tier, select_range_tier, select_tier,
+};
 
 fn open_test_store(path: &std::path::Path) -> ReportingStore {
     let db = sled::open(path).expect("open db");
-    let samples = db.open_tree("samples").expect("open samples tree");
-    let daily_rollups = db
-        .open_tree("daily_rollups")
-        .expect("open daily rollups tree");
 
     ReportingStore {
-        samples,
-        daily_rollups,
+        samples: db.open_tree("samples").expect("open samples tree"),
+        rollups_5m: db.open_tree("rollups_5m").expect("open 5m rollups tree"),
+        rollups_1h: db.open_tree("rollups_1h").expect("open 1h rollups tree"),
+        daily_rollups: db
+            .open_tree("daily_rollups")
+            .expect("open daily rollups tree"),
         sequence: Arc::new(AtomicU32::new(0)),
         retention_days: 7,
+        rollup_5m_retention_days: 30,
+        rollup_1h_retention_days: 365,
+        incidents: None,
     }
 }
 
@@ -46,7 +53,7 @@ fn records_and_reads_recent_samples() {
         })
         .expect("record latest sample");
 
-    let recent = store.latest_window(10);
+    let recent = store.latest_window(10, 100);
     assert!(!recent.is_empty());
     assert!(recent.iter().any(|sample| sample.cpu >= 90.0));
 }
@@ -115,7 +122,7 @@ fn rolling_summary_survives_store_reopen() {
 fn in_memory_store_spy_behaviour() {
     let store = crate::reporting_store::InMemoryReportingStore::new();
     let now = Utc::now();
-    assert!(store.latest_window(10).is_empty());
+    assert!(store.latest_window(10, 100).is_empty());
     store
         .record_sample(MetricSample {
             timestamp: now,
@@ -124,7 +131,453 @@ fn in_memory_store_spy_behaviour() {
             disk: 78.9,
         })
         .unwrap();
-    let recent = store.latest_window(10);
+    let recent = store.latest_window(10, 100);
     assert_eq!(recent.len(), 1);
     assert_eq!(recent[0].cpu, 12.3);
 }
+
+#[test]
+fn tier_selection_prefers_coarsest_sufficient_tier() {
+    let retention = [7, 30, 365];
+    // 1h window at 120 points needs 30s buckets: only raw is fine enough.
+    assert_eq!(select_tier(60, 120, retention), StorageTier::Raw);
+    // 24h at 120 points tolerates 12 minute buckets.
+    assert_eq!(
+        select_tier(24 * 60, 120, retention),
+        StorageTier::FiveMinute
+    );
+    // A week at 120 points tolerates 84 minute buckets.
+    assert_eq!(
+        select_tier(7 * 24 * 60, 120, retention),
+        StorageTier::Hourly
+    );
+    // Raw data no longer covers 14 days, so the finest covering tier is used.
+    assert_eq!(
+        select_tier(14 * 24 * 60, 10_000, retention),
+        StorageTier::FiveMinute
+    );
+    // Beyond every retention the longest-lived tier is used.
+    assert_eq!(
+        select_tier(800 * 24 * 60, 120, retention),
+        StorageTier::Hourly
+    );
+}
+
+#[test]
+fn range_tiers_divide_the_bucket_and_cover_the_start() {
+    let retention = [7, 30, 365];
+    let day = 24 * 60;
+    // A 2h bucket two days back is built from hourly rollups.
+    assert_eq!(
+        select_range_tier(2 * day, 7200, retention),
+        StorageTier::Hourly
+    );
+    // 5 minute buckets cannot come from hourly rollups.
+    assert_eq!(
+        select_range_tier(2 * day, 300, retention),
+        StorageTier::FiveMinute
+    );
+    // Exports read raw samples while they last, then the finest rollups.
+    assert_eq!(finest_covering_tier(2 * day, retention), StorageTier::Raw);
+    assert_eq!(
+        finest_covering_tier(14 * day, retention),
+        StorageTier::FiveMinute
+    );
+    assert_eq!(
+        finest_covering_tier(800 * day, retention),
+        StorageTier::Hourly
+    );
+}
+
+#[test]
+fn long_windows_read_bucket_averages() {
+    let temp = tempfile::tempdir().expect("temp dir");
+    let store = open_test_store(temp.path());
+
+    let now = Utc::now();
+    for (hours_ago, cpu) in [(50, 10.0), (50, 30.0), (2, 60.0)] {
+        store
+            .record_sample(MetricSample {
+                timestamp: now - Duration::hours(hours_ago),
+                cpu,
+                ram: cpu,
+                disk: cpu,
+            })
+            .expect("record sample");
+    }
+
+    let samples = store.latest_window(3 * 24 * 60, 50);
+    assert_eq!(samples.len(), 2);
+    assert!((samples[0].cpu - 20.0).abs() < f32::EPSILON);
+    assert!((samples[1].cpu - 60.0).abs() < f32::EPSILON);
+    assert_eq!(store.rollups_1h.len(), 2);
+    assert_eq!(store.rollups_5m.len(), 2);
+}
+
+#[test]
+fn prune_applies_per_tier_retention() {
+    let temp = tempfile::tempdir().expect("temp dir");
+    let store = open_test_store(temp.path());
+
+    let now = Utc::now();
+    for days_ago in [100, 20, 1] {
+        store
+            .record_sample(MetricSample {
+                timestamp: now - Duration::days(days_ago),
+                cpu: 10.0,
+                ram: 10.0,
+                disk: 10.0,
+            })
+            .expect("record sample");
+    }
+    store.prune_old().expect("prune");
+
+    assert_eq!(store.samples.len(), 1);
+    assert_eq!(store.rollups_5m.len(), 2);
+    assert_eq!(store.rollups_1h.len(), 3);
+    assert_eq!(store.daily_rollups.len(), 3);
+}
+
+#[test]
+fn rollup_backfill_runs_once_in_the_background_and_can_repeat() {
+    let temp = tempfile::tempdir().expect("temp dir");
+    let store = open_test_store(temp.path());
+
+    let now = Utc::now();
+    let earlier = (now - Duration::hours(3))
+        .duration_trunc(Duration::minutes(5))
+        .expect("truncate");
+    for (seq, timestamp) in [earlier, earlier + Duration::seconds(30), now]
+        .into_iter()
+        .enumerate()
+    {
+        let mut key = timestamp.timestamp_millis().to_be_bytes().to_vec();
+        key.extend_from_slice(&(seq as u32).to_be_bytes());
+        let sample = MetricSample {
+            timestamp,
+            cpu: 10.0,
+            ram: 10.0,
+            disk: 10.0,
+        };
+        store
+            .samples
+            .insert(key, &super::codec::encode_sample(&sample))
+            .expect("insert sample");
+    }
+    let meta = sled::Config::new()
+        .temporary(true)
+        .open()
+        .and_then(|db| db.open_tree("meta"))
+        .expect("open meta tree");
+
+    let oldest_count = |store: &ReportingStore| {
+        let (_, value) = store.rollups_5m.first().expect("read").expect("bucket");
+        crate::versioned::decode::<super::model::BucketRollup>(&value)
+            .expect("decode")
+            .sample_count
+    };
+    store
+        .start_rollup_backfill(meta.clone())
+        .expect("start backfill")
+        .expect("backfill runs")
+        .join()
+        .expect("backfill thread");
+    assert_eq!(store.rollups_5m.len(), 2);
+    assert_eq!(store.rollups_1h.len(), 2);
+    assert_eq!(oldest_count(&store), 2);
+    assert!(
+        store
+            .start_rollup_backfill(meta.clone())
+            .expect("check marker")
+            .is_none()
+    );
+
+    // A run interrupted by a restart is repeated without double counting.
+    meta.insert(super::ROLLUP_BACKFILL_KEY, super::BACKFILL_PENDING)
+        .expect("mark pending");
+    store
+        .start_rollup_backfill(meta)
+        .expect("restart backfill")
+        .expect("backfill runs again")
+        .join()
+        .expect("backfill thread");
+    assert_eq!(oldest_count(&store), 2);
+}
+
+#[test]
+fn daily_range_reads_one_bucket_per_stored_day() {
+    let temp = tempfile::tempdir().expect("temp dir");
+    let store = open_test_store(temp.path());
+
+    let day = Utc::now().date_naive() - Duration::days(3);
+    let midnight = day.and_time(chrono::NaiveTime::MIN).and_utc();
+    for (hours, cpu) in [(1, 20.0), (5, 60.0), (26, 10.0), (75, 99.0)] {
+        store
+            .record_sample(MetricSample {
+                timestamp: midnight + Duration::hours(hours),
+                cpu,
+                ram: 30.0,
+                disk: 40.0,
+            })
+            .expect("record sample");
+    }
+
+    let days = store.daily_range(day, day + Duration::days(2));
+    assert_eq!(days.len(), 2);
+    assert_eq!(days[0].start, midnight);
+    assert_eq!(days[0].sample_count, 2);
+    assert_eq!((days[0].avg.cpu, days[0].max.cpu), (40.0, 60.0));
+    assert_eq!(days[1].min.cpu, 10.0);
+}
+
+#[test]
+fn legacy_json_samples_migrate_to_binary() {
+    let temp = tempfile::tempdir().expect("temp dir");
+    let store = open_test_store(temp.path());
+
+    let timestamp = Utc::now() - Duration::minutes(3);
+    let mut key = timestamp.timestamp_millis().to_be_bytes().to_vec();
+    key.extend_from_slice(&0u32.to_be_bytes());
+    let legacy = format!(
+        r#"{{"timestamp_utc":"{}","cpu":33.0,"ram":44.0,"disk":55.0}}"#,
+        timestamp.to_rfc3339()
+    );
+    store
+        .samples
+        .insert(&key, legacy.as_bytes())
+        .expect("insert legacy value");
+
+    let mut broken_key = key.clone();
+    broken_key[11] = 1;
+    store
+        .samples
+        .insert(&broken_key, &br#"{"cpu":"#[..])
+        .expect("insert undecodable value");
+
+    assert_eq!(store.latest_window(10, 100).len(), 1);
+    let meta = sled::Config::new()
+        .temporary(true)
+        .open()
+        .and_then(|db| db.open_tree("meta"))
+        .expect("open meta tree");
+    store.migrate_legacy_samples(&meta).expect("migrate");
+    assert!(
+        store.samples.contains_key(&broken_key).expect("read"),
+        "undecodable samples are kept"
+    );
+
+    let value = store.samples.get(&key).expect("read").expect("value");
+    assert_eq!(value.len(), super::codec::SAMPLE_V1_LEN);
+    let recent = store.latest_window(10, 100);
+    assert_eq!(recent.len(), 1);
+    assert_eq!(recent[0].cpu, 33.0);
+    assert_eq!(
+        recent[0].timestamp.timestamp_millis(),
+        timestamp.timestamp_millis()
+    );
+
+    // The marker skips the scan on later opens.
+    store
+        .samples
+        .insert(&key, legacy.as_bytes())
+        .expect("reinsert legacy value");
+    store.migrate_legacy_samples(&meta).expect("skip migration");
+    let value = store.samples.get(&key).expect("read").expect("value");
+    assert!(value.starts_with(b"{"));
+}
+
+#[test]
+fn absolute_ranges_return_samples_and_buckets() {
+    let temp = tempfile::tempdir().expect("temp dir");
+    let store = open_test_store(temp.path());
+
+    let base = (Utc::now() - Duration::days(2))
+        .duration_trunc(Duration::hours(1))
+        .expect("hour-aligned base");
+    for (minutes, cpu) in [(0, 10.0), (20, 30.0), (70, 50.0), (200, 90.0)] {
+        store
+            .record_sample(MetricSample {
+                timestamp: base + Duration::minutes(minutes),
+                cpu,
+                ram: cpu,
+                disk: cpu,
+            })
+            .expect("record sample");
+    }
+
+    let from = base;
+    let to = base + Duration::minutes(100);
+    let raw = store.range(from, to);
+    assert_eq!(raw.len(), 3);
+    assert!(store.range(to, from).is_empty());
+
+    let buckets = store.rollup_range(from, to, Duration::hours(2));
+    let total: u64 = buckets.iter().map(|bucket| bucket.sample_count).sum();
+    assert_eq!(total, 3);
+    let max = buckets
+        .iter()
+        .map(|bucket| bucket.max.cpu)
+        .fold(f32::MIN, f32::max);
+    assert_eq!(max, 50.0);
+
+    // The hourly rollup starting at `base` holds samples before this `from`.
+    let buckets = store.rollup_range(base + Duration::minutes(10), to, Duration::hours(2));
+    let total: u64 = buckets.iter().map(|bucket| bucket.sample_count).sum();
+    assert_eq!(total, 1);
+}
+
+#[test]
+fn legacy_rollups_are_upgraded_and_newer_ones_left_alone() {
+    let temp = tempfile::tempdir().expect("temp dir");
+    let store = open_test_store(temp.path());
+
+    let now = Utc::now();
+    let today = now.format("%Y-%m-%d").to_string();
+    let legacy = format!(
+        r#"{{"day_utc":"{today}","sample_count":2,"cpu_sum":40.0,"cpu_min":10.0,"cpu_max":30.0,"ram_sum":100.0,"ram_min":50.0,"ram_max":50.0,"disk_sum":20.0,"disk_min":10.0,"disk_max":10.0}}"#
+    );
+    store
+        .daily_rollups
+        .insert(today.as_bytes(), legacy.as_bytes())
+        .expect("insert legacy rollup");
+    let summary = store
+        .rolling_summary_days(1)
+        .expect("legacy rollup is read");
+    assert_eq!(summary.sample_count, 2);
+    assert_eq!(summary.cpu_avg, 20.0);
+
+    let sample = MetricSample {
+        timestamp: now,
+        cpu: 60.0,
+        ram: 50.0,
+        disk: 10.0,
+    };
+    store.record_sample(sample).expect("record sample");
+    let value = store.daily_rollups.get(today.as_bytes()).unwrap().unwrap();
+    assert!(value.starts_with(br#"{"v":1,"data":"#));
+    assert_eq!(store.rolling_summary_days(1).unwrap().sample_count, 3);
+
+    let newer = br#"{"v":99,"data":{"sample_count":1}}"#;
+    store
+        .daily_rollups
+        .insert(today.as_bytes(), &newer[..])
+        .expect("insert newer rollup");
+    store.record_sample(sample).expect("record sample");
+    let value = store.daily_rollups.get(today.as_bytes()).unwrap().unwrap();
+    assert_eq!(value.as_ref(), &newer[..]);
+}
+
+#[test]
+fn incidents_are_listed_newest_first_and_interrupted_ones_closed() {
+    let temp = tempfile::tempdir().expect("temp dir");
+    let db = sled::open(temp.path()).expect("open db");
+    let store = IncidentStore::open(&db, 365).expect("open incident store");
+
+    let now = Utc::now();
+    let mut old = Incident::open(
+        store.next_incident_id(),
+        IncidentMetric::Cpu,
+        85.0,
+        90.0,
+        now - Duration::days(3),
+    );
+    old.resolve(Some(70.0), now - Duration::days(3) + Duration::minutes(10));
+    let open = Incident::open(
+        store.next_incident_id(),
+        IncidentMetric::Disk,
+        90.0,
+        95.0,
+        now - Duration::hours(1),
+    );
+    store.save_incident(&old).expect("save old");
+    store.save_incident(&open).expect("save open");
+
+    let recent = store.incidents_since(now - Duration::days(1));
+    assert_eq!(recent.len(), 1);
+    assert_eq!(recent[0].id, open.id);
+    let all = store.incidents_since(now - Duration::days(7));
+    assert_eq!(
+        all.iter().map(|incident| incident.id).collect::<Vec<_>>(),
+        [2, 1]
+    );
+
+    store
+        .close_interrupted_incidents()
+        .expect("close interrupted incidents");
+    let closed = store.incident(open.id).expect("stored incident");
+    assert_eq!(closed.ended(), open.started());
+    assert_eq!(store.next_incident_id(), 3);
+}
+
+#[test]
+fn disabled_store_keeps_incidents_in_memory_without_opening_sled() {
+    let temp = tempfile::tempdir().expect("temp dir");
+    let path = temp.path().join("store");
+    let mut config = crate::test_utils::base_test_config();
+    config.reporting_store.enabled = false;
+    config.reporting_store.path = path.display().to_string();
+
+    let StoreHandles {
+        reporting,
+        incidents,
+    } = ReportingStore::new_arcs_from_config(&config).expect("open");
+    let incident = Incident::open(
+        incidents.next_incident_id(),
+        IncidentMetric::Ram,
+        90.0,
+        93.0,
+        Utc::now(),
+    );
+    incidents.save_incident(&incident).expect("save incident");
+
+    assert!(reporting.tree_stats().is_empty());
+    assert_eq!(
+        incidents.incident(incident.id).map(|i| i.id),
+        Some(incident.id)
+    );
+    assert!(!path.exists());
+}
+
+#[test]
+fn sample_pruning_also_drops_expired_incidents() {
+    let temp = tempfile::tempdir().expect("temp dir");
+    let mut config = crate::test_utils::base_test_config();
+    config.reporting_store.enabled = true;
+    config.reporting_store.path = temp.path().display().to_string();
+    config.reporting_store.rollup_1h_retention_days = 1;
+
+    let StoreHandles {
+        reporting,
+        incidents,
+    } = ReportingStore::new_arcs_from_config(&config).expect("open");
+    let now = Utc::now();
+    let expired = Incident::open(
+        incidents.next_incident_id(),
+        IncidentMetric::Cpu,
+        80.0,
+        90.0,
+        now - Duration::days(3),
+    );
+    let recent = Incident::open(
+        incidents.next_incident_id(),
+        IncidentMetric::Cpu,
+        80.0,
+        90.0,
+        now,
+    );
+    incidents.save_incident(&expired).expect("save expired");
+    incidents.save_incident(&recent).expect("save recent");
+    assert!(incidents.incident(expired.id).is_some());
+
+    // The first recorded sample runs the periodic prune.
+    reporting
+        .record_sample(MetricSample {
+            timestamp: now,
+            cpu: 10.0,
+            ram: 20.0,
+            disk: 30.0,
+        })
+        .expect("record sample");
+    assert!(incidents.incident(expired.id).is_none());
+    assert!(incidents.incident(recent.id).is_some());
+}
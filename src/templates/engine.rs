@@ -0,0 +1,155 @@
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum TemplateError {
+    #[error("unclosed placeholder starting at byte {0}")]
+    UnclosedPlaceholder(usize),
+    #[error("empty placeholder at byte {0}")]
+    EmptyPlaceholder(usize),
+    #[error("unmatched '}}' at byte {0} (use '}}}}' for a literal brace)")]
+    UnmatchedClosingBrace(usize),
+    #[error("unknown variable {{{name}}} (allowed: {allowed})")]
+    UnknownVariable { name: String, allowed: String },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    Literal(String),
+    Variable(String),
+}
+
+/// Parsed `{name}` template. `{{` and `}}` render as literal braces.
+#[derive(Debug, Clone)]
+pub struct Template {
+    segments: Vec<Segment>,
+}
+
+impl Template {
+    pub fn parse(source: &str) -> Result<Self, TemplateError> {
+        let mut segments = Vec::new();
+        let mut literal = String::new();
+        let mut chars = source.char_indices().peekable();
+
+        while let Some((index, ch)) = chars.next() {
+            match ch {
+                '{' if chars.peek().map(|(_, next)| *next) == Some('{') => {
+                    chars.next();
+                    literal.push('{');
+                }
+                '{' => {
+                    let mut name = String::new();
+                    let mut closed = false;
+                    for (_, next) in chars.by_ref() {
+                        if next == '}' {
+                            closed = true;
+                            break;
+                        }
+                        name.push(next);
+                    }
+
+                    if !closed {
+                        return Err(TemplateError::UnclosedPlaceholder(index));
+                    }
+                    let name = name.trim();
+                    if name.is_empty() {
+                        return Err(TemplateError::EmptyPlaceholder(index));
+                    }
+
+                    if !literal.is_empty() {
+                        segments.push(Segment::Literal(std::mem::take(&mut literal)));
+                    }
+                    segments.push(Segment::Variable(name.to_string()));
+                }
+                '}' if chars.peek().map(|(_, next)| *next) == Some('}') => {
+                    chars.next();
+                    literal.push('}');
+                }
+                '}' => return Err(TemplateError::UnmatchedClosingBrace(index)),
+                _ => literal.push(ch),
+            }
+        }
+
+        if !literal.is_empty() {
+            segments.push(Segment::Literal(literal));
+        }
+
+        Ok(Self { segments })
+    }
+
+    pub fn ensure_variables(&self, allowed: &[&str]) -> Result<(), TemplateError> {
+        for segment in &self.segments {
+            if let Segment::Variable(name) = segment
+                && !allowed.contains(&name.as_str())
+            {
+                return Err(TemplateError::UnknownVariable {
+                    name: name.clone(),
+                    allowed: allowed
+                        .iter()
+                        .map(|name| format!("{{{name}}}"))
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Unknown variables are rendered verbatim so a template that slipped past
+    /// validation still produces a readable message.
+    pub fn render(&self, vars: &[(&str, String)]) -> String {
+        let mut out = String::new();
+        for segment in &self.segments {
+            match segment {
+                Segment::Literal(text) => out.push_str(text),
+                Segment::Variable(name) => match vars.iter().find(|(key, _)| key == name) {
+                    Some((_, value)) => out.push_str(value),
+                    None => {
+                        out.push('{');
+                        out.push_str(name);
+                        out.push('}');
+                    }
+                },
+            }
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Template, TemplateError};
+
+    #[test]
+    fn renders_variables_and_escaped_braces() {
+        let template = Template::parse("{{raw}} {metric} at {value}%").expect("should parse");
+        let rendered =
+            template.render(&[("metric", "CPU".to_string()), ("value", "91.0".to_string())]);
+        assert_eq!(rendered, "{raw} CPU at 91.0%");
+    }
+
+    #[test]
+    fn rejects_malformed_templates() {
+        assert_eq!(
+            Template::parse("{metric").unwrap_err(),
+            TemplateError::UnclosedPlaceholder(0)
+        );
+        assert_eq!(
+            Template::parse("a {} b").unwrap_err(),
+            TemplateError::EmptyPlaceholder(2)
+        );
+        assert_eq!(
+            Template::parse("a } b").unwrap_err(),
+            TemplateError::UnmatchedClosingBrace(2)
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_variables() {
+        let template = Template::parse("{metric} {bogus}").expect("should parse");
+        let error = template
+            .ensure_variables(&["metric", "value"])
+            .expect_err("bogus should be rejected");
+        assert!(error.to_string().contains("{bogus}"));
+    }
+}
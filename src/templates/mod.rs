@@ -0,0 +1,206 @@
+mod engine;
+
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use sysinfo::{System, SystemExt};
+
+use crate::i18n;
+
+pub use engine::{Template, TemplateError};
+
+static HOSTNAME: OnceLock<String> = OnceLock::new();
+
+const ALERT_VARIABLES: &[&str] = &["metric", "value", "threshold", "hostname", "duration"];
+const DAILY_SUMMARY_VARIABLES: &[&str] = &[
+    "hostname",
+    "samples",
+    "alerts",
+    "cpu_avg",
+    "cpu_min",
+    "cpu_max",
+    "ram_avg",
+    "ram_min",
+    "ram_max",
+    "disk_avg",
+    "disk_min",
+    "disk_max",
+    "generated_at",
+];
+const WEEKLY_CAPTION_VARIABLES: &[&str] = &[
+    "hostname",
+    "metric",
+    "duration",
+    "samples",
+    "min",
+    "max",
+    "avg",
+    "labels",
+    "rollup",
+    "incidents",
+    "mttr",
+];
+
+/// Message kinds whose text can be overridden from `[templates]`.
+#[derive(Debug, Clone, Copy)]
+pub enum TemplateKind {
+    Alert,
+    DailySummary,
+    WeeklyCaption,
+}
+
+impl TemplateKind {
+    pub fn config_key(self) -> &'static str {
+        match self {
+            Self::Alert => "alert",
+            Self::DailySummary => "daily_summary",
+            Self::WeeklyCaption => "weekly_caption",
+        }
+    }
+
+    pub fn allowed_variables(self) -> &'static [&'static str] {
+        match self {
+            Self::Alert => ALERT_VARIABLES,
+            Self::DailySummary => DAILY_SUMMARY_VARIABLES,
+            Self::WeeklyCaption => WEEKLY_CAPTION_VARIABLES,
+        }
+    }
+
+    fn catalog_key(self) -> &'static str {
+        match self {
+            Self::Alert => "template.alert",
+            Self::DailySummary => "template.daily_summary",
+            Self::WeeklyCaption => "template.weekly_caption",
+        }
+    }
+
+    /// Built-in text in the configured language.
+    fn default_source(self) -> &'static str {
+        i18n::t(self.catalog_key())
+    }
+}
+
+pub fn validate_template(kind: TemplateKind, source: &str) -> Result<(), TemplateError> {
+    Template::parse(source)?.ensure_variables(kind.allowed_variables())
+}
+
+/// Renders `custom` when set, otherwise the built-in default for `kind`.
+/// `{hostname}` is filled automatically.
+pub fn render_template(
+    kind: TemplateKind,
+    custom: Option<&str>,
+    vars: &[(&'static str, String)],
+) -> String {
+    let template = custom
+        .and_then(|source| match Template::parse(source) {
+            Ok(template) => Some(template),
+            Err(error) => {
+                log::warn!(
+                    "template_invalid key={} error={error} fallback=default",
+                    kind.config_key()
+                );
+                None
+            }
+        })
+        .or_else(|| Template::parse(kind.default_source()).ok());
+
+    let Some(template) = template else {
+        return String::new();
+    };
+
+    let mut all_vars = Vec::with_capacity(vars.len() + 1);
+    all_vars.push(("hostname", hostname().to_string()));
+    all_vars.extend(vars.iter().cloned());
+    template.render(&all_vars)
+}
+
+pub fn hostname() -> &'static str {
+    HOSTNAME.get_or_init(|| {
+        System::new()
+            .host_name()
+            .unwrap_or_else(|| "unknown-host".to_string())
+    })
+}
+
+pub fn format_duration(duration: Duration) -> String {
+    let total_secs = duration.as_secs();
+    let days = total_secs / 86_400;
+    let hours = (total_secs % 86_400) / 3600;
+    let minutes = (total_secs % 3600) / 60;
+    let seconds = total_secs % 60;
+
+    if days > 0 {
+        format!("{days}d {hours}h")
+    } else if hours > 0 {
+        format!("{hours}h {minutes}m")
+    } else if minutes > 0 {
+        format!("{minutes}m {seconds}s")
+    } else {
+        format!("{seconds}s")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::i18n::{Language, lookup};
+
+    use super::{TemplateKind, format_duration, render_template, validate_template};
+
+    #[test]
+    fn default_alert_template_matches_legacy_text() {
+        let text = render_template(
+            TemplateKind::Alert,
+            None,
+            &[
+                ("metric", "CPU".to_string()),
+                ("value", "91.5".to_string()),
+                ("threshold", "85.0".to_string()),
+                ("duration", "0s".to_string()),
+            ],
+        );
+        assert_eq!(text, "⚠️ ALERT: CPU usage is high (91.5%)");
+    }
+
+    #[test]
+    fn custom_template_fills_hostname() {
+        let text = render_template(
+            TemplateKind::Alert,
+            Some("[{hostname}] {metric} {value}/{threshold} for {duration}"),
+            &[
+                ("metric", "RAM".to_string()),
+                ("value", "92.0".to_string()),
+                ("threshold", "90.0".to_string()),
+                ("duration", "5m 0s".to_string()),
+            ],
+        );
+        assert!(text.starts_with('['));
+        assert!(text.ends_with("RAM 92.0/90.0 for 5m 0s"));
+        assert!(!text.contains("{hostname}"));
+    }
+
+    #[test]
+    fn builtin_defaults_pass_validation() {
+        for kind in [
+            TemplateKind::Alert,
+            TemplateKind::DailySummary,
+            TemplateKind::WeeklyCaption,
+        ] {
+            validate_template(kind, kind.default_source()).expect("default should validate");
+            for language in [Language::En, Language::Tr] {
+                validate_template(kind, lookup(language, kind.catalog_key()))
+                    .expect("translated default should validate");
+            }
+        }
+        assert!(validate_template(TemplateKind::Alert, "{samples}").is_err());
+    }
+
+    #[test]
+    fn formats_durations_compactly() {
+        assert_eq!(format_duration(Duration::from_secs(42)), "42s");
+        assert_eq!(format_duration(Duration::from_secs(330)), "5m 30s");
+        assert_eq!(format_duration(Duration::from_secs(7260)), "2h 1m");
+        assert_eq!(format_duration(Duration::from_secs(90_000)), "1d 1h");
+    }
+}
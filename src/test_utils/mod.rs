@@ -14,12 +14,20 @@ pub fn base_test_config() -> Config {
         alerts: Default::default(),
         daily_summary: Default::default(),
         weekly_report: Default::default(),
+        monthly_report: Default::default(),
+        hourly_digest: Default::default(),
+        scheduler: Default::default(),
         graph: Default::default(),
         anomaly_db: Default::default(),
         simulation: Default::default(),
         reporting_store: Default::default(),
         release_notifier: Default::default(),
         security: Default::default(),
+        quiet_hours: Default::default(),
+        templates: Default::default(),
+        backup: Default::default(),
+        language: Default::default(),
+        timezone: "UTC".to_string(),
     }
 }
 
@@ -0,0 +1,64 @@
+//! Equal-time-span grouping shared by the graph downsampler and the alert
+//! sparklines.
+
+use std::ops::Range;
+
+use chrono::{DateTime, Utc};
+
+/// Splits time-ordered `items` into at most `band_count` consecutive groups
+/// covering equal time spans, returned as index ranges. Short inputs keep one
+/// group per item; empty time spans are skipped.
+pub(crate) fn time_bands<T>(
+    items: &[T],
+    band_count: usize,
+    timestamp: impl Fn(&T) -> DateTime<Utc>,
+) -> Vec<Range<usize>> {
+    let band_count = band_count.max(1);
+    let (Some(first), Some(last)) = (items.first(), items.last()) else {
+        return Vec::new();
+    };
+    let start_ts = timestamp(first).timestamp_millis();
+    let span = timestamp(last).timestamp_millis() - start_ts;
+    if items.len() <= band_count || span <= 0 {
+        return (0..items.len()).map(|index| index..index + 1).collect();
+    }
+
+    let band_index = |item: &T| {
+        let position = (timestamp(item).timestamp_millis() - start_ts) as f64 / span as f64;
+        ((position * band_count as f64).floor() as usize).min(band_count - 1)
+    };
+    let mut bands = Vec::with_capacity(band_count);
+    let mut group_start = 0;
+    for index in 1..=items.len() {
+        let closes = items
+            .get(index)
+            .is_none_or(|item| band_index(item) != band_index(&items[group_start]));
+        if closes {
+            bands.push(group_start..index);
+            group_start = index;
+        }
+    }
+    bands
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+
+    use super::time_bands;
+
+    #[test]
+    fn splits_evenly_spaced_items_into_equal_bands() {
+        let start = Utc::now();
+        let times = (0..10)
+            .map(|index| start + Duration::seconds(index))
+            .collect::<Vec<_>>();
+
+        let bands = time_bands(&times, 5, |time| *time);
+        assert_eq!(bands, vec![0..2, 2..4, 4..6, 6..8, 8..10]);
+        assert_eq!(
+            time_bands(&times[..3], 5, |time| *time),
+            vec![0..1, 1..2, 2..3]
+        );
+    }
+}
@@ -0,0 +1,40 @@
+//! Timestamps rendered in the configured timezone for messages and reports.
+
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+
+/// Renders `time` as local wall-clock time with the zone abbreviation.
+pub fn format_local_time(time: DateTime<Utc>, tz: Tz) -> String {
+    time.with_timezone(&tz)
+        .format("%Y-%m-%d %H:%M:%S %Z")
+        .to_string()
+}
+
+/// Like [`format_local_time`] for stored RFC3339 strings; unparsable input is
+/// returned unchanged.
+pub fn format_local_rfc3339(value: &str, tz: Tz) -> String {
+    DateTime::parse_from_rfc3339(value).map_or_else(
+        |_| value.to_string(),
+        |time| format_local_time(time.with_timezone(&Utc), tz),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+    use chrono_tz::Tz;
+
+    use super::{format_local_rfc3339, format_local_time};
+
+    #[test]
+    fn formats_timestamps_in_local_time() {
+        let tz: Tz = "Europe/Istanbul".parse().expect("valid tz");
+        let time = Utc.with_ymd_and_hms(2026, 10, 18, 20, 30, 0).unwrap();
+        assert_eq!(format_local_time(time, tz), "2026-10-18 23:30:00 +03");
+        assert_eq!(
+            format_local_rfc3339("2026-10-18T20:30:00+00:00", Tz::UTC),
+            "2026-10-18 20:30:00 UTC"
+        );
+        assert_eq!(format_local_rfc3339("garbage", tz), "garbage");
+    }
+}
@@ -0,0 +1,294 @@
+//! Versioned envelope for persisted JSON records.
+//!
+//! Records are written as `{"v": <version>, "data": <record>}`. Lines and
+//! values written before the envelope existed are read as version 0. Older
+//! versions are upgraded one step at a time through [`Versioned::upgrade`];
+//! versions newer than this build are reported as [`VersionError::Future`] so
+//! callers skip them instead of rewriting data they do not understand.
+
+use std::fs;
+use std::path::Path;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use thiserror::Error;
+
+const VERSION_FIELD: &str = "v";
+const DATA_FIELD: &str = "data";
+const FORMAT_VERSION_KEY: &[u8] = b"format_version";
+
+#[derive(Debug, Error)]
+pub enum VersionError {
+    #[error(
+        "{kind} data has format version {found} but this build supports up to {supported}; upgrade the bot instead of downgrading over newer data"
+    )]
+    Future {
+        kind: &'static str,
+        found: u32,
+        supported: u32,
+    },
+    #[error("malformed {kind} record: {reason}")]
+    Malformed { kind: &'static str, reason: String },
+}
+
+/// Failure to open a sled-backed store: either sled itself or a newer format.
+#[derive(Debug, Error)]
+pub enum StoreOpenError {
+    #[error(transparent)]
+    Sled(#[from] sled::Error),
+    #[error(transparent)]
+    Version(#[from] VersionError),
+}
+
+/// A record type persisted inside the envelope.
+pub(crate) trait Versioned: Serialize + DeserializeOwned {
+    const KIND: &'static str;
+    const VERSION: u32;
+
+    /// Converts a payload written at `from` into the shape of `from + 1`.
+    /// Version 0 (pre-envelope) payloads already match version 1.
+    fn upgrade(from: u32, data: Value) -> Result<Value, String> {
+        match from {
+            0 => Ok(data),
+            _ => Err(format!("no upgrade from version {from}")),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct EnvelopeRef<'a, T> {
+    v: u32,
+    data: &'a T,
+}
+
+pub(crate) fn encode<T: Versioned>(record: &T) -> serde_json::Result<Vec<u8>> {
+    serde_json::to_vec(&EnvelopeRef {
+        v: T::VERSION,
+        data: record,
+    })
+}
+
+pub(crate) fn decode<T: Versioned>(raw: &[u8]) -> Result<T, VersionError> {
+    let malformed = |reason: String| VersionError::Malformed {
+        kind: T::KIND,
+        reason,
+    };
+    let value =
+        serde_json::from_slice::<Value>(raw).map_err(|error| malformed(error.to_string()))?;
+
+    let (mut version, mut data) = match value {
+        Value::Object(mut fields)
+            if fields.len() == 2
+                && fields.contains_key(DATA_FIELD)
+                && fields.get(VERSION_FIELD).is_some_and(Value::is_u64) =>
+        {
+            let version = fields
+                .get(VERSION_FIELD)
+                .and_then(Value::as_u64)
+                .and_then(|version| u32::try_from(version).ok())
+                .ok_or_else(|| malformed("version out of range".to_string()))?;
+            (version, fields.remove(DATA_FIELD).unwrap_or(Value::Null))
+        }
+        legacy => (0, legacy),
+    };
+
+    if version > T::VERSION {
+        return Err(VersionError::Future {
+            kind: T::KIND,
+            found: version,
+            supported: T::VERSION,
+        });
+    }
+    while version < T::VERSION {
+        data = T::upgrade(version, data).map_err(malformed)?;
+        version += 1;
+    }
+
+    serde_json::from_value(data).map_err(|error| malformed(error.to_string()))
+}
+
+#[derive(Serialize, Deserialize)]
+struct FormatMarker {
+    format_version: u32,
+}
+
+/// Refuses stores stamped by a newer build, then stamps `supported`. The
+/// marker lives in `path` (a small JSON file) for file-based stores. A marker
+/// that cannot be read or parsed is logged and stamped again: only a newer
+/// version blocks startup.
+pub(crate) fn check_format_file(
+    path: &Path,
+    kind: &'static str,
+    supported: u32,
+) -> Result<(), VersionError> {
+    let found = match fs::read(path) {
+        Ok(raw) => match serde_json::from_slice::<FormatMarker>(&raw) {
+            Ok(marker) => Some(marker.format_version),
+            Err(error) => {
+                log::warn!(
+                    "format_marker_unreadable kind={kind} path={} error={error}",
+                    path.display()
+                );
+                None
+            }
+        },
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => None,
+        Err(error) => {
+            log::warn!(
+                "format_marker_unreadable kind={kind} path={} error={error}",
+                path.display()
+            );
+            None
+        }
+    };
+    check_found(kind, found, supported)?;
+
+    if found != Some(supported)
+        && let Err(error) = write_format_file(path, supported)
+    {
+        log::warn!(
+            "format_marker_stamp_failed kind={kind} path={} error={error}",
+            path.display()
+        );
+    }
+    Ok(())
+}
+
+fn write_format_file(path: &Path, supported: u32) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let raw = serde_json::to_vec(&FormatMarker {
+        format_version: supported,
+    })?;
+    fs::write(path, raw)
+}
+
+/// Same as [`check_format_file`] for sled stores, using a key in `meta`.
+pub(crate) fn check_format_tree(
+    meta: &sled::Tree,
+    kind: &'static str,
+    supported: u32,
+) -> Result<(), VersionError> {
+    let found = match meta.get(FORMAT_VERSION_KEY) {
+        Ok(Some(raw)) => match <[u8; 4]>::try_from(raw.as_ref()) {
+            Ok(bytes) => Some(u32::from_be_bytes(bytes)),
+            Err(_) => {
+                log::warn!("format_marker_unreadable kind={kind} error=invalid_value");
+                None
+            }
+        },
+        Ok(None) => None,
+        Err(error) => {
+            log::warn!("format_marker_unreadable kind={kind} error={error}");
+            None
+        }
+    };
+    check_found(kind, found, supported)?;
+
+    if found != Some(supported)
+        && let Err(error) = meta.insert(FORMAT_VERSION_KEY, &supported.to_be_bytes())
+    {
+        log::warn!("format_marker_stamp_failed kind={kind} error={error}");
+    }
+    Ok(())
+}
+
+fn check_found(kind: &'static str, found: Option<u32>, supported: u32) -> Result<(), VersionError> {
+    match found {
+        Some(found) if found > supported => Err(VersionError::Future {
+            kind,
+            found,
+            supported,
+        }),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+    use serde_json::Value;
+
+    use super::{VersionError, Versioned, check_format_file, decode, encode};
+
+    /// v1 stored `total`; v2 renamed it to `sum` and added `count`.
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Counter {
+        sum: u64,
+        count: u64,
+    }
+
+    impl Versioned for Counter {
+        const KIND: &'static str = "counter";
+        const VERSION: u32 = 2;
+
+        fn upgrade(from: u32, mut data: Value) -> Result<Value, String> {
+            match from {
+                0 => Ok(data),
+                1 => {
+                    let fields = data.as_object_mut().ok_or("expected object")?;
+                    let total = fields.remove("total").unwrap_or(Value::from(0));
+                    fields.insert("sum".to_string(), total);
+                    fields.insert("count".to_string(), Value::from(1));
+                    Ok(data)
+                }
+                _ => Err(format!("no upgrade from version {from}")),
+            }
+        }
+    }
+
+    #[test]
+    fn upgrades_old_envelopes_and_rejects_future_ones() {
+        let current = Counter { sum: 5, count: 2 };
+        assert_eq!(
+            decode::<Counter>(&encode(&current).unwrap()).unwrap(),
+            current
+        );
+
+        let v1 = decode::<Counter>(br#"{"v":1,"data":{"total":7}}"#).unwrap();
+        assert_eq!(v1, Counter { sum: 7, count: 1 });
+        let legacy = decode::<Counter>(br#"{"total":3}"#).unwrap();
+        assert_eq!(legacy, Counter { sum: 3, count: 1 });
+
+        assert!(matches!(
+            decode::<Counter>(br#"{"v":3,"data":{"sum":1,"count":1}}"#),
+            Err(VersionError::Future { found: 3, .. })
+        ));
+        assert!(matches!(
+            decode::<Counter>(b"{not json"),
+            Err(VersionError::Malformed { .. })
+        ));
+    }
+
+    #[test]
+    fn format_marker_is_stamped_and_blocks_newer_stores() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let marker = temp.path().join("meta").join("format.json");
+
+        check_format_file(&marker, "test", 1).expect("fresh store is stamped");
+        check_format_file(&marker, "test", 2).expect("newer build upgrades the stamp");
+        assert!(matches!(
+            check_format_file(&marker, "test", 1),
+            Err(VersionError::Future {
+                found: 2,
+                supported: 1,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn corrupt_format_marker_is_stamped_again() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let marker = temp.path().join("format.json");
+        std::fs::write(&marker, b"{not json").unwrap();
+
+        check_format_file(&marker, "test", 1).expect("corrupt marker does not block");
+        assert!(matches!(
+            check_format_file(&marker, "test", 0),
+            Err(VersionError::Future { found: 1, .. })
+        ));
+    }
+}